@@ -0,0 +1,87 @@
+//! Measures how many Theater commands the client completes per second when
+//! many MCP requests arrive at once.
+//!
+//! A fake Theater server answers every command after a fixed delay, standing
+//! in for the real server's processing time. The same burst of concurrent
+//! `list_actors` calls is run with a single pipelined connection, which the
+//! fake server still answers one command at a time, and with the default
+//! connection pool.
+
+use anyhow::Result;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+use theater::theater_server::ManagementResponse;
+use theater_mcp_server::theater::framing::{read_frame, write_frame};
+use theater_mcp_server::theater::pool::DEFAULT_POOL_SIZE;
+use theater_mcp_server::theater::TheaterClient;
+
+/// Simulated time the Theater server spends on each command
+const SERVER_LATENCY: Duration = Duration::from_millis(5);
+
+/// Concurrent requests issued per run
+const REQUESTS: usize = 200;
+
+/// Start a fake Theater server that answers every command with an empty actor list
+async fn start_fake_theater() -> Result<SocketAddr> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                while read_frame::<_, serde_json::Value>(&mut stream, usize::MAX).await.is_ok() {
+                    tokio::time::sleep(SERVER_LATENCY).await;
+                    let response = ManagementResponse::ActorList { actors: vec![] };
+                    if write_frame(&mut stream, &response).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    Ok(addr)
+}
+
+/// Issue `REQUESTS` concurrent commands and return the achieved commands per second
+async fn run(addr: SocketAddr, connections: usize) -> Result<f64> {
+    let client = Arc::new(TheaterClient::connect_with_pool_size(addr, connections).await?);
+
+    let start = Instant::now();
+    let calls: Vec<_> = (0..REQUESTS)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { client.list_actors().await })
+        })
+        .collect();
+    for call in calls {
+        call.await??;
+    }
+
+    Ok(REQUESTS as f64 / start.elapsed().as_secs_f64())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let addr = start_fake_theater().await?;
+
+    let serialized = run(addr, 1).await?;
+    let pooled = run(addr, DEFAULT_POOL_SIZE).await?;
+
+    println!(
+        "{} concurrent commands, {:?} server latency",
+        REQUESTS, SERVER_LATENCY
+    );
+    println!("  1 connection:   {:>8.1} commands/s", serialized);
+    println!(
+        "  {} connections: {:>8.1} commands/s ({:.1}x)",
+        DEFAULT_POOL_SIZE,
+        pooled,
+        pooled / serialized
+    );
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+//! Helpers shared by the example clients
+
+#![allow(dead_code)]
+
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+
+/// The theater-mcp-server binary to launch.
+///
+/// `THEATER_MCP_SERVER` overrides it; otherwise the binary built next to the
+/// running example is used (`target/<profile>/theater-mcp-server`), so run
+/// `cargo build` first.
+pub fn server_binary() -> String {
+    if let Ok(path) = std::env::var("THEATER_MCP_SERVER") {
+        return path;
+    }
+
+    // Examples are built into target/<profile>/examples/
+    let exe = std::env::current_exe().expect("could not locate the running example");
+    let profile_dir = exe
+        .parent()
+        .and_then(|examples| examples.parent())
+        .expect("example is not in a target directory");
+    profile_dir
+        .join(format!("theater-mcp-server{}", std::env::consts::EXE_SUFFIX))
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Address of the Theater server, from `THEATER_ADDRESS` or the default port
+pub fn theater_address() -> String {
+    std::env::var("THEATER_ADDRESS").unwrap_or_else(|_| "127.0.0.1:9000".to_string())
+}
+
+/// Manifest of the bundled echo actor
+pub fn fixture_manifest() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/fixtures/echo-actor/manifest.toml")
+}
+
+/// Fail early when the echo actor's component hasn't been built, which a
+/// real Theater server needs to start it
+pub fn require_fixture_component() -> Result<()> {
+    let component = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/fixtures/echo-actor/echo_actor.wasm");
+    if !component.exists() {
+        bail!(
+            "{} is missing; a real Theater server needs the echo actor's component (see examples/fixtures/README.md)",
+            component.display()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,211 @@
+//! Drives the full hello-world flow through the MCP server, checking every step.
+//!
+//! By default a mock Theater server is started in this process, so the
+//! harness needs nothing but a built `theater-mcp-server` binary:
+//!
+//! ```bash
+//! cargo build
+//! cargo run --example e2e_harness
+//! ```
+//!
+//! Set `THEATER_ADDRESS` to run the same flow against a real Theater server
+//! instead, with the echo actor's component built (see `examples/fixtures`).
+
+#[path = "../tests/mock_theater/mod.rs"]
+mod mock_theater;
+
+mod common;
+
+use anyhow::{anyhow, bail, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use mcp_client::transport::Transport;
+use mcp_protocol::JsonRpcMessage;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::sync::mpsc::Receiver;
+
+use mock_theater::MockTheater;
+
+/// An MCP session over the server's stdio transport
+struct Session<T: Transport> {
+    transport: T,
+    responses: Receiver<JsonRpcMessage>,
+    next_id: u64,
+}
+
+impl<T: Transport> Session<T> {
+    /// Send a request and wait for its result, failing on an error response
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        self.next_id += 1;
+        let id = self.next_id.to_string();
+        self.transport
+            .send(JsonRpcMessage::Request {
+                jsonrpc: "2.0".to_string(),
+                id: id.clone().into(),
+                method: method.to_string(),
+                params: Some(params),
+            })
+            .await?;
+
+        let response = tokio::time::timeout(Duration::from_secs(10), self.responses.recv())
+            .await
+            .map_err(|_| anyhow!("{} timed out", method))?
+            .ok_or_else(|| anyhow!("server exited during {}", method))?;
+        match response {
+            JsonRpcMessage::Response { error: Some(error), .. } => bail!("{} failed: {:?}", method, error),
+            JsonRpcMessage::Response { result: Some(result), .. } => Ok(result),
+            other => bail!("unexpected reply to {}: {:?}", method, other),
+        }
+    }
+
+    /// Call a tool and return the `data` of its result envelope
+    async fn call_tool(&mut self, name: &str, arguments: Value) -> Result<Value> {
+        let result = self
+            .request("tools/call", json!({ "name": name, "arguments": arguments }))
+            .await?;
+        let text = result["content"][0]["text"]
+            .as_str()
+            .ok_or_else(|| anyhow!("{} returned no text content: {}", name, result))?;
+        if result["isError"] == true {
+            bail!("{} returned an error: {}", name, text);
+        }
+        let mut envelope: Value = serde_json::from_str(text)?;
+        Ok(envelope["data"].take())
+    }
+
+    /// Read a resource and parse its JSON text
+    async fn read_resource(&mut self, uri: &str) -> Result<Value> {
+        let result = self.request("resources/read", json!({ "uri": uri })).await?;
+        let text = result["contents"][0]["text"]
+            .as_str()
+            .ok_or_else(|| anyhow!("{} has no text content", uri))?;
+        Ok(serde_json::from_str(text)?)
+    }
+}
+
+fn step(description: &str) {
+    println!("- {}", description);
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Use a real Theater server if one is given, otherwise start the mock
+    let (theater_address, _mock) = match std::env::var("THEATER_ADDRESS") {
+        Ok(address) => {
+            common::require_fixture_component()?;
+            (address, None)
+        }
+        Err(_) => {
+            let mock = MockTheater::start().await?;
+            (mock.address().to_string(), Some(mock))
+        }
+    };
+    println!("Using Theater at {}", theater_address);
+
+    let server = common::server_binary();
+    if !std::path::Path::new(&server).exists() {
+        bail!("{} not found; run `cargo build` first or set THEATER_MCP_SERVER", server);
+    }
+    let (transport, responses) = mcp_client::transport::stdio::StdioTransport::new(
+        &server,
+        vec!["--theater-address".to_string(), theater_address],
+    );
+    transport.start().await?;
+    let mut session = Session { transport, responses, next_id: 0 };
+
+    step("initialize");
+    session
+        .request(
+            "initialize",
+            json!({
+                "protocolVersion": "2024-11-05",
+                "clientInfo": { "name": "e2e-harness", "version": "0.1.0" },
+                "capabilities": {}
+            }),
+        )
+        .await?;
+    session
+        .transport
+        .send(JsonRpcMessage::Notification {
+            jsonrpc: "2.0".to_string(),
+            method: "notifications/initialized".to_string(),
+            params: None,
+        })
+        .await?;
+
+    step("list tools");
+    let tools = session.request("tools/list", json!({})).await?;
+    let tool_names: Vec<&str> = tools["tools"]
+        .as_array()
+        .map(|tools| tools.iter().filter_map(|tool| tool["name"].as_str()).collect())
+        .unwrap_or_default();
+    for required in ["start_actor", "send_message", "request_message", "open_channel"] {
+        if !tool_names.contains(&required) {
+            bail!("tools/list is missing {}", required);
+        }
+    }
+
+    step("start the echo actor");
+    let manifest = common::fixture_manifest();
+    let started = session
+        .call_tool("start_actor", json!({ "manifest": manifest.to_string_lossy() }))
+        .await?;
+    let actor_id = started["actor_id"]
+        .as_str()
+        .ok_or_else(|| anyhow!("start_actor returned no actor_id: {}", started))?
+        .to_string();
+
+    step("read the actor's details");
+    let details = session.read_resource(&format!("theater://actor/{}", actor_id)).await?;
+    if details["id"] != actor_id.as_str() {
+        bail!("unexpected actor details: {}", details);
+    }
+
+    step("send a message");
+    session
+        .call_tool(
+            "send_message",
+            json!({ "actor_id": actor_id, "data": BASE64.encode("Hello from the harness") }),
+        )
+        .await?;
+
+    step("make a request");
+    let reply = session
+        .call_tool(
+            "request_message",
+            json!({ "actor_id": actor_id, "data": BASE64.encode("ping") }),
+        )
+        .await?;
+    let response = BASE64.decode(reply["response"].as_str().unwrap_or_default())?;
+    if response != b"ping" {
+        bail!("echo actor replied {:?}", String::from_utf8_lossy(&response));
+    }
+
+    step("open, use and close a channel");
+    let channel = session
+        .call_tool("open_channel", json!({ "actor_id": actor_id, "encoding": "utf8" }))
+        .await?;
+    let channel_id = channel["channel_id"].as_str().unwrap_or_default().to_string();
+    let echoed = session
+        .call_tool(
+            "channel_request",
+            json!({ "channel_id": channel_id, "message": "Message via channel" }),
+        )
+        .await?;
+    if echoed["reply"] != "Message via channel" {
+        bail!("unexpected channel reply: {}", echoed);
+    }
+    session.call_tool("close_channel", json!({ "channel_id": channel_id })).await?;
+
+    step("read the actor's events");
+    let events = session.read_resource(&format!("theater://events/{}", actor_id)).await?;
+    if events.as_array().map_or(true, |events| events.is_empty()) {
+        bail!("the actor recorded no events");
+    }
+
+    step("stop the actor");
+    session.call_tool("stop_actor", json!({ "actor_id": actor_id })).await?;
+
+    println!("Hello-world flow passed");
+    Ok(())
+}
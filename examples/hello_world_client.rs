@@ -1,3 +1,5 @@
+mod common;
+
 use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use mcp_client::transport::Transport;
@@ -13,10 +15,10 @@ async fn main() -> Result<()> {
 
     // Create a transport connection to the server
     let (transport, mut recv) = mcp_client::transport::stdio::StdioTransport::new(
-        "/Users/colinrozzi/work/mcp-servers/theater-mcp-server/target/debug/theater-mcp-server",
+        &common::server_binary(),
         vec![
             "--theater-address".to_string(),
-            "127.0.0.1:9000".to_string(),
+            common::theater_address(),
         ],
     );
 
@@ -92,7 +94,8 @@ async fn main() -> Result<()> {
 
     // Step 3: Start our Hello World actor - use tools/call as specified in the protocol
     println!("\nStarting Hello World Actor...");
-    let manifest_path = "/Users/colinrozzi/work/actors/hello-world-actor/manifest.toml";
+    common::require_fixture_component()?;
+    let manifest_path = common::fixture_manifest();
     let start_actor_msg: JsonRpcMessage = JsonRpcMessage::Request {
         jsonrpc: "2.0".to_string(),
         id: "4".into(),
@@ -100,7 +103,7 @@ async fn main() -> Result<()> {
         params: Some(json!({
             "name": "start_actor",
             "arguments": {
-                "manifest": manifest_path,
+                "manifest": manifest_path.to_string_lossy(),
                 "initial_state": {"greeting": "Hello from MCP client!"} // Initial state as a simple JSON object
             }
         })),
@@ -357,8 +360,8 @@ fn extract_actor_id(response: &Option<JsonRpcMessage>) -> Result<String> {
                                     // Parse the JSON string inside the text field
                                     match serde_json::from_str::<serde_json::Value>(text) {
                                         Ok(parsed_json) => {
-                                            // The parsed JSON directly contains actor_id, no nested "json" field
-                                            if let Some(actor_id) = parsed_json.get("actor_id") {
+                                            // The tool's result is under the envelope's "data"
+                                            if let Some(actor_id) = parsed_json["data"].get("actor_id") {
                                                 return Ok(actor_id
                                                     .as_str()
                                                     .unwrap_or("")
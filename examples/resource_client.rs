@@ -1,3 +1,5 @@
+mod common;
+
 use anyhow::Result;
 use mcp_client::transport::Transport;
 use mcp_protocol::JsonRpcMessage;
@@ -12,10 +14,10 @@ async fn main() -> Result<()> {
 
     // Create a transport connection to the server
     let (transport, mut recv) = mcp_client::transport::stdio::StdioTransport::new(
-        "/Users/colinrozzi/work/mcp-servers/theater-mcp-server/target/debug/theater-mcp-server",
+        &common::server_binary(),
         vec![
             "--theater-address".to_string(),
-            "127.0.0.1:9000".to_string(),
+            common::theater_address(),
         ],
     );
 
@@ -1,3 +1,5 @@
+mod common;
+
 use anyhow::Result;
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use mcp_client::transport::Transport;
@@ -13,10 +15,10 @@ async fn main() -> Result<()> {
 
     // Create a transport connection to the server
     let (transport, mut recv) = mcp_client::transport::stdio::StdioTransport::new(
-        "/Users/colinrozzi/work/mcp-servers/theater-mcp-server/target/debug/theater-mcp-server",
+        &common::server_binary(),
         vec![
             "--theater-address".to_string(),
-            "127.0.0.1:9000".to_string(),
+            common::theater_address(),
         ],
     );
 
@@ -7,8 +7,8 @@ if ! nc -z localhost 9000 2>/dev/null; then
   exit 1
 fi
 
-echo "Building the hello-world client..."
-cargo build --example hello_world_client
+echo "Building the server and the hello-world client..."
+cargo build --bin theater-mcp-server --example hello_world_client
 
 echo "Running the hello-world client test..."
 RUST_BACKTRACE=1 cargo run --example hello_world_client
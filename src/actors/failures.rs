@@ -0,0 +1,230 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use mcp_server::resources::ResourceManager;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::actors::ActorRegistry;
+use crate::events::render::{decode_payload, event_kind};
+use crate::events::{ActorEvent, EventFilter, EventStreamHub};
+use crate::resources::WORKSPACE_URI;
+use crate::theater::client::TheaterClient;
+use crate::theater::{TheaterId, TheaterIdExt};
+
+/// How often the watcher starts streams for newly started actors
+const ACTOR_DISCOVERY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What the server does when an actor it started fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailurePolicy {
+    /// Tell the client the actor's resources changed; leave the actor alone
+    #[default]
+    Notify,
+    /// Restart the actor, up to `max_restarts` times, and notify
+    Restart,
+    /// Report the actor as FAILED in its resources until it is restarted or
+    /// stopped, and notify
+    MarkFailed,
+}
+
+impl std::str::FromStr for FailurePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "notify" => Ok(Self::Notify),
+            "restart" => Ok(Self::Restart),
+            "mark-failed" => Ok(Self::MarkFailed),
+            other => Err(anyhow!("Unknown failure policy '{}' (expected notify, restart or mark-failed)", other)),
+        }
+    }
+}
+
+/// How actor failures are detected and handled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FailureConfig {
+    pub policy: FailurePolicy,
+    /// Event type patterns (`*` matches any run of characters) that mean an actor failed
+    pub event_types: Vec<String>,
+    /// Restarts per actor before the restart policy gives up and marks it failed
+    pub max_restarts: u32,
+}
+
+impl Default for FailureConfig {
+    fn default() -> Self {
+        Self {
+            policy: FailurePolicy::Notify,
+            event_types: vec!["*error*".to_string(), "*panic*".to_string(), "*fail*".to_string()],
+            max_restarts: 3,
+        }
+    }
+}
+
+/// The last failure seen for an actor
+#[derive(Debug, Clone, Serialize)]
+pub struct ActorFailure {
+    pub at: DateTime<Utc>,
+    pub event_type: String,
+    /// The failure event's payload, decoded where possible
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<serde_json::Value>,
+    /// Restarts made by the restart policy so far
+    pub restarts: u32,
+    /// Whether the actor's resources report it as FAILED
+    pub marked: bool,
+}
+
+/// Watches the actors started through the server for failure events and
+/// handles them according to the [`FailurePolicy`].
+///
+/// Failures are recorded in the [`ActorRegistry`], so the actor's resources
+/// and the session workspace show them, and the client is sent
+/// `notifications/resources/updated` for the actor's resources.
+pub struct FailureWatcher {
+    config: FailureConfig,
+    filters: Vec<EventFilter>,
+    theater_client: Arc<TheaterClient>,
+    actor_registry: Arc<ActorRegistry>,
+    event_stream: Arc<EventStreamHub>,
+    resource_manager: Option<Arc<ResourceManager>>,
+}
+
+impl FailureWatcher {
+    pub fn new(
+        config: FailureConfig,
+        theater_client: Arc<TheaterClient>,
+        actor_registry: Arc<ActorRegistry>,
+        event_stream: Arc<EventStreamHub>,
+    ) -> Self {
+        let filters = config
+            .event_types
+            .iter()
+            .map(|pattern| EventFilter::all().with_event_type(pattern))
+            .collect();
+        Self {
+            config,
+            filters,
+            theater_client,
+            actor_registry,
+            event_stream,
+            resource_manager: None,
+        }
+    }
+
+    /// Notify the client through this resource manager
+    pub fn with_resource_manager(mut self, resource_manager: Arc<ResourceManager>) -> Self {
+        self.resource_manager = Some(resource_manager);
+        self
+    }
+
+    /// Whether an event means its actor failed
+    pub fn is_failure(&self, event: &ActorEvent) -> bool {
+        self.filters.iter().any(|filter| filter.matches(&event.event))
+    }
+
+    /// Handle a failure event of a tracked actor
+    pub async fn handle_failure(&self, event: &ActorEvent) {
+        let actor_id = event.actor_id.as_str();
+        let restarts = self.actor_registry.failure(actor_id).map_or(0, |failure| failure.restarts);
+        warn!(
+            "Actor {} failed ({}: {})",
+            actor_id,
+            event_kind(&event.event.event_type),
+            event.event.event_type
+        );
+
+        let mut failure = ActorFailure {
+            at: event.received_at,
+            event_type: event.event.event_type.clone(),
+            detail: decode_payload(&event.event.data),
+            restarts,
+            marked: self.config.policy == FailurePolicy::MarkFailed,
+        };
+
+        if self.config.policy == FailurePolicy::Restart {
+            if restarts >= self.config.max_restarts {
+                warn!("Actor {} failed after {} restarts; marking it failed", actor_id, restarts);
+                failure.marked = true;
+            } else {
+                match self.restart(actor_id).await {
+                    Ok(()) => {
+                        info!("Restarted failed actor {}", actor_id);
+                        failure.restarts += 1;
+                    }
+                    Err(e) => {
+                        warn!("Could not restart failed actor {}: {}", actor_id, e);
+                        failure.marked = true;
+                    }
+                }
+            }
+        }
+
+        self.actor_registry.record_failure(actor_id, failure);
+        self.notify(actor_id).await;
+    }
+
+    async fn restart(&self, actor_id: &str) -> Result<()> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        self.theater_client.restart_actor(&theater_id).await
+    }
+
+    /// Tell the client the resources describing the actor changed
+    async fn notify(&self, actor_id: &str) {
+        let Some(resource_manager) = &self.resource_manager else {
+            return;
+        };
+        for uri in [
+            format!("theater://actor/{}", actor_id),
+            "theater://actors".to_string(),
+            WORKSPACE_URI.to_string(),
+        ] {
+            if let Err(e) = resource_manager.notify_resource_updated(&uri, None).await {
+                debug!("Failed to notify client of change to {}: {}", uri, e);
+            }
+        }
+    }
+
+    /// Make sure every tracked actor has a running event stream
+    async fn watch_tracked_actors(&self) {
+        for record in self.actor_registry.list() {
+            if let Err(e) = self.event_stream.watch(&record.actor_id).await {
+                debug!("Could not stream events from {} to watch for failures: {}", record.actor_id, e);
+            }
+        }
+    }
+
+    /// Watch for failures until the task is aborted
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        info!("Handling actor failures with the {:?} policy", self.config.policy);
+        tokio::spawn(async move {
+            let mut events = self.event_stream.subscribe_all();
+            let mut discovery = tokio::time::interval(ACTOR_DISCOVERY_INTERVAL);
+
+            loop {
+                tokio::select! {
+                    _ = discovery.tick() => {
+                        self.watch_tracked_actors().await;
+                    }
+                    event = events.recv() => match event {
+                        Ok(event) => {
+                            // Only actors started through the server are handled
+                            if self.actor_registry.get(&event.actor_id).is_some() && self.is_failure(&event) {
+                                self.handle_failure(&event).await;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                            warn!("Failure watcher lagged; skipped {} events", skipped);
+                        }
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,10 @@
+mod failures;
+mod profiles;
+mod registry;
+
+pub use failures::{ActorFailure, FailureConfig, FailurePolicy, FailureWatcher};
+pub use profiles::{ActorProfile, ActorProfiles};
+pub use registry::{ActorQuota, ActorRecord, ActorRegistry, ActorReservation};
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::tools::check_name;
+
+/// A named way of starting an actor, so clients can launch it without
+/// knowing where its manifest lives
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActorProfile {
+    /// Manifest path or content, as start_actor takes it
+    pub manifest: String,
+    /// What the profile starts, shown to clients
+    pub description: Option<String>,
+    /// State the actor starts with unless start_profile gives one
+    pub initial_state: Option<Value>,
+    /// Register the tools the actor exports as `actor.{alias}.{name}`
+    pub alias: Option<String>,
+    /// Stop the actor when the session that started it ends; the server
+    /// default applies if unset
+    pub owned: Option<bool>,
+}
+
+/// Launch profiles by name, e.g. loaded from a profiles file:
+///
+/// ```json
+/// { "counter": { "manifest": "/actors/counter.toml", "initial_state": { "count": 0 },
+///                "alias": "counter", "owned": true } }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ActorProfiles(BTreeMap<String, ActorProfile>);
+
+impl ActorProfiles {
+    /// Load profiles from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read actor profiles {}", path.display()))?;
+        let profiles: Self = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid actor profiles {}", path.display()))?;
+        profiles.validate()
+            .with_context(|| format!("Invalid actor profiles {}", path.display()))?;
+        Ok(profiles)
+    }
+
+    /// Add or replace a profile
+    pub fn with_profile(mut self, name: impl Into<String>, profile: ActorProfile) -> Self {
+        self.0.insert(name.into(), profile);
+        self
+    }
+
+    /// Check that every profile has a manifest and a usable alias
+    pub fn validate(&self) -> Result<()> {
+        for (name, profile) in &self.0 {
+            if profile.manifest.is_empty() {
+                return Err(anyhow!("Profile '{}' has no manifest", name));
+            }
+            if let Some(alias) = &profile.alias {
+                check_name("alias", alias)
+                    .with_context(|| format!("Profile '{}'", name))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ActorProfile> {
+        self.0.get(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.0.keys().map(String::as_str).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ActorProfile)> {
+        self.0.iter().map(|(name, profile)| (name.as_str(), profile))
+    }
+}
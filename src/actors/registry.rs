@@ -0,0 +1,280 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::actors::failures::ActorFailure;
+use crate::session::SessionId;
+use crate::theater::client::TheaterClient;
+use crate::theater::types::TheaterError;
+use crate::theater::{TheaterId, TheaterIdExt};
+
+/// Caps on running actors started through this server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActorQuota {
+    /// Most actors one session may have running; unlimited if unset
+    pub max_per_session: Option<usize>,
+    /// Most actors all sessions together may have running; unlimited if unset
+    pub max_total: Option<usize>,
+}
+
+impl ActorQuota {
+    /// Check whether one more actor may start, given how many are running
+    /// for the session and in total
+    pub fn check(&self, session_count: usize, total_count: usize) -> Result<(), TheaterError> {
+        if let Some(max) = self.max_per_session {
+            if session_count >= max {
+                return Err(TheaterError::QuotaExceeded(format!(
+                    "this session already has {} running actors (limit {})",
+                    session_count, max
+                )));
+            }
+        }
+        if let Some(max) = self.max_total {
+            if total_count >= max {
+                return Err(TheaterError::QuotaExceeded(format!(
+                    "{} actors started through this server are running (limit {})",
+                    total_count, max
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An actor started through MCP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActorRecord {
+    pub actor_id: String,
+    /// Session that started the actor
+    pub session_id: String,
+    pub manifest: String,
+    pub started_at: DateTime<Utc>,
+    /// Whether the actor is stopped when its session ends
+    pub owned: bool,
+}
+
+#[derive(Default)]
+struct Ledger {
+    actors: HashMap<String, ActorRecord>,
+    // Starts in progress, counted against the quota until they finish
+    pending: HashMap<SessionId, usize>,
+    // Last failure of each tracked actor that has failed
+    failures: HashMap<String, ActorFailure>,
+}
+
+impl Ledger {
+    /// Actors running or starting for a session, and in total
+    fn counts(&self, session_id: &SessionId) -> (usize, usize) {
+        let running = self
+            .actors
+            .values()
+            .filter(|record| record.session_id == session_id.as_str())
+            .count();
+        let pending = self.pending.get(session_id).copied().unwrap_or(0);
+        let total = self.actors.len() + self.pending.values().sum::<usize>();
+        (running + pending, total)
+    }
+}
+
+/// Registry of actors started via the actor tools.
+///
+/// Every actor is associated with the MCP session that started it, so the
+/// number each session (and the server as a whole) runs can be capped, and
+/// actors the session owns can be stopped when it ends.
+pub struct ActorRegistry {
+    theater_client: Arc<TheaterClient>,
+    quota: ActorQuota,
+    ledger: Mutex<Ledger>,
+}
+
+impl ActorRegistry {
+    /// Create a new, empty actor registry
+    pub fn new(theater_client: Arc<TheaterClient>, quota: ActorQuota) -> Self {
+        Self {
+            theater_client,
+            quota,
+            ledger: Mutex::new(Ledger::default()),
+        }
+    }
+
+    /// Reserve room in the quota for an actor the session is about to start.
+    ///
+    /// The reservation counts against the quota until it is committed with
+    /// the started actor's ID, or released by dropping it if the start fails.
+    pub async fn reserve(&self, session_id: &SessionId) -> Result<ActorReservation<'_>> {
+        if let Ok(reservation) = self.try_reserve(session_id) {
+            return Ok(reservation);
+        }
+
+        // Tracked actors may have stopped on their own since they were started
+        self.prune().await;
+        Ok(self.try_reserve(session_id)?)
+    }
+
+    fn try_reserve(&self, session_id: &SessionId) -> Result<ActorReservation<'_>, TheaterError> {
+        let mut ledger = self.ledger.lock().unwrap();
+        let (session_count, total_count) = ledger.counts(session_id);
+        self.quota.check(session_count, total_count)?;
+        *ledger.pending.entry(session_id.clone()).or_default() += 1;
+        Ok(ActorReservation {
+            registry: self,
+            session_id: session_id.clone(),
+        })
+    }
+
+    /// Stop tracking actors that are no longer running on the Theater server
+    async fn prune(&self) {
+        let running: HashSet<String> = match self.theater_client.list_actors().await {
+            Ok(actors) => actors.iter().map(|id| id.as_string()).collect(),
+            Err(e) => {
+                debug!("Could not list actors to refresh the actor registry: {}", e);
+                return;
+            }
+        };
+
+        let mut ledger = self.ledger.lock().unwrap();
+        let before = ledger.actors.len();
+        ledger.actors.retain(|actor_id, _| running.contains(actor_id));
+        ledger.failures.retain(|actor_id, _| running.contains(actor_id));
+        if ledger.actors.len() < before {
+            info!("{} tracked actors are no longer running", before - ledger.actors.len());
+        }
+    }
+
+    /// Track actors recorded by an earlier run of the server, if they are
+    /// still running, as belonging to the given session. Returns how many
+    /// were restored.
+    pub async fn restore(&self, records: Vec<ActorRecord>, session_id: &SessionId) -> Result<usize> {
+        let running: HashSet<String> = self
+            .theater_client
+            .list_actors()
+            .await?
+            .iter()
+            .map(|id| id.as_string())
+            .collect();
+
+        let mut ledger = self.ledger.lock().unwrap();
+        let mut restored = 0;
+        for mut record in records {
+            if !running.contains(&record.actor_id) {
+                debug!("Not restoring actor {}: no longer running", record.actor_id);
+                continue;
+            }
+            record.session_id = session_id.to_string();
+            ledger.actors.insert(record.actor_id.clone(), record);
+            restored += 1;
+        }
+        Ok(restored)
+    }
+
+    /// Look up a tracked actor
+    pub fn get(&self, actor_id: &str) -> Option<ActorRecord> {
+        self.ledger.lock().unwrap().actors.get(actor_id).cloned()
+    }
+
+    /// Stop tracking an actor, e.g. because it was stopped
+    pub fn forget(&self, actor_id: &str) -> Option<ActorRecord> {
+        let mut ledger = self.ledger.lock().unwrap();
+        ledger.failures.remove(actor_id);
+        ledger.actors.remove(actor_id)
+    }
+
+    /// Record that a tracked actor failed, replacing its previous failure
+    pub fn record_failure(&self, actor_id: &str, failure: ActorFailure) {
+        let mut ledger = self.ledger.lock().unwrap();
+        if ledger.actors.contains_key(actor_id) {
+            ledger.failures.insert(actor_id.to_string(), failure);
+        }
+    }
+
+    /// The last failure of an actor, if it has failed
+    pub fn failure(&self, actor_id: &str) -> Option<ActorFailure> {
+        self.ledger.lock().unwrap().failures.get(actor_id).cloned()
+    }
+
+    /// Forget an actor's failure, e.g. because it was restarted by hand
+    pub fn clear_failure(&self, actor_id: &str) -> Option<ActorFailure> {
+        self.ledger.lock().unwrap().failures.remove(actor_id)
+    }
+
+    /// List all tracked actors
+    pub fn list(&self) -> Vec<ActorRecord> {
+        self.ledger.lock().unwrap().actors.values().cloned().collect()
+    }
+
+    /// List the actors started by a session
+    pub fn list_for_session(&self, session_id: &SessionId) -> Vec<ActorRecord> {
+        self.list()
+            .into_iter()
+            .filter(|record| record.session_id == session_id.as_str())
+            .collect()
+    }
+
+    /// Stop every actor a session owns
+    pub async fn stop_session(&self, session_id: &SessionId) {
+        let owned: Vec<ActorRecord> = self
+            .list_for_session(session_id)
+            .into_iter()
+            .filter(|record| record.owned)
+            .collect();
+
+        if !owned.is_empty() {
+            info!("Stopping {} owned actors for ended session {}", owned.len(), session_id);
+        }
+        for record in owned {
+            // Stop tracking first so a failed stop is not retried forever
+            self.forget(&record.actor_id);
+
+            let result = match TheaterId::from_str(&record.actor_id) {
+                Ok(actor_id) => self.theater_client.stop_actor(&actor_id).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                warn!("Failed to stop owned actor {}: {}", record.actor_id, e);
+            }
+        }
+    }
+}
+
+/// Room in the actor quota held for a start in progress
+pub struct ActorReservation<'a> {
+    registry: &'a ActorRegistry,
+    session_id: SessionId,
+}
+
+impl ActorReservation<'_> {
+    /// Record the started actor in place of the reservation
+    pub fn commit(self, actor_id: &str, manifest: &str, owned: bool) -> ActorRecord {
+        let record = ActorRecord {
+            actor_id: actor_id.to_string(),
+            session_id: self.session_id.to_string(),
+            manifest: manifest.to_string(),
+            started_at: Utc::now(),
+            owned,
+        };
+        debug!("Registered actor {} for session {}", actor_id, self.session_id);
+        self.registry
+            .ledger
+            .lock()
+            .unwrap()
+            .actors
+            .insert(actor_id.to_string(), record.clone());
+        record
+    }
+}
+
+impl Drop for ActorReservation<'_> {
+    fn drop(&mut self) {
+        let mut ledger = self.registry.ledger.lock().unwrap();
+        if let Some(pending) = ledger.pending.get_mut(&self.session_id) {
+            *pending -= 1;
+            if *pending == 0 {
+                ledger.pending.remove(&self.session_id);
+            }
+        }
+    }
+}
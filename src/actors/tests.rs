@@ -0,0 +1,41 @@
+use crate::actors::{ActorProfile, ActorProfiles, ActorQuota};
+use serde_json::json;
+use crate::theater::types::TheaterError;
+
+#[test]
+fn test_actor_quota() {
+    let quota = ActorQuota {
+        max_per_session: Some(2),
+        max_total: Some(3),
+    };
+
+    assert!(quota.check(1, 1).is_ok());
+    // The session is at its own cap
+    assert!(matches!(quota.check(2, 2), Err(TheaterError::QuotaExceeded(_))));
+    // Other sessions used up the server-wide cap
+    assert!(matches!(quota.check(0, 3), Err(TheaterError::QuotaExceeded(_))));
+
+    assert!(ActorQuota::default().check(1000, 1000).is_ok());
+}
+
+#[test]
+fn test_actor_profiles() {
+    let profiles: ActorProfiles = serde_json::from_value(json!({
+        "counter": { "manifest": "/actors/counter.toml", "initial_state": { "count": 0 }, "alias": "counter" },
+        "echo": { "manifest": "/actors/echo.toml" }
+    }))
+    .unwrap();
+    assert!(profiles.validate().is_ok());
+    assert_eq!(profiles.names(), vec!["counter", "echo"]);
+    assert_eq!(profiles.get("counter").unwrap().initial_state, Some(json!({ "count": 0 })));
+    assert_eq!(profiles.get("echo").unwrap().owned, None);
+
+    // Aliases become part of tool names
+    let bad_alias = profiles.clone().with_profile("bad", ActorProfile {
+        manifest: "/actors/bad.toml".to_string(),
+        alias: Some("not an alias".to_string()),
+        ..Default::default()
+    });
+    assert!(bad_alias.validate().is_err());
+    assert!(ActorProfiles::default().with_profile("empty", ActorProfile::default()).validate().is_err());
+}
@@ -0,0 +1,95 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Shortest bearer token accepted, so a typo'd or placeholder file isn't used
+const MIN_TOKEN_LEN: usize = 16;
+
+/// Authentication required from clients of network transports.
+///
+/// The stdio transport is only reachable by the process that spawned the
+/// server, so it is never authenticated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// File holding the bearer token clients must present
+    pub token_file: Option<PathBuf>,
+}
+
+impl AuthConfig {
+    /// Load the configured bearer token, if any
+    pub fn bearer_auth(&self) -> Result<Option<BearerAuth>> {
+        self.token_file.as_deref().map(BearerAuth::from_file).transpose()
+    }
+}
+
+/// Why a connection was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// No `Authorization` header, or not a bearer token
+    Missing,
+    /// A bearer token that doesn't match
+    Invalid,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Missing => write!(f, "missing bearer token"),
+            AuthError::Invalid => write!(f, "invalid bearer token"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Checks the bearer token presented by a connecting client
+#[derive(Clone)]
+pub struct BearerAuth {
+    token: String,
+}
+
+impl BearerAuth {
+    pub fn new(token: impl Into<String>) -> Result<Self> {
+        let token = token.into();
+        if token.len() < MIN_TOKEN_LEN {
+            return Err(anyhow!("Bearer token must be at least {} characters", MIN_TOKEN_LEN));
+        }
+        Ok(Self { token })
+    }
+
+    /// Read the token from a file, ignoring surrounding whitespace
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read bearer token from {}", path.display()))?;
+        Self::new(contents.trim())
+    }
+
+    /// Check the value of a request's `Authorization` header
+    pub fn check(&self, authorization: Option<&str>) -> Result<(), AuthError> {
+        let presented = authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::Missing)?;
+        if constant_time_eq(presented.trim().as_bytes(), self.token.as_bytes()) {
+            Ok(())
+        } else {
+            Err(AuthError::Invalid)
+        }
+    }
+}
+
+impl fmt::Debug for BearerAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BearerAuth").finish_non_exhaustive()
+    }
+}
+
+/// Compare without exiting early, so response timing doesn't reveal how much
+/// of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,119 @@
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::RwLock;
+use tracing::{debug, info};
+
+/// Live actor event streaming extension
+pub const EVENT_STREAMING: &str = "theater/eventStreaming";
+
+/// Buffered inbound channel messages extension
+pub const CHANNEL_INBOX: &str = "theater/channelInbox";
+
+/// JSON Patch diffs in actor state update notifications
+pub const STATE_DIFFS: &str = "theater/stateDiffs";
+
+/// Theater-specific extensions advertised under the `experimental` capabilities key.
+///
+/// Every extension carries a version string. Clients opt in by echoing the
+/// extension name with a version of the same major number in their own
+/// `experimental` capabilities during `initialize`.
+pub struct ExperimentalCapabilities {
+    offered: BTreeMap<&'static str, &'static str>,
+    enabled: RwLock<HashSet<String>>,
+}
+
+impl ExperimentalCapabilities {
+    /// Create the default set of offered extensions
+    pub fn new() -> Self {
+        let mut offered = BTreeMap::new();
+        offered.insert(EVENT_STREAMING, "0.1.0");
+        offered.insert(CHANNEL_INBOX, "0.1.0");
+        offered.insert(STATE_DIFFS, "0.1.0");
+
+        Self {
+            offered,
+            enabled: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Stop offering an extension, e.g. because the Theater server can't back it
+    pub fn without(mut self, name: &str) -> Self {
+        self.offered.remove(name);
+        self
+    }
+
+    /// Build the value advertised under `capabilities.experimental`
+    pub fn advertised(&self) -> Value {
+        let mut map = Map::new();
+        for (name, version) in &self.offered {
+            let mut entry = Map::new();
+            entry.insert("version".to_string(), Value::String(version.to_string()));
+            map.insert(name.to_string(), Value::Object(entry));
+        }
+        Value::Object(map)
+    }
+
+    /// Negotiate against the client's `experimental` capabilities.
+    ///
+    /// Returns the names of the extensions that were enabled.
+    pub fn negotiate(&self, client_experimental: Option<&Value>) -> Vec<String> {
+        let mut enabled = self.enabled.write().unwrap();
+        enabled.clear();
+
+        let requested = match client_experimental.and_then(|v| v.as_object()) {
+            Some(requested) => requested,
+            None => {
+                debug!("Client did not request any experimental capabilities");
+                return Vec::new();
+            }
+        };
+
+        for (name, version) in &self.offered {
+            let Some(entry) = requested.get(*name) else {
+                continue;
+            };
+
+            // Accept either `{"version": "x.y.z"}` or a bare `true`
+            let client_version = match entry {
+                Value::Object(obj) => obj.get("version").and_then(|v| v.as_str()),
+                Value::Bool(true) => Some(*version),
+                _ => None,
+            };
+
+            match client_version {
+                Some(client_version) if same_major(client_version, version) => {
+                    info!("Enabled experimental capability {} ({})", name, version);
+                    enabled.insert(name.to_string());
+                }
+                Some(client_version) => {
+                    debug!(
+                        "Client requested {} version {}, but server offers {}",
+                        name, client_version, version
+                    );
+                }
+                None => {}
+            }
+        }
+
+        let mut names: Vec<String> = enabled.iter().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Check whether the client opted in to an extension
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled.read().unwrap().contains(name)
+    }
+}
+
+impl Default for ExperimentalCapabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compare the major component of two version strings
+fn same_major(a: &str, b: &str) -> bool {
+    let major = |v: &str| v.split('.').next().unwrap_or("").to_string();
+    major(a) == major(b)
+}
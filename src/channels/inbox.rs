@@ -0,0 +1,219 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::futures::Notified;
+use tokio::sync::Notify;
+
+/// What to do with an inbound message when a channel's buffer is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Evict the oldest buffered messages to make room
+    #[default]
+    DropOldest,
+    /// Reject the new message; the next read reports the overflow as an error
+    Reject,
+}
+
+impl std::str::FromStr for OverflowPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "drop-oldest" | "drop_oldest" => Ok(OverflowPolicy::DropOldest),
+            "reject" => Ok(OverflowPolicy::Reject),
+            other => Err(anyhow::anyhow!(
+                "Unknown overflow policy '{}' (expected 'drop-oldest' or 'reject')",
+                other
+            )),
+        }
+    }
+}
+
+/// Limits on the inbound message buffer of a single channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InboxLimits {
+    pub max_messages: usize,
+    pub max_bytes: usize,
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for InboxLimits {
+    fn default() -> Self {
+        Self {
+            max_messages: 1000,
+            max_bytes: 4 * 1024 * 1024,
+            overflow: OverflowPolicy::DropOldest,
+        }
+    }
+}
+
+/// A message received on a channel
+#[derive(Debug, Clone)]
+pub struct InboundMessage {
+    /// Position of the message in the channel's inbound stream, starting at 1
+    pub seq: u64,
+    pub received_at: DateTime<Utc>,
+    pub data: Vec<u8>,
+}
+
+/// Result of buffering an inbound message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    Buffered,
+    /// The message was buffered after evicting this many older messages
+    Evicted(usize),
+    Rejected,
+}
+
+#[derive(Debug, Default)]
+struct InboxState {
+    messages: VecDeque<InboundMessage>,
+    bytes: usize,
+    dropped: u64,
+    rejected_since_read: u64,
+    last_seq: u64,
+}
+
+/// Bounded buffer of messages received on a channel
+#[derive(Debug)]
+pub struct ChannelInbox {
+    limits: InboxLimits,
+    state: Mutex<InboxState>,
+    notify: Notify,
+}
+
+impl ChannelInbox {
+    pub fn new(limits: InboxLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(InboxState::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Buffer a message, applying the overflow policy if limits are exceeded
+    pub fn push(&self, data: Vec<u8>) -> PushOutcome {
+        let mut state = self.state.lock().unwrap();
+        let size = data.len();
+
+        // A single message larger than the whole buffer can never fit
+        if size > self.limits.max_bytes {
+            state.dropped += 1;
+            state.rejected_since_read += 1;
+            return PushOutcome::Rejected;
+        }
+
+        let fits = |state: &InboxState| {
+            state.messages.len() < self.limits.max_messages
+                && state.bytes + size <= self.limits.max_bytes
+        };
+
+        let mut evicted = 0;
+        if !fits(&state) {
+            match self.limits.overflow {
+                OverflowPolicy::Reject => {
+                    state.dropped += 1;
+                    state.rejected_since_read += 1;
+                    return PushOutcome::Rejected;
+                }
+                OverflowPolicy::DropOldest => {
+                    while !fits(&state) {
+                        match state.messages.pop_front() {
+                            Some(old) => {
+                                state.bytes -= old.data.len();
+                                state.dropped += 1;
+                                evicted += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+        }
+
+        state.bytes += size;
+        state.last_seq += 1;
+        let seq = state.last_seq;
+        state.messages.push_back(InboundMessage {
+            seq,
+            received_at: Utc::now(),
+            data,
+        });
+        drop(state);
+        self.notify.notify_waiters();
+
+        if evicted > 0 {
+            PushOutcome::Evicted(evicted)
+        } else {
+            PushOutcome::Buffered
+        }
+    }
+
+    /// Remove up to `max` buffered messages, oldest first.
+    ///
+    /// Returns the number of messages rejected since the previous read
+    /// alongside the messages.
+    pub fn drain(&self, max: usize) -> (Vec<InboundMessage>, u64) {
+        let mut state = self.state.lock().unwrap();
+        let count = max.min(state.messages.len());
+        let messages: Vec<InboundMessage> = state.messages.drain(..count).collect();
+        state.bytes -= messages.iter().map(|m| m.data.len()).sum::<usize>();
+        let rejected = std::mem::take(&mut state.rejected_since_read);
+        (messages, rejected)
+    }
+
+    /// Remove the first buffered message received after `seq`, leaving
+    /// earlier messages in place
+    pub fn take_after(&self, seq: u64) -> Option<InboundMessage> {
+        let mut state = self.state.lock().unwrap();
+        let index = state.messages.iter().position(|m| m.seq > seq)?;
+        let message = state.messages.remove(index)?;
+        state.bytes -= message.data.len();
+        Some(message)
+    }
+
+    /// Wake everyone waiting on this inbox, e.g. because the channel ended
+    pub fn wake(&self) {
+        self.notify.notify_waiters();
+    }
+
+    /// Sequence number of the most recently received message
+    pub fn last_seq(&self) -> u64 {
+        self.state.lock().unwrap().last_seq
+    }
+
+    /// Number of buffered messages
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of buffered bytes
+    pub fn bytes(&self) -> usize {
+        self.state.lock().unwrap().bytes
+    }
+
+    /// Total messages dropped or rejected because of the limits
+    pub fn dropped(&self) -> u64 {
+        self.state.lock().unwrap().dropped
+    }
+
+    /// Future resolving when a message is next pushed.
+    ///
+    /// Create (and `enable`) it before checking for messages so a push in
+    /// between is not missed.
+    pub fn notified(&self) -> Notified<'_> {
+        self.notify.notified()
+    }
+
+    /// The limits this inbox enforces
+    pub fn limits(&self) -> &InboxLimits {
+        &self.limits
+    }
+}
@@ -0,0 +1,10 @@
+mod inbox;
+mod participant;
+mod registry;
+
+pub use inbox::{ChannelInbox, InboundMessage, InboxLimits, OverflowPolicy, PushOutcome};
+pub use participant::{ParticipantKind, ParticipantRef};
+pub use registry::{ChannelRecord, ChannelRegistry, ChannelState, ChannelStats};
+
+#[cfg(test)]
+mod tests;
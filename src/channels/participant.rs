@@ -0,0 +1,94 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+use theater::id::TheaterId;
+use theater::messages::ChannelParticipant;
+use crate::theater::TheaterIdExt;
+
+/// Kind of endpoint on the other side of a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParticipantKind {
+    Actor,
+    External,
+}
+
+/// MCP-facing description of a `ChannelParticipant`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantRef {
+    #[serde(rename = "type")]
+    pub kind: ParticipantKind,
+    pub id: String,
+}
+
+impl ParticipantRef {
+    /// An actor participant
+    pub fn actor(id: &str) -> Self {
+        Self {
+            kind: ParticipantKind::Actor,
+            id: id.to_string(),
+        }
+    }
+
+    /// An external (non-actor) participant
+    pub fn external(id: &str) -> Self {
+        Self {
+            kind: ParticipantKind::External,
+            id: id.to_string(),
+        }
+    }
+
+    /// Parse tool arguments.
+    ///
+    /// Accepts either a `participant` object (`{"type": "actor" | "external", "id": "..."}`)
+    /// or the legacy `actor_id` string.
+    pub fn from_args(args: &Value) -> Result<Self> {
+        if let Some(participant) = args.get("participant") {
+            let kind = participant["type"].as_str().unwrap_or("actor");
+            let id = participant["id"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Missing participant.id parameter"))?;
+
+            return match kind {
+                "actor" => Ok(Self::actor(id)),
+                "external" => Ok(Self::external(id)),
+                other => Err(anyhow!(
+                    "Unsupported participant type '{}' (expected 'actor' or 'external')",
+                    other
+                )),
+            };
+        }
+
+        let actor_id = args["actor_id"]
+            .as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id or participant parameter"))?;
+        Ok(Self::actor(actor_id))
+    }
+
+    /// The actor ID, if this participant is an actor
+    pub fn actor_id(&self) -> Option<&str> {
+        match self.kind {
+            ParticipantKind::Actor => Some(&self.id),
+            ParticipantKind::External => None,
+        }
+    }
+
+    /// Convert to the Theater wire type
+    pub fn to_theater(&self) -> Result<ChannelParticipant> {
+        match self.kind {
+            ParticipantKind::Actor => Ok(ChannelParticipant::Actor(TheaterId::from_str(&self.id)?)),
+            ParticipantKind::External => Ok(ChannelParticipant::External(self.id.clone())),
+        }
+    }
+}
+
+impl fmt::Display for ParticipantRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParticipantKind::Actor => write!(f, "actor:{}", self.id),
+            ParticipantKind::External => write!(f, "external:{}", self.id),
+        }
+    }
+}
@@ -0,0 +1,539 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::channels::inbox::{ChannelInbox, InboundMessage, InboxLimits, PushOutcome};
+use crate::channels::ParticipantRef;
+use crate::encoding::PayloadEncoding;
+use crate::session::SessionId;
+use crate::theater::client::{ChannelEvent, ChannelStream, TheaterClient};
+use crate::theater::types::TheaterError;
+
+/// Seconds without traffic after which an open channel is reported idle or stalled
+const STALLED_AFTER_SECS: i64 = 300;
+
+/// Channel IDs with new inbound messages kept for slow listeners
+const INBOUND_CAPACITY: usize = 256;
+
+/// Lifecycle state of a tracked channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChannelState {
+    Open,
+    /// Lost with a dropped Theater connection and not re-established
+    Invalidated,
+    Closed,
+}
+
+/// Traffic counters for a channel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelStats {
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+/// Metadata recorded for every channel opened through MCP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelRecord {
+    /// Stable ID handed to MCP clients
+    pub channel_id: String,
+    /// ID of the channel on the Theater server; changes when the channel is reopened
+    pub theater_channel_id: String,
+    pub participant: ParticipantRef,
+    /// Encoding used for messages sent and received on this channel
+    pub encoding: PayloadEncoding,
+    /// Session that opened (and owns) the channel
+    pub session_id: String,
+    /// Whether sessions other than the owner may use the channel
+    pub shared: bool,
+    pub opened_at: DateTime<Utc>,
+    pub state: ChannelState,
+    /// Number of times the channel was reopened after its connection dropped
+    pub reopen_count: u32,
+    /// Why the channel was invalidated, if it was
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalidated_reason: Option<String>,
+    pub stats: ChannelStats,
+}
+
+/// Registry of channels opened via the channel tools.
+///
+/// Every channel is associated with the MCP session that opened it so it
+/// can be closed when that session ends or the server shuts down. Messages
+/// the other participant sends back are buffered in a bounded per-channel
+/// inbox.
+pub struct ChannelRegistry {
+    theater_client: Arc<TheaterClient>,
+    inbox_limits: InboxLimits,
+    channels: RwLock<HashMap<String, ChannelRecord>>,
+    inboxes: RwLock<HashMap<String, Arc<ChannelInbox>>>,
+    // Tasks reading each channel's dedicated Theater connection
+    readers: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    // IDs of channels as messages are buffered in their inboxes
+    inbound: broadcast::Sender<String>,
+}
+
+impl ChannelRegistry {
+    /// Create a new, empty channel registry
+    pub fn new(theater_client: Arc<TheaterClient>, inbox_limits: InboxLimits) -> Self {
+        Self {
+            theater_client,
+            inbox_limits,
+            channels: RwLock::new(HashMap::new()),
+            inboxes: RwLock::new(HashMap::new()),
+            readers: Mutex::new(HashMap::new()),
+            inbound: broadcast::channel(INBOUND_CAPACITY).0,
+        }
+    }
+
+    /// Open a channel and start buffering its inbound messages
+    pub async fn open(
+        self: &Arc<Self>,
+        participant: ParticipantRef,
+        encoding: PayloadEncoding,
+        initial_message: Option<&[u8]>,
+        session_id: &SessionId,
+    ) -> Result<ChannelRecord> {
+        let stream = self
+            .theater_client
+            .open_channel(participant.to_theater()?, initial_message)
+            .await?;
+        let channel_id = stream.channel_id.clone();
+
+        let record = ChannelRecord {
+            channel_id: channel_id.clone(),
+            theater_channel_id: channel_id.clone(),
+            participant,
+            encoding,
+            session_id: session_id.to_string(),
+            shared: false,
+            opened_at: Utc::now(),
+            state: ChannelState::Open,
+            reopen_count: 0,
+            invalidated_reason: None,
+            stats: ChannelStats::default(),
+        };
+
+        debug!("Registered channel {} for session {}", channel_id, session_id);
+        self.channels
+            .write()
+            .unwrap()
+            .insert(channel_id.clone(), record.clone());
+        self.inboxes
+            .write()
+            .unwrap()
+            .insert(channel_id.clone(), Arc::new(ChannelInbox::new(self.inbox_limits.clone())));
+
+        self.spawn_pump(channel_id, stream);
+        Ok(record)
+    }
+
+    /// Reopen a channel recorded by an earlier run of the server, keeping its
+    /// MCP-facing ID, as belonging to the given session.
+    ///
+    /// Channels that can't be reopened stay tracked as invalidated, so
+    /// clients using the old ID learn why it stopped working.
+    pub async fn restore(self: &Arc<Self>, mut record: ChannelRecord, session_id: &SessionId) -> Result<()> {
+        let channel_id = record.channel_id.clone();
+        record.session_id = session_id.to_string();
+        record.state = ChannelState::Open;
+        record.invalidated_reason = None;
+
+        self.channels
+            .write()
+            .unwrap()
+            .insert(channel_id.clone(), record);
+        self.inboxes
+            .write()
+            .unwrap()
+            .insert(channel_id.clone(), Arc::new(ChannelInbox::new(self.inbox_limits.clone())));
+
+        self.reopen(&channel_id).await
+    }
+
+    /// Move messages from a channel's connection into its inbox
+    fn spawn_pump(self: &Arc<Self>, channel_id: String, stream: ChannelStream) {
+        let ChannelStream { mut messages, reader, .. } = stream;
+        if let Some(previous) = self.readers.lock().unwrap().insert(channel_id.clone(), reader) {
+            previous.abort();
+        }
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut closed_by_peer = false;
+            while let Some(event) = messages.recv().await {
+                match event {
+                    ChannelEvent::Message(data) => {
+                        let Some(inbox) = registry.inbox(&channel_id) else {
+                            // Channel was closed locally
+                            return;
+                        };
+                        registry.record_received(&channel_id, data.len());
+                        match inbox.push(data) {
+                            PushOutcome::Buffered => {}
+                            PushOutcome::Evicted(count) => {
+                                warn!("Channel {} inbox full; dropped {} oldest messages", channel_id, count);
+                            }
+                            PushOutcome::Rejected => {
+                                warn!("Channel {} inbox full; rejected inbound message", channel_id);
+                                continue;
+                            }
+                        }
+                        let _ = registry.inbound.send(channel_id.clone());
+                    }
+                    ChannelEvent::Closed => {
+                        closed_by_peer = true;
+                        break;
+                    }
+                }
+            }
+
+            if closed_by_peer {
+                registry.set_state(&channel_id, ChannelState::Closed, None);
+            } else if registry.get(&channel_id).map(|r| r.state) == Some(ChannelState::Open) {
+                // The connection dropped underneath an open channel
+                let _ = registry.reopen(&channel_id).await;
+            }
+        });
+    }
+
+    /// Look up a tracked channel
+    pub fn get(&self, channel_id: &str) -> Option<ChannelRecord> {
+        self.channels.read().unwrap().get(channel_id).cloned()
+    }
+
+    /// Receive the ID of a channel each time a message is buffered in its inbox
+    pub fn subscribe_inbound(&self) -> broadcast::Receiver<String> {
+        self.inbound.subscribe()
+    }
+
+    /// Inbound message buffer of a tracked channel
+    pub fn inbox(&self, channel_id: &str) -> Option<Arc<ChannelInbox>> {
+        self.inboxes.read().unwrap().get(channel_id).cloned()
+    }
+
+    /// Encoding configured for a channel (base64 for untracked channels)
+    pub fn encoding(&self, channel_id: &str) -> PayloadEncoding {
+        self.get(channel_id)
+            .map(|record| record.encoding)
+            .unwrap_or_default()
+    }
+
+    /// Look up a channel, failing if it is unknown, closed, or invalidated
+    pub fn get_open(&self, channel_id: &str) -> Result<ChannelRecord> {
+        match self.get(channel_id) {
+            Some(record) if record.state == ChannelState::Open => Ok(record),
+            Some(record) if record.state == ChannelState::Invalidated => Err(TheaterError::ChannelInvalidated(
+                channel_id.to_string(),
+                record.invalidated_reason.unwrap_or_default(),
+            )
+            .into()),
+            _ => Err(TheaterError::ChannelNotFound(channel_id.to_string()).into()),
+        }
+    }
+
+    /// Check that a session may use a channel.
+    ///
+    /// Only the owning session may use a channel unless it has been shared.
+    /// Channels not opened through this registry are not restricted.
+    pub fn check_access(&self, channel_id: &str, session_id: &SessionId) -> Result<()> {
+        match self.get(channel_id) {
+            Some(record) if !record.shared && record.session_id != session_id.as_str() => {
+                Err(TheaterError::PermissionDenied(format!(
+                    "channel {} belongs to another session and has not been shared",
+                    channel_id
+                ))
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Allow (or stop allowing) other sessions to use a channel; owner only
+    pub fn set_shared(&self, channel_id: &str, session_id: &SessionId, shared: bool) -> Result<ChannelRecord> {
+        let mut channels = self.channels.write().unwrap();
+        let entry = channels
+            .get_mut(channel_id)
+            .ok_or_else(|| TheaterError::ChannelNotFound(channel_id.to_string()))?;
+
+        if entry.session_id != session_id.as_str() {
+            return Err(TheaterError::PermissionDenied(format!(
+                "only the session that opened channel {} can change its sharing",
+                channel_id
+            ))
+            .into());
+        }
+
+        entry.shared = shared;
+        Ok(entry.clone())
+    }
+
+    /// Send a message on a channel and wait for the next inbound message as the reply.
+    ///
+    /// Messages that were already buffered before the send are left in the
+    /// inbox for regular reads.
+    pub async fn request(&self, channel_id: &str, message: &[u8], timeout: Duration) -> Result<InboundMessage> {
+        let theater_channel_id = self.get_open(channel_id)?.theater_channel_id;
+        let inbox = self
+            .inbox(channel_id)
+            .ok_or_else(|| TheaterError::ChannelNotFound(channel_id.to_string()))?;
+
+        // Anything received after this point counts as the reply
+        let after = inbox.last_seq();
+        self.theater_client.send_on_channel(&theater_channel_id, message).await?;
+        self.record_sent(channel_id, message.len());
+
+        let wait = async {
+            loop {
+                let notified = inbox.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+
+                if let Some(reply) = inbox.take_after(after) {
+                    return Ok(reply);
+                }
+                // The channel may have ended while we were waiting
+                self.get_open(channel_id)?;
+
+                notified.await;
+            }
+        };
+
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(result) => result,
+            Err(_) => Err(TheaterError::Timeout(format!(
+                "no reply on channel {} within {}ms",
+                channel_id,
+                timeout.as_millis()
+            ))
+            .into()),
+        }
+    }
+
+    /// Take up to `max` buffered messages from a channel, oldest first,
+    /// waiting up to `wait` for one to arrive if none are buffered.
+    ///
+    /// Returns the messages with the number rejected since the previous
+    /// read. Messages buffered before the channel closed can still be read.
+    pub async fn receive(&self, channel_id: &str, max: usize, wait: Duration) -> Result<(Vec<InboundMessage>, u64)> {
+        let inbox = self
+            .inbox(channel_id)
+            .ok_or_else(|| TheaterError::ChannelNotFound(channel_id.to_string()))?;
+
+        let wait_for_message = async {
+            loop {
+                let notified = inbox.notified();
+                tokio::pin!(notified);
+                notified.as_mut().enable();
+
+                let (messages, rejected) = inbox.drain(max);
+                if !messages.is_empty() || rejected > 0 {
+                    return Ok((messages, rejected));
+                }
+                // Nothing more will arrive on a closed channel
+                self.get_open(channel_id)?;
+
+                notified.await;
+            }
+        };
+
+        match tokio::time::timeout(wait, wait_for_message).await {
+            Ok(result) => result,
+            Err(_) => Ok((Vec::new(), 0)),
+        }
+    }
+
+    /// Resolve the Theater-side ID for a channel before using it.
+    ///
+    /// Channels not opened through this registry are passed through unchanged.
+    pub async fn resolve(&self, channel_id: &str) -> Result<String> {
+        if self.get(channel_id).is_none() {
+            return Ok(channel_id.to_string());
+        }
+
+        Ok(self.get_open(channel_id)?.theater_channel_id)
+    }
+
+    /// Re-establish a channel whose connection dropped, or invalidate it
+    async fn reopen(self: &Arc<Self>, channel_id: &str) -> Result<()> {
+        let record = self.get_open(channel_id)?;
+
+        let result = match record.participant.to_theater() {
+            Ok(participant) => self.theater_client.open_channel(participant, None).await,
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(stream) => {
+                info!(
+                    "Reopened channel {} to {} (theater channel {})",
+                    channel_id, record.participant, stream.channel_id
+                );
+                {
+                    let mut channels = self.channels.write().unwrap();
+                    let Some(entry) = channels.get_mut(channel_id) else {
+                        // Closed while we were reopening
+                        return Ok(());
+                    };
+                    entry.theater_channel_id = stream.channel_id.clone();
+                    entry.reopen_count += 1;
+                }
+                self.spawn_pump(channel_id.to_string(), stream);
+                Ok(())
+            }
+            Err(e) => {
+                warn!("Could not reopen channel {}: {}", channel_id, e);
+                self.set_state(channel_id, ChannelState::Invalidated, Some(e.to_string()));
+                Err(TheaterError::ChannelInvalidated(channel_id.to_string(), e.to_string()).into())
+            }
+        }
+    }
+
+    /// Count a message sent on a tracked channel
+    pub fn record_sent(&self, channel_id: &str, bytes: usize) {
+        if let Some(entry) = self.channels.write().unwrap().get_mut(channel_id) {
+            entry.stats.messages_sent += 1;
+            entry.stats.bytes_sent += bytes as u64;
+            entry.stats.last_activity = Some(Utc::now());
+        }
+    }
+
+    /// Count a message received on a tracked channel
+    fn record_received(&self, channel_id: &str, bytes: usize) {
+        if let Some(entry) = self.channels.write().unwrap().get_mut(channel_id) {
+            entry.stats.messages_received += 1;
+            entry.stats.bytes_received += bytes as u64;
+            entry.stats.last_activity = Some(Utc::now());
+        }
+    }
+
+    /// Status of a tracked channel: its record plus inbox and activity health
+    pub fn status(&self, channel_id: &str) -> Result<Value> {
+        let record = self
+            .get(channel_id)
+            .ok_or_else(|| TheaterError::ChannelNotFound(channel_id.to_string()))?;
+
+        let last_activity = record.stats.last_activity.unwrap_or(record.opened_at);
+        let idle_seconds = (Utc::now() - last_activity).num_seconds().max(0);
+
+        let (buffered_messages, buffered_bytes, dropped_messages) = match self.inbox(channel_id) {
+            Some(inbox) => (inbox.len(), inbox.bytes(), inbox.dropped()),
+            None => (0, 0, 0),
+        };
+
+        // Sent messages with nothing coming back for a while usually means a stuck peer
+        let health = match record.state {
+            ChannelState::Open if idle_seconds < STALLED_AFTER_SECS => "active",
+            ChannelState::Open if record.stats.messages_sent > record.stats.messages_received => "stalled",
+            ChannelState::Open => "idle",
+            ChannelState::Invalidated => "invalidated",
+            ChannelState::Closed => "closed",
+        };
+
+        Ok(json!({
+            "channel": record,
+            "health": health,
+            "idle_seconds": idle_seconds,
+            "inbox": {
+                "buffered_messages": buffered_messages,
+                "buffered_bytes": buffered_bytes,
+                "dropped_messages": dropped_messages
+            }
+        }))
+    }
+
+    fn set_state(&self, channel_id: &str, state: ChannelState, reason: Option<String>) {
+        if let Some(entry) = self.channels.write().unwrap().get_mut(channel_id) {
+            entry.state = state;
+            entry.invalidated_reason = reason;
+        }
+
+        // Let pending readers observe the new state
+        if let Some(inbox) = self.inbox(channel_id) {
+            inbox.wake();
+        }
+    }
+
+    /// List all tracked channels, oldest first
+    pub fn list(&self) -> Vec<ChannelRecord> {
+        let mut records: Vec<ChannelRecord> = self.channels.read().unwrap().values().cloned().collect();
+        records.sort_by(|a, b| a.opened_at.cmp(&b.opened_at));
+        records
+    }
+
+    /// List the channels owned by a session
+    pub fn list_for_session(&self, session_id: &SessionId) -> Vec<ChannelRecord> {
+        self.list()
+            .into_iter()
+            .filter(|record| record.session_id == session_id.as_str())
+            .collect()
+    }
+
+    /// Mark a channel as closed and stop tracking it
+    pub fn mark_closed(&self, channel_id: &str) -> Option<ChannelRecord> {
+        let removed = self.channels.write().unwrap().remove(channel_id);
+        if let Some(inbox) = self.inboxes.write().unwrap().remove(channel_id) {
+            inbox.wake();
+        }
+        // Stopping the reader closes the channel's dedicated connection; the
+        // pump then sees the stream end and, with the record gone, exits
+        if let Some(reader) = self.readers.lock().unwrap().remove(channel_id) {
+            reader.abort();
+        }
+        removed.map(|mut record| {
+            record.state = ChannelState::Closed;
+            record
+        })
+    }
+
+    /// Close every channel owned by a session
+    pub async fn close_session(&self, session_id: &SessionId) {
+        let channel_ids: Vec<String> = self
+            .list_for_session(session_id)
+            .into_iter()
+            .map(|record| record.channel_id)
+            .collect();
+
+        if !channel_ids.is_empty() {
+            info!("Closing {} channels for ended session {}", channel_ids.len(), session_id);
+        }
+        self.close_channels(channel_ids).await;
+    }
+
+    /// Close every tracked channel (used on server shutdown)
+    pub async fn close_all(&self) {
+        let channel_ids: Vec<String> = self.channels.read().unwrap().keys().cloned().collect();
+
+        if !channel_ids.is_empty() {
+            info!("Closing {} channels on shutdown", channel_ids.len());
+        }
+        self.close_channels(channel_ids).await;
+    }
+
+    async fn close_channels(&self, channel_ids: Vec<String>) {
+        for channel_id in channel_ids {
+            // Stop tracking first so a failed close is not retried forever
+            let Some(record) = self.mark_closed(&channel_id) else {
+                continue;
+            };
+
+            // Invalidated channels no longer exist on the Theater side
+            if record.invalidated_reason.is_some() {
+                continue;
+            }
+
+            if let Err(e) = self.theater_client.close_channel(&record.theater_channel_id).await {
+                warn!("Failed to close channel {}: {}", channel_id, e);
+            }
+        }
+    }
+}
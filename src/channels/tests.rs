@@ -0,0 +1,50 @@
+use crate::channels::{ChannelInbox, InboxLimits, OverflowPolicy, PushOutcome};
+
+fn limits(max_messages: usize, max_bytes: usize, overflow: OverflowPolicy) -> InboxLimits {
+    InboxLimits {
+        max_messages,
+        max_bytes,
+        overflow,
+    }
+}
+
+#[test]
+fn test_inbox_drops_oldest_when_full() {
+    let inbox = ChannelInbox::new(limits(2, 1024, OverflowPolicy::DropOldest));
+
+    assert_eq!(inbox.push(b"one".to_vec()), PushOutcome::Buffered);
+    assert_eq!(inbox.push(b"two".to_vec()), PushOutcome::Buffered);
+    assert_eq!(inbox.push(b"three".to_vec()), PushOutcome::Evicted(1));
+
+    let (messages, rejected) = inbox.drain(10);
+    let data: Vec<&[u8]> = messages.iter().map(|m| m.data.as_slice()).collect();
+    assert_eq!(data, vec![b"two".as_slice(), b"three".as_slice()]);
+    assert_eq!(rejected, 0);
+    assert_eq!(inbox.dropped(), 1);
+    assert_eq!(inbox.bytes(), 0);
+}
+
+#[test]
+fn test_inbox_rejects_when_full() {
+    let inbox = ChannelInbox::new(limits(10, 6, OverflowPolicy::Reject));
+
+    assert_eq!(inbox.push(b"abcd".to_vec()), PushOutcome::Buffered);
+    assert_eq!(inbox.push(b"efg".to_vec()), PushOutcome::Rejected);
+
+    let (messages, rejected) = inbox.drain(10);
+    assert_eq!(messages.len(), 1);
+    assert_eq!(rejected, 1);
+
+    // The rejection is only reported once
+    let (_, rejected) = inbox.drain(10);
+    assert_eq!(rejected, 0);
+}
+
+#[test]
+fn test_inbox_rejects_oversized_message() {
+    let inbox = ChannelInbox::new(limits(10, 4, OverflowPolicy::DropOldest));
+
+    assert_eq!(inbox.push(b"ok".to_vec()), PushOutcome::Buffered);
+    assert_eq!(inbox.push(b"too large".to_vec()), PushOutcome::Rejected);
+    assert_eq!(inbox.len(), 1);
+}
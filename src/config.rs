@@ -0,0 +1,341 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::actors::{ActorProfiles, ActorQuota, FailureConfig};
+use crate::auth::AuthConfig;
+use crate::channels::InboxLimits;
+use crate::events::webhook::WebhookConfig;
+use crate::logging::LoggingConfig;
+use crate::plugins::PluginConfig;
+use crate::resources::{PollIntervals, DEFAULT_POLL_INTERVAL, DEFAULT_READ_TIMEOUT};
+use crate::theater::client::{DEFAULT_RECONNECT_QUEUE, DEFAULT_RECONNECT_WINDOW};
+use crate::theater::pool::DEFAULT_POOL_SIZE;
+use crate::theater::{CompatibilityMode, Redaction, SizeLimits, TlsConfig};
+use crate::tools::{AdmissionLimits, ToolPolicy};
+use crate::transport::TransportKind;
+
+/// Runtime configuration for the Theater MCP server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub theater: TheaterConfig,
+    pub channels: ChannelConfig,
+    pub events: EventConfig,
+    pub resources: ResourceConfig,
+    pub tools: ToolConfig,
+    pub actors: ActorConfig,
+    pub auth: AuthConfig,
+    pub plugins: PluginConfig,
+    pub proxy: ProxyConfig,
+    pub health: HealthConfig,
+    pub state: StateConfig,
+    pub transport: TransportConfig,
+    pub logging: LoggingConfig,
+}
+
+/// Prefix of environment variables that override configuration values
+pub const ENV_PREFIX: &str = "THEATER_MCP__";
+
+impl ServerConfig {
+    /// Read a TOML configuration file; settings it leaves out keep their defaults
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Could not read config file {}: {}", path.display(), e))?;
+        toml::from_str(&text).map_err(|e| anyhow!("Invalid config file {}: {}", path.display(), e))
+    }
+
+    /// How long a detached client may take to come back before its session
+    /// ends, which is when its grace periods have all run out
+    pub fn session_resume_window(&self) -> Duration {
+        Duration::from_secs(
+            self.actors
+                .session_grace_period_secs
+                .max(self.channels.session_grace_period_secs),
+        )
+    }
+
+    /// Override settings from environment variables named after their path
+    /// with `__` between sections, e.g. `THEATER_MCP__THEATER__ADDRESS`.
+    ///
+    /// Values of string settings are taken as they are. Other values are read
+    /// as JSON when they parse as JSON, and as strings otherwise; for unset
+    /// optional settings, the JSON reading is only used if the configuration
+    /// accepts it.
+    pub fn with_env_overrides(self, vars: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        let mut settings = serde_json::to_value(&self)?;
+        let mut overridden = false;
+        for (name, raw) in vars {
+            let Some(path) = name.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let keys: Vec<String> = path.split("__").map(|key| key.to_lowercase()).collect();
+            let value = match (get_setting(&settings, &keys), serde_json::from_str::<Value>(&raw)) {
+                // A token of 12345 or a name of true is still a string
+                (Some(Value::String(_)), _) | (_, Err(_)) => Value::String(raw),
+                (None | Some(Value::Null), Ok(parsed)) if !accepts(&settings, &keys, &parsed) => Value::String(raw),
+                (_, Ok(parsed)) => parsed,
+            };
+            set_setting(&mut settings, &keys, value).map_err(|e| anyhow!("Invalid {}: {}", name, e))?;
+            overridden = true;
+        }
+        if !overridden {
+            return Ok(self);
+        }
+        serde_json::from_value(settings).map_err(|e| anyhow!("Invalid configuration from the environment: {}", e))
+    }
+}
+
+/// The current value of the setting at `keys`, if it is set
+fn get_setting<'a>(settings: &'a Value, keys: &[String]) -> Option<&'a Value> {
+    keys.iter().try_fold(settings, |target, key| target.get(key))
+}
+
+/// Whether the configuration would be valid with `value` at `keys`
+fn accepts(settings: &Value, keys: &[String], value: &Value) -> bool {
+    let mut candidate = settings.clone();
+    set_setting(&mut candidate, keys, value.clone()).is_ok()
+        && serde_json::from_value::<ServerConfig>(candidate).is_ok()
+}
+
+/// Set the setting at `keys`, creating sections that are unset
+fn set_setting(settings: &mut Value, keys: &[String], value: Value) -> Result<()> {
+    let (last, sections) = keys.split_last().ok_or_else(|| anyhow!("no setting named"))?;
+    let mut target = settings;
+    for key in sections {
+        let section = target
+            .as_object_mut()
+            .ok_or_else(|| anyhow!("{} is inside a setting, not a section", key))?
+            .entry(key.clone())
+            .or_insert(Value::Null);
+        if section.is_null() {
+            *section = Value::Object(Map::new());
+        }
+        target = section;
+    }
+    target
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("{} is inside a setting, not a section", last))?
+        .insert(last.clone(), value);
+    Ok(())
+}
+
+/// Theater connection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TheaterConfig {
+    /// Address of the Theater server
+    pub address: SocketAddr,
+    /// Maximum number of pipelined connections commands to the Theater
+    /// server are spread over
+    pub connections: usize,
+    /// Limits on frame, actor state and message sizes
+    pub limits: SizeLimits,
+    /// Commands held while the Theater server is unreachable; more fail immediately
+    pub max_queued_commands: usize,
+    /// Seconds a held command waits for the Theater server to come back
+    pub reconnect_window_secs: u64,
+    /// Masking and truncation applied to logged commands and responses
+    pub redaction: Redaction,
+    /// Connect over TLS with these settings instead of plain TCP
+    pub tls: Option<TlsConfig>,
+    /// Probe the server at startup and leave out tools for commands it lacks
+    pub probe_features: bool,
+    /// Whether a server speaking an incompatible protocol stops startup or
+    /// only logs a warning
+    pub compatibility: CompatibilityMode,
+    /// Run a Theater runtime in this process instead of connecting to the
+    /// configured address (requires the `embedded-theater` feature)
+    pub embedded: bool,
+}
+
+impl Default for TheaterConfig {
+    fn default() -> Self {
+        Self {
+            address: SocketAddr::from(([127, 0, 0, 1], 9000)),
+            connections: DEFAULT_POOL_SIZE,
+            limits: SizeLimits::default(),
+            max_queued_commands: DEFAULT_RECONNECT_QUEUE,
+            reconnect_window_secs: DEFAULT_RECONNECT_WINDOW.as_secs(),
+            redaction: Redaction::default(),
+            tls: None,
+            probe_features: true,
+            compatibility: CompatibilityMode::default(),
+            embedded: false,
+        }
+    }
+}
+
+/// Channel-related configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelConfig {
+    /// Limits on each channel's inbound message buffer
+    pub inbox: InboxLimits,
+    /// Seconds a detached session's channels stay open in case its client
+    /// comes back. Only clients of the SSE transport can detach; when the
+    /// session ends, its channels are closed immediately.
+    pub session_grace_period_secs: u64,
+}
+
+impl Default for ChannelConfig {
+    fn default() -> Self {
+        Self {
+            inbox: InboxLimits::default(),
+            session_grace_period_secs: 30,
+        }
+    }
+}
+
+/// Event-related configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventConfig {
+    /// SQLite file to archive events in (requires the `event-store` feature)
+    pub store_path: Option<PathBuf>,
+    /// HTTP endpoints to forward matching events to
+    pub webhooks: Vec<WebhookConfig>,
+    /// OTLP collector to export events to (requires the `otlp` feature)
+    pub otlp_endpoint: Option<String>,
+    /// Event filter expression applied to OTLP export
+    pub otlp_filter: String,
+}
+
+/// Configuration of actors started through the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActorConfig {
+    /// Caps on running actors per session and in total
+    pub quota: ActorQuota,
+    /// Whether actors are owned by the session that starts them, and stopped
+    /// when it ends, unless start_actor says otherwise
+    pub owned_by_default: bool,
+    /// Seconds a detached session's owned actors keep running in case its
+    /// client comes back. Only clients of the SSE transport can detach; when
+    /// the session ends, owned actors are stopped immediately.
+    pub session_grace_period_secs: u64,
+    /// Named launch profiles offered by start_profile
+    pub profiles: ActorProfiles,
+    /// How failures of actors started through the server are handled
+    pub failures: FailureConfig,
+}
+
+impl Default for ActorConfig {
+    fn default() -> Self {
+        Self {
+            quota: ActorQuota::default(),
+            owned_by_default: false,
+            session_grace_period_secs: 30,
+            profiles: ActorProfiles::default(),
+            failures: FailureConfig::default(),
+        }
+    }
+}
+
+/// Resource configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceConfig {
+    /// Milliseconds a resource read may take before it fails with a timeout
+    pub read_timeout_ms: u64,
+    /// Milliseconds between checks of a subscribed resource for changes
+    pub poll_interval_ms: u64,
+    /// Check interval in milliseconds for subscribed URIs matching each
+    /// `*` pattern, overriding `poll_interval_ms`
+    pub poll_intervals: HashMap<String, u64>,
+}
+
+impl Default for ResourceConfig {
+    fn default() -> Self {
+        Self {
+            read_timeout_ms: DEFAULT_READ_TIMEOUT.as_millis() as u64,
+            poll_interval_ms: DEFAULT_POLL_INTERVAL.as_millis() as u64,
+            poll_intervals: HashMap::new(),
+        }
+    }
+}
+
+impl ResourceConfig {
+    /// Polling intervals for subscribed resources
+    pub fn poll_intervals(&self) -> PollIntervals {
+        self.poll_intervals.iter().fold(
+            PollIntervals::new(Duration::from_millis(self.poll_interval_ms)),
+            |intervals, (pattern, ms)| intervals.with_interval(pattern.clone(), Duration::from_millis(*ms)),
+        )
+    }
+}
+
+/// Single-actor proxy configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// Offer only the tools, resources and prompts this actor exports,
+    /// under their own names, instead of the Theater tools
+    pub actor_id: Option<String>,
+}
+
+/// Health check configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Serve `/healthz` and `/readyz` on this address
+    pub listen: Option<SocketAddr>,
+}
+
+/// How MCP clients reach the server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransportConfig {
+    pub kind: TransportKind,
+    /// Address network transports listen on, unless a socket is passed by
+    /// socket activation
+    pub listen: SocketAddr,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        Self {
+            kind: TransportKind::Stdio,
+            listen: SocketAddr::from(([127, 0, 0, 1], 8080)),
+        }
+    }
+}
+
+/// Persistence of server state across restarts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateConfig {
+    /// JSON file the managed actors, aliases and channels are saved to and
+    /// restored from; nothing is persisted if unset
+    pub path: Option<PathBuf>,
+    /// Seconds between saves while the server runs
+    pub save_interval_secs: u64,
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            save_interval_secs: 5,
+        }
+    }
+}
+
+/// Tool call configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolConfig {
+    /// Rate and concurrency limits applied before a tool call runs
+    pub admission: AdmissionLimits,
+    /// Maximum concurrent calls of individual tools, by tool name
+    pub concurrency: HashMap<String, usize>,
+    /// Directories or `*` patterns start_actor may load manifests from.
+    /// Empty allows any manifest.
+    pub allowed_manifests: Vec<String>,
+    /// Which tools may be called, refused, or need the user's confirmation
+    pub policy: ToolPolicy,
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::encoding::PayloadEncoding;
+
+/// Exchanges kept per conversation; older ones are dropped
+pub const MAX_EXCHANGES: usize = 50;
+
+/// One request and the actor's reply
+#[derive(Debug, Clone, Serialize)]
+pub struct Exchange {
+    pub seq: u64,
+    pub sent_at: DateTime<Utc>,
+    pub message: Value,
+    pub reply: Value,
+}
+
+/// A run of requests to one actor, kept server-side so clients don't have to
+#[derive(Debug, Clone, Serialize)]
+pub struct Conversation {
+    pub conversation_id: String,
+    pub actor_id: String,
+    /// Encoding of the messages and replies
+    pub encoding: PayloadEncoding,
+    pub started_at: DateTime<Utc>,
+    /// Sequence number of the next exchange
+    pub next_seq: u64,
+    /// The most recent exchanges, oldest first
+    pub exchanges: VecDeque<Exchange>,
+}
+
+/// Conversations held with actors through converse_with_actor
+pub struct ConversationStore {
+    max_exchanges: usize,
+    conversations: Mutex<HashMap<String, Conversation>>,
+}
+
+impl ConversationStore {
+    pub fn new() -> Self {
+        Self {
+            max_exchanges: MAX_EXCHANGES,
+            conversations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep this many exchanges per conversation
+    pub fn with_max_exchanges(mut self, max_exchanges: usize) -> Self {
+        self.max_exchanges = max_exchanges.max(1);
+        self
+    }
+
+    /// Begin a conversation with an actor
+    pub fn start(&self, actor_id: &str, encoding: PayloadEncoding) -> Conversation {
+        let conversation = Conversation {
+            conversation_id: Uuid::new_v4().to_string(),
+            actor_id: actor_id.to_string(),
+            encoding,
+            started_at: Utc::now(),
+            next_seq: 1,
+            exchanges: VecDeque::new(),
+        };
+        self.conversations
+            .lock()
+            .unwrap()
+            .insert(conversation.conversation_id.clone(), conversation.clone());
+        conversation
+    }
+
+    pub fn get(&self, conversation_id: &str) -> Option<Conversation> {
+        self.conversations.lock().unwrap().get(conversation_id).cloned()
+    }
+
+    /// Take the next sequence number of a conversation
+    pub fn next_seq(&self, conversation_id: &str) -> Option<u64> {
+        let mut conversations = self.conversations.lock().unwrap();
+        let conversation = conversations.get_mut(conversation_id)?;
+        let seq = conversation.next_seq;
+        conversation.next_seq += 1;
+        Some(seq)
+    }
+
+    /// Add an exchange, dropping the oldest beyond the limit
+    pub fn record(&self, conversation_id: &str, exchange: Exchange) {
+        if let Some(conversation) = self.conversations.lock().unwrap().get_mut(conversation_id) {
+            conversation.exchanges.push_back(exchange);
+            while conversation.exchanges.len() > self.max_exchanges {
+                conversation.exchanges.pop_front();
+            }
+        }
+    }
+
+    /// Forget a conversation
+    pub fn end(&self, conversation_id: &str) -> Option<Conversation> {
+        self.conversations.lock().unwrap().remove(conversation_id)
+    }
+
+    /// All conversations, oldest first
+    pub fn list(&self) -> Vec<Conversation> {
+        let mut conversations: Vec<Conversation> = self.conversations.lock().unwrap().values().cloned().collect();
+        conversations.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        conversations
+    }
+}
+
+impl Default for ConversationStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// URI of a conversation's transcript resource
+pub fn transcript_uri(conversation_id: &str) -> String {
+    format!("theater://conversation/{}", conversation_id)
+}
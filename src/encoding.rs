@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Value};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use theater::chain::ChainEvent;
+use crate::events::verify::to_hex;
+
+/// How a payload is represented in tool arguments and results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadEncoding {
+    /// Plain UTF-8 text
+    Utf8,
+    /// Any JSON value, serialized to bytes
+    Json,
+    /// Base64 encoded bytes
+    #[default]
+    Base64,
+    /// Any JSON value, transcoded to MessagePack
+    #[serde(rename = "msgpack")]
+    MessagePack,
+    /// Any JSON value, transcoded to CBOR
+    Cbor,
+}
+
+impl PayloadEncoding {
+    /// Read an optional `encoding` argument, falling back to the given default
+    pub fn from_args(args: &Value, default: PayloadEncoding) -> Result<Self> {
+        match args.get("encoding").and_then(|v| v.as_str()) {
+            Some(name) => name.parse(),
+            None => Ok(default),
+        }
+    }
+
+    /// Convert a tool argument into bytes for the actor, in a pooled buffer
+    pub fn encode(&self, value: &Value) -> Result<PooledBytes> {
+        let mut buffer = PooledBytes::take();
+        match self {
+            PayloadEncoding::Utf8 => {
+                let text = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Expected a string payload for utf8 encoding"))?;
+                buffer.0.extend_from_slice(text.as_bytes());
+            }
+            PayloadEncoding::Json => serde_json::to_writer(&mut buffer.0, value)?,
+            PayloadEncoding::Base64 => {
+                let data = value
+                    .as_str()
+                    .ok_or_else(|| anyhow!("Expected a base64 string payload"))?;
+                BASE64.decode_vec(data, &mut buffer.0)?;
+            }
+            PayloadEncoding::MessagePack => rmp_serde::encode::write_named(&mut buffer.0, value)?,
+            PayloadEncoding::Cbor => ciborium::ser::into_writer(value, &mut buffer.0)?,
+        }
+        Ok(buffer)
+    }
+
+    /// Convert bytes from the actor into a tool result value.
+    ///
+    /// Bytes that don't fit the encoding fall back to `{"base64": "..."}`
+    /// so no data is lost.
+    pub fn decode(&self, data: &[u8]) -> Value {
+        match self {
+            PayloadEncoding::Utf8 => match std::str::from_utf8(data) {
+                Ok(text) => Value::String(text.to_string()),
+                Err(_) => json!({ "base64": BASE64.encode(data) }),
+            },
+            PayloadEncoding::Json => match serde_json::from_slice::<Value>(data) {
+                Ok(value) => value,
+                Err(_) => json!({ "base64": BASE64.encode(data) }),
+            },
+            PayloadEncoding::Base64 => Value::String(BASE64.encode(data)),
+            PayloadEncoding::MessagePack => match rmp_serde::from_slice::<Value>(data) {
+                Ok(value) => value,
+                Err(_) => json!({ "base64": BASE64.encode(data) }),
+            },
+            PayloadEncoding::Cbor => match ciborium::de::from_reader::<Value, _>(data) {
+                Ok(value) => value,
+                Err(_) => json!({ "base64": BASE64.encode(data) }),
+            },
+        }
+    }
+}
+
+impl FromStr for PayloadEncoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "utf8" | "utf-8" | "text" => Ok(PayloadEncoding::Utf8),
+            "json" => Ok(PayloadEncoding::Json),
+            "base64" => Ok(PayloadEncoding::Base64),
+            "msgpack" | "messagepack" => Ok(PayloadEncoding::MessagePack),
+            "cbor" => Ok(PayloadEncoding::Cbor),
+            other => Err(anyhow!(
+                "Unsupported encoding '{}' (expected 'utf8', 'json', 'base64', 'msgpack' or 'cbor')",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PayloadEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadEncoding::Utf8 => write!(f, "utf8"),
+            PayloadEncoding::Json => write!(f, "json"),
+            PayloadEncoding::Base64 => write!(f, "base64"),
+            PayloadEncoding::MessagePack => write!(f, "msgpack"),
+            PayloadEncoding::Cbor => write!(f, "cbor"),
+        }
+    }
+}
+
+/// Most buffers kept for reuse
+const MAX_POOLED_BUFFERS: usize = 64;
+
+/// Buffers that grew past this many bytes are freed rather than pooled
+const MAX_POOLED_CAPACITY: usize = 1024 * 1024;
+
+static BYTE_BUFFERS: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+/// A byte buffer borrowed from a shared pool and returned to it on drop.
+///
+/// Decoding every message payload into a fresh `Vec` adds up when clients
+/// pump many messages per second; pooled buffers keep their capacity
+/// between messages instead.
+#[derive(Debug, Default)]
+pub struct PooledBytes(Vec<u8>);
+
+impl PooledBytes {
+    /// Take an empty buffer from the pool, or a new one if the pool is empty
+    pub fn take() -> Self {
+        Self(BYTE_BUFFERS.lock().unwrap().pop().unwrap_or_default())
+    }
+}
+
+impl Deref for PooledBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Drop for PooledBytes {
+    fn drop(&mut self) {
+        if self.0.capacity() == 0 || self.0.capacity() > MAX_POOLED_CAPACITY {
+            return;
+        }
+        let mut buffer = std::mem::take(&mut self.0);
+        buffer.clear();
+        let mut pool = BYTE_BUFFERS.lock().unwrap();
+        if pool.len() < MAX_POOLED_BUFFERS {
+            pool.push(buffer);
+        }
+    }
+}
+
+/// Decode base64 text into a pooled buffer
+pub fn decode_base64(text: &str) -> Result<PooledBytes> {
+    let mut buffer = PooledBytes::take();
+    BASE64.decode_vec(text, &mut buffer.0)?;
+    Ok(buffer)
+}
+
+/// A chain event serialized with compact byte fields.
+///
+/// serde writes `Vec<u8>` as an array of numbers, roughly four times the
+/// size of the bytes themselves. This writes the hashes as hex and `data`
+/// as a base64 string instead; all other fields are unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct CompactEvent<'a>(pub &'a ChainEvent);
+
+impl Serialize for CompactEvent<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let event = self.0;
+        let mut state = serializer.serialize_struct("ChainEvent", 6)?;
+        state.serialize_field("hash", &to_hex(&event.hash))?;
+        state.serialize_field("parent_hash", &event.parent_hash.as_deref().map(to_hex))?;
+        state.serialize_field("event_type", &event.event_type)?;
+        state.serialize_field("data", &BASE64.encode(&event.data))?;
+        state.serialize_field("timestamp", &event.timestamp)?;
+        state.serialize_field("description", &event.description)?;
+        state.end()
+    }
+}
+
+/// `serialize_with` adapter writing a chain event as a [`CompactEvent`]
+pub fn serialize_event<S: Serializer>(event: &ChainEvent, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    CompactEvent(event).serialize(serializer)
+}
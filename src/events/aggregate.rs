@@ -0,0 +1,114 @@
+use anyhow::Result;
+use futures::future::join_all;
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use theater::chain::ChainEvent;
+use theater::id::TheaterId;
+use crate::encoding::serialize_event;
+use crate::events::filter::EventFilter;
+use crate::events::history::EventHistory;
+use crate::theater::TheaterIdExt;
+
+/// An event tagged with the actor that produced it
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregatedEvent {
+    pub actor_id: String,
+    /// Position of the event in its actor's chain
+    pub chain_index: usize,
+    #[serde(serialize_with = "serialize_event")]
+    pub event: ChainEvent,
+}
+
+/// Merge per-actor event chains into one timestamp-ordered feed.
+///
+/// Each chain is already causally ordered, so events from the same actor
+/// keep their chain order even when timestamps tie or go backwards. Across
+/// actors, ties are broken by actor ID so the output is deterministic.
+pub fn merge_ordered(chains: Vec<(String, Vec<ChainEvent>)>) -> Vec<AggregatedEvent> {
+    let total: usize = chains.iter().map(|(_, events)| events.len()).sum();
+    let mut merged = Vec::with_capacity(total);
+
+    let mut chains: Vec<(String, std::vec::IntoIter<ChainEvent>, usize)> = chains
+        .into_iter()
+        .map(|(actor_id, events)| (actor_id, events.into_iter(), 0))
+        .collect();
+    chains.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // Heap of the next pending event per chain: (timestamp, chain)
+    let mut heads: Vec<Option<ChainEvent>> = chains.iter_mut().map(|(_, iter, _)| iter.next()).collect();
+    let mut heap = BinaryHeap::new();
+    for (i, head) in heads.iter().enumerate() {
+        if let Some(event) = head {
+            heap.push(Reverse((event.timestamp, i)));
+        }
+    }
+
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let event = heads[i].take().expect("heap entry without a head event");
+        let (actor_id, iter, index) = &mut chains[i];
+
+        merged.push(AggregatedEvent {
+            actor_id: actor_id.clone(),
+            chain_index: *index,
+            event,
+        });
+        *index += 1;
+
+        if let Some(next) = iter.next() {
+            // Never let a later chain event sort before its predecessor
+            let timestamp = next.timestamp.max(merged.last().map(|e| e.event.timestamp).unwrap_or(0));
+            heads[i] = Some(next);
+            heap.push(Reverse((timestamp, i)));
+        }
+    }
+
+    merged
+}
+
+/// Keep only the most recent `limit` events of an ordered feed
+pub fn latest(mut events: Vec<AggregatedEvent>, limit: usize) -> Vec<AggregatedEvent> {
+    if events.len() > limit {
+        events.drain(..events.len() - limit);
+    }
+    events
+}
+
+/// Fetch the chains of several actors concurrently (every known actor if none
+/// are given)
+pub async fn collect_chains(
+    history: &EventHistory,
+    actor_ids: Option<Vec<TheaterId>>,
+) -> Result<Vec<(String, Vec<ChainEvent>)>> {
+    let actor_ids = match actor_ids {
+        Some(ids) => ids,
+        None => history.known_actors().await?,
+    };
+
+    let fetches = actor_ids.iter().map(|id| async move {
+        let events = history.actor_events(id).await?;
+        Ok::<_, anyhow::Error>((id.as_string(), events))
+    });
+
+    join_all(fetches).await.into_iter().collect()
+}
+
+/// Fetch the chains of several actors and merge the matching events into one
+/// ordered feed
+pub async fn collect_merged(
+    history: &EventHistory,
+    actor_ids: Option<Vec<TheaterId>>,
+    filter: &EventFilter,
+) -> Result<Vec<AggregatedEvent>> {
+    let chains = collect_chains(history, actor_ids)
+        .await?
+        .into_iter()
+        .map(|(actor_id, events)| {
+            let events: Vec<ChainEvent> = events.into_iter().filter(|e| filter.matches(e)).collect();
+            (actor_id, events)
+        })
+        .collect();
+
+    Ok(merge_ordered(chains))
+}
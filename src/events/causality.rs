@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use theater::chain::ChainEvent;
+
+/// Operations remembered for correlation; older ones are forgotten
+const MAX_OPERATIONS: usize = 1024;
+
+/// How long after an operation its actor's events are attributed to it
+pub const CAUSALITY_WINDOW_SECS: u64 = 60;
+
+/// A tool call that acted on an actor
+#[derive(Debug, Clone, Serialize)]
+pub struct Operation {
+    pub id: String,
+    pub tool: String,
+    pub actor_id: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Remembers tool calls that act on actors so the chain events they cause
+/// can be traced back to them.
+///
+/// Correlation is by time: an actor's events from the moment an operation
+/// starts until the next operation on the same actor (or the end of the
+/// causality window) are attributed to it. Chain timestamps have one-second
+/// resolution, so operations on the same actor within a second are
+/// indistinguishable and the later one wins.
+pub struct CausalityTracker {
+    operations: Mutex<VecDeque<Operation>>,
+    recorded: broadcast::Sender<Operation>,
+}
+
+impl CausalityTracker {
+    pub fn new() -> Self {
+        Self {
+            operations: Mutex::new(VecDeque::new()),
+            recorded: broadcast::channel(64).0,
+        }
+    }
+
+    /// Record a tool call acting on an actor, starting now
+    pub fn record(&self, tool: &str, actor_id: &str) -> Operation {
+        self.record_since(tool, actor_id, Utc::now())
+    }
+
+    /// Record a tool call that started earlier, e.g. before the actor's ID was known
+    pub fn record_since(&self, tool: &str, actor_id: &str, started_at: DateTime<Utc>) -> Operation {
+        let operation = Operation {
+            id: format!("op-{}", Uuid::new_v4()),
+            tool: tool.to_string(),
+            actor_id: actor_id.to_string(),
+            started_at,
+        };
+
+        let mut operations = self.operations.lock().unwrap();
+        operations.push_back(operation.clone());
+        if operations.len() > MAX_OPERATIONS {
+            operations.pop_front();
+        }
+
+        let _ = self.recorded.send(operation.clone());
+        operation
+    }
+
+    /// Look up a recorded operation
+    pub fn operation(&self, id: &str) -> Option<Operation> {
+        self.operations.lock().unwrap().iter().find(|op| op.id == id).cloned()
+    }
+
+    /// Be notified of operations as they are recorded
+    pub fn subscribe(&self) -> broadcast::Receiver<Operation> {
+        self.recorded.subscribe()
+    }
+
+    /// URI of the resource listing the events an operation caused
+    pub fn caused_events_uri(operation: &Operation) -> String {
+        format!("theater://actor/{}/events?caused_by={}", operation.actor_id, operation.id)
+    }
+
+    /// The events in an actor's chain attributed to an operation, with their chain indices
+    pub fn caused_events<'a>(&self, operation: &Operation, events: &'a [ChainEvent]) -> Vec<(usize, &'a ChainEvent)> {
+        let start = operation.started_at.timestamp().max(0) as u64;
+        let window_end = start + CAUSALITY_WINDOW_SECS;
+
+        // The next operation on the same actor takes over attribution
+        let end = self
+            .operations
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|op| op.actor_id == operation.actor_id && op.started_at > operation.started_at)
+            .map(|op| op.started_at.timestamp().max(0) as u64)
+            .min()
+            .map_or(window_end, |next| next.min(window_end));
+
+        events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.timestamp >= start && event.timestamp < end)
+            .collect()
+    }
+}
+
+impl Default for CausalityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
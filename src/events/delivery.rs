@@ -0,0 +1,154 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::events::stream::{ActorEvent, EventStreamHub};
+
+/// Recent events kept per actor for replay to resuming subscribers
+pub const REPLAY_CAPACITY: usize = 1024;
+
+/// The most recent events of one actor, in delivery order
+#[derive(Debug, Default)]
+pub struct ReplayBuffer {
+    events: VecDeque<ActorEvent>,
+    // Sequence number of the newest event that no longer fits
+    evicted_through: u64,
+}
+
+impl ReplayBuffer {
+    /// Whether an event with this chain hash was already delivered
+    pub fn contains(&self, hash: &[u8]) -> bool {
+        self.events.iter().any(|e| e.event.hash == hash)
+    }
+
+    /// Remember an event, forgetting the oldest once full
+    pub fn push(&mut self, event: ActorEvent) {
+        self.events.push_back(event);
+        if self.events.len() > REPLAY_CAPACITY {
+            if let Some(evicted) = self.events.pop_front() {
+                self.evicted_through = evicted.seq;
+            }
+        }
+    }
+
+    /// Buffered events with a sequence number after `seq`
+    pub fn after(&self, seq: u64) -> VecDeque<ActorEvent> {
+        self.events.iter().filter(|e| e.seq > seq).cloned().collect()
+    }
+
+    /// Whether events after `seq` have already been evicted and can't be replayed
+    pub fn has_gap_after(&self, seq: u64) -> bool {
+        self.evicted_through > seq
+    }
+}
+
+/// Acknowledged positions of named subscribers.
+///
+/// Cursors only move forward, so a late or repeated ack can't cause events
+/// to be redelivered twice.
+#[derive(Debug, Default)]
+pub struct DeliveryCursors {
+    cursors: Mutex<HashMap<String, u64>>,
+}
+
+impl DeliveryCursors {
+    /// Last acknowledged sequence number for a subscriber
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.cursors.lock().unwrap().get(key).copied()
+    }
+
+    /// Acknowledge everything up to and including `seq`
+    pub fn ack(&self, key: &str, seq: u64) {
+        let mut cursors = self.cursors.lock().unwrap();
+        let cursor = cursors.entry(key.to_string()).or_insert(seq);
+        *cursor = (*cursor).max(seq);
+    }
+}
+
+/// A resumable, deduplicated event stream for one named subscriber.
+///
+/// Delivery is at-least-once: events are replayed from the subscriber's last
+/// acknowledgement when it resumes, and each event is yielded at most once
+/// per `DurableEvents`. Events older than the replay buffer can't be
+/// replayed; a warning is logged if a resume falls behind it.
+pub struct DurableEvents {
+    key: String,
+    actor_id: String,
+    hub: Arc<EventStreamHub>,
+    receiver: broadcast::Receiver<ActorEvent>,
+    pending: VecDeque<ActorEvent>,
+    delivered: u64,
+}
+
+impl DurableEvents {
+    pub(crate) fn new(
+        key: String,
+        actor_id: String,
+        hub: Arc<EventStreamHub>,
+        receiver: broadcast::Receiver<ActorEvent>,
+        pending: VecDeque<ActorEvent>,
+        delivered: u64,
+    ) -> Self {
+        Self {
+            key,
+            actor_id,
+            hub,
+            receiver,
+            pending,
+            delivered,
+        }
+    }
+
+    /// Wait for the next undelivered event; `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<ActorEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                if event.seq > self.delivered {
+                    self.delivered = event.seq;
+                    return Some(event);
+                }
+                continue;
+            }
+
+            match self.receiver.recv().await {
+                Ok(event) if event.seq > self.delivered => {
+                    self.delivered = event.seq;
+                    return Some(event);
+                }
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // Catch up from the replay buffer instead of dropping events
+                    self.pending = self.hub.replay_after(&self.actor_id, self.delivered);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Acknowledge processing up to and including `seq`; a resumed stream
+    /// starts after the last acknowledged event
+    pub fn ack(&self, seq: u64) {
+        self.hub.cursors().ack(&self.key, seq);
+    }
+
+    /// Sequence number of the last event yielded
+    pub fn delivered(&self) -> u64 {
+        self.delivered
+    }
+}
+
+/// Cursor key for a subscriber's stream of one actor
+pub fn cursor_key(subscriber_id: &str, actor_id: &str) -> String {
+    format!("{}/{}", subscriber_id, actor_id)
+}
+
+/// Log when a resume starts before the oldest replayable event
+pub(crate) fn warn_if_gap(key: &str, cursor: u64, buffer: &ReplayBuffer) {
+    if buffer.has_gap_after(cursor) {
+        warn!(
+            "Subscriber {} resumed after event {}, which is older than the replay buffer; some events were missed",
+            key, cursor
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+use theater::chain::ChainEvent;
+
+/// Comparison used by a field matcher
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Substring match on the value's text form
+    Contains,
+}
+
+/// One clause of a filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Clause {
+    /// `type:<pattern>`; `*` matches any run of characters
+    EventType(String),
+    /// `since:<time>`, inclusive
+    Since(DateTime<Utc>),
+    /// `until:<time>`, exclusive
+    Until(DateTime<Utc>),
+    /// `data.<path><op><value>` against the event payload parsed as JSON
+    Field { path: Vec<String>, op: Op, value: String },
+}
+
+/// A parsed event filter expression.
+///
+/// The syntax is a whitespace-separated list of clauses, all of which must
+/// match:
+///
+/// ```text
+/// type:wasm* since:2025-04-01T00:00:00Z data.counter<0 data.user~"bob smith"
+/// ```
+///
+/// Times are RFC 3339 or Unix seconds. Field operators are `=`, `!=`, `<`,
+/// `<=`, `>`, `>=` and `~` (contains). Values may be double-quoted to include
+/// spaces. An empty expression matches everything.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventFilter {
+    clauses: Vec<Clause>,
+    source: String,
+}
+
+impl EventFilter {
+    /// A filter that matches every event
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Parse a filter expression
+    pub fn parse(expr: &str) -> Result<Self> {
+        let clauses = tokenize(expr)?
+            .into_iter()
+            .map(|token| parse_clause(&token))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            clauses,
+            source: expr.trim().to_string(),
+        })
+    }
+
+    /// Add an event type clause
+    pub fn with_event_type(mut self, pattern: &str) -> Self {
+        self.clauses.push(Clause::EventType(pattern.to_string()));
+        self
+    }
+
+    /// Add a lower time bound
+    pub fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.clauses.push(Clause::Since(since));
+        self
+    }
+
+    /// Add an upper time bound
+    pub fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.clauses.push(Clause::Until(until));
+        self
+    }
+
+    /// Whether the filter has no clauses
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+    }
+
+    /// The parsed clauses
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    /// Check a chain event against the filter
+    pub fn matches(&self, event: &ChainEvent) -> bool {
+        self.matches_parts(&event.event_type, event.timestamp, &event.data)
+    }
+
+    /// Check the parts of an event against the filter
+    pub fn matches_parts(&self, event_type: &str, timestamp: u64, data: &[u8]) -> bool {
+        // Only parse the payload if a field clause needs it
+        let mut payload: Option<Option<Value>> = None;
+        let time = event_time(timestamp);
+
+        self.clauses.iter().all(|clause| match clause {
+            Clause::EventType(pattern) => glob_match(pattern, event_type),
+            Clause::Since(since) => time >= *since,
+            Clause::Until(until) => time < *until,
+            Clause::Field { path, op, value } => {
+                let parsed = payload.get_or_insert_with(|| serde_json::from_slice(data).ok());
+                match parsed.as_ref().and_then(|v| lookup(v, path)) {
+                    Some(actual) => compare(actual, *op, value),
+                    None => false,
+                }
+            }
+        })
+    }
+}
+
+impl FromStr for EventFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for EventFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// Convert a chain event timestamp (Unix seconds) to a UTC time
+pub fn event_time(timestamp: u64) -> DateTime<Utc> {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .unwrap_or_default()
+}
+
+/// Parse an RFC 3339 time or Unix seconds
+pub fn parse_time(s: &str) -> Result<DateTime<Utc>> {
+    if let Ok(seconds) = s.parse::<i64>() {
+        return Utc
+            .timestamp_opt(seconds, 0)
+            .single()
+            .ok_or_else(|| anyhow!("Invalid timestamp: {}", s));
+    }
+
+    DateTime::parse_from_rfc3339(s)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|e| anyhow!("Invalid time '{}': {}", s, e))
+}
+
+fn tokenize(expr: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in expr.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(anyhow!("Unterminated quote in filter expression"));
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+fn parse_clause(token: &str) -> Result<Clause> {
+    if let Some(pattern) = token.strip_prefix("type:") {
+        return Ok(Clause::EventType(pattern.to_string()));
+    }
+    if let Some(time) = token.strip_prefix("since:") {
+        return Ok(Clause::Since(parse_time(time)?));
+    }
+    if let Some(time) = token.strip_prefix("until:") {
+        return Ok(Clause::Until(parse_time(time)?));
+    }
+    if let Some(rest) = token.strip_prefix("data.") {
+        // Longest operators first so `<=` isn't read as `<`
+        const OPS: [(&str, Op); 7] = [
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("=", Op::Eq),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+            ("~", Op::Contains),
+        ];
+
+        let (index, symbol, op) = OPS
+            .iter()
+            .filter_map(|(symbol, op)| rest.find(symbol).map(|i| (i, *symbol, *op)))
+            .min_by_key(|(i, symbol, _)| (*i, std::cmp::Reverse(symbol.len())))
+            .ok_or_else(|| anyhow!("Missing operator in field clause '{}'", token))?;
+
+        let path: Vec<String> = rest[..index].split('.').map(|p| p.to_string()).collect();
+        if path.iter().any(|p| p.is_empty()) {
+            return Err(anyhow!("Invalid field path in clause '{}'", token));
+        }
+
+        return Ok(Clause::Field {
+            path,
+            op,
+            value: rest[index + symbol.len()..].to_string(),
+        });
+    }
+
+    Err(anyhow!(
+        "Unknown filter clause '{}' (expected type:, since:, until: or data.<path><op><value>)",
+        token
+    ))
+}
+
+/// Match text against a pattern where `*` matches any run of characters
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let mut rest = text;
+    for (i, part) in parts.iter().enumerate() {
+        if i == 0 {
+            match rest.strip_prefix(part) {
+                Some(r) => rest = r,
+                None => return false,
+            }
+        } else if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        } else {
+            match rest.find(part) {
+                Some(pos) => rest = &rest[pos + part.len()..],
+                None => return false,
+            }
+        }
+    }
+    true
+}
+
+fn lookup<'a>(value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |current, key| match current {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
+}
+
+fn compare(actual: &Value, op: Op, expected: &str) -> bool {
+    let actual_text = match actual {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+
+    if op == Op::Contains {
+        return actual_text.contains(expected);
+    }
+
+    // Compare numerically when both sides are numbers
+    if let (Some(a), Ok(b)) = (actual.as_f64(), expected.parse::<f64>()) {
+        return match op {
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+            Op::Contains => unreachable!(),
+        };
+    }
+
+    match op {
+        Op::Eq => actual_text == expected,
+        Op::Ne => actual_text != expected,
+        Op::Lt => actual_text.as_str() < expected,
+        Op::Le => actual_text.as_str() <= expected,
+        Op::Gt => actual_text.as_str() > expected,
+        Op::Ge => actual_text.as_str() >= expected,
+        Op::Contains => unreachable!(),
+    }
+}
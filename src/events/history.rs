@@ -0,0 +1,168 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+#[cfg(feature = "event-store")]
+use tracing::{debug, warn};
+
+use theater::chain::ChainEvent;
+use theater::id::TheaterId;
+#[cfg(feature = "event-store")]
+use crate::events::store::EventStore;
+use crate::events::stream::EventStreamHub;
+use crate::events::verify;
+use crate::theater::client::TheaterClient;
+#[cfg(feature = "event-store")]
+use crate::theater::TheaterIdExt;
+
+/// The ends of an actor's chain: when it started, when it last grew, and its length
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainBounds {
+    pub first_timestamp: Option<u64>,
+    pub last_timestamp: Option<u64>,
+    pub count: u64,
+}
+
+impl ChainBounds {
+    pub fn of(events: &[ChainEvent]) -> Self {
+        Self {
+            first_timestamp: events.first().map(|event| event.timestamp),
+            last_timestamp: events.last().map(|event| event.timestamp),
+            count: events.len() as u64,
+        }
+    }
+}
+
+/// Source of actor event history.
+///
+/// Without an event store this is the live chain from the Theater server.
+/// With one, fetched and streamed events are archived locally and reads
+/// return the archive, so history survives actor stops and Theater restarts.
+pub struct EventHistory {
+    theater_client: Arc<TheaterClient>,
+    #[cfg(feature = "event-store")]
+    store: Option<Arc<EventStore>>,
+}
+
+impl EventHistory {
+    /// Event history backed only by the Theater server
+    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+        Self {
+            theater_client,
+            #[cfg(feature = "event-store")]
+            store: None,
+        }
+    }
+
+    /// Archive events in a local store
+    #[cfg(feature = "event-store")]
+    pub fn with_store(mut self, store: Arc<EventStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// The Theater client used for live reads
+    pub fn theater_client(&self) -> &Arc<TheaterClient> {
+        &self.theater_client
+    }
+
+    /// Running actors, plus archived ones when an event store is configured
+    pub async fn known_actors(&self) -> Result<Vec<TheaterId>> {
+        let mut actors = self.theater_client.list_actors().await?;
+
+        #[cfg(feature = "event-store")]
+        if let Some(store) = &self.store {
+            for id in store.actors().await? {
+                let id = TheaterId::from_str(&id)?;
+                if !actors.contains(&id) {
+                    actors.push(id);
+                }
+            }
+        }
+
+        Ok(actors)
+    }
+
+    /// Full event history for an actor
+    pub async fn actor_events(&self, actor_id: &TheaterId) -> Result<Vec<ChainEvent>> {
+        let live = self.theater_client.get_actor_events(actor_id).await;
+
+        #[cfg(feature = "event-store")]
+        if let Some(store) = &self.store {
+            let id = actor_id.as_string();
+            return match live {
+                Ok(events) => {
+                    // The archive holds everything live has plus anything from before a restart
+                    store.append(&id, &events).await?;
+                    store.load(&id).await
+                }
+                Err(e) => {
+                    let archived = store.load(&id).await?;
+                    if archived.is_empty() {
+                        Err(e)
+                    } else {
+                        debug!("Serving archived events for {} ({})", id, e);
+                        Ok(archived)
+                    }
+                }
+            };
+        }
+
+        live
+    }
+
+    /// The ends of an actor's chain.
+    ///
+    /// Theater only serves whole chains, so the archive answers when it
+    /// holds the actor's events, reading just the first and last; otherwise
+    /// the live chain is fetched.
+    pub async fn chain_bounds(&self, actor_id: &TheaterId) -> Result<ChainBounds> {
+        #[cfg(feature = "event-store")]
+        if let Some(store) = &self.store {
+            let bounds = store.bounds(&actor_id.as_string()).await?;
+            if bounds.count > 0 {
+                return Ok(bounds);
+            }
+        }
+
+        Ok(ChainBounds::of(&self.theater_client.get_actor_events(actor_id).await?))
+    }
+
+    /// Archive events as they stream in, for every actor with an active stream
+    pub fn spawn_recorder(self: &Arc<Self>, event_stream: &EventStreamHub) -> Option<tokio::task::JoinHandle<()>> {
+        #[cfg(feature = "event-store")]
+        if let Some(store) = self.store.clone() {
+            let mut events = event_stream.subscribe_all();
+            return Some(tokio::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => {
+                            if let Err(e) = store.append(&event.actor_id, std::slice::from_ref(&event.event)).await {
+                                warn!("Failed to archive event for {}: {}", event.actor_id, e);
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                            // Missed events are picked up on the next chain fetch
+                            warn!("Event recorder lagged; {} events not archived yet", skipped);
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            }));
+        }
+
+        let _ = event_stream;
+        None
+    }
+}
+
+/// Events after the one a cursor points at (all events without a cursor)
+pub fn events_after<'a>(events: &'a [ChainEvent], cursor: Option<&str>) -> Result<&'a [ChainEvent]> {
+    let Some(cursor) = cursor else {
+        return Ok(events);
+    };
+
+    events
+        .iter()
+        .position(|event| verify::to_hex(&event.hash) == cursor)
+        .map(|index| &events[index + 1..])
+        .ok_or_else(|| anyhow!("Unknown cursor {}; the actor's chain may have been reset", cursor))
+}
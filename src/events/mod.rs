@@ -0,0 +1,31 @@
+pub mod aggregate;
+pub mod causality;
+pub mod delivery;
+pub mod filter;
+pub mod history;
+#[cfg(feature = "otlp")]
+pub mod otlp;
+pub mod rate;
+pub mod render;
+pub mod search;
+#[cfg(feature = "event-store")]
+pub mod store;
+pub mod stream;
+pub mod summary;
+pub mod verify;
+pub mod webhook;
+
+pub use aggregate::AggregatedEvent;
+pub use causality::{CausalityTracker, Operation};
+pub use delivery::DurableEvents;
+pub use filter::EventFilter;
+pub use history::{ChainBounds, EventHistory};
+pub use rate::RateSnapshot;
+#[cfg(feature = "event-store")]
+pub use store::EventStore;
+pub use stream::{ActorEvent, EventStreamHub, FilteredEvents};
+pub use verify::{ChainIssue, ChainVerification};
+pub use webhook::WebhookConfig;
+
+#[cfg(test)]
+mod tests;
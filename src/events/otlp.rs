@@ -0,0 +1,107 @@
+use anyhow::Result;
+use opentelemetry::trace::{Span, SpanKind, Tracer, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::events::filter::EventFilter;
+use crate::events::render::{decode_payload, event_kind};
+use crate::events::stream::{ActorEvent, EventStreamHub};
+use crate::events::verify::to_hex;
+
+/// How often the exporter looks for newly started actors
+const ACTOR_DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Longest payload attached to a span
+const MAX_PAYLOAD_CHARS: usize = 4096;
+
+/// Export live actor events to an OTLP collector.
+///
+/// Each chain event becomes a zero-length span at the event's timestamp,
+/// with the actor, event type, hashes and decoded payload as attributes, so
+/// the events line up with any other traces in the collector.
+pub fn spawn_otlp_exporter(
+    endpoint: &str,
+    filter: &str,
+    event_stream: &Arc<EventStreamHub>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let filter = EventFilter::parse(filter)?;
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    let provider = TracerProvider::builder()
+        .with_batch_exporter(exporter, runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new("service.name", "theater")]))
+        .build();
+
+    info!("Exporting actor events to OTLP collector at {}", endpoint);
+
+    let event_stream = event_stream.clone();
+    Ok(tokio::spawn(async move {
+        let tracer = provider.tracer("theater-mcp");
+        let mut events = event_stream.subscribe_all();
+        let mut discovery = tokio::time::interval(ACTOR_DISCOVERY_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = discovery.tick() => {
+                    if let Err(e) = event_stream.watch_running_actors().await {
+                        debug!("Could not list actors for OTLP export: {}", e);
+                    }
+                }
+                event = events.recv() => match event {
+                    Ok(event) if filter.matches(&event.event) => export(&tracer, &event),
+                    Ok(_) => {}
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("OTLP exporter lagged; skipped {} events", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+
+        if let Err(e) = provider.shutdown() {
+            warn!("Failed to flush OTLP exporter: {}", e);
+        }
+    }))
+}
+
+fn export(tracer: &impl Tracer, event: &ActorEvent) {
+    let chain_event = &event.event;
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(chain_event.timestamp);
+
+    let mut attributes = vec![
+        KeyValue::new("theater.actor_id", event.actor_id.clone()),
+        KeyValue::new("theater.event.type", chain_event.event_type.clone()),
+        KeyValue::new("theater.event.kind", event_kind(&chain_event.event_type)),
+        KeyValue::new("theater.event.hash", to_hex(&chain_event.hash)),
+    ];
+    if let Some(parent) = &chain_event.parent_hash {
+        attributes.push(KeyValue::new("theater.event.parent_hash", to_hex(parent)));
+    }
+    if let Some(description) = &chain_event.description {
+        attributes.push(KeyValue::new("theater.event.description", description.clone()));
+    }
+    if let Some(payload) = decode_payload(&chain_event.data) {
+        let text = payload.to_string();
+        let text = match text.char_indices().nth(MAX_PAYLOAD_CHARS) {
+            Some((index, _)) => text[..index].to_string(),
+            None => text,
+        };
+        attributes.push(KeyValue::new("theater.event.payload", text));
+    }
+
+    let mut span = tracer
+        .span_builder(chain_event.event_type.clone())
+        .with_kind(SpanKind::Internal)
+        .with_start_time(time)
+        .with_attributes(attributes)
+        .start(tracer);
+    span.end_with_timestamp(time);
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Window the rolling rate is computed over
+pub const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Rolling event-rate and payload-size statistics for one actor
+#[derive(Debug, Default)]
+pub struct EventRate {
+    /// Arrival time and payload size of every event inside the window
+    recent: VecDeque<(Instant, usize)>,
+    total_events: u64,
+    total_bytes: u64,
+    max_bytes: usize,
+    last_event_at: Option<DateTime<Utc>>,
+}
+
+/// Point-in-time view of an actor's event rate
+#[derive(Debug, Clone, Serialize)]
+pub struct RateSnapshot {
+    pub events_per_second: f64,
+    pub events_in_window: usize,
+    pub window_secs: u64,
+    pub bytes_per_second: f64,
+    pub total_events: u64,
+    pub average_size: f64,
+    pub max_size: usize,
+    pub last_event_at: Option<DateTime<Utc>>,
+}
+
+impl EventRate {
+    /// Record an event with the given payload size
+    pub fn record(&mut self, size: usize, now: Instant) {
+        self.recent.push_back((now, size));
+        self.total_events += 1;
+        self.total_bytes += size as u64;
+        self.max_bytes = self.max_bytes.max(size);
+        self.last_event_at = Some(Utc::now());
+        self.expire(now);
+    }
+
+    /// Statistics as of `now`
+    pub fn snapshot(&mut self, now: Instant) -> RateSnapshot {
+        self.expire(now);
+
+        let window = RATE_WINDOW.as_secs_f64();
+        let window_bytes: usize = self.recent.iter().map(|(_, size)| size).sum();
+
+        RateSnapshot {
+            events_per_second: self.recent.len() as f64 / window,
+            events_in_window: self.recent.len(),
+            window_secs: RATE_WINDOW.as_secs(),
+            bytes_per_second: window_bytes as f64 / window,
+            total_events: self.total_events,
+            average_size: match self.total_events {
+                0 => 0.0,
+                n => self.total_bytes as f64 / n as f64,
+            },
+            max_size: self.max_bytes,
+            last_event_at: self.last_event_at,
+        }
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some((at, _)) = self.recent.front() {
+            if now.duration_since(*at) < RATE_WINDOW {
+                break;
+            }
+            self.recent.pop_front();
+        }
+    }
+}
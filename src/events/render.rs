@@ -0,0 +1,125 @@
+use serde_json::{json, Map, Value};
+
+use theater::chain::ChainEvent;
+use crate::encoding::CompactEvent;
+use crate::events::filter::event_time;
+use crate::events::verify::to_hex;
+
+/// Longest payload text included in a summary
+const SUMMARY_PAYLOAD_CHARS: usize = 120;
+
+/// Human-readable kinds for known event type prefixes, most specific first
+const KINDS: [(&str, &str); 12] = [
+    ("wasm", "WebAssembly call"),
+    ("message-server", "Actor message"),
+    ("channel", "Channel message"),
+    ("http-client", "Outgoing HTTP request"),
+    ("http", "HTTP request"),
+    ("filesystem", "Filesystem access"),
+    ("store", "Content store access"),
+    ("supervisor", "Supervisor action"),
+    ("runtime", "Runtime lifecycle"),
+    ("timing", "Timer"),
+    ("process", "Process"),
+    ("environment", "Environment access"),
+];
+
+/// Human-readable kind of an event type
+pub fn event_kind(event_type: &str) -> &'static str {
+    KINDS
+        .iter()
+        .find(|(prefix, _)| event_type.starts_with(prefix))
+        .map(|(_, kind)| *kind)
+        .unwrap_or("Other")
+}
+
+/// Decode an event payload into JSON.
+///
+/// JSON payloads are returned as-is, with byte arrays inside them (how
+/// message bodies are usually embedded) decoded to text or nested JSON.
+/// Other UTF-8 payloads become strings; binary payloads return `None`.
+pub fn decode_payload(data: &[u8]) -> Option<Value> {
+    if data.is_empty() {
+        return None;
+    }
+    if let Ok(value) = serde_json::from_slice::<Value>(data) {
+        return Some(decode_byte_arrays(value));
+    }
+    std::str::from_utf8(data).ok().map(|text| Value::String(text.to_string()))
+}
+
+/// Render a chain event as summarized JSON, keeping the raw event (in compact form) under `raw`
+pub fn render_event(event: &ChainEvent) -> Value {
+    let kind = event_kind(&event.event_type);
+    let payload = decode_payload(&event.data);
+
+    json!({
+        "kind": kind,
+        "event_type": event.event_type,
+        "summary": summarize(kind, event, payload.as_ref()),
+        "time": event_time(event.timestamp).to_rfc3339(),
+        "hash": to_hex(&event.hash),
+        "parent_hash": event.parent_hash.as_deref().map(to_hex),
+        "payload": payload,
+        "raw": CompactEvent(event),
+    })
+}
+
+fn summarize(kind: &str, event: &ChainEvent, payload: Option<&Value>) -> String {
+    if let Some(description) = event.description.as_deref().filter(|d| !d.is_empty()) {
+        return format!("{}: {}", kind, description);
+    }
+
+    match payload {
+        Some(payload) => {
+            let text = match payload {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            format!("{} ({}): {}", kind, event.event_type, truncate(&text, SUMMARY_PAYLOAD_CHARS))
+        }
+        None if event.data.is_empty() => format!("{} ({})", kind, event.event_type),
+        None => format!("{} ({}): {} bytes of binary data", kind, event.event_type, event.data.len()),
+    }
+}
+
+fn decode_byte_arrays(value: Value) -> Value {
+    match value {
+        Value::Array(items) => match as_bytes(&items) {
+            Some(bytes) => decode_embedded(&bytes).unwrap_or(Value::Array(items)),
+            None => Value::Array(items.into_iter().map(decode_byte_arrays).collect()),
+        },
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, decode_byte_arrays(value)))
+                .collect::<Map<_, _>>(),
+        ),
+        other => other,
+    }
+}
+
+/// Decode embedded bytes, unless they look like a plain list of numbers
+fn decode_embedded(bytes: &[u8]) -> Option<Value> {
+    match decode_payload(bytes)? {
+        Value::String(text) if text.chars().any(|c| c.is_control() && !c.is_whitespace()) => None,
+        value => Some(value),
+    }
+}
+
+/// Treat a non-empty array of 0-255 integers as bytes
+fn as_bytes(items: &[Value]) -> Option<Vec<u8>> {
+    if items.is_empty() {
+        return None;
+    }
+    items
+        .iter()
+        .map(|item| item.as_u64().filter(|b| *b <= u8::MAX as u64).map(|b| b as u8))
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
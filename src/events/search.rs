@@ -0,0 +1,82 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use theater::chain::ChainEvent;
+use crate::events::filter::EventFilter;
+use crate::events::render::{decode_payload, render_event};
+
+/// An event that matched a search, with its neighbours for context
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchMatch {
+    pub actor_id: String,
+    /// Position of the event in the actor's chain
+    pub chain_index: usize,
+    pub event: Value,
+    pub before: Vec<Value>,
+    pub after: Vec<Value>,
+}
+
+/// A search over event history.
+///
+/// `query` is matched case-insensitively against the event type,
+/// description and decoded payload; `filter` narrows matches with the event
+/// filter syntax, including `data.<path>` comparisons. Both must match.
+#[derive(Debug, Clone, Default)]
+pub struct EventSearch {
+    pub query: Option<String>,
+    pub filter: EventFilter,
+    /// Events to include on each side of a match
+    pub context: usize,
+}
+
+impl EventSearch {
+    /// Whether a single event matches
+    pub fn matches(&self, event: &ChainEvent) -> bool {
+        if !self.filter.matches(event) {
+            return false;
+        }
+
+        match &self.query {
+            Some(query) => searchable_text(event).contains(&query.to_lowercase()),
+            None => true,
+        }
+    }
+
+    /// Search one actor's chain, oldest match first
+    pub fn search_chain(&self, actor_id: &str, events: &[ChainEvent]) -> Vec<SearchMatch> {
+        events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| self.matches(event))
+            .map(|(index, event)| {
+                let start = index.saturating_sub(self.context);
+                let end = (index + 1 + self.context).min(events.len());
+
+                SearchMatch {
+                    actor_id: actor_id.to_string(),
+                    chain_index: index,
+                    event: render_event(event),
+                    before: events[start..index].iter().map(render_event).collect(),
+                    after: events[index + 1..end].iter().map(render_event).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Lowercased text a query is matched against
+fn searchable_text(event: &ChainEvent) -> String {
+    let payload = match decode_payload(&event.data) {
+        Some(Value::String(text)) => text,
+        Some(value) => value.to_string(),
+        None => String::new(),
+    };
+
+    format!(
+        "{}\n{}\n{}",
+        event.event_type,
+        event.description.as_deref().unwrap_or(""),
+        payload
+    )
+    .to_lowercase()
+}
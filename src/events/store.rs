@@ -0,0 +1,178 @@
+use anyhow::{anyhow, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+use theater::chain::ChainEvent;
+use crate::events::history::ChainBounds;
+
+/// Local SQLite archive of actor events.
+///
+/// Events are keyed by actor and hash, so storing the same event twice (for
+/// example once from a live stream and once from a chain fetch) is a no-op.
+pub struct EventStore {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl EventStore {
+    /// Open (or create) the archive at the given path
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open(path)
+            .map_err(|e| anyhow!("Failed to open event store {}: {}", path.display(), e))?;
+        Self::init(connection)
+    }
+
+    /// Open a throwaway in-memory archive
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(connection: Connection) -> Result<Self> {
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS events (
+                seq INTEGER PRIMARY KEY AUTOINCREMENT,
+                actor_id TEXT NOT NULL,
+                hash BLOB NOT NULL,
+                parent_hash BLOB,
+                event_type TEXT NOT NULL,
+                data BLOB NOT NULL,
+                timestamp INTEGER NOT NULL,
+                description TEXT,
+                UNIQUE (actor_id, hash)
+            );
+            CREATE INDEX IF NOT EXISTS events_actor ON events (actor_id, seq);",
+        )?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
+    /// Archive events for an actor, skipping ones already stored.
+    ///
+    /// Returns the number of newly stored events.
+    pub async fn append(&self, actor_id: &str, events: &[ChainEvent]) -> Result<usize> {
+        let connection = self.connection.clone();
+        let actor_id = actor_id.to_string();
+        let events = events.to_vec();
+
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection.lock().unwrap();
+            let tx = connection.transaction()?;
+            let mut inserted = 0;
+            {
+                let mut stmt = tx.prepare_cached(
+                    "INSERT OR IGNORE INTO events
+                        (actor_id, hash, parent_hash, event_type, data, timestamp, description)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                )?;
+                for event in &events {
+                    inserted += stmt.execute(params![
+                        actor_id,
+                        event.hash,
+                        event.parent_hash,
+                        event.event_type,
+                        event.data,
+                        event.timestamp as i64,
+                        event.description,
+                    ])?;
+                }
+            }
+            tx.commit()?;
+
+            if inserted > 0 {
+                debug!("Archived {} events for actor {}", inserted, actor_id);
+            }
+            Ok(inserted)
+        })
+        .await?
+    }
+
+    /// Archived events for an actor, in the order they were stored
+    pub async fn load(&self, actor_id: &str) -> Result<Vec<ChainEvent>> {
+        let connection = self.connection.clone();
+        let actor_id = actor_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap();
+            let mut stmt = connection.prepare_cached(
+                "SELECT hash, parent_hash, event_type, data, timestamp, description
+                 FROM events WHERE actor_id = ?1 ORDER BY seq",
+            )?;
+
+            let events = stmt
+                .query_map(params![actor_id], |row| {
+                    Ok(ChainEvent {
+                        hash: row.get(0)?,
+                        parent_hash: row.get(1)?,
+                        event_type: row.get(2)?,
+                        data: row.get(3)?,
+                        timestamp: row.get::<_, i64>(4)? as u64,
+                        description: row.get(5)?,
+                    })
+                })?
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok(events)
+        })
+        .await?
+    }
+
+    /// Actors with archived events
+    pub async fn actors(&self) -> Result<Vec<String>> {
+        let connection = self.connection.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap();
+            let mut stmt = connection.prepare_cached("SELECT DISTINCT actor_id FROM events ORDER BY actor_id")?;
+            let actors = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<Vec<String>>>()?;
+            Ok(actors)
+        })
+        .await?
+    }
+
+    /// The ends of an actor's archived chain, without loading the events in between
+    pub async fn bounds(&self, actor_id: &str) -> Result<ChainBounds> {
+        let connection = self.connection.clone();
+        let actor_id = actor_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap();
+            let (count, first, last): (i64, Option<i64>, Option<i64>) = connection.query_row(
+                "SELECT COUNT(*),
+                    (SELECT timestamp FROM events WHERE actor_id = ?1 ORDER BY seq LIMIT 1),
+                    (SELECT timestamp FROM events WHERE actor_id = ?1 ORDER BY seq DESC LIMIT 1)
+                 FROM events WHERE actor_id = ?1",
+                params![actor_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+            Ok(ChainBounds {
+                first_timestamp: first.map(|timestamp| timestamp as u64),
+                last_timestamp: last.map(|timestamp| timestamp as u64),
+                count: count as u64,
+            })
+        })
+        .await?
+    }
+
+    /// Number of archived events for an actor
+    pub async fn count(&self, actor_id: &str) -> Result<u64> {
+        let connection = self.connection.clone();
+        let actor_id = actor_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let connection = connection.lock().unwrap();
+            let count: Option<i64> = connection
+                .query_row(
+                    "SELECT COUNT(*) FROM events WHERE actor_id = ?1",
+                    params![actor_id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            Ok(count.unwrap_or(0) as u64)
+        })
+        .await?
+    }
+}
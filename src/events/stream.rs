@@ -0,0 +1,371 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use theater::chain::ChainEvent;
+use theater::id::TheaterId;
+use crate::events::delivery::{self, DeliveryCursors, DurableEvents, ReplayBuffer};
+use crate::events::filter::EventFilter;
+use crate::events::rate::{EventRate, RateSnapshot};
+use crate::theater::client::TheaterClient;
+use crate::theater::TheaterIdExt;
+
+/// Events buffered per consumer before slow consumers start lagging
+const BROADCAST_CAPACITY: usize = 1024;
+
+/// How often idle actor streams check whether anyone is still listening
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Attempts to resubscribe after an actor's Theater subscription drops
+const RESUBSCRIBE_ATTEMPTS: u32 = 5;
+
+/// Delay before the first resubscribe attempt; doubled on each further attempt
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// A chain event received live from an actor
+#[derive(Debug, Clone)]
+pub struct ActorEvent {
+    /// Position in the hub's delivery order, increasing across all actors
+    pub seq: u64,
+    pub actor_id: String,
+    pub received_at: DateTime<Utc>,
+    pub event: ChainEvent,
+}
+
+struct ActorStream {
+    sender: broadcast::Sender<ActorEvent>,
+    // Kept alive for good even without direct receivers (e.g. for firehose consumers)
+    pinned: bool,
+    // Consumers keeping the stream alive until they unwatch it
+    watchers: HashSet<String>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+/// Maintains Theater event subscriptions per actor and fans the events out
+/// to interested consumers.
+///
+/// Each actor has at most one Theater subscription no matter how many
+/// consumers are listening. Consumers either subscribe to a single actor or
+/// to the firehose of every actor with an active stream.
+pub struct EventStreamHub {
+    theater_client: Arc<TheaterClient>,
+    streams: Mutex<HashMap<String, ActorStream>>,
+    firehose: broadcast::Sender<ActorEvent>,
+    // Kept across stream restarts so rates don't reset when a stream goes idle
+    rates: Mutex<HashMap<String, EventRate>>,
+    next_seq: AtomicU64,
+    // Recent events per actor, for deduplication and replay to resuming subscribers
+    replay: Mutex<HashMap<String, ReplayBuffer>>,
+    cursors: DeliveryCursors,
+}
+
+impl EventStreamHub {
+    /// Create a new hub with no active streams
+    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+        Self {
+            theater_client,
+            streams: Mutex::new(HashMap::new()),
+            firehose: broadcast::channel(BROADCAST_CAPACITY).0,
+            rates: Mutex::new(HashMap::new()),
+            next_seq: AtomicU64::new(1),
+            replay: Mutex::new(HashMap::new()),
+            cursors: DeliveryCursors::default(),
+        }
+    }
+
+    /// Receive live events for one actor, starting its stream if needed
+    pub async fn subscribe(self: &Arc<Self>, actor_id: &str) -> Result<broadcast::Receiver<ActorEvent>> {
+        if let Some(stream) = self.streams.lock().unwrap().get(actor_id) {
+            return Ok(stream.sender.subscribe());
+        }
+
+        let sender = self.start_stream(actor_id, false, None).await?;
+        Ok(sender.subscribe())
+    }
+
+    /// Receive live events for one actor that match a filter
+    pub async fn subscribe_filtered(self: &Arc<Self>, actor_id: &str, filter: EventFilter) -> Result<FilteredEvents> {
+        Ok(FilteredEvents::new(self.subscribe(actor_id).await?, filter))
+    }
+
+    /// Receive an actor's events as a named subscriber, resuming after the
+    /// subscriber's last acknowledged event.
+    ///
+    /// A subscriber seen for the first time starts with new events only.
+    pub async fn subscribe_durable(self: &Arc<Self>, subscriber_id: &str, actor_id: &str) -> Result<DurableEvents> {
+        // Subscribe before reading the replay buffer so nothing falls in between
+        let receiver = self.subscribe(actor_id).await?;
+        let key = delivery::cursor_key(subscriber_id, actor_id);
+
+        let (pending, delivered) = match self.cursors.get(&key) {
+            Some(cursor) => {
+                let replay = self.replay.lock().unwrap();
+                match replay.get(actor_id) {
+                    Some(buffer) => {
+                        delivery::warn_if_gap(&key, cursor, buffer);
+                        (buffer.after(cursor), cursor)
+                    }
+                    None => (VecDeque::new(), cursor),
+                }
+            }
+            None => (VecDeque::new(), self.next_seq.load(Ordering::SeqCst) - 1),
+        };
+
+        Ok(DurableEvents::new(key, actor_id.to_string(), self.clone(), receiver, pending, delivered))
+    }
+
+    /// Buffered events for an actor after a sequence number
+    pub fn replay_after(&self, actor_id: &str, seq: u64) -> VecDeque<ActorEvent> {
+        self.replay
+            .lock()
+            .unwrap()
+            .get(actor_id)
+            .map(|buffer| buffer.after(seq))
+            .unwrap_or_default()
+    }
+
+    /// Acknowledged positions of durable subscribers
+    pub fn cursors(&self) -> &DeliveryCursors {
+        &self.cursors
+    }
+
+    /// Receive live events from every actor with an active stream
+    pub fn subscribe_all(&self) -> broadcast::Receiver<ActorEvent> {
+        self.firehose.subscribe()
+    }
+
+    /// Keep an actor's stream running for firehose consumers, even with no direct subscribers
+    pub async fn watch(self: &Arc<Self>, actor_id: &str) -> Result<()> {
+        if let Some(stream) = self.streams.lock().unwrap().get_mut(actor_id) {
+            stream.pinned = true;
+            return Ok(());
+        }
+
+        self.start_stream(actor_id, true, None).await?;
+        Ok(())
+    }
+
+    /// Keep an actor's stream running on behalf of `consumer` until it calls
+    /// [`unwatch`](Self::unwatch). Other consumers' watches are unaffected.
+    pub async fn watch_as(self: &Arc<Self>, consumer: &str, actor_id: &str) -> Result<()> {
+        if let Some(stream) = self.streams.lock().unwrap().get_mut(actor_id) {
+            stream.watchers.insert(consumer.to_string());
+            return Ok(());
+        }
+
+        self.start_stream(actor_id, false, Some(consumer)).await?;
+        Ok(())
+    }
+
+    /// Watch every running actor, e.g. for consumers that want all events.
+    /// Actors that can't be streamed are skipped.
+    pub async fn watch_running_actors(self: &Arc<Self>) -> Result<()> {
+        for id in self.theater_client.list_actors().await? {
+            let actor_id = id.as_string();
+            if let Err(e) = self.watch(&actor_id).await {
+                debug!("Could not stream events from {}: {}", actor_id, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop `consumer`'s watch on an actor's stream; it ends once nobody
+    /// else watches or listens
+    pub fn unwatch(&self, consumer: &str, actor_id: &str) {
+        if let Some(stream) = self.streams.lock().unwrap().get_mut(actor_id) {
+            stream.watchers.remove(consumer);
+        }
+    }
+
+    /// Actors with an active stream
+    pub fn active_actors(&self) -> Vec<String> {
+        let mut actors: Vec<String> = self.streams.lock().unwrap().keys().cloned().collect();
+        actors.sort();
+        actors
+    }
+
+    /// Rolling event rates for every actor that has been streamed, busiest first
+    pub fn rates(&self) -> Vec<(String, RateSnapshot)> {
+        let now = Instant::now();
+        let mut rates: Vec<(String, RateSnapshot)> = self
+            .rates
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .map(|(actor_id, rate)| (actor_id.clone(), rate.snapshot(now)))
+            .collect();
+        rates.sort_by(|a, b| b.1.events_per_second.total_cmp(&a.1.events_per_second));
+        rates
+    }
+
+    /// Stop every stream (used on server shutdown)
+    pub fn shutdown(&self) {
+        for (actor_id, stream) in self.streams.lock().unwrap().drain() {
+            debug!("Stopping event stream for {}", actor_id);
+            stream.task.abort();
+        }
+    }
+
+    async fn start_stream(
+        self: &Arc<Self>,
+        actor_id: &str,
+        pinned: bool,
+        watcher: Option<&str>,
+    ) -> Result<broadcast::Sender<ActorEvent>> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let mut subscription = self.theater_client.subscribe_to_actor(&theater_id).await?;
+
+        let mut streams = self.streams.lock().unwrap();
+
+        // Another consumer may have started the stream while we subscribed
+        if let Some(existing) = streams.get_mut(actor_id) {
+            existing.pinned |= pinned;
+            existing.watchers.extend(watcher.map(str::to_string));
+            return Ok(existing.sender.clone());
+        }
+
+        let sender = broadcast::channel(BROADCAST_CAPACITY).0;
+        let hub = self.clone();
+        let task_sender = sender.clone();
+        let task_actor_id = actor_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut idle_check = tokio::time::interval(IDLE_CHECK_INTERVAL);
+            idle_check.tick().await;
+
+            loop {
+                tokio::select! {
+                    event = subscription.events.recv() => {
+                        let Some(event) = event else {
+                            // A dropped subscription is usually a transient disconnect
+                            if hub.is_idle(&task_actor_id) {
+                                info!("Event stream for actor {} ended", task_actor_id);
+                                break;
+                            }
+                            match hub.resubscribe(&theater_id).await {
+                                Some(resumed) => {
+                                    subscription = resumed;
+                                    continue;
+                                }
+                                None => {
+                                    warn!("Event stream for actor {} ended; could not resubscribe", task_actor_id);
+                                    break;
+                                }
+                            }
+                        };
+
+                        if let Some(actor_event) = hub.accept(&task_actor_id, event) {
+                            // Sending only fails when there are no receivers, which is fine
+                            let _ = task_sender.send(actor_event.clone());
+                            let _ = hub.firehose.send(actor_event);
+                        }
+                    }
+                    _ = idle_check.tick() => {
+                        if hub.is_idle(&task_actor_id) {
+                            debug!("No consumers left for actor {}; stopping its event stream", task_actor_id);
+                            break;
+                        }
+                    }
+                }
+            }
+
+            hub.streams.lock().unwrap().remove(&task_actor_id);
+        });
+
+        streams.insert(
+            actor_id.to_string(),
+            ActorStream {
+                sender: sender.clone(),
+                pinned,
+                watchers: watcher.map(str::to_string).into_iter().collect(),
+                task,
+            },
+        );
+        info!("Started event stream for actor {}", actor_id);
+
+        Ok(sender)
+    }
+
+    /// Sequence, record and buffer an incoming event; `None` if it was
+    /// already delivered (Theater may resend events after a resubscribe)
+    fn accept(&self, actor_id: &str, event: ChainEvent) -> Option<ActorEvent> {
+        let mut replay = self.replay.lock().unwrap();
+        let buffer = replay.entry(actor_id.to_string()).or_default();
+        if buffer.contains(&event.hash) {
+            debug!("Skipping duplicate event from {}", actor_id);
+            return None;
+        }
+
+        self.rates
+            .lock()
+            .unwrap()
+            .entry(actor_id.to_string())
+            .or_default()
+            .record(event.data.len(), Instant::now());
+
+        let actor_event = ActorEvent {
+            seq: self.next_seq.fetch_add(1, Ordering::SeqCst),
+            actor_id: actor_id.to_string(),
+            received_at: Utc::now(),
+            event,
+        };
+        buffer.push(actor_event.clone());
+        Some(actor_event)
+    }
+
+    /// Try to re-establish a dropped Theater subscription, backing off between attempts
+    async fn resubscribe(&self, theater_id: &TheaterId) -> Option<crate::theater::client::EventSubscription> {
+        let mut delay = RESUBSCRIBE_DELAY;
+        for attempt in 1..=RESUBSCRIBE_ATTEMPTS {
+            tokio::time::sleep(delay).await;
+            match self.theater_client.subscribe_to_actor(theater_id).await {
+                Ok(subscription) => {
+                    info!("Resubscribed to events for actor {} (attempt {})", theater_id, attempt);
+                    return Some(subscription);
+                }
+                Err(e) => {
+                    debug!("Resubscribe to {} failed (attempt {}): {}", theater_id, attempt, e);
+                    delay *= 2;
+                }
+            }
+        }
+        None
+    }
+
+    fn is_idle(&self, actor_id: &str) -> bool {
+        match self.streams.lock().unwrap().get(actor_id) {
+            Some(stream) => !stream.pinned && stream.watchers.is_empty() && stream.sender.receiver_count() == 0,
+            None => true,
+        }
+    }
+}
+
+/// A live event receiver that only yields events matching a filter
+pub struct FilteredEvents {
+    receiver: broadcast::Receiver<ActorEvent>,
+    filter: EventFilter,
+}
+
+impl FilteredEvents {
+    pub fn new(receiver: broadcast::Receiver<ActorEvent>, filter: EventFilter) -> Self {
+        Self { receiver, filter }
+    }
+
+    /// Wait for the next matching event; `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<ActorEvent> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(event) if self.filter.matches(&event.event) => return Some(event),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event consumer lagged; skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
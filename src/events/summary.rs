@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use theater::chain::ChainEvent;
+use crate::events::filter::event_time;
+use crate::events::render::{event_kind, render_event};
+use crate::events::verify::to_hex;
+
+/// Most notable events included in a summary
+const MAX_NOTABLE_EVENTS: usize = 50;
+
+/// Quiet periods at least this long are called out
+const NOTABLE_GAP_SECS: u64 = 300;
+
+/// Counts and time range for one event type
+#[derive(Debug, Clone, Serialize)]
+pub struct TypeStats {
+    pub kind: &'static str,
+    pub count: usize,
+    pub first_at: DateTime<Utc>,
+    pub last_at: DateTime<Utc>,
+}
+
+/// An event worth calling out in a summary
+#[derive(Debug, Clone, Serialize)]
+pub struct NotableEvent {
+    pub chain_index: usize,
+    pub reason: &'static str,
+    pub time: DateTime<Utc>,
+    pub event_type: String,
+    pub summary: String,
+}
+
+/// A compact description of a chain, small enough to replace the raw events
+/// in a model's context
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainSummary {
+    pub event_count: usize,
+    pub first_event_at: Option<DateTime<Utc>>,
+    pub last_event_at: Option<DateTime<Utc>>,
+    pub duration_secs: u64,
+    pub head: Option<String>,
+    pub by_type: BTreeMap<String, TypeStats>,
+    /// Lifecycle changes, errors, first occurrences and long gaps, oldest first
+    pub notable: Vec<NotableEvent>,
+    /// Whether notable events were cut off at the limit
+    pub notable_truncated: bool,
+}
+
+/// Summarize a chain, oldest event first
+pub fn summarize_chain(events: &[ChainEvent]) -> ChainSummary {
+    let mut by_type: BTreeMap<String, TypeStats> = BTreeMap::new();
+    let mut notable = Vec::new();
+    let mut notable_truncated = false;
+    let mut previous: Option<u64> = None;
+
+    for (index, event) in events.iter().enumerate() {
+        let time = event_time(event.timestamp);
+        let first_of_type = !by_type.contains_key(&event.event_type);
+
+        let stats = by_type.entry(event.event_type.clone()).or_insert_with(|| TypeStats {
+            kind: event_kind(&event.event_type),
+            count: 0,
+            first_at: time,
+            last_at: time,
+        });
+        stats.count += 1;
+        stats.last_at = time;
+
+        let reason = if is_error(event) {
+            Some("error")
+        } else if matches!(stats.kind, "Runtime lifecycle" | "Supervisor action") {
+            Some("lifecycle")
+        } else if previous.is_some_and(|p| event.timestamp.saturating_sub(p) >= NOTABLE_GAP_SECS) {
+            Some("after_quiet_period")
+        } else if first_of_type {
+            Some("first_of_type")
+        } else {
+            None
+        };
+        previous = Some(event.timestamp);
+
+        if let Some(reason) = reason {
+            if notable.len() == MAX_NOTABLE_EVENTS {
+                notable_truncated = true;
+                continue;
+            }
+            let rendered = render_event(event);
+            notable.push(NotableEvent {
+                chain_index: index,
+                reason,
+                time,
+                event_type: event.event_type.clone(),
+                summary: rendered["summary"].as_str().unwrap_or_default().to_string(),
+            });
+        }
+    }
+
+    let first = events.first().map(|e| e.timestamp);
+    let last = events.last().map(|e| e.timestamp);
+
+    ChainSummary {
+        event_count: events.len(),
+        first_event_at: first.map(event_time),
+        last_event_at: last.map(event_time),
+        duration_secs: match (first, last) {
+            (Some(first), Some(last)) => last.saturating_sub(first),
+            _ => 0,
+        },
+        head: events.last().map(|e| to_hex(&e.hash)),
+        by_type,
+        notable,
+        notable_truncated,
+    }
+}
+
+fn is_error(event: &ChainEvent) -> bool {
+    let mentions_error = |text: &str| {
+        let text = text.to_lowercase();
+        text.contains("error") || text.contains("fail") || text.contains("panic")
+    };
+
+    mentions_error(&event.event_type) || event.description.as_deref().is_some_and(mentions_error)
+}
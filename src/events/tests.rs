@@ -0,0 +1,288 @@
+use crate::events::filter::{Clause, EventFilter, Op};
+
+#[test]
+fn test_filter_parse_clauses() {
+    let filter = EventFilter::parse(r#"type:wasm* since:0 data.user~"bob smith" data.count>=3"#).unwrap();
+
+    assert_eq!(filter.clauses().len(), 4);
+    assert_eq!(filter.clauses()[0], Clause::EventType("wasm*".to_string()));
+    assert_eq!(
+        filter.clauses()[2],
+        Clause::Field {
+            path: vec!["user".to_string()],
+            op: Op::Contains,
+            value: "bob smith".to_string(),
+        }
+    );
+    assert_eq!(
+        filter.clauses()[3],
+        Clause::Field {
+            path: vec!["count".to_string()],
+            op: Op::Ge,
+            value: "3".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_filter_rejects_bad_expressions() {
+    assert!(EventFilter::parse("bogus").is_err());
+    assert!(EventFilter::parse("data.count").is_err());
+    assert!(EventFilter::parse("since:yesterday").is_err());
+    assert!(EventFilter::parse(r#"data.name="unterminated"#).is_err());
+}
+
+#[test]
+fn test_filter_matches_events() {
+    let data = br#"{"counter": -2, "nested": {"name": "alpha"}}"#;
+
+    let filter = EventFilter::parse("type:wasm* data.counter<0").unwrap();
+    assert!(filter.matches_parts("wasm-call", 100, data));
+    assert!(!filter.matches_parts("http-request", 100, data));
+
+    let filter = EventFilter::parse("data.nested.name=alpha until:200").unwrap();
+    assert!(filter.matches_parts("any", 100, data));
+    assert!(!filter.matches_parts("any", 200, data));
+
+    // Field clauses never match payloads that aren't JSON
+    let filter = EventFilter::parse("data.counter<0").unwrap();
+    assert!(!filter.matches_parts("any", 100, b"not json"));
+
+    assert!(EventFilter::all().matches_parts("any", 0, b""));
+}
+
+fn event(event_type: &str, timestamp: u64) -> theater::chain::ChainEvent {
+    theater::chain::ChainEvent {
+        hash: vec![],
+        parent_hash: None,
+        event_type: event_type.to_string(),
+        data: vec![],
+        timestamp,
+        description: None,
+    }
+}
+
+#[test]
+fn test_merge_orders_across_actors() {
+    use crate::events::aggregate::merge_ordered;
+
+    let merged = merge_ordered(vec![
+        ("b".to_string(), vec![event("b0", 1), event("b1", 5)]),
+        ("a".to_string(), vec![event("a0", 1), event("a1", 3), event("a2", 2)]),
+    ]);
+
+    let order: Vec<&str> = merged.iter().map(|e| e.event.event_type.as_str()).collect();
+    // Ties go to the lower actor ID; a2 stays after a1 despite its earlier timestamp
+    assert_eq!(order, vec!["a0", "b0", "a1", "a2", "b1"]);
+    assert_eq!(merged[3].chain_index, 2);
+}
+
+fn linked(parent: Option<&theater::chain::ChainEvent>, data: &[u8]) -> theater::chain::ChainEvent {
+    use crate::events::verify::compute_hash;
+
+    let parent_hash = parent.map(|p| p.hash.clone());
+    theater::chain::ChainEvent {
+        hash: compute_hash(parent_hash.as_deref(), data),
+        parent_hash,
+        event_type: "test".to_string(),
+        data: data.to_vec(),
+        timestamp: 0,
+        description: None,
+    }
+}
+
+#[test]
+fn test_verify_chain_detects_tampering_and_gaps() {
+    use crate::events::verify::{verify_chain, ChainIssue};
+
+    let first = linked(None, b"one");
+    let second = linked(Some(&first), b"two");
+    let third = linked(Some(&second), b"three");
+
+    let report = verify_chain(&[first.clone(), second.clone(), third.clone()]);
+    assert!(report.valid);
+    assert_eq!(report.event_count, 3);
+
+    // Dropping an event breaks the parent link
+    let report = verify_chain(&[first.clone(), third.clone()]);
+    assert!(!report.valid);
+    assert!(matches!(report.issues[0], ChainIssue::Gap { index: 1, .. }));
+
+    // Rewriting a payload breaks the hash
+    let mut tampered = second.clone();
+    tampered.data = b"forged".to_vec();
+    let report = verify_chain(&[first, tampered, third]);
+    assert_eq!(report.issues.len(), 1);
+    assert!(matches!(report.issues[0], ChainIssue::HashMismatch { index: 1, .. }));
+}
+
+#[test]
+fn test_render_decodes_embedded_messages() {
+    use crate::events::render::{decode_payload, event_kind};
+
+    assert_eq!(event_kind("wasm-call"), "WebAssembly call");
+    assert_eq!(event_kind("http-client/request"), "Outgoing HTTP request");
+    assert_eq!(event_kind("mystery"), "Other");
+
+    // Byte arrays holding JSON or text are decoded in place
+    let payload = serde_json::to_vec(&serde_json::json!({
+        "message": br#"{"count": 3}"#.to_vec(),
+        "note": b"hello".to_vec(),
+        "sizes": [1, 2, 3],
+    }))
+    .unwrap();
+    let decoded = decode_payload(&payload).unwrap();
+    assert_eq!(decoded["message"]["count"], 3);
+    assert_eq!(decoded["note"], "hello");
+    assert_eq!(decoded["sizes"], serde_json::json!([1, 2, 3]));
+
+    assert_eq!(decode_payload(&[0xff, 0xfe]), None);
+
+    // The raw event keeps its bytes compact instead of as arrays of numbers
+    let rendered = crate::events::render::render_event(&theater::chain::ChainEvent {
+        hash: vec![0xab, 0x01],
+        data: b"hi".to_vec(),
+        ..event("wasm-call", 0)
+    });
+    assert_eq!(rendered["raw"]["hash"], "ab01");
+    assert_eq!(rendered["raw"]["data"], "aGk=");
+}
+
+#[test]
+fn test_search_matches_text_and_fields_with_context() {
+    use crate::events::search::EventSearch;
+
+    let events: Vec<_> = [r#"{"counter": 1}"#, r#"{"counter": -1, "note": "Went Negative"}"#, r#"{"counter": 0}"#]
+        .iter()
+        .map(|data| theater::chain::ChainEvent {
+            data: data.as_bytes().to_vec(),
+            ..event("state-update", 0)
+        })
+        .collect();
+
+    let search = EventSearch {
+        query: Some("negative".to_string()),
+        filter: EventFilter::parse("data.counter<0").unwrap(),
+        context: 1,
+    };
+    let matches = search.search_chain("actor", &events);
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].chain_index, 1);
+    assert_eq!(matches[0].before.len(), 1);
+    assert_eq!(matches[0].after.len(), 1);
+
+    let search = EventSearch {
+        query: Some("missing".to_string()),
+        ..Default::default()
+    };
+    assert!(search.search_chain("actor", &events).is_empty());
+}
+
+#[test]
+fn test_event_rate_window() {
+    use crate::events::rate::{EventRate, RATE_WINDOW};
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let mut rate = EventRate::default();
+    for i in 0..30 {
+        rate.record(100 + i, start + Duration::from_secs(i as u64));
+    }
+
+    let snapshot = rate.snapshot(start + Duration::from_secs(30));
+    assert_eq!(snapshot.events_in_window, 30);
+    assert_eq!(snapshot.events_per_second, 0.5);
+    assert_eq!(snapshot.max_size, 129);
+
+    // Old events fall out of the window but still count toward totals
+    let snapshot = rate.snapshot(start + RATE_WINDOW + Duration::from_secs(15));
+    assert_eq!(snapshot.events_in_window, 14);
+    assert_eq!(snapshot.total_events, 30);
+}
+
+#[test]
+fn test_causality_attributes_events_until_next_operation() {
+    use crate::events::causality::{CausalityTracker, CAUSALITY_WINDOW_SECS};
+    use chrono::TimeZone;
+
+    let tracker = CausalityTracker::new();
+    let at = |secs: i64| chrono::Utc.timestamp_opt(secs, 0).unwrap();
+
+    let first = tracker.record_since("send_message", "a", at(1000));
+    let second = tracker.record_since("send_message", "a", at(1010));
+    let other = tracker.record_since("send_message", "b", at(1005));
+
+    let chain: Vec<_> = [999, 1000, 1004, 1010, 1011, 1010 + CAUSALITY_WINDOW_SECS]
+        .iter()
+        .map(|t| event("x", *t))
+        .collect();
+
+    let indices = |op| -> Vec<usize> { tracker.caused_events(op, &chain).into_iter().map(|(i, _)| i).collect() };
+    assert_eq!(indices(&first), vec![1, 2]);
+    assert_eq!(indices(&second), vec![3, 4]);
+
+    // Operations on other actors neither cut the window short nor are cut by it
+    assert_eq!(indices(&other), vec![3, 4]);
+    assert!(tracker.operation(&first.id).is_some());
+}
+
+#[test]
+fn test_summary_counts_and_notable_events() {
+    use crate::events::summary::summarize_chain;
+
+    let chain = vec![
+        event("runtime-init", 100),
+        event("wasm-call", 101),
+        event("wasm-call", 102),
+        event("wasm-call", 1000),
+        event("wasm-error", 1001),
+    ];
+    let summary = summarize_chain(&chain);
+
+    assert_eq!(summary.event_count, 5);
+    assert_eq!(summary.duration_secs, 901);
+    assert_eq!(summary.by_type["wasm-call"].count, 3);
+
+    let reasons: Vec<_> = summary.notable.iter().map(|n| (n.chain_index, n.reason)).collect();
+    assert_eq!(
+        reasons,
+        vec![(0, "lifecycle"), (1, "first_of_type"), (3, "after_quiet_period"), (4, "error")]
+    );
+}
+
+#[test]
+fn test_replay_buffer_and_cursors() {
+    use crate::events::delivery::{DeliveryCursors, ReplayBuffer, REPLAY_CAPACITY};
+    use crate::events::stream::ActorEvent;
+
+    let actor_event = |seq: u64| ActorEvent {
+        seq,
+        actor_id: "a".to_string(),
+        received_at: chrono::Utc::now(),
+        event: theater::chain::ChainEvent {
+            hash: seq.to_be_bytes().to_vec(),
+            ..event("x", 0)
+        },
+    };
+
+    let mut buffer = ReplayBuffer::default();
+    for seq in 1..=5 {
+        buffer.push(actor_event(seq * 2));
+    }
+    assert!(buffer.contains(&4u64.to_be_bytes()));
+    assert_eq!(buffer.after(6).iter().map(|e| e.seq).collect::<Vec<_>>(), vec![8, 10]);
+    assert!(!buffer.has_gap_after(0));
+
+    for seq in 100..100 + REPLAY_CAPACITY as u64 {
+        buffer.push(actor_event(seq));
+    }
+    assert!(buffer.has_gap_after(6));
+
+    // Cursors never move backwards
+    let cursors = DeliveryCursors::default();
+    cursors.ack("sub/a", 10);
+    cursors.ack("sub/a", 4);
+    assert_eq!(cursors.get("sub/a"), Some(10));
+    assert_eq!(cursors.get("other/a"), None);
+}
@@ -0,0 +1,86 @@
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+
+use theater::chain::ChainEvent;
+
+/// A problem found while verifying a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainIssue {
+    /// The stored hash doesn't match the one recomputed from the event
+    HashMismatch {
+        index: usize,
+        stored: String,
+        computed: String,
+    },
+    /// The event's parent isn't the event before it, so events are missing
+    Gap {
+        index: usize,
+        expected_parent: Option<String>,
+        parent: Option<String>,
+    },
+}
+
+/// Result of verifying an actor's event chain
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainVerification {
+    pub valid: bool,
+    pub event_count: usize,
+    /// Hash of the last event, hex encoded
+    pub head: Option<String>,
+    pub issues: Vec<ChainIssue>,
+}
+
+/// Hash an event the way Theater's state chain does: SHA-1 over the parent
+/// hash (if any) followed by the event payload.
+pub fn compute_hash(parent_hash: Option<&[u8]>, data: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha1::new();
+    if let Some(parent) = parent_hash {
+        hasher.update(parent);
+    }
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+/// Verify a chain, oldest event first.
+///
+/// Every hash is recomputed from its stored parent and payload, and every
+/// parent link is checked against the previous event, so both tampering and
+/// missing events are reported.
+pub fn verify_chain(events: &[ChainEvent]) -> ChainVerification {
+    let mut issues = Vec::new();
+    let mut previous: Option<&[u8]> = None;
+
+    for (index, event) in events.iter().enumerate() {
+        if event.parent_hash.as_deref() != previous {
+            issues.push(ChainIssue::Gap {
+                index,
+                expected_parent: previous.map(to_hex),
+                parent: event.parent_hash.as_deref().map(to_hex),
+            });
+        }
+
+        let computed = compute_hash(event.parent_hash.as_deref(), &event.data);
+        if computed != event.hash {
+            issues.push(ChainIssue::HashMismatch {
+                index,
+                stored: to_hex(&event.hash),
+                computed: to_hex(&computed),
+            });
+        }
+
+        previous = Some(&event.hash);
+    }
+
+    ChainVerification {
+        valid: issues.is_empty(),
+        event_count: events.len(),
+        head: events.last().map(|e| to_hex(&e.hash)),
+        issues,
+    }
+}
+
+/// Lowercase hex encoding of a hash
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{debug, info, warn};
+
+use crate::encoding::CompactEvent;
+use crate::events::filter::EventFilter;
+use crate::events::stream::{ActorEvent, EventStreamHub};
+
+/// Events queued per webhook before new ones are dropped
+const DELIVERY_QUEUE_CAPACITY: usize = 256;
+
+/// How often webhooks without an actor list look for newly started actors
+const ACTOR_DISCOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before the first retry; doubled on each further attempt
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// An HTTP endpoint that receives actor events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// URL events are POSTed to
+    pub url: String,
+    /// Event filter expression; empty matches every event
+    pub filter: String,
+    /// Actors to forward events from; empty means every running actor
+    pub actors: Vec<String>,
+    /// Per-request timeout in milliseconds
+    pub timeout_ms: u64,
+    /// Delivery attempts after the first before an event is dropped
+    pub max_retries: u32,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            filter: String::new(),
+            actors: Vec::new(),
+            timeout_ms: 5000,
+            max_retries: 3,
+        }
+    }
+}
+
+/// Start a delivery task for every configured webhook.
+///
+/// Each webhook listens to the event firehose, keeps streams running for the
+/// actors it cares about, and POSTs matching events as JSON. Delivery is
+/// best effort: failed requests are retried with backoff, and events are
+/// dropped if the endpoint can't keep up.
+pub fn spawn_webhooks(
+    webhooks: &[WebhookConfig],
+    event_stream: &Arc<EventStreamHub>,
+) -> Result<Vec<tokio::task::JoinHandle<()>>> {
+    let mut handles = Vec::new();
+
+    for config in webhooks {
+        // Reject bad filters at startup rather than silently forwarding nothing
+        let filter = EventFilter::parse(&config.filter)?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        let (queue, pending) = mpsc::channel(DELIVERY_QUEUE_CAPACITY);
+        handles.push(tokio::spawn(deliver(client, config.clone(), pending)));
+        handles.push(tokio::spawn(collect(
+            config.clone(),
+            filter,
+            event_stream.clone(),
+            queue,
+        )));
+
+        info!("Forwarding events matching '{}' to {}", config.filter, config.url);
+    }
+
+    Ok(handles)
+}
+
+/// Pick matching events off the firehose and queue them for delivery
+async fn collect(
+    config: WebhookConfig,
+    filter: EventFilter,
+    event_stream: Arc<EventStreamHub>,
+    queue: mpsc::Sender<ActorEvent>,
+) {
+    let mut events = event_stream.subscribe_all();
+    let mut discovery = tokio::time::interval(ACTOR_DISCOVERY_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = discovery.tick() => {
+                watch_actors(&config, &event_stream).await;
+            }
+            event = events.recv() => match event {
+                Ok(event) => {
+                    if !config.actors.is_empty() && !config.actors.contains(&event.actor_id) {
+                        continue;
+                    }
+                    if !filter.matches(&event.event) {
+                        continue;
+                    }
+                    if queue.try_send(event).is_err() {
+                        warn!("Webhook {} is falling behind; dropping event", config.url);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Webhook {} lagged; skipped {} events", config.url, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+/// Make sure every actor this webhook covers has a running stream
+async fn watch_actors(config: &WebhookConfig, event_stream: &Arc<EventStreamHub>) {
+    if config.actors.is_empty() {
+        if let Err(e) = event_stream.watch_running_actors().await {
+            debug!("Could not list actors for webhook {}: {}", config.url, e);
+        }
+        return;
+    }
+
+    for actor_id in &config.actors {
+        if let Err(e) = event_stream.watch(actor_id).await {
+            debug!("Could not stream events from {} for webhook {}: {}", actor_id, config.url, e);
+        }
+    }
+}
+
+/// POST queued events, retrying failures with exponential backoff
+async fn deliver(client: reqwest::Client, config: WebhookConfig, mut pending: mpsc::Receiver<ActorEvent>) {
+    while let Some(event) = pending.recv().await {
+        let body = payload(&event);
+        let mut delay = INITIAL_RETRY_DELAY;
+
+        for attempt in 0..=config.max_retries {
+            let result = client.post(&config.url).json(&body).send().await;
+            match result.and_then(|response| response.error_for_status()) {
+                Ok(_) => break,
+                Err(e) if attempt < config.max_retries => {
+                    debug!("Webhook {} failed (attempt {}): {}", config.url, attempt + 1, e);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                }
+                Err(e) => {
+                    warn!(
+                        "Dropping {} event from {} after {} attempts to reach {}: {}",
+                        event.event.event_type,
+                        event.actor_id,
+                        attempt + 1,
+                        config.url,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// JSON body sent for an event
+fn payload(event: &ActorEvent) -> Value {
+    json!({
+        "actor_id": event.actor_id,
+        "received_at": event.received_at.to_rfc3339(),
+        "event": CompactEvent(&event.event)
+    })
+}
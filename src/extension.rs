@@ -0,0 +1,49 @@
+//! Extension points for crates embedding the server.
+//!
+//! Downstream crates add their own domain tools and resources next to the
+//! built-in ones by passing extensions to
+//! [`TheaterMcpServer::with_extensions`](crate::TheaterMcpServer::with_extensions).
+//! Each extension is given the same shared pieces the built-in tools use, and
+//! registers through the same [`ToolDispatcher`], so its tools pass the tool
+//! policy, admission checks and concurrency limits like any other.
+
+use anyhow::Result;
+use mcp_server::resources::ResourceManager;
+use std::sync::Arc;
+
+use crate::actors::ActorRegistry;
+use crate::channels::ChannelRegistry;
+use crate::config::ServerConfig;
+use crate::events::{CausalityTracker, EventHistory, EventStreamHub};
+use crate::session::Session;
+use crate::theater::{TheaterClient, TheaterFeatures};
+use crate::tools::ToolDispatcher;
+
+/// The shared state of a running server, handed to extensions
+#[derive(Clone)]
+pub struct ServerContext {
+    pub config: ServerConfig,
+    pub theater_client: Arc<TheaterClient>,
+    /// Commands the Theater server was found to support
+    pub features: TheaterFeatures,
+    pub session: Session,
+    pub actor_registry: Arc<ActorRegistry>,
+    pub channel_registry: Arc<ChannelRegistry>,
+    pub event_stream: Arc<EventStreamHub>,
+    pub event_history: Arc<EventHistory>,
+    pub causality: Arc<CausalityTracker>,
+}
+
+/// Where an extension registers its tools and resources
+pub struct Registrar<'a> {
+    /// Registers tools behind the server's policy and admission checks; use
+    /// [`register_async_tool`](crate::tools::register_async_tool)
+    pub tools: &'a ToolDispatcher,
+    /// Registers resources; use
+    /// [`register_async_resource`](crate::resources::register_async_resource)
+    /// or [`register_async_template`](crate::resources::register_async_template)
+    pub resources: &'a Arc<ResourceManager>,
+}
+
+/// Registers extra tools and resources when the server is built
+pub type Extension = Box<dyn FnOnce(&ServerContext, &Registrar) -> Result<()> + Send>;
@@ -1,8 +1,26 @@
 // Export modules
+pub mod actors;
+pub mod auth;
+pub mod capabilities;
+pub mod channels;
+pub mod config;
+pub mod conversations;
+pub mod encoding;
+pub mod events;
+pub mod extension;
+pub mod logging;
+pub mod plugins;
+pub mod prompts;
 pub mod server;
 pub mod theater;
 pub mod resources;
+pub mod schedule;
+pub mod schemas;
+pub mod session;
+pub mod state;
 pub mod tools;
+pub mod transport;
 
 // Re-export important types
+pub use extension::{Extension, Registrar, ServerContext};
 pub use server::TheaterMcpServer;
@@ -0,0 +1,209 @@
+use anyhow::{anyhow, Result};
+use mcp_server::transport::Transport;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use tracing::{debug, info, Level};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::FmtSubscriber;
+
+use crate::theater::client::TheaterClient;
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Pretty,
+    /// One JSON object per line, for log aggregation systems
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(anyhow!(
+                "Unknown log format '{}' (expected 'pretty' or 'json')",
+                other
+            )),
+        }
+    }
+}
+
+/// Where and how much the server logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Most verbose level logged: `trace`, `debug`, `info`, `warn` or `error`
+    pub level: String,
+    /// Per-module levels in `EnvFilter` syntax, e.g.
+    /// `theater_mcp_server::theater=trace,hyper=warn`; modules it doesn't
+    /// name are logged at `level`
+    pub filter: Option<String>,
+    pub format: LogFormat,
+    /// File to append logs to; logs go to stderr if unset
+    pub file: Option<PathBuf>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            filter: None,
+            format: LogFormat::Pretty,
+            file: None,
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// The filter deciding which events are logged
+    pub fn env_filter(&self) -> Result<EnvFilter> {
+        let level: Level = self
+            .level
+            .parse()
+            .map_err(|_| anyhow!("Invalid log level {}", self.level))?;
+        EnvFilter::builder()
+            .with_default_directive(LevelFilter::from_level(level).into())
+            .parse(self.filter.as_deref().unwrap_or_default())
+            .map_err(|e| anyhow!("Invalid log filter {:?}: {}", self.filter, e))
+    }
+
+    /// Install the global subscriber logging to the configured file, or to
+    /// stderr since stdout may be the MCP transport
+    pub fn init(&self) -> Result<()> {
+        let writer = match &self.file {
+            Some(path) => {
+                let file_name = path
+                    .file_name()
+                    .ok_or_else(|| anyhow!("Log file {} has no file name", path.display()))?;
+                let directory = path.parent().unwrap_or_else(|| Path::new("."));
+                BoxMakeWriter::new(tracing_appender::rolling::never(directory, file_name))
+            }
+            None => BoxMakeWriter::new(std::io::stderr),
+        };
+        let builder = FmtSubscriber::builder()
+            .with_env_filter(self.env_filter()?)
+            .with_writer(writer);
+        match self.format {
+            LogFormat::Pretty => tracing::subscriber::set_global_default(builder.finish()),
+            LogFormat::Json => tracing::subscriber::set_global_default(builder.json().finish()),
+        }
+        .map_err(|e| anyhow!("Failed to set tracing subscriber: {}", e))
+    }
+}
+
+/// Severity of a log message sent to the MCP client, from RFC 5424
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientLogLevel {
+    Debug,
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+    Alert,
+    Emergency,
+}
+
+/// Operational messages for the MCP client, sent as `notifications/message`.
+///
+/// Messages below the level the client chose with `logging/setLevel`
+/// (`info` until it chooses) are dropped, as are messages before the
+/// session is initialized.
+pub struct ClientLog {
+    level: RwLock<ClientLogLevel>,
+    transport: Mutex<Option<Box<dyn Transport>>>,
+}
+
+impl ClientLog {
+    pub fn new() -> Self {
+        Self {
+            level: RwLock::new(ClientLogLevel::Info),
+            transport: Mutex::new(None),
+        }
+    }
+
+    /// Least severe level sent to the client
+    pub fn level(&self) -> ClientLogLevel {
+        *self.level.read().unwrap()
+    }
+
+    pub fn set_level(&self, level: ClientLogLevel) {
+        info!("Client log level set to {:?}", level);
+        *self.level.write().unwrap() = level;
+    }
+
+    /// Start sending messages over the session's transport
+    pub fn attach(&self, transport: Box<dyn Transport>) {
+        *self.transport.lock().unwrap() = Some(transport);
+    }
+
+    /// Send a message from `logger` to the client if it is at or above its level
+    pub async fn log(&self, level: ClientLogLevel, logger: &str, data: Value) {
+        if level < self.level() {
+            return;
+        }
+        let Some(transport) = self.transport.lock().unwrap().as_ref().map(|t| t.box_clone()) else {
+            return;
+        };
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/message",
+            "params": { "level": level, "logger": logger, "data": data }
+        });
+        let sent = match serde_json::from_value(notification) {
+            Ok(message) => transport.send(message).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = sent {
+            debug!("Could not send log message to the client: {}", e);
+        }
+    }
+
+    /// Tell the client when the Theater server at `address` becomes
+    /// unreachable and when it comes back
+    pub fn spawn_connection_monitor(
+        self: Arc<Self>,
+        theater_client: Arc<TheaterClient>,
+        address: SocketAddr,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut connection = theater_client.subscribe_connection();
+        tokio::spawn(async move {
+            while connection.changed().await.is_ok() {
+                let connected = *connection.borrow_and_update();
+                if connected {
+                    self.log(ClientLogLevel::Notice, "theater", json!({
+                        "message": format!("Reconnected to the Theater server at {}", address),
+                        "address": address.to_string()
+                    }))
+                    .await;
+                } else {
+                    self.log(ClientLogLevel::Warning, "theater", json!({
+                        "message": format!(
+                            "Lost the connection to the Theater server at {}; commands are held while reconnecting",
+                            address
+                        ),
+                        "address": address.to_string()
+                    }))
+                    .await;
+                }
+            }
+        })
+    }
+}
+
+impl Default for ClientLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,17 +1,31 @@
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{anyhow, Result};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use mcp_server::transport::stdio::StdioTransport;
+use mcp_server::transport::Transport;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use theater_mcp_server::actors::{ActorProfiles, FailurePolicy};
+use theater_mcp_server::channels::OverflowPolicy;
+use theater_mcp_server::config::ServerConfig;
+use theater_mcp_server::logging::LogFormat;
+use theater_mcp_server::events::WebhookConfig;
+use theater_mcp_server::plugins::ToolPacks;
 use theater_mcp_server::server::TheaterMcpServer;
-use tracing::{info, Level};
-use tracing_appender;
-use tracing_subscriber::FmtSubscriber;
+use theater_mcp_server::session::Presence;
+use theater_mcp_server::theater::{CompatibilityMode, TlsConfig};
+use theater_mcp_server::tools::ToolPolicy;
+use theater_mcp_server::transport::{HttpTransport, SseTransport, TransportKind};
+use tracing::{info, warn, Level};
 
 /// MCP server for interfacing with the Theater WebAssembly actor system
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    /// TOML configuration file; flags given on the command line override it
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
     /// Theater server address
     #[arg(short, long, default_value = "127.0.0.1:9000")]
     theater_address: String,
@@ -20,35 +34,391 @@ struct Args {
     #[arg(short, long, default_value = "info")]
     log_level: Level,
 
-    /// Log to file instead of stderr
-    #[arg(
-        long,
-        default_value = "/Users/colinrozzi/work/mcp-servers/theater-mcp-server/theater_mcp.log"
-    )]
-    log_file: PathBuf,
+    /// Per-module log levels in EnvFilter syntax, e.g. theater_mcp_server::theater=trace
+    #[arg(long)]
+    log_filter: Option<String>,
+
+    /// Log format: pretty, or json for log aggregation systems
+    #[arg(long, default_value = "pretty")]
+    log_format: LogFormat,
+
+    /// Log to this file instead of stderr
+    #[arg(long)]
+    log_file: Option<PathBuf>,
+
+    /// Maximum number of pipelined command connections to the Theater server
+    #[arg(long, default_value_t = 8)]
+    theater_connections: usize,
+
+    /// Seconds commands wait for the Theater server to come back before failing
+    #[arg(long, default_value_t = 30)]
+    reconnect_window_secs: u64,
+
+    /// Commands held while waiting for the Theater server; more fail immediately
+    #[arg(long, default_value_t = 256)]
+    max_queued_commands: usize,
+
+    /// Largest response frame accepted from the Theater server, in bytes
+    #[arg(long, default_value_t = 64 * 1024 * 1024)]
+    max_frame_bytes: usize,
+
+    /// Largest actor state sent or accepted, in bytes
+    #[arg(long, default_value_t = 16 * 1024 * 1024)]
+    max_state_bytes: usize,
+
+    /// Largest actor message payload sent or accepted, in bytes
+    #[arg(long, default_value_t = 4 * 1024 * 1024)]
+    max_message_bytes: usize,
+
+    /// Connect to the Theater server over TLS, verifying it against this CA file
+    #[arg(long)]
+    theater_ca_file: Option<PathBuf>,
+
+    /// Client certificate chain presented to the Theater server (requires --theater-ca-file)
+    #[arg(long, requires = "theater_client_key")]
+    theater_client_cert: Option<PathBuf>,
+
+    /// Private key of the client certificate
+    #[arg(long, requires = "theater_client_cert")]
+    theater_client_key: Option<PathBuf>,
+
+    /// Name the Theater server's certificate is verified against, if not its IP address
+    #[arg(long)]
+    theater_server_name: Option<String>,
+
+    /// Run a Theater runtime in this process instead of connecting to --theater-address
+    /// (requires the embedded-theater feature)
+    #[arg(long)]
+    embedded_theater: bool,
+
+    /// How MCP clients connect: stdio, http for MCP's Streamable HTTP transport,
+    /// or sse for the legacy HTTP+SSE transport
+    #[arg(long, default_value = "stdio")]
+    transport: TransportKind,
+
+    /// Address network transports listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+
+    /// Serve /healthz and /readyz on this address, for container orchestrators
+    #[arg(long)]
+    health_listen: Option<SocketAddr>,
+
+    /// Refuse to start (strict) or only warn (warn) if the Theater server speaks an incompatible protocol
+    #[arg(long, default_value = "strict")]
+    theater_compat: CompatibilityMode,
+
+    /// Register every tool without probing which commands the Theater server supports
+    #[arg(long)]
+    no_feature_probe: bool,
+
+    /// Also mask this field in logged commands and responses (repeatable)
+    #[arg(long = "redact-field")]
+    redact_fields: Vec<String>,
+
+    /// Truncate logged payloads and strings longer than this many bytes
+    #[arg(long, default_value_t = 256)]
+    max_logged_bytes: usize,
+
+    /// Milliseconds a resource read may take before failing with a timeout
+    #[arg(long, default_value_t = 10_000)]
+    resource_timeout_ms: u64,
+
+    /// Milliseconds between checks of a subscribed resource for changes
+    #[arg(long, default_value_t = 1000)]
+    poll_interval_ms: u64,
+
+    /// Check subscribed URIs matching a `*` pattern at another interval, as PATTERN=MS (repeatable)
+    #[arg(long = "poll-interval", value_parser = parse_poll_interval)]
+    poll_intervals: Vec<(String, u64)>,
+
+    /// Maximum tool calls running at once; further calls are rejected with a retry-after
+    #[arg(long, default_value_t = 32)]
+    max_concurrent_calls: usize,
+
+    /// Sustained tool calls per second allowed for the session
+    #[arg(long, default_value_t = 10.0)]
+    calls_per_second: f64,
+
+    /// Tool calls the session may make in a burst above the sustained rate
+    #[arg(long, default_value_t = 20)]
+    call_burst: u32,
+
+    /// Most running actors one session may start
+    #[arg(long)]
+    max_actors_per_session: Option<usize>,
+
+    /// Most running actors all sessions together may start
+    #[arg(long)]
+    max_actors: Option<usize>,
+
+    /// Stop actors when the session that started them ends, unless start_actor sets owned=false
+    #[arg(long)]
+    owned_actors: bool,
+
+    /// Seconds an SSE client that disconnected has to come back before its owned actors are stopped
+    #[arg(long, default_value_t = 30)]
+    actor_grace_period_secs: u64,
+
+    /// Only start actors from manifests in this directory or matching this `*` pattern (repeatable)
+    #[arg(long = "allow-manifests")]
+    allowed_manifests: Vec<String>,
+
+    /// JSON file of named actor launch profiles offered by start_profile
+    #[arg(long)]
+    actor_profiles: Option<PathBuf>,
+
+    /// What to do when an actor started through the server fails (notify, restart or mark-failed)
+    #[arg(long, default_value = "notify")]
+    on_actor_failure: FailurePolicy,
+
+    /// Restarts per failed actor before the restart policy marks it failed instead
+    #[arg(long, default_value_t = 3)]
+    max_actor_restarts: u32,
+
+    /// Save managed actors, aliases and channels to this file and restore them on the next start
+    #[arg(long)]
+    state_file: Option<PathBuf>,
+
+    /// Serve only the tools, resources and prompts this actor exports, as if it were the whole server
+    #[arg(long)]
+    proxy_actor: Option<String>,
+
+    /// Register this tool pack (repeatable)
+    #[arg(long = "plugin")]
+    plugins: Vec<String>,
+
+    /// Load a tool pack from this shared library (repeatable; requires the dynamic-plugins feature)
+    #[arg(long = "plugin-library")]
+    plugin_libraries: Vec<PathBuf>,
+
+    /// JSON file allowing, denying or requiring confirmation for tools by name
+    #[arg(long)]
+    tool_policy: Option<PathBuf>,
+
+    /// Limit concurrent calls of one tool, as TOOL=N (repeatable), e.g. start_actor=2
+    #[arg(long = "tool-concurrency", value_parser = parse_tool_limit)]
+    tool_concurrency: Vec<(String, usize)>,
+
+    /// Maximum number of buffered inbound messages per channel
+    #[arg(long, default_value_t = 1000)]
+    channel_buffer_messages: usize,
+
+    /// Maximum number of buffered inbound bytes per channel
+    #[arg(long, default_value_t = 4 * 1024 * 1024)]
+    channel_buffer_bytes: usize,
+
+    /// What to do when a channel buffer is full (drop-oldest or reject)
+    #[arg(long, default_value = "drop-oldest")]
+    channel_overflow: OverflowPolicy,
+
+    /// Seconds an SSE client that disconnected has to come back before its channels are closed
+    #[arg(long, default_value_t = 30)]
+    channel_grace_period_secs: u64,
+
+    /// Archive actor events in this SQLite file (requires the event-store feature)
+    #[arg(long)]
+    event_store: Option<PathBuf>,
+
+    /// POST live actor events to this URL (repeatable)
+    #[arg(long = "webhook")]
+    webhooks: Vec<String>,
+
+    /// Event filter expression applied to --webhook endpoints
+    #[arg(long, default_value = "")]
+    webhook_filter: String,
+
+    /// File holding the bearer token network transport clients must present
+    #[arg(long)]
+    auth_token_file: Option<PathBuf>,
+
+    /// Export actor events to this OTLP collector (requires the otlp feature)
+    #[arg(long)]
+    otlp_endpoint: Option<String>,
+
+    /// Event filter expression applied to OTLP export
+    #[arg(long, default_value = "")]
+    otlp_filter: String,
+}
+
+/// Parse a `TOOL=N` concurrency limit
+fn parse_tool_limit(value: &str) -> Result<(String, usize), String> {
+    let (tool, limit) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected TOOL=N, got '{}'", value))?;
+    let limit = limit
+        .parse()
+        .map_err(|_| format!("invalid limit '{}' for {}", limit, tool))?;
+    Ok((tool.to_string(), limit))
+}
+
+/// Parse a `PATTERN=MS` polling interval
+fn parse_poll_interval(value: &str) -> Result<(String, u64), String> {
+    let (pattern, ms) = value
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected PATTERN=MS, got '{}'", value))?;
+    let ms = ms
+        .parse()
+        .map_err(|_| format!("invalid interval '{}' for {}", ms, pattern))?;
+    Ok((pattern.to_string(), ms))
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Parse command-line arguments
-    let args = Args::parse();
+    // Parse command-line arguments, noting which were actually given
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches)?;
+    let given = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+
+    // Build the server configuration: defaults, then the config file, then
+    // THEATER_MCP__* environment variables, then command-line flags
+    let mut config = match &args.config {
+        Some(path) => ServerConfig::load(path)?,
+        None => ServerConfig::default(),
+    }
+    .with_env_overrides(std::env::vars())?;
+
+    macro_rules! override_with {
+        ($($flag:ident => $setting:expr),* $(,)?) => {
+            $(if given(stringify!($flag)) {
+                $setting = args.$flag.into();
+            })*
+        };
+    }
+    override_with! {
+        log_filter => config.logging.filter,
+        log_format => config.logging.format,
+        log_file => config.logging.file,
+        theater_connections => config.theater.connections,
+        reconnect_window_secs => config.theater.reconnect_window_secs,
+        max_queued_commands => config.theater.max_queued_commands,
+        max_frame_bytes => config.theater.limits.max_frame_bytes,
+        max_state_bytes => config.theater.limits.max_state_bytes,
+        max_message_bytes => config.theater.limits.max_message_bytes,
+        theater_compat => config.theater.compatibility,
+        embedded_theater => config.theater.embedded,
+        health_listen => config.health.listen,
+        max_logged_bytes => config.theater.redaction.max_logged_bytes,
+        resource_timeout_ms => config.resources.read_timeout_ms,
+        poll_interval_ms => config.resources.poll_interval_ms,
+        max_concurrent_calls => config.tools.admission.max_concurrent_calls,
+        calls_per_second => config.tools.admission.calls_per_second,
+        call_burst => config.tools.admission.burst,
+        allowed_manifests => config.tools.allowed_manifests,
+        max_actors_per_session => config.actors.quota.max_per_session,
+        max_actors => config.actors.quota.max_total,
+        owned_actors => config.actors.owned_by_default,
+        actor_grace_period_secs => config.actors.session_grace_period_secs,
+        on_actor_failure => config.actors.failures.policy,
+        max_actor_restarts => config.actors.failures.max_restarts,
+        channel_buffer_messages => config.channels.inbox.max_messages,
+        channel_buffer_bytes => config.channels.inbox.max_bytes,
+        channel_overflow => config.channels.inbox.overflow,
+        channel_grace_period_secs => config.channels.session_grace_period_secs,
+        event_store => config.events.store_path,
+        otlp_endpoint => config.events.otlp_endpoint,
+        otlp_filter => config.events.otlp_filter,
+        auth_token_file => config.auth.token_file,
+        plugins => config.plugins.enabled,
+        plugin_libraries => config.plugins.libraries,
+        proxy_actor => config.proxy.actor_id,
+        state_file => config.state.path,
+        transport => config.transport.kind,
+        listen => config.transport.listen,
+    }
+    if given("theater_address") {
+        config.theater.address = args.theater_address.parse()
+            .map_err(|e| anyhow!("Invalid Theater address {}: {}", args.theater_address, e))?;
+    }
+    if given("log_level") {
+        config.logging.level = args.log_level.to_string().to_lowercase();
+    }
+    if args.theater_ca_file.is_some() || args.theater_client_cert.is_some() {
+        config.theater.tls = Some(TlsConfig {
+            ca_file: args.theater_ca_file,
+            cert_file: args.theater_client_cert,
+            key_file: args.theater_client_key,
+            server_name: args.theater_server_name,
+        });
+    }
+    if args.no_feature_probe {
+        config.theater.probe_features = false;
+    }
+    config.theater.redaction.fields.extend(args.redact_fields);
+    config.resources.poll_intervals.extend(args.poll_intervals);
+    config.tools.concurrency.extend(args.tool_concurrency);
+    if let Some(path) = &args.actor_profiles {
+        config.actors.profiles = ActorProfiles::load(path)?;
+    }
+    if let Some(path) = &args.tool_policy {
+        config.tools.policy = ToolPolicy::load(path)?;
+    }
+    if given("webhooks") {
+        config.events.webhooks = args
+            .webhooks
+            .into_iter()
+            .map(|url| WebhookConfig {
+                url,
+                ..Default::default()
+            })
+            .collect();
+    }
+    if given("webhook_filter") {
+        for webhook in &mut config.events.webhooks {
+            webhook.filter = args.webhook_filter.clone();
+        }
+    }
 
     // Initialize logging
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(args.log_level)
-        .with_writer(tracing_appender::rolling::never(
-            args.log_file,
-            "theater_mcp.log",
-        ))
-        .finish();
-    tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
-
-    // Parse Theater server address
-    let theater_addr: SocketAddr = args.theater_address.parse()?;
-    info!("Connecting to Theater server at {}", theater_addr);
-
-    // Create and run the Theater MCP server
-    let server = TheaterMcpServer::new(theater_addr, StdioTransport::new()).await?;
+    config.logging.init()?;
+
+    let theater_addr = config.theater.address;
+    if !config.theater.embedded {
+        info!("Connecting to Theater server at {}", theater_addr);
+    }
+
+    // Create and run the Theater MCP server over the chosen transport
+    let auth = config.auth.bearer_auth()?;
+    match config.transport.kind {
+        TransportKind::Stdio => {
+            // Fail fast on an unreadable token, even though stdio clients aren't asked for it
+            if auth.is_some() {
+                warn!("A bearer token is configured, but the stdio transport is not authenticated");
+            }
+            serve(theater_addr, StdioTransport::new(), config, None).await
+        }
+        TransportKind::Http => {
+            if auth.is_none() && !config.transport.listen.ip().is_loopback() {
+                warn!("Serving MCP on {} without --auth-token-file; anyone who can reach it can control actors", config.transport.listen);
+            }
+            let transport = HttpTransport::bind(config.transport.listen, auth).await?;
+            serve(theater_addr, transport, config, None).await
+        }
+        TransportKind::Sse => {
+            if auth.is_none() && !config.transport.listen.ip().is_loopback() {
+                warn!("Serving MCP on {} without --auth-token-file; anyone who can reach it can control actors", config.transport.listen);
+            }
+            // SSE clients can drop their stream and come back to the session
+            let presence = Presence::new();
+            let transport = SseTransport::bind(config.transport.listen, auth)
+                .await?
+                .with_resume_window(presence.clone(), config.session_resume_window());
+            serve(theater_addr, transport, config, Some(presence)).await
+        }
+    }
+}
+
+/// Run the server over a transport until the client goes away
+async fn serve<T: Transport + 'static>(
+    theater_addr: SocketAddr,
+    transport: T,
+    config: ServerConfig,
+    presence: Option<Presence>,
+) -> Result<()> {
+    let mut server = TheaterMcpServer::with_tool_packs(theater_addr, transport, config, ToolPacks::new()).await?;
+    if let Some(presence) = presence {
+        server = server.with_presence(presence);
+    }
     info!("Theater MCP server created");
 
     // Run the server (blocks until completion)
@@ -0,0 +1,60 @@
+//! Loading tool packs from shared libraries.
+//!
+//! A plugin library is a `cdylib` crate depending on this one that exports
+//! its pack with [`export_tool_pack!`](crate::export_tool_pack). Rust trait
+//! objects have no stable ABI, so the library must be built with the same
+//! compiler and the same version of this crate as the server; the exported
+//! API version catches the most common mismatch.
+
+use anyhow::{anyhow, Result};
+use libloading::{Library, Symbol};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::info;
+
+use super::packs::{ToolPack, ToolPacks, PLUGIN_API_VERSION};
+
+/// Exported by plugin libraries: the plugin API version they were built against
+pub const API_VERSION_SYMBOL: &[u8] = b"theater_mcp_plugin_api_version";
+
+/// Exported by plugin libraries: constructs the pack
+pub const CONSTRUCTOR_SYMBOL: &[u8] = b"theater_mcp_tool_pack";
+
+impl ToolPacks {
+    /// Load the pack exported by a shared library and make it available.
+    ///
+    /// The library stays loaded for the life of the process, since its tools
+    /// may be called at any time.
+    pub fn load_library(&mut self, path: &Path) -> Result<String> {
+        // Safety: running the library's initializers and exported functions is
+        // the point of loading a plugin; the API version check guards the
+        // layout of what it hands back.
+        unsafe {
+            let library = Library::new(path)
+                .map_err(|e| anyhow!("Could not load plugin {}: {}", path.display(), e))?;
+
+            let version: Symbol<extern "C" fn() -> u32> = library
+                .get(API_VERSION_SYMBOL)
+                .map_err(|e| anyhow!("{} is not a theater-mcp plugin: {}", path.display(), e))?;
+            if version() != PLUGIN_API_VERSION {
+                return Err(anyhow!(
+                    "Plugin {} was built for plugin API {}, this server uses {}",
+                    path.display(),
+                    version(),
+                    PLUGIN_API_VERSION
+                ));
+            }
+
+            let constructor: Symbol<extern "C" fn() -> *mut Box<dyn ToolPack>> = library
+                .get(CONSTRUCTOR_SYMBOL)
+                .map_err(|e| anyhow!("{} exports no tool pack: {}", path.display(), e))?;
+            let pack: Box<dyn ToolPack> = *Box::from_raw(constructor());
+            let name = pack.name().to_string();
+
+            std::mem::forget(library);
+            self.add(Arc::from(pack));
+            info!("Loaded tool pack {} from {}", name, path.display());
+            Ok(name)
+        }
+    }
+}
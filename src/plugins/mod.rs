@@ -0,0 +1,29 @@
+//! Tool packs: named bundles of tools enabled from configuration.
+//!
+//! A pack implements [`ToolPack`] and is either added to [`ToolPacks`] by an
+//! embedding crate or, with the `dynamic-plugins` feature, loaded from a
+//! shared library listed in the configuration. Only packs named in
+//! `plugins.enabled` are registered.
+
+mod packs;
+
+pub use packs::{ToolPack, ToolPacks, PLUGIN_API_VERSION};
+
+#[cfg(feature = "dynamic-plugins")]
+mod dynamic;
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Which tool packs to load and enable
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginConfig {
+    /// Names of the packs to register
+    pub enabled: Vec<String>,
+    /// Shared libraries exporting a pack each (requires the `dynamic-plugins` feature)
+    pub libraries: Vec<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,92 @@
+use anyhow::{anyhow, Result};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::info;
+
+use crate::extension::{Extension, Registrar, ServerContext};
+
+/// Version of the interface between the server and dynamically loaded packs.
+/// Libraries built against a different version are refused.
+pub const PLUGIN_API_VERSION: u32 = 1;
+
+/// A named bundle of tools and resources, such as a "deployment" or
+/// "debugging" pack
+pub trait ToolPack: Send + Sync {
+    /// Name the pack is enabled by in the configuration
+    fn name(&self) -> &str;
+
+    /// Register the pack's tools and resources
+    fn register(&self, context: &ServerContext, registrar: &Registrar) -> Result<()>;
+}
+
+/// The tool packs available to a server, by name
+#[derive(Default, Clone)]
+pub struct ToolPacks {
+    packs: BTreeMap<String, Arc<dyn ToolPack>>,
+}
+
+impl ToolPacks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a pack available; enabling it is still up to the configuration
+    pub fn with_pack(mut self, pack: impl ToolPack + 'static) -> Self {
+        self.add(Arc::new(pack));
+        self
+    }
+
+    pub(crate) fn add(&mut self, pack: Arc<dyn ToolPack>) {
+        self.packs.insert(pack.name().to_string(), pack);
+    }
+
+    /// Names of the available packs
+    pub fn names(&self) -> Vec<String> {
+        self.packs.keys().cloned().collect()
+    }
+
+    /// Extensions registering each enabled pack, failing on names that no
+    /// available pack has
+    pub fn extensions(&self, enabled: &[String]) -> Result<Vec<Extension>> {
+        enabled
+            .iter()
+            .map(|name| {
+                let pack = self.packs.get(name).cloned().ok_or_else(|| {
+                    anyhow!(
+                        "Unknown tool pack '{}'; available packs: {}",
+                        name,
+                        self.names().join(", ")
+                    )
+                })?;
+                let extension: Extension = Box::new(move |context, registrar| {
+                    info!("Registering tool pack {}", pack.name());
+                    pack.register(context, registrar)
+                });
+                Ok(extension)
+            })
+            .collect()
+    }
+}
+
+/// Export a tool pack from a plugin library, for servers built with the
+/// `dynamic-plugins` feature to load:
+///
+/// ```ignore
+/// theater_mcp_server::export_tool_pack!(DeploymentPack::new());
+/// ```
+#[macro_export]
+macro_rules! export_tool_pack {
+    ($constructor:expr) => {
+        #[no_mangle]
+        pub extern "C" fn theater_mcp_plugin_api_version() -> u32 {
+            $crate::plugins::PLUGIN_API_VERSION
+        }
+
+        #[no_mangle]
+        #[allow(improper_ctypes_definitions)]
+        pub extern "C" fn theater_mcp_tool_pack() -> *mut ::std::boxed::Box<dyn $crate::plugins::ToolPack> {
+            let pack: ::std::boxed::Box<dyn $crate::plugins::ToolPack> = ::std::boxed::Box::new($constructor);
+            ::std::boxed::Box::into_raw(::std::boxed::Box::new(pack))
+        }
+    };
+}
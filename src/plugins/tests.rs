@@ -0,0 +1,29 @@
+use anyhow::Result;
+
+use crate::extension::{Registrar, ServerContext};
+use crate::plugins::{ToolPack, ToolPacks};
+
+struct NamedPack(&'static str);
+
+impl ToolPack for NamedPack {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn register(&self, _context: &ServerContext, _registrar: &Registrar) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_only_known_packs_can_be_enabled() {
+    let packs = ToolPacks::new()
+        .with_pack(NamedPack("debugging"))
+        .with_pack(NamedPack("deployment"));
+
+    assert_eq!(packs.extensions(&["deployment".to_string()]).unwrap().len(), 1);
+    assert!(packs.extensions(&[]).unwrap().is_empty());
+
+    let error = packs.extensions(&["billing".to_string()]).err().unwrap();
+    assert!(error.to_string().contains("debugging, deployment"), "Error: {}", error);
+}
@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::prompt::{GetPromptResult, Prompt};
+use mcp_server::prompts::PromptManager;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::prompts::utils::register_async_prompt;
+use crate::theater::client::TheaterClient;
+use crate::theater::types::TheaterIdExt;
+use crate::theater::TheaterId;
+use crate::tools::EXPORTED_TOOL_PREFIX;
+
+/// How long an actor has to list or render its prompts
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Prompt templates declared by actors, rendered by the actor on request.
+///
+/// An actor opts in by answering two JSON requests sent with request_message:
+///
+/// - `{"mcp": "prompts/list"}` with
+///   `{"prompts": [{"name": ..., "description": ..., "arguments": [{"name": ..., "required": ...}]}]}`
+/// - `{"mcp": "prompts/get", "name": ..., "arguments": {...}}` with an MCP
+///   `prompts/get` result: `{"description": ..., "messages": [...]}`
+///
+/// `register_actor_tools` registers an actor's prompts as `actor.{alias}.{prompt}`
+/// next to its tools, and like them they follow the alias to its current actor.
+pub struct ExportedPrompts {
+    theater_client: Arc<TheaterClient>,
+    prompt_manager: Arc<PromptManager>,
+    aliases: Mutex<BTreeMap<String, String>>,
+}
+
+impl ExportedPrompts {
+    pub fn new(theater_client: Arc<TheaterClient>, prompt_manager: Arc<PromptManager>) -> Self {
+        Self {
+            theater_client,
+            prompt_manager,
+            aliases: Mutex::new(BTreeMap::new()),
+        }
+    }
+    
+    /// Ask an actor for its prompts and register them under `alias`, or under
+    /// their own names unless `qualified`, returning their full names.
+    /// Actors that don't declare prompts have none.
+    pub async fn register_actor(self: &Arc<Self>, actor_id: &str, alias: &str, qualified: bool) -> Result<Vec<String>> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let listing = match self.theater_client
+            .request_json(&theater_id, &json!({ "mcp": "prompts/list" }), PROMPT_TIMEOUT)
+            .await
+        {
+            Ok(listing) => listing,
+            Err(e) => {
+                info!("Actor {} declares no prompts: {}", actor_id, e);
+                return Ok(Vec::new());
+            }
+        };
+        let declared = listing["prompts"].as_array().cloned().unwrap_or_default();
+        
+        self.aliases.lock().unwrap().insert(alias.to_string(), actor_id.to_string());
+        
+        let mut registered = Vec::new();
+        for declaration in declared {
+            match self.register_prompt(alias, declaration, qualified) {
+                Ok(name) => registered.push(name),
+                Err(e) => warn!("Skipping prompt declared by actor {}: {}", actor_id, e),
+            }
+        }
+        info!("Registered {} prompts of actor {} as {}", registered.len(), actor_id, alias);
+        Ok(registered)
+    }
+    
+    /// Register one declared prompt under the alias, returning its full name
+    fn register_prompt(self: &Arc<Self>, alias: &str, declaration: Value, qualified: bool) -> Result<String> {
+        let mut prompt: Prompt = serde_json::from_value(declaration)
+            .map_err(|e| anyhow!("invalid prompt declaration: {}", e))?;
+        crate::tools::check_name("prompt name", &prompt.name)?;
+        
+        let prompt_name = prompt.name.clone();
+        if qualified {
+            prompt.name = format!("{}.{}.{}", EXPORTED_TOOL_PREFIX, alias, prompt_name);
+        }
+        let name = prompt.name.clone();
+        
+        let prompts_self = self.clone();
+        let alias = alias.to_string();
+        register_async_prompt(
+            &self.prompt_manager,
+            prompt,
+            move |arguments| {
+                let prompts_self = prompts_self.clone();
+                let alias = alias.clone();
+                let prompt_name = prompt_name.clone();
+                async move {
+                    prompts_self.get_prompt(&alias, &prompt_name, arguments).await
+                }
+            },
+        );
+        Ok(name)
+    }
+    
+    /// Have the actor currently behind the alias render a prompt
+    async fn get_prompt(&self, alias: &str, prompt_name: &str, arguments: HashMap<String, String>) -> Result<GetPromptResult> {
+        let actor_id = self.aliases.lock().unwrap()
+            .get(alias)
+            .cloned()
+            .ok_or_else(|| anyhow!("No actor is registered as {}", alias))?;
+        let theater_id = TheaterId::from_str(&actor_id)?;
+        
+        let request = json!({ "mcp": "prompts/get", "name": prompt_name, "arguments": arguments });
+        let reply = self.theater_client.request_json(&theater_id, &request, PROMPT_TIMEOUT).await?;
+        serde_json::from_value(reply)
+            .map_err(|e| anyhow!("Actor {} rendered prompt {} incorrectly: {}", actor_id, prompt_name, e))
+    }
+}
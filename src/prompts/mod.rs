@@ -0,0 +1,7 @@
+mod exported;
+mod playbooks;
+mod utils;
+
+pub use exported::ExportedPrompts;
+pub use playbooks::PlaybookPrompts;
+pub use utils::register_async_prompt;
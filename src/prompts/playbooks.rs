@@ -0,0 +1,165 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::prompt::{GetPromptResult, Prompt};
+use mcp_server::prompts::PromptManager;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::events::render::render_event;
+use crate::events::EventHistory;
+use crate::prompts::utils::register_async_prompt;
+use crate::theater::types::TheaterIdExt;
+use crate::theater::TheaterId;
+
+/// How many of an actor's latest events debug-actor includes
+const DEBUG_EVENT_COUNT: usize = 20;
+
+/// Operational playbooks offered as MCP prompts.
+///
+/// `debug-actor` gathers an actor's status, state and latest events into
+/// the prompt and asks for a diagnosis with next steps; `deploy-actor`
+/// walks through validating a manifest, starting the actor and checking
+/// that it came up.
+pub struct PlaybookPrompts {
+    history: Arc<EventHistory>,
+}
+
+impl PlaybookPrompts {
+    pub fn new(history: Arc<EventHistory>) -> Self {
+        Self { history }
+    }
+
+    /// Render debug-actor for the actor in the `actor_id` argument.
+    ///
+    /// Anything that can't be read is noted in the prompt rather than
+    /// failing it, since a broken actor is what the prompt is for.
+    pub async fn debug_actor(&self, arguments: HashMap<String, String>) -> Result<GetPromptResult> {
+        let actor_id = required(&arguments, "actor_id")?;
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let client = self.history.theater_client();
+
+        let status = match client.get_actor_status(&theater_id).await {
+            Ok(status) => format!("{:?}", status),
+            Err(e) => format!("unavailable ({})", e),
+        };
+        let state = match client.get_actor_state(&theater_id).await {
+            Ok(Some(bytes)) => match serde_json::from_slice::<Value>(&bytes) {
+                Ok(state) => serde_json::to_string_pretty(&state)?,
+                Err(_) => format!("{} bytes of non-JSON state", bytes.len()),
+            },
+            Ok(None) => "none".to_string(),
+            Err(e) => format!("unavailable ({})", e),
+        };
+        let events = match self.history.actor_events(&theater_id).await {
+            Ok(events) => {
+                let latest = &events[events.len().saturating_sub(DEBUG_EVENT_COUNT)..];
+                let rendered: Vec<Value> = latest.iter().map(render_event).collect();
+                format!(
+                    "The latest {} of {} events, oldest first:\n```json\n{}\n```",
+                    latest.len(),
+                    events.len(),
+                    serde_json::to_string_pretty(&rendered)?
+                )
+            }
+            Err(e) => format!("Events are unavailable ({}).", e),
+        };
+
+        let text = format!(
+            "Debug the Theater actor {actor_id}.\n\n\
+             Status: {status}\n\n\
+             State:\n```json\n{state}\n```\n\n\
+             {events}\n\n\
+             Explain what the actor has been doing and whether anything is wrong, pointing at the \
+             events that show it. Then suggest next steps with this server's tools: get_actor_events \
+             with an event_type pattern to dig further, request_message to probe the actor, or \
+             restart_actor if it is stuck."
+        );
+        prompt_result(format!("Debugging actor {}", actor_id), text)
+    }
+
+    /// Render deploy-actor for the manifest in the `manifest` argument
+    pub fn deploy_actor(&self, arguments: HashMap<String, String>) -> Result<GetPromptResult> {
+        let manifest = required(&arguments, "manifest")?;
+        let mut start_args = json!({ "manifest": manifest });
+        if let Some(initial_state) = arguments.get("initial_state").filter(|s| !s.is_empty()) {
+            start_args["initial_state"] = serde_json::from_str(initial_state)
+                .map_err(|e| anyhow!("initial_state is not valid JSON: {}", e))?;
+        }
+
+        let text = format!(
+            "Deploy a Theater actor from the manifest at {manifest}.\n\n\
+             1. Call validate_manifest with {{\"manifest\": {manifest:?}}}. If it reports diagnostics, \
+             explain them and stop.\n\
+             2. Call start_actor with {start_args}.\n\
+             3. Check the result for warnings, then read theater://actor/{{actor_id}} and call \
+             get_actor_events for the new actor to confirm it started cleanly.\n\
+             4. Report the actor ID and anything unexpected."
+        );
+        prompt_result(format!("Deploying {}", manifest), text)
+    }
+
+    /// Register the prompts with the MCP prompt manager
+    pub fn register_prompts(self: Arc<Self>, prompt_manager: &Arc<PromptManager>) {
+        // Register the debug-actor prompt
+        let debug_actor_prompt: Prompt = serde_json::from_value(json!({
+            "name": "debug-actor",
+            "description": "Diagnose an actor from its status, state and latest events",
+            "arguments": [
+                { "name": "actor_id", "description": "ID of the actor to debug", "required": true }
+            ]
+        }))
+        .expect("debug-actor prompt is valid");
+
+        let prompts_self = self.clone();
+        register_async_prompt(
+            prompt_manager,
+            debug_actor_prompt,
+            move |arguments| {
+                let prompts_self = prompts_self.clone();
+                async move {
+                    prompts_self.debug_actor(arguments).await
+                }
+            },
+        );
+
+        // Register the deploy-actor prompt
+        let deploy_actor_prompt: Prompt = serde_json::from_value(json!({
+            "name": "deploy-actor",
+            "description": "Validate a manifest, start the actor and check that it came up",
+            "arguments": [
+                { "name": "manifest", "description": "Path to the actor manifest", "required": true },
+                { "name": "initial_state", "description": "Initial state for the actor, as JSON", "required": false }
+            ]
+        }))
+        .expect("deploy-actor prompt is valid");
+
+        let prompts_self = self.clone();
+        register_async_prompt(
+            prompt_manager,
+            deploy_actor_prompt,
+            move |arguments| {
+                let prompts_self = prompts_self.clone();
+                async move {
+                    prompts_self.deploy_actor(arguments)
+                }
+            },
+        );
+    }
+}
+
+fn required<'a>(arguments: &'a HashMap<String, String>, name: &str) -> Result<&'a str> {
+    arguments
+        .get(name)
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| anyhow!("Missing {} argument", name))
+}
+
+/// A prompt result with a single user message
+fn prompt_result(description: String, text: String) -> Result<GetPromptResult> {
+    serde_json::from_value(json!({
+        "description": description,
+        "messages": [{ "role": "user", "content": { "type": "text", "text": text } }]
+    }))
+    .map_err(|e| anyhow!("Could not build prompt result: {}", e))
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use mcp_protocol::types::prompt::{GetPromptResult, Prompt};
+use mcp_server::prompts::PromptManager;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Boxed future returned by async prompt handlers
+pub type PromptFuture = Pin<Box<dyn Future<Output = Result<GetPromptResult>> + Send>>;
+
+/// Register a prompt whose messages are produced asynchronously when the
+/// client asks for it, from the arguments it filled in
+pub fn register_async_prompt<F, Fut>(
+    prompt_manager: &Arc<PromptManager>,
+    prompt: Prompt,
+    handler: F,
+)
+where
+    F: Fn(HashMap<String, String>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<GetPromptResult>> + Send + 'static,
+{
+    prompt_manager.register_async_prompt(prompt, move |arguments: HashMap<String, String>| -> PromptFuture {
+        Box::pin(handler(arguments))
+    });
+}
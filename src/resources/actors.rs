@@ -1,76 +1,194 @@
 use anyhow::{anyhow, Result};
-use mcp_protocol::types::resource::{Resource, ResourceContent};
-use serde_json::json;
+use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
+use futures::future::join_all;
+use serde::de::IgnoredAny;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::debug;
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
 
 use theater::id::TheaterId;
-use crate::theater::client::TheaterClient;
+use crate::actors::ActorRegistry;
+use crate::events::filter::event_time;
+use crate::events::{ChainBounds, EventHistory};
+use crate::resources::utils::{register_async_resource, register_async_template, template_param, DEFAULT_READ_TIMEOUT};
+use crate::theater::client::{is_not_found, TheaterClient};
+use crate::theater::types::TheaterError;
 use crate::theater::TheaterIdExt;
 
+/// Marks the events Theater's supervisor handler records when an actor spawns a child
+const SPAWN_EVENT: &str = "supervisor/spawn";
+
+/// Field of a spawn event's data naming the spawned actor
+const CHILD_ID_FIELD: &str = "child_id";
+
+/// Most actor chains read to build the supervision tree
+const MAX_TREE_CHAINS: usize = 100;
+
+/// How long cached actor statuses are trusted before they are read again
+const STATUS_TTL: Duration = Duration::from_secs(5);
+
+/// The last actor list read from Theater, with each actor's status
+#[derive(Debug, Default)]
+struct ActorListCache {
+    /// Bumped whenever the set of actors or one of their statuses changes
+    version: u64,
+    statuses: BTreeMap<String, String>,
+    /// When every status was last read
+    refreshed_at: Option<Instant>,
+}
+
 /// Resources for accessing Theater actors
 pub struct ActorResources {
     theater_client: Arc<TheaterClient>,
+    actor_list: Mutex<ActorListCache>,
+    read_timeout: Duration,
+    actor_registry: Option<Arc<ActorRegistry>>,
+    event_history: Arc<EventHistory>,
 }
 
 impl ActorResources {
     /// Create a new actor resources instance
     pub fn new(theater_client: Arc<TheaterClient>) -> Self {
-        Self { theater_client }
+        Self {
+            actor_list: Mutex::new(ActorListCache::default()),
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            actor_registry: None,
+            event_history: Arc::new(EventHistory::new(theater_client.clone())),
+            theater_client,
+        }
     }
     
-    /// Helper method to handle Theater connection errors
-    fn handle_connection_error<T>(&self, result: Result<T>, context: &str) -> Result<T> {
-        match result {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("connect") || error_msg.contains("connection") || 
-                   error_msg.contains("read") || error_msg.contains("write") {
-                    // This is likely a connection issue
-                    warn!("Theater connection issue during {}: {}. Will attempt reconnection on next request.", context, error_msg);
-                    Err(anyhow!("Theater server connection issue: {}. The server will attempt to reconnect on the next request.", error_msg))
-                } else {
-                    // Other type of error
-                    Err(e)
-                }
+    /// Set the deadline for reading any actor resource
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+    
+    /// Read chain bounds through the event history, and its archive if it has one
+    pub fn with_event_history(mut self, event_history: Arc<EventHistory>) -> Self {
+        self.event_history = event_history;
+        self
+    }
+    
+    /// Report failures of actors started through the server
+    pub fn with_actor_registry(mut self, actor_registry: Arc<ActorRegistry>) -> Self {
+        self.actor_registry = Some(actor_registry);
+        self
+    }
+    
+    /// Bring the cached actor list up to date, returning its version and statuses.
+    ///
+    /// Actors that appeared since the last refresh have their status
+    /// fetched, and every status is read again once it is older than
+    /// `STATUS_TTL`; departed actors are dropped.
+    async fn refresh_actor_list(&self) -> Result<(u64, BTreeMap<String, String>)> {
+        // Get actors
+        let actor_ids = self.theater_client.list_actors().await?;
+        
+        // Held across the status fetches so concurrent polls don't duplicate them
+        let mut cache = self.actor_list.lock().await;
+        
+        let current: HashSet<String> = actor_ids.iter().map(|id| id.as_string()).collect();
+        let stale = !matches!(cache.refreshed_at, Some(at) if at.elapsed() < STATUS_TTL);
+        let fetch: Vec<&TheaterId> = actor_ids.iter()
+            .filter(|id| stale || !cache.statuses.contains_key(&id.as_string()))
+            .collect();
+        let mut changed = cache.statuses.keys().any(|id| !current.contains(id));
+        
+        if fetch.is_empty() && !changed {
+            return Ok((cache.version, cache.statuses.clone()));
+        }
+        
+        debug!("Reading the status of {} actors", fetch.len());
+        let statuses = join_all(fetch.iter().map(|id| async move {
+            (id.as_string(), self.theater_client.actor_status_name(id).await)
+        }))
+        .await;
+        
+        cache.statuses.retain(|id, _| current.contains(id));
+        for (id, status) in statuses {
+            if cache.statuses.get(&id) != Some(&status) {
+                cache.statuses.insert(id, status);
+                changed = true;
             }
         }
+        if stale {
+            cache.refreshed_at = Some(Instant::now());
+        }
+        if changed {
+            cache.version += 1;
+        }
+        
+        Ok((cache.version, cache.statuses.clone()))
     }
     
     /// Get resource content for the actor list
     pub async fn get_actors_list_content(&self) -> Result<ResourceContent> {
         debug!("Getting actor list content");
         
-        // Get actors with connection error handling
-        let actor_ids = self.handle_connection_error(
-            self.theater_client.list_actors().await,
-            "actor list retrieval"
-        )?;
-        
-        let actors = actor_ids.iter().map(|id| {
-            json!({
-                "id": id.as_string(),
-                "name": format!("Actor {}", id),
-                "status": "RUNNING",
-                "uri": format!("theater://actor/{}", id.as_string())
-            })
-        }).collect::<Vec<_>>();
+        let (version, statuses) = self.refresh_actor_list().await?;
+        Ok(Self::actor_list_content("theater://actors".to_string(), version, &statuses))
+    }
+    
+    /// Get resource content for the actor list, leaving out the actors if
+    /// the list is still at `known_version`
+    pub async fn get_actors_list_changes_content(&self, known_version: u64) -> Result<ResourceContent> {
+        let uri = format!("theater://actors?since={}", known_version);
+        let (version, statuses) = self.refresh_actor_list().await?;
+        if version != known_version {
+            return Ok(Self::actor_list_content(uri, version, &statuses));
+        }
         
         let content = json!({
-            "actors": actors,
-            "total": actors.len()
+            "version": version,
+            "unchanged": true
         });
         
         Ok(ResourceContent {
-            uri: "theater://actors".to_string(),
+            uri,
+            mime_type: "application/json".to_string(),
+            text: Some(content.to_string()),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for `limit` actors of the list from `offset`, in ID order
+    pub async fn get_actors_page_content(&self, offset: usize, limit: usize) -> Result<ResourceContent> {
+        if limit == 0 {
+            return Err(anyhow!("Actor list limit must be at least 1"));
+        }
+        let uri = format!("theater://actors?offset={}&limit={}", offset, limit);
+        let (version, statuses) = self.refresh_actor_list().await?;
+        
+        let mut content = actor_list_json(version, statuses.iter().skip(offset).take(limit), statuses.len());
+        content["offset"] = json!(offset);
+        if offset.saturating_add(limit) < statuses.len() {
+            content["next"] = json!(format!("theater://actors?offset={}&limit={}", offset + limit, limit));
+        }
+        
+        Ok(ResourceContent {
+            uri,
             mime_type: "application/json".to_string(),
             text: Some(content.to_string()),
             blob: None,
         })
     }
     
+    fn actor_list_content(uri: String, version: u64, statuses: &BTreeMap<String, String>) -> ResourceContent {
+        let content = actor_list_json(version, statuses.iter(), statuses.len());
+        
+        ResourceContent {
+            uri,
+            mime_type: "application/json".to_string(),
+            text: Some(content.to_string()),
+            blob: None,
+        }
+    }
+    
     /// Get resource content for an actor's details
     pub async fn get_actor_details_content(&self, actor_id: &str) -> Result<ResourceContent> {
         debug!("Getting actor details for {}", actor_id);
@@ -78,22 +196,37 @@ impl ActorResources {
         // Convert string ID to TheaterId
         let theater_id = TheaterId::from_str(actor_id)?;
         
-        // Attempt to get the actor state to verify it exists with connection error handling
-        if let Err(e) = self.handle_connection_error(
-            self.theater_client.get_actor_state(&theater_id).await,
-            &format!("actor details retrieval for {}", actor_id)
-        ) {
-            debug!("Failed to get actor state: {}", e);
-            return Err(anyhow!("Actor not found or connection issue: {}", actor_id));
-        }
+        // Get the actor's status, which also verifies it exists
+        let status = match self.theater_client.get_actor_status(&theater_id).await {
+            Ok(status) => format!("{:?}", status).to_uppercase(),
+            Err(e) if is_not_found(&e) => {
+                return Err(TheaterError::ActorNotFound(actor_id.to_string()).into());
+            }
+            Err(e) => return Err(e),
+        };
         
-        let content = json!({
+        // The chain's first event marks the start; details don't need the chain to be readable
+        let bounds = self.event_history.chain_bounds(&theater_id).await.unwrap_or_else(|e| {
+            debug!("Failed to get events of actor {}: {}", actor_id, e);
+            ChainBounds::default()
+        });
+        let event_at = |timestamp: Option<u64>| timestamp.map(|timestamp| event_time(timestamp).to_rfc3339());
+        
+        let mut content = json!({
             "id": actor_id,
-            "status": "RUNNING", // We're simplifying for now
-            "created_at": chrono::Utc::now().to_rfc3339(),
+            "status": status,
+            "created_at": event_at(bounds.first_timestamp),
+            "last_event_at": event_at(bounds.last_timestamp),
+            "event_count": bounds.count,
             "events_uri": format!("theater://events/{}", actor_id),
             "state_uri": format!("theater://actor/{}/state", actor_id)
         });
+        if let Some(failure) = self.actor_registry.as_ref().and_then(|registry| registry.failure(actor_id)) {
+            if failure.marked {
+                content["status"] = json!("FAILED");
+            }
+            content["failure"] = json!(failure);
+        }
         
         Ok(ResourceContent {
             uri: format!("theater://actor/{}", actor_id),
@@ -110,201 +243,391 @@ impl ActorResources {
         // Convert string ID to TheaterId
         let theater_id = TheaterId::from_str(actor_id)?;
         
-        // Get the actor state with connection error handling
-        let state_result = self.handle_connection_error(
-            self.theater_client.get_actor_state(&theater_id).await,
-            &format!("actor state retrieval for {}", actor_id)
-        )?;
-        
-        // Process the state
-        let content = if let Some(state_bytes) = state_result {
-            // Try to parse the binary data as JSON
-            match serde_json::from_slice::<serde_json::Value>(&state_bytes) {
-                Ok(json_value) => json_value,
-                Err(_) => {
-                    // If not valid JSON, encode as base64
-                    let base64_str = BASE64.encode(&state_bytes);
-                    json!({
-                        "_raw_state_base64": base64_str
-                    })
+        // Get the actor state
+        let state_result = self.theater_client.get_actor_state(&theater_id).await?;
+        
+        // Process the state without building a Value tree: JSON state is
+        // passed through as-is, reusing the bytes Theater sent
+        let text = match state_result {
+            Some(state_bytes) if serde_json::from_slice::<IgnoredAny>(&state_bytes).is_ok() => {
+                match String::from_utf8(state_bytes) {
+                    Ok(text) => text,
+                    Err(e) => base64_state(e.as_bytes()),
                 }
             }
-        } else {
+            // If not valid JSON, encode as base64
+            Some(state_bytes) => base64_state(&state_bytes),
             // No state available
-            json!({
-                "_state": "empty"
-            })
+            None => r#"{"_state":"empty"}"#.to_string(),
         };
         
         Ok(ResourceContent {
             uri: format!("theater://actor/{}/state", actor_id),
             mime_type: "application/json".to_string(),
+            text: Some(text),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for the manifest an actor was started from.
+    ///
+    /// Theater doesn't hand manifests back, so only actors started through
+    /// this server have one; manifest files are read again from disk.
+    pub async fn get_actor_manifest_content(&self, actor_id: &str) -> Result<ResourceContent> {
+        debug!("Getting manifest for {}", actor_id);
+        
+        let record = self.actor_registry.as_ref()
+            .and_then(|registry| registry.get(actor_id))
+            .ok_or_else(|| anyhow!(
+                "No manifest known for actor {}: only actors started through this server have one",
+                actor_id
+            ))?;
+        
+        // A TOML manifest spans lines, so a single line is a path
+        let (source, text) = if record.manifest.contains('\n') {
+            ("inline", record.manifest.clone())
+        } else {
+            let text = tokio::fs::read_to_string(&record.manifest).await
+                .map_err(|e| anyhow!("Could not read manifest {}: {}", record.manifest, e))?;
+            (record.manifest.as_str(), text)
+        };
+        let manifest: toml::Table = text.parse()
+            .map_err(|e| anyhow!("Manifest of actor {} is not valid TOML: {}", actor_id, e))?;
+        
+        let content = json!({
+            "actor_id": actor_id,
+            "source": source,
+            "manifest": manifest
+        });
+        
+        Ok(ResourceContent {
+            uri: format!("theater://manifest/{}", actor_id),
+            mime_type: "application/json".to_string(),
+            text: Some(content.to_string()),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for the supervision tree of running actors.
+    ///
+    /// Theater doesn't report an actor's parent, so each actor's chain is
+    /// searched for supervisor spawn events naming another running actor.
+    /// Actors without a running parent are roots.
+    pub async fn get_actor_tree_content(&self) -> Result<ResourceContent> {
+        debug!("Getting actor supervision tree");
+        
+        let (_, statuses) = self.refresh_actor_list().await?;
+        
+        // Find each actor's children, keeping the first parent seen for a child
+        let statuses_ref = &statuses;
+        let spawned = join_all(statuses.keys().take(MAX_TREE_CHAINS).map(|id| async move {
+            (id, self.spawned_children(id, statuses_ref).await)
+        }))
+        .await;
+        let mut children: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut has_parent = HashSet::new();
+        for (parent, spawned) in spawned {
+            match spawned {
+                Ok(spawned) => {
+                    for child in spawned {
+                        if has_parent.insert(child.clone()) {
+                            children.entry(parent.clone()).or_default().push(child);
+                        }
+                    }
+                }
+                Err(e) => debug!("Failed to read events of actor {}: {}", parent, e),
+            }
+        }
+        
+        // Roots first; anything left is on a cycle, so it becomes a root too
+        let mut placed = HashSet::new();
+        let mut roots = Vec::new();
+        let ordered = statuses.keys().filter(|id| !has_parent.contains(*id))
+            .chain(statuses.keys().filter(|id| has_parent.contains(*id)));
+        for id in ordered {
+            if !placed.contains(id) {
+                roots.push(tree_node(id, &statuses, &children, &mut placed));
+            }
+        }
+        
+        let mut content = json!({
+            "actors": roots,
+            "total": statuses.len()
+        });
+        if statuses.len() > MAX_TREE_CHAINS {
+            // Children of the actors past the cap are shown at the top level
+            content["truncated"] = json!(true);
+        }
+        
+        Ok(ResourceContent {
+            uri: "theater://actors/tree".to_string(),
+            mime_type: "application/json".to_string(),
             text: Some(content.to_string()),
             blob: None,
         })
     }
     
-    /// Register actor resources with the MCP resource manager
-    pub async fn register_actor_resources(
+    /// Running actors named as the child in the supervisor spawn events of `actor_id`
+    async fn spawned_children(&self, actor_id: &str, running: &BTreeMap<String, String>) -> Result<Vec<String>> {
+        let events = self.theater_client.get_actor_events(&TheaterId::from_str(actor_id)?).await?;
+        
+        let mut spawned = Vec::new();
+        for event in events.iter().filter(|event| event.event_type.contains(SPAWN_EVENT)) {
+            let Ok(data) = serde_json::from_slice::<Value>(&event.data) else {
+                continue;
+            };
+            if let Some(child) = child_id(&data) {
+                if child != actor_id && running.contains_key(child) && !spawned.iter().any(|id| id == child) {
+                    spawned.push(child.to_string());
+                }
+            }
+        }
+        Ok(spawned)
+    }
+    
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
         self: Arc<Self>,
-        actor_id: String,
-        resource_manager: Arc<mcp_server::resources::ResourceManager>,
-    ) -> Result<()> {
-        // Actor details resource
-        let actor_details_resource = Resource {
-            uri: format!("theater://actor/{}", actor_id),
-            name: format!("Actor {}", actor_id),
-            description: Some(format!("Details for actor {}", actor_id)),
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        // Register the actors list resource
+        let actors_list_resource = Resource {
+            uri: "theater://actors".to_string(),
+            name: "Theater Actors".to_string(),
+            description: Some("List of actors in the Theater system".to_string()),
             mime_type: Some("application/json".to_string()),
             size: None,
             annotations: None,
         };
         
-        let self_clone = self.clone();
-        let details_actor_id = actor_id.clone();
-        // Create a safe content provider that won't block the current async context
-        let client = self_clone.theater_client.clone();
-        let aid = details_actor_id.clone();
-        let self_ref = self_clone.clone();
+        let self_ref = self.clone();
         
-        // Use a thread-safe channel to communicate between threads
-        resource_manager.register_resource(
-            actor_details_resource,
+        register_async_resource(
+            resource_manager,
+            actors_list_resource,
+            self.read_timeout,
             move || {
-                // Clone for the thread
                 let self_ref = self_ref.clone();
-                let aid = aid.clone();
-                
-                // Use a thread to avoid blocking the Tokio runtime
-                let (tx, rx) = std::sync::mpsc::channel();
-                
-                // Spawn a new thread to run the future
-                std::thread::spawn(move || {
-                    // Create a new runtime for this thread only
-                    let rt = tokio::runtime::Builder::new_current_thread()
-                        .enable_all()
-                        .build()
-                        .unwrap();
-                    
-                    // Run the async code in this isolated runtime
-                    let result = rt.block_on(async {
-                        self_ref.get_actor_details_content(&aid).await
-                    });
-                    
-                    // Send the result back to the main thread
-                    let _ = tx.send(result.map(|content| vec![content]));
-                });
-                
-                // Receive the result - this is a blocking operation but we're not in an async context here
-                rx.recv().unwrap_or_else(|e| {
-                    Err(anyhow::anyhow!("Failed to get actor details: {}", e))
-                })
+                async move {
+                    self_ref.get_actors_list_content().await.map(|content| vec![content])
+                }
             },
         );
         
-        // Actor state resource
-        let actor_state_resource = Resource {
-            uri: format!("theater://actor/{}/state", actor_id),
-            name: format!("Actor {} State", actor_id),
-            description: Some(format!("Current state for actor {}", actor_id)),
+        // Register the supervision tree resource
+        let actors_tree_resource = Resource {
+            uri: "theater://actors/tree".to_string(),
+            name: "Theater Actor Tree".to_string(),
+            description: Some("Running actors arranged by which actor spawned which".to_string()),
             mime_type: Some("application/json".to_string()),
             size: None,
             annotations: None,
         };
         
-        let self_clone = self.clone();
-        let state_actor_id = actor_id.clone();
-        // Create a safe content provider that won't block the current async context
-        let aid = state_actor_id.clone();
-        let self_ref = self_clone.clone();
+        let self_ref = self.clone();
         
-        resource_manager.register_resource(
-            actor_state_resource,
+        register_async_resource(
+            resource_manager,
+            actors_tree_resource,
+            self.read_timeout,
             move || {
-                // Clone for the thread
                 let self_ref = self_ref.clone();
-                let aid = aid.clone();
-                
-                // Use a thread-safe channel to communicate between threads
-                let (tx, rx) = std::sync::mpsc::channel();
-                
-                // Spawn a new thread to run the future
-                std::thread::spawn(move || {
-                    // Create a new runtime for this thread only
-                    let rt = tokio::runtime::Builder::new_current_thread()
-                        .enable_all()
-                        .build()
-                        .unwrap();
-                    
-                    // Run the async code in this isolated runtime
-                    let result = rt.block_on(async {
-                        self_ref.get_actor_state_content(&aid).await
-                    });
-                    
-                    // Send the result back to the main thread
-                    let _ = tx.send(result.map(|content| vec![content]));
-                });
-                
-                // Receive the result - this is a blocking operation but we're not in an async context here
-                rx.recv().unwrap_or_else(|e| {
-                    Err(anyhow::anyhow!("Failed to get actor state: {}", e))
-                })
+                async move {
+                    self_ref.get_actor_tree_content().await.map(|content| vec![content])
+                }
             },
         );
         
-        Ok(())
-    }
-    
-    /// Register resources with the MCP resource manager
-    pub fn register_resources(
-        self: Arc<Self>,
-        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
-    ) {
-        // Register the actors list resource
-        let actors_list_resource = Resource {
-            uri: "theater://actors".to_string(),
-            name: "Theater Actors".to_string(),
-            description: Some("List of actors in the Theater system".to_string()),
+        // Polling variant that skips the actor list when nothing changed
+        let actors_since_template = ResourceTemplate {
+            uri_template: "theater://actors?since={version}".to_string(),
+            name: "Theater Actors Since".to_string(),
+            description: Some("The actor list, or just {\"unchanged\": true} if it is still at the given version".to_string()),
             mime_type: Some("application/json".to_string()),
-            size: None,
             annotations: None,
         };
         
-        let self_clone = self.clone();
-        // Create a safe content provider that won't block the current async context
-        let self_ref = self_clone.clone();
+        let self_ref = self.clone();
         
-        resource_manager.register_resource(
-            actors_list_resource,
-            move || {
-                // Clone for the thread
+        register_async_template(
+            resource_manager,
+            actors_since_template,
+            self.read_timeout,
+            move |_uri, params| {
                 let self_ref = self_ref.clone();
-                
-                // Use a thread-safe channel to communicate between threads
-                let (tx, rx) = std::sync::mpsc::channel();
-                
-                // Spawn a new thread to run the future
-                std::thread::spawn(move || {
-                    // Create a new runtime for this thread only
-                    let rt = tokio::runtime::Builder::new_current_thread()
-                        .enable_all()
-                        .build()
-                        .unwrap();
-                    
-                    // Run the async code in this isolated runtime
-                    let result = rt.block_on(async {
-                        self_ref.get_actors_list_content().await
-                    });
-                    
-                    // Send the result back to the main thread
-                    let _ = tx.send(result.map(|content| vec![content]));
-                });
-                
-                // Receive the result - this is a blocking operation but we're not in an async context here
-                rx.recv().unwrap_or_else(|e| {
-                    Err(anyhow::anyhow!("Failed to get actors list: {}", e))
-                })
+                async move {
+                    let version = template_param(&params, "version")?
+                        .parse::<u64>()
+                        .map_err(|_| anyhow!("Invalid actor list version"))?;
+                    self_ref.get_actors_list_changes_content(version).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        // Paged variant for hosts with more actors than fit in one read
+        let actors_page_template = ResourceTemplate {
+            uri_template: "theater://actors?offset={offset}&limit={limit}".to_string(),
+            name: "Theater Actors Page".to_string(),
+            description: Some("Up to limit actors of the list from offset, in ID order, with a next URI while more remain".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            actors_page_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let offset = template_param(&params, "offset")?
+                        .parse::<usize>()
+                        .map_err(|_| anyhow!("Invalid actor list offset"))?;
+                    let limit = template_param(&params, "limit")?
+                        .parse::<usize>()
+                        .map_err(|_| anyhow!("Invalid actor list limit"))?;
+                    self_ref.get_actors_page_content(offset, limit).await.map(|content| vec![content])
+                }
             },
         );
+        
+        // Per-actor resources are resolved from templates when read, so actors
+        // started outside this server are covered and resources/list stays small
+        let actor_details_template = ResourceTemplate {
+            uri_template: "theater://actor/{actor_id}".to_string(),
+            name: "Actor Details".to_string(),
+            description: Some("Details for a specific actor".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            actor_details_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    self_ref.get_actor_details_content(actor_id).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let actor_state_template = ResourceTemplate {
+            uri_template: "theater://actor/{actor_id}/state".to_string(),
+            name: "Actor State".to_string(),
+            description: Some("Current state for a specific actor".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            actor_state_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    self_ref.get_actor_state_content(actor_id).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let actor_manifest_template = ResourceTemplate {
+            uri_template: "theater://manifest/{actor_id}".to_string(),
+            name: "Actor Manifest".to_string(),
+            description: Some("The manifest an actor started through this server was started from, as JSON".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            actor_manifest_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    self_ref.get_actor_manifest_content(actor_id).await.map(|content| vec![content])
+                }
+            },
+        );
+    }
+}
+
+/// The actor list resource's JSON for some of the actors, out of `total`
+fn actor_list_json<'a>(
+    version: u64,
+    actors: impl Iterator<Item = (&'a String, &'a String)>,
+    total: usize,
+) -> Value {
+    let actors = actors.map(|(id, status)| {
+        json!({
+            "id": id,
+            "name": format!("Actor {}", id),
+            "status": status,
+            "uri": format!("theater://actor/{}", id)
+        })
+    }).collect::<Vec<_>>();
+    
+    json!({
+        "actors": actors,
+        "total": total,
+        "version": version
+    })
+}
+
+/// The spawned actor's ID in a spawn event's data, which Theater may nest
+/// inside its event enums
+fn child_id(data: &Value) -> Option<&str> {
+    match data {
+        Value::Object(fields) => fields
+            .get(CHILD_ID_FIELD)
+            .and_then(Value::as_str)
+            .or_else(|| fields.values().find_map(child_id)),
+        Value::Array(items) => items.iter().find_map(child_id),
+        _ => None,
     }
-}
\ No newline at end of file
+}
+
+/// An actor and, recursively, the children not already placed in the tree
+fn tree_node(
+    actor_id: &str,
+    statuses: &BTreeMap<String, String>,
+    children: &BTreeMap<String, Vec<String>>,
+    placed: &mut HashSet<String>,
+) -> Value {
+    placed.insert(actor_id.to_string());
+    let mut child_nodes = Vec::new();
+    for child in children.get(actor_id).into_iter().flatten() {
+        if !placed.contains(child) {
+            child_nodes.push(tree_node(child, statuses, children, placed));
+        }
+    }
+    json!({
+        "id": actor_id,
+        "status": statuses.get(actor_id),
+        "uri": format!("theater://actor/{}", actor_id),
+        "children": child_nodes
+    })
+}
+
+/// Wrap non-JSON state as `{"_raw_state_base64": "..."}`, encoding straight into the output
+fn base64_state(state: &[u8]) -> String {
+    let mut text = String::with_capacity(state.len() * 4 / 3 + 32);
+    text.push_str(r#"{"_raw_state_base64":""#);
+    BASE64.encode_string(state, &mut text);
+    text.push_str(r#""}"#);
+    text
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::channels::ChannelRegistry;
+use crate::resources::utils::{
+    register_async_resource, register_async_template, template_param, DEFAULT_READ_TIMEOUT,
+};
+
+/// Channels opened through the channel tools, and each one's details
+pub struct ChannelResources {
+    channel_registry: Arc<ChannelRegistry>,
+}
+
+impl ChannelResources {
+    pub fn new(channel_registry: Arc<ChannelRegistry>) -> Self {
+        Self { channel_registry }
+    }
+
+    /// Get resource content for the list of channels
+    pub fn get_channels_content(&self) -> Result<ResourceContent> {
+        debug!("Listing channels");
+
+        let channels: Vec<_> = self.channel_registry.list()
+            .into_iter()
+            .map(|record| json!({
+                "channel_id": record.channel_id,
+                "participant": record.participant,
+                "state": record.state,
+                "opened_at": record.opened_at,
+                "messages_sent": record.stats.messages_sent,
+                "messages_received": record.stats.messages_received,
+                "uri": format!("theater://channel/{}", record.channel_id)
+            }))
+            .collect();
+        let content = json!({
+            "channels": channels,
+            "total": channels.len()
+        });
+
+        Ok(ResourceContent {
+            uri: "theater://channels".to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&content)?),
+            blob: None,
+        })
+    }
+
+    /// Get resource content for a channel: its record, health and inbox
+    pub fn get_channel_content(&self, channel_id: &str) -> Result<ResourceContent> {
+        debug!("Getting details of channel {}", channel_id);
+
+        let status = self.channel_registry.status(channel_id)?;
+
+        Ok(ResourceContent {
+            uri: format!("theater://channel/{}", channel_id),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&status)?),
+            blob: None,
+        })
+    }
+
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        let channels_resource = Resource {
+            uri: "theater://channels".to_string(),
+            name: "Channels".to_string(),
+            description: Some("Channels opened through the server, with their participants and message counts".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+
+        let self_ref = self.clone();
+
+        register_async_resource(
+            resource_manager,
+            channels_resource,
+            DEFAULT_READ_TIMEOUT,
+            move || {
+                let self_ref = self_ref.clone();
+                async move {
+                    self_ref.get_channels_content().map(|content| vec![content])
+                }
+            },
+        );
+
+        let channel_template = ResourceTemplate {
+            uri_template: "theater://channel/{channel_id}".to_string(),
+            name: "Channel Details".to_string(),
+            description: Some("A channel's participant, open time, traffic counters, health and inbox depth".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+
+        let self_ref = self.clone();
+
+        register_async_template(
+            resource_manager,
+            channel_template,
+            DEFAULT_READ_TIMEOUT,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let channel_id = template_param(&params, "channel_id")?;
+                    self_ref.get_channel_content(channel_id).map(|content| vec![content])
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::conversations::{transcript_uri, ConversationStore};
+use crate::resources::utils::{
+    register_async_resource, register_async_template, template_param, DEFAULT_READ_TIMEOUT,
+};
+
+/// Conversations held through converse_with_actor and their transcripts
+pub struct ConversationResources {
+    conversations: Arc<ConversationStore>,
+}
+
+impl ConversationResources {
+    pub fn new(conversations: Arc<ConversationStore>) -> Self {
+        Self { conversations }
+    }
+
+    /// Get resource content for the list of open conversations
+    pub fn get_conversations_content(&self) -> Result<ResourceContent> {
+        debug!("Listing conversations");
+
+        let conversations: Vec<_> = self.conversations.list()
+            .into_iter()
+            .map(|conversation| json!({
+                "conversation_id": conversation.conversation_id,
+                "actor_id": conversation.actor_id,
+                "encoding": conversation.encoding,
+                "started_at": conversation.started_at,
+                "exchanges": conversation.next_seq - 1,
+                "uri": transcript_uri(&conversation.conversation_id)
+            }))
+            .collect();
+        let content = json!({
+            "conversations": conversations,
+            "total": conversations.len()
+        });
+
+        Ok(ResourceContent {
+            uri: "theater://conversations".to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&content)?),
+            blob: None,
+        })
+    }
+
+    /// Get resource content for a conversation's transcript
+    pub fn get_transcript_content(&self, conversation_id: &str) -> Result<ResourceContent> {
+        debug!("Reading transcript of conversation {}", conversation_id);
+
+        let conversation = self.conversations.get(conversation_id)
+            .ok_or_else(|| anyhow!("Unknown conversation {}", conversation_id))?;
+
+        Ok(ResourceContent {
+            uri: transcript_uri(conversation_id),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&conversation)?),
+            blob: None,
+        })
+    }
+
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        let conversations_resource = Resource {
+            uri: "theater://conversations".to_string(),
+            name: "Conversations".to_string(),
+            description: Some("Conversations held with actors through converse_with_actor".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+
+        let self_ref = self.clone();
+
+        register_async_resource(
+            resource_manager,
+            conversations_resource,
+            DEFAULT_READ_TIMEOUT,
+            move || {
+                let self_ref = self_ref.clone();
+                async move {
+                    self_ref.get_conversations_content().map(|content| vec![content])
+                }
+            },
+        );
+
+        let transcript_template = ResourceTemplate {
+            uri_template: "theater://conversation/{conversation_id}".to_string(),
+            name: "Conversation Transcript".to_string(),
+            description: Some("The most recent exchanges of a conversation, oldest first".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+
+        let self_ref = self.clone();
+
+        register_async_template(
+            resource_manager,
+            transcript_template,
+            DEFAULT_READ_TIMEOUT,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let conversation_id = template_param(&params, "conversation_id")?;
+                    self_ref.get_transcript_content(conversation_id).map(|content| vec![content])
+                }
+            },
+        );
+    }
+}
@@ -1,133 +1,235 @@
 use anyhow::{anyhow, Result};
 use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
-use serde_json::json;
+use serde::Serializer as _;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, warn};
+use std::time::Duration;
+use tracing::debug;
+use url::form_urlencoded;
 
 use theater::id::TheaterId;
-use crate::theater::client::TheaterClient;
+use crate::events::history::events_after;
+use crate::events::render::render_event;
+use crate::events::verify::to_hex;
+use crate::events::{aggregate, CausalityTracker, EventFilter, EventHistory, EventStreamHub};
+use crate::resources::utils::{register_async_resource, register_async_template, template_param, DEFAULT_READ_TIMEOUT};
 use crate::theater::TheaterIdExt;
 
+/// Maximum number of events in the aggregated stream resource
+const EVENT_STREAM_LIMIT: usize = 500;
+
+/// Serialize items into a JSON array as they are produced.
+///
+/// Resource content has to end up as one string, but this keeps only that
+/// string and the item being written in memory, rather than every rendered
+/// event as a `Value` plus the string built from them.
+fn to_json_array<T: Serialize>(items: impl Iterator<Item = T>) -> Result<String> {
+    let mut buffer = Vec::new();
+    serde_json::Serializer::new(&mut buffer).collect_seq(items)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
 /// Resources for accessing Theater events
 pub struct EventResources {
-    theater_client: Arc<TheaterClient>,
+    history: Arc<EventHistory>,
+    event_stream: Arc<EventStreamHub>,
+    causality: Arc<CausalityTracker>,
+    read_timeout: Duration,
 }
 
 impl EventResources {
     /// Create a new event resources instance
-    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
-        Self { theater_client }
+    pub fn new(
+        history: Arc<EventHistory>,
+        event_stream: Arc<EventStreamHub>,
+        causality: Arc<CausalityTracker>,
+    ) -> Self {
+        Self {
+            history,
+            event_stream,
+            causality,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        }
     }
     
-    /// Helper method to handle Theater connection errors
-    fn handle_connection_error<T>(&self, result: Result<T>, context: &str) -> Result<T> {
-        match result {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("connect") || error_msg.contains("connection") || 
-                   error_msg.contains("read") || error_msg.contains("write") {
-                    // This is likely a connection issue
-                    warn!("Theater connection issue during {}: {}. Will attempt reconnection on next request.", context, error_msg);
-                    Err(anyhow!("Theater server connection issue: {}. The server will attempt to reconnect on the next request.", error_msg))
-                } else {
-                    // Other type of error
-                    Err(e)
-                }
-            }
-        }
+    /// Set the deadline for reading any event resource
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
     }
     
-    /// Get resource content for an actor's events
-    pub async fn get_actor_events_content(&self, actor_id: &str) -> Result<ResourceContent> {
+    /// Get resource content for an actor's events, keeping only those matching the filter
+    pub async fn get_actor_events_content(&self, actor_id: &str, filter: &EventFilter) -> Result<ResourceContent> {
         debug!("Getting events for actor {}", actor_id);
         
         // Convert string ID to TheaterId
         let theater_id = TheaterId::from_str(actor_id)?;
         
-        // Get actor events with connection error handling
-        let events = self.handle_connection_error(
-            self.theater_client.get_actor_events(&theater_id).await,
-            &format!("actor events retrieval for {}", actor_id)
+        // Get actor events
+        let events = self.history.actor_events(&theater_id).await?;
+        
+        // Apply the filter and render each event for readability, one at a time
+        let text = to_json_array(
+            events
+                .iter()
+                .filter(|event| filter.matches(event))
+                .map(render_event)
         )?;
         
         // Return the events as JSON
         Ok(ResourceContent {
-            uri: format!("theater://events/{}", actor_id),
+            uri: events_uri(actor_id, None, None, filter),
             mime_type: "application/json".to_string(),
-            text: Some(json!(events).to_string()),
+            text: Some(text),
             blob: None,
         })
     }
     
-    /// Register a specific actor's event resources
-    pub async fn register_actor_events(
-        self: Arc<Self>,
-        actor_id: String,
-        resource_manager: Arc<mcp_server::resources::ResourceManager>,
-    ) -> Result<()> {
+    /// Get resource content for up to `limit` of an actor's events matching
+    /// the filter after `cursor` (from the start of the chain without one),
+    /// so long chains can be read a page at a time
+    pub async fn get_actor_events_page_content(
+        &self,
+        actor_id: &str,
+        filter: &EventFilter,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<ResourceContent> {
+        debug!("Getting a page of events for actor {}", actor_id);
+        if limit == 0 {
+            return Err(anyhow!("Event page limit must be at least 1"));
+        }
+        
         // Convert string ID to TheaterId
-        let theater_id = TheaterId::from_str(&actor_id)?;
+        let theater_id = TheaterId::from_str(actor_id)?;
         
-        // Check if actor exists
-        if !self.theater_client.actor_exists(&theater_id).await? {
-            return Err(anyhow!("Actor not found: {}", actor_id));
-        }
+        // Get actor events and take the page of matching events after the cursor
+        let events = self.history.actor_events(&theater_id).await?;
+        let total = events.iter().filter(|event| filter.matches(event)).count();
+        let after: Vec<_> = events_after(&events, cursor)?
+            .iter()
+            .filter(|event| filter.matches(event))
+            .collect();
+        let returned = &after[..after.len().min(limit)];
+        let has_more = after.len() > limit;
         
-        // Register actor events resource
-        let events_resource = Resource {
-            uri: format!("theater://events/{}", actor_id),
-            name: format!("Actor {} Events", actor_id),
-            description: Some("Event history for a specific actor".to_string()),
-            mime_type: Some("application/json".to_string()),
-            size: None,
-            annotations: None,
+        // The next cursor is the last event returned, or the old one if nothing is new
+        let next_cursor = returned.last().map(|event| to_hex(&event.hash)).or(cursor.map(str::to_string));
+        let next = match &next_cursor {
+            Some(next_cursor) if has_more => json!(events_uri(actor_id, Some(next_cursor), Some(limit), filter)),
+            _ => Value::Null,
         };
         
-        let events_self = self.clone();
-        let events_actor_id = actor_id.clone();
-        // Create a safe content provider that won't block the current async context
-        let self_ref = events_self.clone();
-        let aid = events_actor_id.clone();
+        // Splice the pre-rendered array in rather than building it as a Value
+        let text = format!(
+            r#"{{"events":{},"returned":{},"total":{},"cursor":{},"has_more":{},"next":{}}}"#,
+            to_json_array(returned.iter().map(|event| render_event(event)))?,
+            returned.len(),
+            total,
+            json!(next_cursor),
+            has_more,
+            next
+        );
         
-        resource_manager.register_resource(
-            events_resource,
-            move || {
-                // Clone for the thread
-                let self_ref = self_ref.clone();
-                let aid = aid.clone();
-                
-                // Use a thread-safe channel to communicate between threads
-                let (tx, rx) = std::sync::mpsc::channel();
-                
-                // Spawn a new thread to run the future
-                std::thread::spawn(move || {
-                    // Create a new runtime for this thread only
-                    let rt = tokio::runtime::Builder::new_current_thread()
-                        .enable_all()
-                        .build()
-                        .unwrap();
-                    
-                    // Run the async code in this isolated runtime
-                    let result = rt.block_on(async {
-                        self_ref.get_actor_events_content(&aid).await
-                    });
-                    
-                    // Send the result back to the main thread
-                    let _ = tx.send(result.map(|content| vec![content]));
-                });
-                
-                // Receive the result - this is a blocking operation but we're not in an async context here
-                rx.recv().unwrap_or_else(|e| {
-                    Err(anyhow::anyhow!("Failed to get actor events: {}", e))
-                })
-            }
+        Ok(ResourceContent {
+            uri: events_uri(actor_id, cursor, Some(limit), filter),
+            mime_type: "application/json".to_string(),
+            text: Some(text),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for the merged, ordered feed of every running actor's events
+    pub async fn get_event_stream_content(&self) -> Result<ResourceContent> {
+        debug!("Getting aggregated event stream");
+        
+        // Merge all actor chains
+        let events = aggregate::collect_merged(&self.history, None, &EventFilter::all()).await?;
+        let total = events.len();
+        let events = aggregate::latest(events, EVENT_STREAM_LIMIT);
+        
+        let rendered = to_json_array(events.iter().map(|aggregated| {
+            let mut rendered = render_event(&aggregated.event);
+            rendered["actor_id"] = json!(aggregated.actor_id);
+            rendered["chain_index"] = json!(aggregated.chain_index);
+            rendered
+        }))?;
+        
+        // Splice the pre-rendered array in rather than building it as a Value
+        let text = format!(
+            r#"{{"events":{},"returned":{},"total":{}}}"#,
+            rendered,
+            events.len(),
+            total
         );
-
         
-        Ok(())
+        Ok(ResourceContent {
+            uri: "theater://events/stream".to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(text),
+            blob: None,
+        })
     }
-
+    
+    /// Get resource content for the events attributed to a tool call
+    pub async fn get_caused_events_content(&self, operation_id: &str) -> Result<ResourceContent> {
+        debug!("Getting events caused by {}", operation_id);
+        
+        let operation = self.causality.operation(operation_id)
+            .ok_or_else(|| anyhow!("Unknown or expired operation: {}", operation_id))?;
+        let theater_id = TheaterId::from_str(&operation.actor_id)?;
+        
+        // Get actor events
+        let events = self.history.actor_events(&theater_id).await?;
+        
+        let caused: Vec<Value> = self.causality.caused_events(&operation, &events)
+            .into_iter()
+            .map(|(index, event)| {
+                let mut rendered = render_event(event);
+                rendered["chain_index"] = json!(index);
+                rendered
+            })
+            .collect();
+        
+        let content = json!({
+            "operation": operation,
+            "events": caused
+        });
+        
+        Ok(ResourceContent {
+            uri: CausalityTracker::caused_events_uri(&operation),
+            mime_type: "application/json".to_string(),
+            text: Some(content.to_string()),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for per-actor event rates
+    pub fn get_event_rates_content(&self) -> Result<ResourceContent> {
+        let rates: Vec<Value> = self.event_stream.rates()
+            .into_iter()
+            .map(|(actor_id, rate)| {
+                let mut entry = json!(rate);
+                entry["actor_id"] = json!(actor_id);
+                entry
+            })
+            .collect();
+        
+        let content = json!({
+            "actors": rates,
+            "streaming": self.event_stream.active_actors()
+        });
+        
+        Ok(ResourceContent {
+            uri: "theater://metrics/events".to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(content.to_string()),
+            blob: None,
+        })
+    }
+    
     /// Register event resources with the MCP resource manager
     pub fn register_resources(
         self: Arc<Self>,
@@ -142,9 +244,255 @@ impl EventResources {
             annotations: None,
         };
         
-        resource_manager.register_template(events_template, move |uri, _params| {
-            // We just need to return the expanded URI here
-            Ok(uri)
-        });
+        // Resolved on read, so every actor's events are reachable without registering
+        // a resource per actor. The static stream resource below takes precedence
+        // over this template for theater://events/stream.
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            events_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    self_ref.get_actor_events_content(actor_id, &EventFilter::all()).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        // Filtered variants take an event filter expression, URL-encoded
+        let filtered_events_template = ResourceTemplate {
+            uri_template: "theater://events/{actor_id}?filter={filter}".to_string(),
+            name: "Filtered Actor Events".to_string(),
+            description: Some("An actor's events matching a filter expression, e.g. type:wasm* data.counter>5".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            filtered_events_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let filter = event_filter(&params)?;
+                    self_ref.get_actor_events_content(actor_id, &filter).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        // Paged variants for walking long chains
+        let events_first_page_template = ResourceTemplate {
+            uri_template: "theater://events/{actor_id}?limit={limit}".to_string(),
+            name: "Actor Events Page".to_string(),
+            description: Some("The first limit events of an actor's chain, with the chain's total and a cursor for the next page".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            events_first_page_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let limit = page_limit(&params)?;
+                    self_ref.get_actor_events_page_content(actor_id, &EventFilter::all(), None, limit).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let filtered_first_page_template = ResourceTemplate {
+            uri_template: "theater://events/{actor_id}?limit={limit}&filter={filter}".to_string(),
+            name: "Filtered Actor Events Page".to_string(),
+            description: Some("The first limit events of an actor's chain matching a filter expression".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            filtered_first_page_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let limit = page_limit(&params)?;
+                    let filter = event_filter(&params)?;
+                    self_ref.get_actor_events_page_content(actor_id, &filter, None, limit).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let events_page_template = ResourceTemplate {
+            uri_template: "theater://events/{actor_id}?cursor={cursor}&limit={limit}".to_string(),
+            name: "Actor Events After Cursor".to_string(),
+            description: Some("Up to limit events of an actor's chain after the event the cursor names".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            events_page_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let cursor = template_param(&params, "cursor")?;
+                    let limit = page_limit(&params)?;
+                    self_ref.get_actor_events_page_content(actor_id, &EventFilter::all(), Some(cursor), limit).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let filtered_page_template = ResourceTemplate {
+            uri_template: "theater://events/{actor_id}?cursor={cursor}&limit={limit}&filter={filter}".to_string(),
+            name: "Filtered Actor Events After Cursor".to_string(),
+            description: Some("Up to limit events matching a filter expression after the event the cursor names".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            filtered_page_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let cursor = template_param(&params, "cursor")?;
+                    let limit = page_limit(&params)?;
+                    let filter = event_filter(&params)?;
+                    self_ref.get_actor_events_page_content(actor_id, &filter, Some(cursor), limit).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        // Register the aggregated event stream resource
+        let stream_resource = Resource {
+            uri: "theater://events/stream".to_string(),
+            name: "Theater Event Stream".to_string(),
+            description: Some("Most recent events of all actors merged into one timestamp-ordered feed".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_resource(
+            resource_manager,
+            stream_resource,
+            self.read_timeout,
+            move || {
+                let self_ref = self_ref.clone();
+                async move {
+                    self_ref.get_event_stream_content().await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        // Register the event rate metrics resource
+        let rates_resource = Resource {
+            uri: "theater://metrics/events".to_string(),
+            name: "Event Rates".to_string(),
+            description: Some("Rolling events per second and payload sizes for each streamed actor, busiest first".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+        
+        // Rates are kept in memory, so this handler can stay synchronous
+        let self_ref = self.clone();
+        resource_manager.register_resource(
+            rates_resource,
+            move || self_ref.get_event_rates_content().map(|content| vec![content]),
+        );
+        
+        // Register the caused-events resource template
+        let caused_template = ResourceTemplate {
+            uri_template: "theater://actor/{actor_id}/events?caused_by={operation_id}".to_string(),
+            name: "Caused Events".to_string(),
+            description: Some("Events attributed to the tool call that returned operation_id".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            caused_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let operation_id = template_param(&params, "operation_id")?;
+                    if let Some(operation) = self_ref.causality.operation(operation_id) {
+                        if operation.actor_id != actor_id {
+                            return Err(anyhow!("Operation {} was not on actor {}", operation_id, actor_id));
+                        }
+                    }
+                    self_ref.get_caused_events_content(operation_id).await.map(|content| vec![content])
+                }
+            },
+        );
+    }
+}
+
+/// The `limit` of a paged events URI
+fn page_limit(params: &HashMap<String, String>) -> Result<usize> {
+    template_param(params, "limit")?
+        .parse::<usize>()
+        .map_err(|_| anyhow!("Invalid event page limit"))
+}
+
+/// The `filter` of a filtered events URI, decoded and parsed
+fn event_filter(params: &HashMap<String, String>) -> Result<EventFilter> {
+    let raw = template_param(params, "filter")?;
+    let expr: String = form_urlencoded::parse(format!("filter={}", raw).as_bytes())
+        .next()
+        .map(|(_, expr)| expr.into_owned())
+        .unwrap_or_default();
+    EventFilter::parse(&expr)
+}
+
+/// URI of an actor's events, with the page and filter they were read with
+fn events_uri(actor_id: &str, cursor: Option<&str>, limit: Option<usize>, filter: &EventFilter) -> String {
+    let mut query = Vec::new();
+    if let Some(cursor) = cursor {
+        query.push(format!("cursor={}", cursor));
     }
-}
\ No newline at end of file
+    if let Some(limit) = limit {
+        query.push(format!("limit={}", limit));
+    }
+    let expr = filter.to_string();
+    if !expr.is_empty() {
+        query.push(format!("filter={}", form_urlencoded::byte_serialize(expr.as_bytes()).collect::<String>()));
+    }
+    match query.is_empty() {
+        true => format!("theater://events/{}", actor_id),
+        false => format!("theater://events/{}?{}", actor_id, query.join("&")),
+    }
+}
@@ -0,0 +1,231 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+use theater::id::TheaterId;
+use crate::resources::utils::{
+    register_async_resource, register_async_template, template_param, DEFAULT_READ_TIMEOUT,
+};
+use crate::theater::client::TheaterClient;
+use crate::theater::TheaterIdExt;
+
+/// Resources actors publish, served by asking the actor on every read.
+///
+/// An actor opts in by answering two JSON requests sent with request_message:
+///
+/// - `{"mcp": "resources/list"}` with
+///   `{"resources": [{"name": ..., "description": ..., "mimeType": ...}]}`
+/// - `{"mcp": "resources/read", "name": ...}` with `{"mimeType": ..., "text": ...}`
+///   or `{"mimeType": ..., "blob": <base64>}`; any other JSON value is
+///   served as `application/json`
+///
+/// The resources appear under `theater://actor/{actor_id}/exported/{name}`,
+/// and `theater://actor/{actor_id}/exported` lists them.
+pub struct ExportedResources {
+    theater_client: Arc<TheaterClient>,
+    read_timeout: Duration,
+}
+
+impl ExportedResources {
+    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+        Self {
+            theater_client,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+        }
+    }
+    
+    /// Set the deadline for reading any exported resource, including the actor's reply
+    pub fn with_read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+    
+    /// Get resource content for the list of resources an actor exports
+    pub async fn get_exported_list_content(&self, actor_id: &str) -> Result<ResourceContent> {
+        debug!("Listing resources exported by actor {}", actor_id);
+        
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let listing = self.theater_client
+            .request_json(&theater_id, &json!({ "mcp": "resources/list" }), self.read_timeout)
+            .await
+            .map_err(|e| anyhow!("Actor {} did not list its resources: {}", actor_id, e))?;
+        let declared = listing["resources"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Actor {} replied without a resources list: {}", actor_id, listing))?;
+        
+        // Give each resource the URI it is read at
+        let resources: Vec<Value> = declared
+            .iter()
+            .filter_map(|resource| {
+                let name = resource["name"].as_str()?;
+                Some(json!({
+                    "name": name,
+                    "uri": exported_uri(actor_id, name),
+                    "description": resource["description"],
+                    "mimeType": resource["mimeType"]
+                }))
+            })
+            .collect();
+        let content = json!({
+            "actor_id": actor_id,
+            "resources": resources,
+            "total": resources.len()
+        });
+        
+        Ok(ResourceContent {
+            uri: format!("theater://actor/{}/exported", actor_id),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&content)?),
+            blob: None,
+        })
+    }
+    
+    /// Get resource content for one exported resource, as the actor returns it now
+    pub async fn get_exported_content(&self, actor_id: &str, name: &str) -> Result<ResourceContent> {
+        debug!("Reading resource {} exported by actor {}", name, actor_id);
+        
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let reply = self.theater_client
+            .request_json(&theater_id, &json!({ "mcp": "resources/read", "name": name }), self.read_timeout)
+            .await?;
+        
+        let uri = exported_uri(actor_id, name);
+        let mime_type = reply["mimeType"].as_str().map(|m| m.to_string());
+        let content = match (&reply["text"], &reply["blob"]) {
+            (Value::String(text), _) => ResourceContent {
+                uri,
+                mime_type: mime_type.unwrap_or_else(|| "text/plain".to_string()),
+                text: Some(text.clone()),
+                blob: None,
+            },
+            (_, Value::String(blob)) => ResourceContent {
+                uri,
+                mime_type: mime_type.unwrap_or_else(|| "application/octet-stream".to_string()),
+                text: None,
+                blob: Some(blob.clone()),
+            },
+            _ => ResourceContent {
+                uri,
+                mime_type: "application/json".to_string(),
+                text: Some(serde_json::to_string(&reply)?),
+                blob: None,
+            },
+        };
+        Ok(content)
+    }
+    
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        let exported_list_template = ResourceTemplate {
+            uri_template: "theater://actor/{actor_id}/exported".to_string(),
+            name: "Actor Exported Resources".to_string(),
+            description: Some("Resources a specific actor publishes, with the URIs to read them at".to_string()),
+            mime_type: Some("application/json".to_string()),
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            exported_list_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    self_ref.get_exported_list_content(actor_id).await.map(|content| vec![content])
+                }
+            },
+        );
+        
+        let exported_template = ResourceTemplate {
+            uri_template: "theater://actor/{actor_id}/exported/{name}".to_string(),
+            name: "Actor Exported Resource".to_string(),
+            description: Some("A resource published by an actor, read from the actor on demand".to_string()),
+            mime_type: None,
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_template(
+            resource_manager,
+            exported_template,
+            self.read_timeout,
+            move |_uri, params| {
+                let self_ref = self_ref.clone();
+                async move {
+                    let actor_id = template_param(&params, "actor_id")?;
+                    let name = template_param(&params, "name")?;
+                    self_ref.get_exported_content(actor_id, name).await.map(|content| vec![content])
+                }
+            },
+        );
+    }
+    /// Register the resources one actor exports now as concrete resources,
+    /// so they appear in `resources/list` without the templates, returning
+    /// their URIs. Used when proxying a single actor.
+    pub async fn register_actor_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+        actor_id: &str,
+    ) -> Result<Vec<String>> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let listing = match self.theater_client
+            .request_json(&theater_id, &json!({ "mcp": "resources/list" }), self.read_timeout)
+            .await
+        {
+            Ok(listing) => listing,
+            Err(e) => {
+                info!("Actor {} publishes no resources: {}", actor_id, e);
+                return Ok(Vec::new());
+            }
+        };
+        
+        let mut uris = Vec::new();
+        for declared in listing["resources"].as_array().cloned().unwrap_or_default() {
+            let Some(name) = declared["name"].as_str().map(|n| n.to_string()) else {
+                continue;
+            };
+            let resource = Resource {
+                uri: exported_uri(actor_id, &name),
+                name: name.clone(),
+                description: declared["description"].as_str().map(|d| d.to_string()),
+                mime_type: declared["mimeType"].as_str().map(|m| m.to_string()),
+                size: None,
+                annotations: None,
+            };
+            uris.push(resource.uri.clone());
+            
+            let self_ref = self.clone();
+            let actor_id = actor_id.to_string();
+            register_async_resource(
+                resource_manager,
+                resource,
+                self.read_timeout,
+                move || {
+                    let self_ref = self_ref.clone();
+                    let actor_id = actor_id.clone();
+                    let name = name.clone();
+                    async move {
+                        self_ref.get_exported_content(&actor_id, &name).await.map(|content| vec![content])
+                    }
+                },
+            );
+        }
+        info!("Registered {} resources of actor {}", uris.len(), actor_id);
+        Ok(uris)
+    }
+}
+
+/// URI an exported resource is read at
+fn exported_uri(actor_id: &str, name: &str) -> String {
+    format!("theater://actor/{}/exported/{}", actor_id, name)
+}
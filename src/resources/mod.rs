@@ -1,5 +1,22 @@
 mod actors;
+mod channels;
+mod conversations;
 mod events;
+mod exported;
+mod schemas;
+mod utils;
+mod watch;
+mod workspace;
+
+#[cfg(test)]
+mod tests;
 
 pub use actors::ActorResources;
+pub use channels::ChannelResources;
+pub use conversations::ConversationResources;
 pub use events::EventResources;
+pub use exported::ExportedResources;
+pub use schemas::SchemaResources;
+pub use watch::{PollIntervals, ResourceWatcher, DEFAULT_POLL_INTERVAL};
+pub use workspace::{WorkspaceResources, WORKSPACE_URI};
+pub use utils::{register_async_resource, register_async_template, DEFAULT_READ_TIMEOUT};
@@ -0,0 +1,110 @@
+use anyhow::Result;
+use mcp_protocol::types::resource::{Resource, ResourceContent};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use crate::resources::utils::{register_async_resource, DEFAULT_READ_TIMEOUT};
+use crate::schemas;
+
+/// JSON Schemas for Theater wire types and tool results, published as
+/// `theater://schemas/{name}` with an index at `theater://schemas`
+pub struct SchemaResources {
+    schemas: Vec<(&'static str, Value)>,
+}
+
+impl SchemaResources {
+    pub fn new() -> Self {
+        Self {
+            schemas: schemas::all(),
+        }
+    }
+    
+    /// Get resource content for the schema index
+    pub fn get_index_content(&self) -> Result<ResourceContent> {
+        let entries: Vec<Value> = self.schemas
+            .iter()
+            .map(|(name, schema)| json!({
+                "name": name,
+                "uri": schema_uri(name),
+                "title": schema["title"]
+            }))
+            .collect();
+        let content = json!({
+            "schemas": entries,
+            "total": entries.len()
+        });
+        
+        Ok(ResourceContent {
+            uri: "theater://schemas".to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&content)?),
+            blob: None,
+        })
+    }
+    
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        let index_resource = Resource {
+            uri: "theater://schemas".to_string(),
+            name: "Theater Schemas".to_string(),
+            description: Some("JSON Schemas for Theater events, statuses, manifests and tool results".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+        
+        let self_ref = self.clone();
+        
+        register_async_resource(
+            resource_manager,
+            index_resource,
+            DEFAULT_READ_TIMEOUT,
+            move || {
+                let self_ref = self_ref.clone();
+                async move { self_ref.get_index_content().map(|content| vec![content]) }
+            },
+        );
+        
+        for (name, schema) in &self.schemas {
+            let resource = Resource {
+                uri: schema_uri(name),
+                name: format!("{} schema", schema["title"].as_str().unwrap_or(name)),
+                description: schema["description"].as_str().map(|d| d.to_string()),
+                mime_type: Some("application/schema+json".to_string()),
+                size: None,
+                annotations: None,
+            };
+            
+            let content = ResourceContent {
+                uri: resource.uri.clone(),
+                mime_type: "application/schema+json".to_string(),
+                text: Some(schema.to_string()),
+                blob: None,
+            };
+            
+            register_async_resource(
+                resource_manager,
+                resource,
+                DEFAULT_READ_TIMEOUT,
+                move || {
+                    let content = content.clone();
+                    async move { Ok(vec![content]) }
+                },
+            );
+        }
+    }
+}
+
+impl Default for SchemaResources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// URI a schema is published at
+fn schema_uri(name: &str) -> String {
+    format!("theater://schemas/{}", name)
+}
@@ -0,0 +1,61 @@
+use serde_json::json;
+use std::time::Duration;
+
+use crate::resources::watch::{events_uri_actor, resource_actor, state_diff, state_uri_actor, PollIntervals};
+
+#[test]
+fn test_poll_intervals_prefer_longest_pattern() {
+    let intervals = PollIntervals::new(Duration::from_secs(1))
+        .with_interval("theater://actor/*", Duration::from_secs(5))
+        .with_interval("theater://actor/*/state", Duration::from_millis(200));
+
+    assert_eq!(intervals.interval_for("theater://actor/abc/state"), Duration::from_millis(200));
+    assert_eq!(intervals.interval_for("theater://actor/abc"), Duration::from_secs(5));
+    assert_eq!(intervals.interval_for("theater://actors"), Duration::from_secs(1));
+}
+
+#[test]
+fn test_resource_actor() {
+    assert_eq!(resource_actor("theater://actor/abc/state"), Some("abc"));
+    assert_eq!(resource_actor("theater://actor/abc/events?caused_by=op"), Some("abc"));
+    assert_eq!(resource_actor("theater://events/abc"), Some("abc"));
+    assert_eq!(resource_actor("theater://actors"), None);
+}
+
+#[test]
+fn test_state_uri_actor() {
+    assert_eq!(state_uri_actor("theater://actor/abc/state"), Some("abc"));
+    assert_eq!(state_uri_actor("theater://actor/abc"), None);
+    assert_eq!(state_uri_actor("theater://actor/abc/exported/state"), None);
+    assert_eq!(state_uri_actor("theater://events/abc"), None);
+}
+
+#[test]
+fn test_events_uri_actor() {
+    assert_eq!(events_uri_actor("theater://events/abc"), Some("abc"));
+    assert_eq!(events_uri_actor("theater://events/abc?type=error"), None);
+    assert_eq!(events_uri_actor("theater://actor/abc/events"), None);
+    assert_eq!(events_uri_actor("theater://events/"), None);
+}
+
+#[test]
+fn test_state_diff_applies_to_previous_state() {
+    let previous = json!({ "count": 1, "items": ["a", "b"], "blob": "x".repeat(1000) });
+    let current = json!({ "count": 2, "items": ["a", "b", "c"], "blob": "x".repeat(1000) });
+
+    let update = state_diff(4, &previous, &current);
+    assert_eq!(update["fromVersion"], 4);
+    assert_eq!(update["toVersion"], 5);
+
+    let patch: json_patch::Patch = serde_json::from_value(update["patch"].clone()).unwrap();
+    let mut patched = previous.clone();
+    json_patch::patch(&mut patched, &patch).unwrap();
+    assert_eq!(patched, current);
+}
+
+#[test]
+fn test_state_diff_omits_patch_larger_than_state() {
+    let update = state_diff(0, &json!({ "a": { "deeply": { "nested": [1, 2, 3] } } }), &json!(1));
+    assert!(update.get("patch").is_none());
+    assert_eq!(update["toVersion"], 1);
+}
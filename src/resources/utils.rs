@@ -0,0 +1,85 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::resource::{Resource, ResourceContent, ResourceTemplate};
+use mcp_server::resources::ResourceManager;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::theater::types::TheaterError;
+
+/// Boxed future returned by async resource handlers
+pub type ResourceFuture = Pin<Box<dyn Future<Output = Result<Vec<ResourceContent>>> + Send>>;
+
+/// Default deadline for producing resource content
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Register a resource whose content is produced asynchronously.
+///
+/// The handler runs on the server's runtime when the resource is read, so
+/// reads never block a worker thread or spin up a runtime of their own.
+/// Reads that take longer than `timeout` fail with a timeout error.
+pub fn register_async_resource<F, Fut>(
+    resource_manager: &Arc<ResourceManager>,
+    resource: Resource,
+    timeout: Duration,
+    handler: F,
+)
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+{
+    let uri = resource.uri.clone();
+    resource_manager.register_async_resource(resource, move || -> ResourceFuture {
+        Box::pin(with_deadline(uri.clone(), timeout, handler()))
+    });
+}
+
+/// Register a resource template whose matching URIs are resolved on read.
+///
+/// The handler receives the requested URI and the values bound to the
+/// template's variables, so per-actor resources don't need registering
+/// ahead of time and stay out of `resources/list`. Reads are bounded by
+/// `timeout` like other resources.
+pub fn register_async_template<F, Fut>(
+    resource_manager: &Arc<ResourceManager>,
+    template: ResourceTemplate,
+    timeout: Duration,
+    handler: F,
+)
+where
+    F: Fn(String, HashMap<String, String>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Vec<ResourceContent>>> + Send + 'static,
+{
+    resource_manager.register_async_template(
+        template,
+        move |uri: String, params: HashMap<String, String>| -> ResourceFuture {
+            Box::pin(with_deadline(uri.clone(), timeout, handler(uri, params)))
+        },
+    );
+}
+
+/// Fail a resource read that doesn't finish within `timeout`
+async fn with_deadline<Fut>(uri: String, timeout: Duration, read: Fut) -> Result<Vec<ResourceContent>>
+where
+    Fut: Future<Output = Result<Vec<ResourceContent>>>,
+{
+    match tokio::time::timeout(timeout, read).await {
+        Ok(result) => result,
+        Err(_) => Err(TheaterError::Timeout(format!(
+            "reading {} took longer than {} ms",
+            uri,
+            timeout.as_millis()
+        ))
+        .into()),
+    }
+}
+
+/// Look up a template variable bound when a URI was matched
+pub fn template_param<'a>(params: &'a HashMap<String, String>, name: &str) -> Result<&'a str> {
+    params
+        .get(name)
+        .map(String::as_str)
+        .ok_or_else(|| anyhow!("Missing {} in resource URI", name))
+}
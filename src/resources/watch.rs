@@ -0,0 +1,550 @@
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use mcp_server::resources::ResourceManager;
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+use crate::capabilities::{ExperimentalCapabilities, CHANNEL_INBOX, EVENT_STREAMING, STATE_DIFFS};
+use crate::channels::ChannelRegistry;
+use crate::events::filter::glob_match;
+use crate::events::render::render_event;
+use crate::events::{ActorEvent, DurableEvents, EventStreamHub};
+use crate::session::SessionId;
+use crate::theater::client::TheaterClient;
+use crate::theater::types::TheaterIdExt;
+use crate::theater::TheaterId;
+
+/// Default interval between checks of a subscribed resource
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Shortest interval the watcher wakes up at, whatever the configuration
+const MIN_TICK: Duration = Duration::from_millis(100);
+
+/// Name the watcher's event stream watches are held under, so releasing
+/// them leaves other consumers' watches in place
+const WATCHER_CONSUMER: &str = "resource-watcher";
+
+/// How often subscribed resources are checked, by URI.
+///
+/// Patterns may contain `*`; a URI uses the interval of the longest pattern
+/// it matches, or the default interval if none does.
+#[derive(Debug, Clone)]
+pub struct PollIntervals {
+    default: Duration,
+    patterns: Vec<(String, Duration)>,
+}
+
+impl PollIntervals {
+    pub fn new(default: Duration) -> Self {
+        Self {
+            default,
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Check URIs matching `pattern` at this interval
+    pub fn with_interval(mut self, pattern: impl Into<String>, interval: Duration) -> Self {
+        self.patterns.push((pattern.into(), interval));
+        self
+    }
+
+    /// Interval a URI is checked at
+    pub fn interval_for(&self, uri: &str) -> Duration {
+        self.patterns
+            .iter()
+            .filter(|(pattern, _)| glob_match(pattern, uri))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map_or(self.default, |(_, interval)| *interval)
+    }
+
+    /// Shortest configured interval, which the watcher wakes up at
+    fn shortest(&self) -> Duration {
+        self.patterns
+            .iter()
+            .map(|(_, interval)| *interval)
+            .chain(std::iter::once(self.default))
+            .min()
+            .unwrap_or(self.default)
+            .max(MIN_TICK)
+    }
+}
+
+impl Default for PollIntervals {
+    fn default() -> Self {
+        Self::new(DEFAULT_POLL_INTERVAL)
+    }
+}
+
+/// What the watcher last saw of a subscribed resource
+enum Seen {
+    /// An actor state, as clients were last told about it
+    State { version: u64, state: Value },
+    /// Any other resource, by a hash of its content
+    Content(u64),
+}
+
+struct Watched {
+    checked_at: Instant,
+    seen: Seen,
+}
+
+/// Watches the resources clients subscribed to and sends `resources/updated`
+/// when they change.
+///
+/// Every subscribed URI is read again at its polling interval, so updates
+/// work even when Theater can't stream events. With an event stream, the
+/// events of actors whose state or events are subscribed are streamed, and
+/// an event from an actor whose resources are subscribed gets them checked
+/// right away, so they don't wait for their interval.
+///
+/// Actor states (`theater://actor/{actor_id}/state`) are compared as JSON.
+/// Clients that enabled the `theater/stateDiffs` extension also get the
+/// change as a JSON Patch (RFC 6902) under `_meta`, so they can update their
+/// copy instead of reading the whole state again:
+///
+/// ```json
+/// {"uri": "theater://actor/{id}/state",
+///  "_meta": {"theater/stateDiffs": {"fromVersion": 3, "toVersion": 4, "patch": [...]}}}
+/// ```
+///
+/// Versions count changes seen since the subscription began. States that
+/// aren't JSON, and patches larger than the state itself, come without a
+/// patch; clients then read the resource as usual.
+///
+/// Clients that enabled `theater/eventStreaming` get each new event of a
+/// subscribed `theater://events/{actor_id}` pushed as it happens, one
+/// notification per event:
+///
+/// ```json
+/// {"uri": "theater://events/{id}",
+///  "_meta": {"theater/eventStreaming": {"seq": 42, "event": {...}}}}
+/// ```
+///
+/// Clients that enabled `theater/channelInbox` are told as soon as a message
+/// is buffered for a subscribed `theater://channel/{channel_id}`, with the
+/// inbox's size, so they know to receive it:
+///
+/// ```json
+/// {"uri": "theater://channel/{id}",
+///  "_meta": {"theater/channelInbox": {"pending": 3, "dropped": 0}}}
+/// ```
+///
+/// Pushed events are delivered durably, keyed by session and URI: each is
+/// acknowledged once its notification is sent, and a client that
+/// unsubscribes and subscribes again gets the events it missed in between,
+/// as far as the replay buffer reaches.
+pub struct ResourceWatcher {
+    theater_client: Arc<TheaterClient>,
+    resource_manager: Arc<ResourceManager>,
+    experimental: Arc<ExperimentalCapabilities>,
+    intervals: PollIntervals,
+    event_stream: Option<Arc<EventStreamHub>>,
+    channel_registry: Option<Arc<ChannelRegistry>>,
+    watched: Mutex<HashMap<String, Watched>>,
+    // Actors with events since their resources were last checked
+    dirty_actors: Mutex<HashSet<String>>,
+    // Wakes the checks early when a subscribed actor has events
+    wake: Notify,
+    // Actors whose event streams are kept running for their subscribers
+    streamed_actors: Mutex<HashSet<String>>,
+    // Name durable deliveries are acknowledged under, with the URI
+    subscriber: String,
+    // Tasks pushing the events of subscribed events URIs, by URI
+    pushers: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl ResourceWatcher {
+    pub fn new(
+        theater_client: Arc<TheaterClient>,
+        resource_manager: Arc<ResourceManager>,
+        experimental: Arc<ExperimentalCapabilities>,
+    ) -> Self {
+        Self {
+            theater_client,
+            resource_manager,
+            experimental,
+            intervals: PollIntervals::default(),
+            event_stream: None,
+            channel_registry: None,
+            watched: Mutex::new(HashMap::new()),
+            dirty_actors: Mutex::new(HashSet::new()),
+            wake: Notify::new(),
+            streamed_actors: Mutex::new(HashSet::new()),
+            subscriber: WATCHER_CONSUMER.to_string(),
+            pushers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set how often subscribed resources are checked
+    pub fn with_intervals(mut self, intervals: PollIntervals) -> Self {
+        self.intervals = intervals;
+        self
+    }
+
+    /// Check an actor's resources as soon as it has new events
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStreamHub>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+
+    /// Tell clients with the channel inbox extension about inbound messages
+    pub fn with_channel_registry(mut self, channel_registry: Arc<ChannelRegistry>) -> Self {
+        self.channel_registry = Some(channel_registry);
+        self
+    }
+
+    /// Acknowledge pushed events under this session, so its subscriptions
+    /// resume where they left off
+    pub fn with_session(mut self, session_id: &SessionId) -> Self {
+        self.subscriber = session_id.to_string();
+        self
+    }
+
+    /// Check subscribed resources until the task is aborted
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        if let Some(event_stream) = &self.event_stream {
+            let mut events = event_stream.subscribe_all();
+            let watcher = Arc::downgrade(&self);
+            tokio::spawn(async move {
+                loop {
+                    let event = match events.recv().await {
+                        Ok(event) => event,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    };
+                    let Some(watcher) = watcher.upgrade() else {
+                        break;
+                    };
+                    if watcher.is_watching_actor(&event.actor_id) {
+                        watcher.dirty_actors.lock().unwrap().insert(event.actor_id);
+                        watcher.wake.notify_one();
+                    }
+                }
+            });
+        }
+
+        if let Some(channel_registry) = &self.channel_registry {
+            let mut inbound = channel_registry.subscribe_inbound();
+            let watcher = Arc::downgrade(&self);
+            tokio::spawn(async move {
+                loop {
+                    let channel_id = match inbound.recv().await {
+                        Ok(channel_id) => channel_id,
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    };
+                    let Some(watcher) = watcher.upgrade() else {
+                        break;
+                    };
+                    if watcher.experimental.is_enabled(CHANNEL_INBOX) {
+                        watcher.push_inbox(&channel_id).await;
+                    }
+                }
+            });
+        }
+
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(self.intervals.shortest());
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = ticks.tick() => {}
+                    _ = self.wake.notified() => {}
+                }
+                self.check_subscribed().await;
+            }
+        })
+    }
+
+    /// Push each event of a subscribed events URI, acknowledging it once
+    /// the notification is sent
+    async fn push_events(watcher: Weak<Self>, uri: String, mut events: DurableEvents) {
+        while let Some(event) = events.next().await {
+            let Some(watcher) = watcher.upgrade() else {
+                break;
+            };
+            if watcher.push_event(&uri, &event).await {
+                events.ack(event.seq);
+            }
+        }
+    }
+
+    /// Push an event to clients subscribed to its actor's events, returning
+    /// whether the notification was sent
+    async fn push_event(&self, uri: &str, event: &ActorEvent) -> bool {
+        // The push already told the client; the next check starts afresh
+        self.watched.lock().unwrap().remove(uri);
+        let meta = json!({
+            EVENT_STREAMING: {
+                "seq": event.seq,
+                "event": render_event(&event.event)
+            }
+        });
+        self.notify(uri, Some(meta)).await
+    }
+
+    /// Tell clients subscribed to a channel that its inbox has new messages
+    async fn push_inbox(&self, channel_id: &str) {
+        let uri = format!("theater://channel/{}", channel_id);
+        if !self.resource_manager.subscribed_uris().contains(&uri) {
+            return;
+        }
+        let Some(inbox) = self.channel_registry.as_ref().and_then(|registry| registry.inbox(channel_id)) else {
+            return;
+        };
+        // The push already told the client; the next check starts afresh
+        self.watched.lock().unwrap().remove(&uri);
+        let meta = json!({
+            CHANNEL_INBOX: {
+                "pending": inbox.len(),
+                "dropped": inbox.dropped()
+            }
+        });
+        self.notify(&uri, Some(meta)).await;
+    }
+
+    /// Keep a durable push running for every subscribed events URI while
+    /// clients have event streaming enabled
+    async fn push_subscribed_events<'a>(self: &Arc<Self>, subscribed: impl IntoIterator<Item = &'a String>) {
+        let Some(event_stream) = &self.event_stream else {
+            return;
+        };
+        let wanted: HashSet<&String> = match self.experimental.is_enabled(EVENT_STREAMING) {
+            true => subscribed.into_iter().filter(|uri| events_uri_actor(uri).is_some()).collect(),
+            false => HashSet::new(),
+        };
+
+        let start: Vec<String> = {
+            let mut pushers = self.pushers.lock().unwrap();
+            pushers.retain(|uri, pusher| {
+                let keep = wanted.contains(uri) && !pusher.is_finished();
+                if !keep {
+                    pusher.abort();
+                }
+                keep
+            });
+            wanted
+                .into_iter()
+                .filter(|uri| !pushers.contains_key(*uri))
+                .cloned()
+                .collect()
+        };
+
+        for uri in start {
+            let Some(actor_id) = events_uri_actor(&uri) else {
+                continue;
+            };
+            let subscriber_id = format!("{}:{}", self.subscriber, uri);
+            match event_stream.subscribe_durable(&subscriber_id, actor_id).await {
+                Ok(events) => {
+                    let pusher = tokio::spawn(Self::push_events(Arc::downgrade(self), uri.clone(), events));
+                    self.pushers.lock().unwrap().insert(uri, pusher);
+                }
+                Err(e) => debug!("Could not push events of {} to subscribers: {}", actor_id, e),
+            }
+        }
+    }
+
+    /// Keep event streams running for the actors whose state or events are
+    /// subscribed, so their events wake the checks and can be pushed
+    async fn stream_subscribed_actors<'a>(&self, subscribed: impl IntoIterator<Item = &'a String>) {
+        let Some(event_stream) = &self.event_stream else {
+            return;
+        };
+        let wanted: HashSet<String> = subscribed
+            .into_iter()
+            .filter_map(|uri| state_uri_actor(uri).or_else(|| events_uri_actor(uri)))
+            .map(str::to_string)
+            .collect();
+
+        let (start, stop): (Vec<String>, Vec<String>) = {
+            let mut streamed = self.streamed_actors.lock().unwrap();
+            let start = wanted.difference(&streamed).cloned().collect();
+            let stop = streamed.difference(&wanted).cloned().collect();
+            *streamed = wanted;
+            (start, stop)
+        };
+
+        for actor_id in stop {
+            event_stream.unwatch(WATCHER_CONSUMER, &actor_id);
+        }
+        for actor_id in start {
+            if let Err(e) = event_stream.watch_as(WATCHER_CONSUMER, &actor_id).await {
+                debug!("Could not stream events from {} for subscribers: {}", actor_id, e);
+            }
+        }
+    }
+
+    /// Whether any subscribed resource belongs to the actor
+    fn is_watching_actor(&self, actor_id: &str) -> bool {
+        self.watched
+            .lock()
+            .unwrap()
+            .keys()
+            .any(|uri| resource_actor(uri) == Some(actor_id))
+    }
+
+    /// Check every subscribed resource that is due, notifying on changes
+    async fn check_subscribed(self: &Arc<Self>) {
+        let subscribed = self.resource_manager.subscribed_uris();
+        self.stream_subscribed_actors(&subscribed).await;
+        self.push_subscribed_events(&subscribed).await;
+        let dirty_actors = std::mem::take(&mut *self.dirty_actors.lock().unwrap());
+
+        let due: Vec<String> = {
+            let mut watched = self.watched.lock().unwrap();
+
+            // Forget resources nobody watches any more
+            watched.retain(|uri, _| subscribed.contains(uri));
+
+            subscribed
+                .into_iter()
+                .filter(|uri| match watched.get(uri) {
+                    None => true,
+                    Some(entry) => {
+                        entry.checked_at.elapsed() >= self.intervals.interval_for(uri)
+                            || resource_actor(uri).is_some_and(|actor_id| dirty_actors.contains(actor_id))
+                    }
+                })
+                .collect()
+        };
+
+        for uri in due {
+            let checked = match state_uri_actor(&uri) {
+                Some(actor_id) => self.check_state(&uri, actor_id).await,
+                None => self.check_content(&uri).await,
+            };
+            if let Err(e) = checked {
+                debug!("Could not check subscribed resource {}: {}", uri, e);
+            }
+        }
+    }
+
+    /// Compare an actor's state with what the client was last told
+    async fn check_state(&self, uri: &str, actor_id: &str) -> Result<()> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        let state = match self.theater_client.get_actor_state(&theater_id).await? {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| json!({ "_raw_state_base64": BASE64.encode(&bytes) })),
+            None => json!({ "_state": "empty" }),
+        };
+
+        let update = {
+            let mut watched = self.watched.lock().unwrap();
+            let previous = watched.get(uri).and_then(|entry| match &entry.seen {
+                Seen::State { version, state } => Some((*version, state)),
+                Seen::Content(_) => None,
+            });
+            let (version, update) = match previous {
+                // The first look only records the state the client started from
+                None => (0, None),
+                Some((version, previous)) if *previous == state => (version, None),
+                Some((version, previous)) => (version + 1, Some(state_diff(version, previous, &state))),
+            };
+            watched.insert(uri.to_string(), Watched {
+                checked_at: Instant::now(),
+                seen: Seen::State { version, state },
+            });
+            update
+        };
+
+        if let Some(update) = update {
+            let meta = match self.experimental.is_enabled(STATE_DIFFS) {
+                true => Some(json!({ STATE_DIFFS: update })),
+                false => None,
+            };
+            self.notify(uri, meta).await;
+        }
+        Ok(())
+    }
+
+    /// Read any other resource and compare it with the last read
+    async fn check_content(&self, uri: &str) -> Result<()> {
+        let contents = self.resource_manager.read_resource(uri).await?;
+        let mut hasher = DefaultHasher::new();
+        for content in &contents {
+            content.text.hash(&mut hasher);
+            content.blob.hash(&mut hasher);
+        }
+        let hash = hasher.finish();
+
+        let changed = {
+            let mut watched = self.watched.lock().unwrap();
+            let changed = matches!(
+                watched.get(uri),
+                Some(Watched { seen: Seen::Content(previous), .. }) if *previous != hash
+            );
+            watched.insert(uri.to_string(), Watched {
+                checked_at: Instant::now(),
+                seen: Seen::Content(hash),
+            });
+            changed
+        };
+
+        if changed {
+            self.notify(uri, None).await;
+        }
+        Ok(())
+    }
+
+    async fn notify(&self, uri: &str, meta: Option<Value>) -> bool {
+        match self.resource_manager.notify_resource_updated(uri, meta).await {
+            Ok(_) => true,
+            Err(e) => {
+                warn!("Failed to notify client of change to {}: {}", uri, e);
+                false
+            }
+        }
+    }
+}
+
+impl Drop for ResourceWatcher {
+    fn drop(&mut self) {
+        for pusher in self.pushers.lock().unwrap().values() {
+            pusher.abort();
+        }
+    }
+}
+
+/// Describe the change from `previous` (at `from_version`) to `current` as a
+/// JSON Patch, leaving the patch out when it is larger than the new state
+pub(crate) fn state_diff(from_version: u64, previous: &Value, current: &Value) -> Value {
+    let patch = serde_json::to_value(json_patch::diff(previous, current)).unwrap_or(Value::Null);
+    let mut update = json!({
+        "fromVersion": from_version,
+        "toVersion": from_version + 1
+    });
+    if patch.to_string().len() <= current.to_string().len() {
+        update["patch"] = patch;
+    }
+    update
+}
+
+/// Actor ID of an actor state resource URI
+pub(crate) fn state_uri_actor(uri: &str) -> Option<&str> {
+    uri.strip_prefix("theater://actor/")?
+        .strip_suffix("/state")
+        .filter(|actor_id| !actor_id.is_empty() && !actor_id.contains('/'))
+}
+
+/// Actor ID of an actor events resource URI
+pub(crate) fn events_uri_actor(uri: &str) -> Option<&str> {
+    uri.strip_prefix("theater://events/")
+        .filter(|actor_id| !actor_id.is_empty() && !actor_id.contains(['/', '?']))
+}
+
+/// Actor a per-actor resource URI belongs to, if any
+pub(crate) fn resource_actor(uri: &str) -> Option<&str> {
+    let rest = uri
+        .strip_prefix("theater://actor/")
+        .or_else(|| uri.strip_prefix("theater://events/"))?;
+    rest.split(['/', '?']).next().filter(|actor_id| !actor_id.is_empty())
+}
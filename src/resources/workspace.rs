@@ -0,0 +1,145 @@
+use anyhow::Result;
+use mcp_protocol::types::resource::{Resource, ResourceContent};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::actors::ActorRegistry;
+use crate::channels::ChannelRegistry;
+use crate::events::EventStreamHub;
+use crate::resources::utils::{register_async_resource, DEFAULT_READ_TIMEOUT};
+use crate::session::Session;
+use crate::tools::{ExportedTools, ToolCallLog};
+
+/// URI of the working-set resource
+pub const WORKSPACE_URI: &str = "theater://session/workspace";
+
+/// One resource summarizing what the current session has touched: the
+/// actors it started, their aliases, its channels and event streams, and
+/// its latest tool calls.
+///
+/// It is rebuilt from the registries on every read, so a host can pin it to
+/// keep the model oriented without it ever going stale.
+pub struct WorkspaceResources {
+    session: Session,
+    actor_registry: Arc<ActorRegistry>,
+    channel_registry: Arc<ChannelRegistry>,
+    event_stream: Arc<EventStreamHub>,
+    calls: Arc<ToolCallLog>,
+    exported_tools: Option<Arc<ExportedTools>>,
+}
+
+impl WorkspaceResources {
+    pub fn new(
+        session: Session,
+        actor_registry: Arc<ActorRegistry>,
+        channel_registry: Arc<ChannelRegistry>,
+        event_stream: Arc<EventStreamHub>,
+        calls: Arc<ToolCallLog>,
+    ) -> Self {
+        Self {
+            session,
+            actor_registry,
+            channel_registry,
+            event_stream,
+            calls,
+            exported_tools: None,
+        }
+    }
+
+    /// Include the aliases of the session's actors
+    pub fn with_exported_tools(mut self, exported_tools: Arc<ExportedTools>) -> Self {
+        self.exported_tools = Some(exported_tools);
+        self
+    }
+
+    /// Get resource content for the session's working set
+    pub fn get_workspace_content(&self) -> Result<ResourceContent> {
+        debug!("Building working set of session {}", self.session.id);
+
+        let mut actors = self.actor_registry.list_for_session(&self.session.id);
+        actors.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        let actor_ids: HashSet<String> = actors.iter().map(|record| record.actor_id.clone()).collect();
+        let actors: Vec<Value> = actors
+            .into_iter()
+            .map(|record| {
+                let mut actor = json!(record);
+                if let Some(failure) = self.actor_registry.failure(&record.actor_id) {
+                    actor["failure"] = json!(failure);
+                }
+                actor
+            })
+            .collect();
+
+        // Aliases of the session's actors
+        let aliases: serde_json::Map<String, Value> = self
+            .exported_tools
+            .as_ref()
+            .map(|tools| tools.aliases())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, actor_id)| actor_ids.contains(actor_id))
+            .map(|(alias, actor_id)| (alias, json!(actor_id)))
+            .collect();
+
+        let mut channels = self.channel_registry.list_for_session(&self.session.id);
+        channels.sort_by(|a, b| a.opened_at.cmp(&b.opened_at));
+        let channels: Vec<Value> = channels
+            .into_iter()
+            .map(|record| json!({
+                "channel_id": record.channel_id,
+                "participant": record.participant,
+                "state": record.state,
+                "messages_sent": record.stats.messages_sent,
+                "messages_received": record.stats.messages_received
+            }))
+            .collect();
+
+        let content = json!({
+            "session_id": self.session.id.as_str(),
+            "started_at": self.session.started_at,
+            "actors": actors,
+            "aliases": aliases,
+            "channels": channels,
+            "event_streams": self.event_stream.active_actors(),
+            "recent_calls": self.calls.recent()
+        });
+
+        Ok(ResourceContent {
+            uri: WORKSPACE_URI.to_string(),
+            mime_type: "application/json".to_string(),
+            text: Some(serde_json::to_string(&content)?),
+            blob: None,
+        })
+    }
+
+    /// Register resources with the MCP resource manager
+    pub fn register_resources(
+        self: Arc<Self>,
+        resource_manager: &Arc<mcp_server::resources::ResourceManager>,
+    ) {
+        let workspace_resource = Resource {
+            uri: WORKSPACE_URI.to_string(),
+            name: "Session Workspace".to_string(),
+            description: Some("Everything this session has touched: actors started, aliases, channels, event streams and recent tool calls".to_string()),
+            mime_type: Some("application/json".to_string()),
+            size: None,
+            annotations: None,
+        };
+
+        let self_ref = self.clone();
+
+        register_async_resource(
+            resource_manager,
+            workspace_resource,
+            DEFAULT_READ_TIMEOUT,
+            move || {
+                let self_ref = self_ref.clone();
+                async move {
+                    self_ref.get_workspace_content().map(|content| vec![content])
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use theater::id::TheaterId;
+use crate::theater::client::TheaterClient;
+use crate::theater::TheaterIdExt;
+
+/// Most messages that can be scheduled at once
+pub const MAX_SCHEDULED: usize = 1000;
+
+/// Shortest interval between deliveries of a recurring message
+pub const MIN_INTERVAL_SECS: u64 = 1;
+
+/// Longest delay before a message is sent, and longest interval between
+/// deliveries of a recurring one: ten years
+pub const MAX_SCHEDULE_SECS: u64 = 10 * 365 * 24 * 60 * 60;
+
+/// How often the scheduler checks for messages that are due
+pub const DEFAULT_TICK: Duration = Duration::from_secs(1);
+
+/// A message waiting to be sent to an actor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledMessage {
+    pub schedule_id: String,
+    pub actor_id: String,
+    /// The encoded message, base64
+    pub data: String,
+    /// When the message is next sent
+    pub deliver_at: DateTime<Utc>,
+    /// Seconds between deliveries, for a recurring message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every_secs: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    /// Times the message has been sent
+    #[serde(default)]
+    pub deliveries: u64,
+    /// Why the last delivery failed, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Sends messages to actors at a later time, once or on an interval.
+///
+/// Due messages are sent from a background task with send_message. A
+/// delivery that fails is not retried: a one-off message is dropped and a
+/// recurring one waits for its next time, with the error kept in
+/// `last_error`. Deliveries missed while the server was down are made once
+/// when it comes back, not once per missed interval.
+pub struct Scheduler {
+    theater_client: Arc<TheaterClient>,
+    scheduled: Mutex<HashMap<String, ScheduledMessage>>,
+}
+
+impl Scheduler {
+    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+        Self {
+            theater_client,
+            scheduled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Schedule a message, returning its schedule
+    pub fn schedule(
+        &self,
+        actor_id: &str,
+        data: &[u8],
+        deliver_at: DateTime<Utc>,
+        every_secs: Option<u64>,
+    ) -> Result<ScheduledMessage> {
+        if let Some(every_secs) = every_secs {
+            if every_secs < MIN_INTERVAL_SECS {
+                return Err(anyhow!("Recurring messages must be at least {} second apart", MIN_INTERVAL_SECS));
+            }
+            if every_secs > MAX_SCHEDULE_SECS {
+                return Err(anyhow!("Recurring messages must be at most {} seconds apart", MAX_SCHEDULE_SECS));
+            }
+        }
+
+        let mut scheduled = self.scheduled.lock().unwrap();
+        if scheduled.len() >= MAX_SCHEDULED {
+            return Err(anyhow!("Too many scheduled messages (limit {}); cancel some first", MAX_SCHEDULED));
+        }
+        let message = ScheduledMessage {
+            schedule_id: Uuid::new_v4().to_string(),
+            actor_id: actor_id.to_string(),
+            data: BASE64.encode(data),
+            deliver_at,
+            every_secs,
+            created_at: Utc::now(),
+            deliveries: 0,
+            last_error: None,
+        };
+        scheduled.insert(message.schedule_id.clone(), message.clone());
+        Ok(message)
+    }
+
+    /// The time `delay_secs` from now
+    pub fn after(delay_secs: u64) -> Result<DateTime<Utc>> {
+        if delay_secs > MAX_SCHEDULE_SECS {
+            return Err(anyhow!("delay_secs must be at most {}", MAX_SCHEDULE_SECS));
+        }
+        TimeDelta::try_seconds(delay_secs as i64)
+            .and_then(|delay| Utc::now().checked_add_signed(delay))
+            .ok_or_else(|| anyhow!("delay_secs {} is out of range", delay_secs))
+    }
+
+    /// Stop a scheduled message from being sent
+    pub fn cancel(&self, schedule_id: &str) -> Option<ScheduledMessage> {
+        self.scheduled.lock().unwrap().remove(schedule_id)
+    }
+
+    /// Scheduled messages, soonest first
+    pub fn list(&self) -> Vec<ScheduledMessage> {
+        let mut scheduled: Vec<ScheduledMessage> = self.scheduled.lock().unwrap().values().cloned().collect();
+        scheduled.sort_by(|a, b| a.deliver_at.cmp(&b.deliver_at));
+        scheduled
+    }
+
+    /// Take over messages scheduled by an earlier run
+    pub fn restore(&self, messages: Vec<ScheduledMessage>) -> usize {
+        let mut scheduled = self.scheduled.lock().unwrap();
+        let restored = messages.len();
+        for message in messages {
+            scheduled.insert(message.schedule_id.clone(), message);
+        }
+        restored
+    }
+
+    /// Send every message that is due, returning how many were sent
+    pub async fn deliver_due(&self) -> usize {
+        let now = Utc::now();
+        let due: Vec<ScheduledMessage> = self
+            .scheduled
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|message| message.deliver_at <= now)
+            .cloned()
+            .collect();
+
+        let mut delivered = 0;
+        for message in due {
+            let result = self.deliver(&message).await;
+            if let Err(e) = &result {
+                warn!("Scheduled message {} to actor {} failed: {}", message.schedule_id, message.actor_id, e);
+            } else {
+                delivered += 1;
+            }
+
+            let mut scheduled = self.scheduled.lock().unwrap();
+            match message.every_secs {
+                Some(every_secs) => {
+                    // Cancelled while it was being sent
+                    let Some(entry) = scheduled.get_mut(&message.schedule_id) else {
+                        continue;
+                    };
+                    entry.deliveries += result.is_ok() as u64;
+                    entry.last_error = result.err().map(|e| e.to_string());
+                    match next_delivery(message.deliver_at, every_secs, now) {
+                        Some(next) => entry.deliver_at = next,
+                        None => {
+                            // Only a schedule restored from a tampered state file gets here
+                            warn!("Recurring message {} has no next delivery time; dropping it", message.schedule_id);
+                            scheduled.remove(&message.schedule_id);
+                        }
+                    }
+                }
+                None => {
+                    scheduled.remove(&message.schedule_id);
+                }
+            }
+        }
+        delivered
+    }
+
+    async fn deliver(&self, message: &ScheduledMessage) -> Result<()> {
+        let theater_id = TheaterId::from_str(&message.actor_id)?;
+        let data = BASE64.decode(&message.data)?;
+        debug!("Delivering scheduled message {} to actor {}", message.schedule_id, message.actor_id);
+        self.theater_client.send_message(&theater_id, &data).await
+    }
+
+    /// Deliver due messages, checking at this interval until the task is aborted
+    pub fn spawn(self: Arc<Self>, tick: Duration) -> JoinHandle<()> {
+        info!("Scheduler checking for due messages every {:?}", tick);
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(tick);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                self.deliver_due().await;
+            }
+        })
+    }
+}
+
+/// The first delivery time after `now` on a recurring message's interval,
+/// or `None` if the interval is out of range
+pub(crate) fn next_delivery(last: DateTime<Utc>, every_secs: u64, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let every_secs = i64::try_from(every_secs).ok().filter(|secs| *secs > 0)?;
+    let every = TimeDelta::try_seconds(every_secs)?;
+    let missed = (now - last).num_seconds().max(0) / every_secs;
+    let intervals = i32::try_from(missed).ok()?.checked_add(1)?;
+    last.checked_add_signed(every.checked_mul(intervals)?)
+}
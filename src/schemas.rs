@@ -0,0 +1,172 @@
+//! JSON Schemas for the shapes theater-mcp sends and accepts.
+//!
+//! The Theater types don't implement `JsonSchema`, so these mirror them as
+//! they appear on the wire here: chain events as written by
+//! [`CompactEvent`](crate::encoding::CompactEvent), statuses as Theater
+//! serializes them, and tool results as the tools build them. Keep them in
+//! step when those shapes change.
+
+use schemars::{schema_for, JsonSchema};
+use serde_json::Value;
+
+/// A chain event, with hex hashes and base64 data
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ChainEvent")]
+struct ChainEvent {
+    /// Hash of the event, as hex
+    hash: String,
+    /// Hash of the previous event in the actor's chain, as hex
+    parent_hash: Option<String>,
+    /// Event type, e.g. `wasm`, `message-server-host/send` or `runtime/init`
+    event_type: String,
+    /// Event payload, base64 encoded
+    data: String,
+    /// Time of the event, in milliseconds since the Unix epoch
+    timestamp: u64,
+    /// Human-readable description recorded with the event
+    description: Option<String>,
+}
+
+/// A chain event as served by the event resources
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "RenderedEvent")]
+struct RenderedEvent {
+    /// Broad category of the event, derived from its type
+    kind: String,
+    event_type: String,
+    /// One-line description of what happened
+    summary: String,
+    /// Time of the event, RFC 3339
+    time: String,
+    hash: String,
+    parent_hash: Option<String>,
+    /// The payload decoded as JSON or text, when it is either
+    payload: Option<Value>,
+    /// The event exactly as recorded
+    raw: ChainEvent,
+}
+
+/// Lifecycle status of an actor
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ActorStatus")]
+enum ActorStatus {
+    Running,
+    Stopped,
+    Failed,
+}
+
+/// An actor manifest, as accepted by start_actor in TOML form
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ActorManifest")]
+struct ActorManifest {
+    name: String,
+    version: String,
+    description: Option<String>,
+    /// Path to the actor's WebAssembly component, relative to the manifest
+    component_path: String,
+    /// Path to a file holding the actor's initial state
+    init_state: Option<String>,
+    handlers: Vec<HandlerConfig>,
+}
+
+/// A handler attached to an actor
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct HandlerConfig {
+    /// Handler type, e.g. `message-server`, `http-client` or `runtime`
+    #[schemars(rename = "type")]
+    handler_type: String,
+    /// Handler-specific settings
+    config: Value,
+}
+
+/// Envelope every built-in tool result comes in; `data` follows the
+/// tool's own schema
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ToolResult")]
+struct ToolResult {
+    /// `ok` or `error`
+    status: String,
+    /// The tool's result
+    data: Value,
+    /// Problems that didn't stop the call
+    warnings: Option<Vec<String>>,
+    /// URIs of resources related to the result, e.g. the events the call caused
+    resources: Option<Vec<String>>,
+    /// How long the call took
+    elapsed_ms: Option<u64>,
+}
+
+/// Result of start_actor, stop_actor and restart_actor
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "ActorLifecycleResult")]
+struct ActorLifecycleResult {
+    actor_id: String,
+    /// `RUNNING` or `STOPPED`
+    status: String,
+    /// Whether the actor stops when the session that started it ends (start_actor only)
+    owned: Option<bool>,
+    /// Operation the start belongs to, for finding the events it caused (start_actor only)
+    operation_id: Option<String>,
+}
+
+/// Result of send_message
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "SendMessageResult")]
+struct SendMessageResult {
+    success: bool,
+    actor_id: String,
+    /// Operation the message belongs to, for finding the events it caused
+    operation_id: Option<String>,
+}
+
+/// Result of request_message
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "RequestMessageResult")]
+struct RequestMessageResult {
+    actor_id: String,
+    /// The actor's reply, in the request's encoding (base64 by default)
+    response: Value,
+    operation_id: Option<String>,
+}
+
+/// Result of open_channel
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[schemars(rename = "OpenChannelResult")]
+struct OpenChannelResult {
+    channel_id: String,
+    actor_id: String,
+    participant: Value,
+    /// Encoding of the messages sent on the channel
+    encoding: String,
+    /// Whether other sessions may use the channel
+    shared: bool,
+}
+
+/// Names and schemas of every published schema, in a stable order
+pub fn all() -> Vec<(&'static str, Value)> {
+    let schemas = vec![
+        ("chain-event", schema_for!(ChainEvent)),
+        ("rendered-event", schema_for!(RenderedEvent)),
+        ("actor-status", schema_for!(ActorStatus)),
+        ("actor-manifest", schema_for!(ActorManifest)),
+        ("tools/result", schema_for!(ToolResult)),
+        ("tools/actor-lifecycle", schema_for!(ActorLifecycleResult)),
+        ("tools/send-message", schema_for!(SendMessageResult)),
+        ("tools/request-message", schema_for!(RequestMessageResult)),
+        ("tools/open-channel", schema_for!(OpenChannelResult)),
+    ];
+    schemas
+        .into_iter()
+        .map(|(name, schema)| (name, serde_json::to_value(schema).expect("schemas serialize to JSON")))
+        .collect()
+}
@@ -1,21 +1,75 @@
 use anyhow::Result;
 use mcp_server::{
-    resources::ResourceManager, server::ServerBuilder, tools::ToolManager, transport::Transport,
+    prompts::PromptManager, resources::ResourceManager, server::ServerBuilder, tools::ToolManager, transport::Transport,
 };
+use mcp_protocol::messages::ClientCapabilities;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{info, warn};
 
-use crate::resources::{ActorResources, EventResources};
+use crate::actors::{ActorRegistry, FailureWatcher};
+use crate::capabilities::{ExperimentalCapabilities, CHANNEL_INBOX, EVENT_STREAMING, STATE_DIFFS};
+use crate::channels::ChannelRegistry;
+use crate::config::ServerConfig;
+use crate::conversations::ConversationStore;
+use crate::events::{webhook, CausalityTracker, EventHistory, EventStreamHub};
+use crate::extension::{Extension, Registrar, ServerContext};
+use crate::logging::ClientLog;
+use crate::plugins::ToolPacks;
+use crate::prompts::{ExportedPrompts, PlaybookPrompts};
+use crate::resources::{ActorResources, ChannelResources, ConversationResources, EventResources, ExportedResources, SchemaResources, ResourceWatcher, WorkspaceResources};
+use crate::schedule::{Scheduler, DEFAULT_TICK};
+use crate::session::{PendingReleases, Presence, Session};
+use crate::state::{ServerState, StateStore};
 use crate::theater::client::TheaterClient;
-use crate::tools::{ActorTools, ChannelTools, MessageTools};
+use crate::theater::{compat, Connector, TheaterFeatures};
+#[cfg(feature = "embedded-theater")]
+use crate::theater::EmbeddedTheater;
+use crate::transport::{ClientLogTransport, Health};
+use crate::tools::{
+    ActorTools, Admission, ChannelTools, ConverseTools, EventTools, ExportedTools, ManifestAllowlist, MessageTools,
+    PlanTools, ProfileTools, ScheduleTools, ToolCallLog, ToolDispatcher,
+};
 
 /// MCP server that interfaces with the Theater actor system
 pub struct TheaterMcpServer {
-    server: mcp_server::server::Server,
+    server: Option<mcp_server::server::Server>,
+    experimental: Arc<ExperimentalCapabilities>,
+    channel_registry: Arc<ChannelRegistry>,
+    actor_registry: Arc<ActorRegistry>,
+    event_stream: Arc<EventStreamHub>,
+    client_log: Arc<ClientLog>,
+    session: Session,
+    // Whether the session's client is attached, for transports it can detach from
+    presence: Presence,
+    // How long a detached session's channels stay open and owned actors keep running
+    channel_grace_period: Duration,
+    actor_grace_period: Duration,
     // Store heartbeat handle for cleanup (optional)
     #[allow(dead_code)]
     theater_heartbeat: Option<tokio::task::JoinHandle<()>>,
+    // Archives streamed events when an event store is configured
+    event_recorder: Option<tokio::task::JoinHandle<()>>,
+    // Tasks forwarding events to webhooks and collectors
+    event_sinks: Vec<tokio::task::JoinHandle<()>>,
+    // Notifies subscribers when the resources they watch change
+    resource_watcher: Option<tokio::task::JoinHandle<()>>,
+    // Restarts or reports actors that fail
+    failure_watcher: Option<tokio::task::JoinHandle<()>>,
+    // Serves liveness and readiness checks, if configured
+    health_listener: Option<tokio::task::JoinHandle<()>>,
+    // Tells the client when the Theater connection drops and comes back
+    connection_monitor: Option<tokio::task::JoinHandle<()>>,
+    // Persists managed actors and channels across restarts, if configured
+    server_state: Option<Arc<ServerState>>,
+    state_saver: Option<tokio::task::JoinHandle<()>>,
+    // Sends scheduled messages when they are due
+    scheduler: Option<tokio::task::JoinHandle<()>>,
+    // Theater runtime hosted in this process, if configured
+    #[cfg(feature = "embedded-theater")]
+    #[allow(dead_code)]
+    embedded_theater: Option<EmbeddedTheater>,
 }
 
 impl TheaterMcpServer {
@@ -24,62 +78,570 @@ impl TheaterMcpServer {
         theater_addr: SocketAddr,
         transport: T,
     ) -> Result<Self> {
+        Self::with_config(theater_addr, transport, ServerConfig::default()).await
+    }
+
+    /// Create a new Theater MCP server with the given configuration
+    pub async fn with_config<T: Transport + 'static>(
+        theater_addr: SocketAddr,
+        transport: T,
+        config: ServerConfig,
+    ) -> Result<Self> {
+        Self::with_extensions(theater_addr, transport, config, Vec::new()).await
+    }
+
+    /// Create a new Theater MCP server that also registers the tool packs
+    /// enabled in the configuration, drawn from `packs` and the configured
+    /// plugin libraries
+    pub async fn with_tool_packs<T: Transport + 'static>(
+        theater_addr: SocketAddr,
+        transport: T,
+        config: ServerConfig,
+        packs: ToolPacks,
+    ) -> Result<Self> {
+        #[cfg(feature = "dynamic-plugins")]
+        let packs = {
+            let mut packs = packs;
+            for library in &config.plugins.libraries {
+                packs.load_library(library)?;
+            }
+            packs
+        };
+        #[cfg(not(feature = "dynamic-plugins"))]
+        if !config.plugins.libraries.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Plugin libraries were configured, but this build lacks the `dynamic-plugins` feature"
+            ));
+        }
+
+        let extensions = packs.extensions(&config.plugins.enabled)?;
+        Self::with_extensions(theater_addr, transport, config, extensions).await
+    }
+
+    /// Create a new Theater MCP server that also offers the tools and
+    /// resources registered by `extensions`, in order, after the built-in ones
+    pub async fn with_extensions<T: Transport + 'static>(
+        theater_addr: SocketAddr,
+        transport: T,
+        config: ServerConfig,
+        extensions: Vec<Extension>,
+    ) -> Result<Self> {
+        // Run Theater in this process instead of connecting to a separate server
+        #[cfg(feature = "embedded-theater")]
+        let embedded_theater = match config.theater.embedded {
+            true => Some(EmbeddedTheater::start().await?),
+            false => None,
+        };
+        #[cfg(feature = "embedded-theater")]
+        let theater_addr = embedded_theater.as_ref().map_or(theater_addr, |theater| theater.address());
+        #[cfg(not(feature = "embedded-theater"))]
+        if config.theater.embedded {
+            return Err(anyhow::anyhow!(
+                "An embedded Theater runtime was configured, but this build lacks the `embedded-theater` feature"
+            ));
+        }
+
         // Connect to the Theater server
-        let theater_client = Arc::new(TheaterClient::connect(theater_addr).await?);
+        let connector = match &config.theater.tls {
+            Some(tls) => Connector::tls(theater_addr, tls)?,
+            None => Connector::plain(theater_addr),
+        };
+        let theater_client = Arc::new(
+            TheaterClient::connect_with(connector, config.theater.connections)
+                .await?
+                .with_limits(config.theater.limits.clone())
+                .with_redaction(config.theater.redaction.clone())
+                .with_reconnect_queue(
+                    config.theater.max_queued_commands,
+                    Duration::from_secs(config.theater.reconnect_window_secs),
+                ),
+        );
         info!("Connected to Theater server at {}", theater_addr);
 
+        // Fail fast if the server speaks another release's protocol
+        compat::check_compatibility(&theater_client, config.theater.compatibility).await?;
+
+        // Find out which commands this Theater release supports
+        let features = if config.theater.probe_features {
+            TheaterFeatures::probe(&theater_client).await
+        } else {
+            TheaterFeatures::default()
+        };
+
         // Start the heartbeat process for connection health checking
         let heartbeat = theater_client.clone().start_heartbeat();
         info!("Started Theater connection heartbeat");
 
+        // Operational messages for the client, sent as MCP log notifications
+        let client_log = Arc::new(ClientLog::new());
+        let connection_monitor = Some(client_log.clone().spawn_connection_monitor(theater_client.clone(), theater_addr));
+
+        // Report liveness and readiness to container orchestrators
+        let health_listener = match config.health.listen {
+            Some(address) => Some(Arc::new(Health::new(theater_client.clone())).spawn_listener(address).await?),
+            None => None,
+        };
+
+        // Every transport serves a single MCP session per process
+        let session = Session::new();
+        let channel_registry = Arc::new(ChannelRegistry::new(
+            theater_client.clone(),
+            config.channels.inbox.clone(),
+        ));
+
+        // Actors started through the server, by session
+        let actor_registry = Arc::new(ActorRegistry::new(
+            theater_client.clone(),
+            config.actors.quota.clone(),
+        ));
+
+        // Live actor events, shared by every consumer that wants them
+        let event_stream = Arc::new(EventStreamHub::new(theater_client.clone()));
+
+        // Event history, optionally archived locally
+        let event_history = Arc::new(Self::event_history(theater_client.clone(), &config)?);
+        let event_recorder = event_history.spawn_recorder(&event_stream);
+
+        // Forward events to any configured webhooks and collectors
+        let mut event_sinks = webhook::spawn_webhooks(&config.events.webhooks, &event_stream)?;
+        event_sinks.extend(Self::otlp_exporter(&config, &event_stream)?);
+
         // Create shared managers
         let tool_manager = Arc::new(ToolManager::new());
         let resource_manager = Arc::new(ResourceManager::new());
+        let prompt_manager = Arc::new(PromptManager::new());
+
+        // Ties tool calls to the events they cause
+        let causality = Arc::new(CausalityTracker::new());
 
         // Create and register resources
-        let actor_resources = Arc::new(ActorResources::new(theater_client.clone()));
-        let event_resources = Arc::new(EventResources::new(theater_client.clone()));
+        let read_timeout = Duration::from_millis(config.resources.read_timeout_ms);
+        let actor_resources = Arc::new(
+            ActorResources::new(theater_client.clone())
+                .with_read_timeout(read_timeout)
+                .with_actor_registry(actor_registry.clone())
+                .with_event_history(event_history.clone())
+        );
+        let event_resources = Arc::new(
+            EventResources::new(event_history.clone(), event_stream.clone(), causality.clone())
+                .with_read_timeout(read_timeout)
+        );
+
+        let exported_resources = Arc::new(
+            ExportedResources::new(theater_client.clone()).with_read_timeout(read_timeout)
+        );
+        let conversations = Arc::new(ConversationStore::new());
 
-        actor_resources.clone().register_resources(&resource_manager);
-        event_resources.clone().register_resources(&resource_manager);
+        let proxy_actor = config.proxy.actor_id.as_deref();
+        if let Some(actor_id) = proxy_actor {
+            // Serve one actor's resources as if they were this server's own
+            exported_resources.register_actor_resources(&resource_manager, actor_id).await?;
+        } else {
+            actor_resources.register_resources(&resource_manager);
+            Arc::new(SchemaResources::new()).register_resources(&resource_manager);
+            if features.messages {
+                // Actors can publish their own resources over request_message
+                exported_resources.register_resources(&resource_manager);
+                Arc::new(ConversationResources::new(conversations.clone())).register_resources(&resource_manager);
+            }
+            if features.actor_events {
+                event_resources.register_resources(&resource_manager);
+            }
+            if features.channels {
+                Arc::new(ChannelResources::new(channel_registry.clone())).register_resources(&resource_manager);
+            }
+        }
 
         // Create and register tools
-        let actor_tools = Arc::new(
-            ActorTools::new(theater_client.clone())
-                .with_resources(
-                    resource_manager.clone(),
-                    actor_resources.clone(),
-                    event_resources.clone()
-                )
+        let mut actor_tools = ActorTools::new(
+            theater_client.clone(),
+            actor_registry.clone(),
+            session.id.clone(),
+        )
+        .with_causality(causality.clone())
+        .with_owned_by_default(config.actors.owned_by_default)
+        .with_client_log(client_log.clone());
+        if !config.tools.allowed_manifests.is_empty() {
+            actor_tools = actor_tools.with_manifest_allowlist(ManifestAllowlist::new(&config.tools.allowed_manifests)?);
+        }
+        if features.subscriptions && (event_recorder.is_some() || !event_sinks.is_empty()) {
+            // Something consumes every actor's events, so stream new actors from their start
+            actor_tools = actor_tools.with_event_stream(event_stream.clone());
+        }
+        let actor_tools = Arc::new(actor_tools);
+        let message_tools = Arc::new(
+            MessageTools::new(theater_client.clone()).with_causality(causality.clone())
+        );
+        let channel_tools = Arc::new(ChannelTools::new(
+            theater_client.clone(),
+            channel_registry.clone(),
+            session.id.clone(),
+        ));
+        let event_tools = Arc::new(EventTools::new(event_history.clone(), event_stream.clone()));
+        let scheduler = Arc::new(Scheduler::new(theater_client.clone()));
+        let plan_tools = Arc::new(
+            PlanTools::new(actor_tools.clone(), message_tools.clone(), channel_tools.clone())
+                .with_policy(config.tools.policy.clone())
         );
-        let message_tools = Arc::new(MessageTools::new(theater_client.clone()));
-        let channel_tools = Arc::new(ChannelTools::new(theater_client.clone()));
 
-        actor_tools.register_tools(&tool_manager);
-        message_tools.register_tools(&tool_manager);
-        channel_tools.register_tools(&tool_manager);
+        // Every tool call passes the admission checks before it runs
+        let admission = Arc::new(Admission::new(config.tools.admission.clone()));
+        let calls = Arc::new(ToolCallLog::new());
+        let dispatcher = ToolDispatcher::new(tool_manager.clone(), admission, session.id.clone())
+            .with_tool_limits(config.tools.concurrency.clone())
+            .with_policy(config.tools.policy.clone())
+            .with_call_log(calls.clone());
+
+        // Actors can export their own tools and prompts over request_message
+        let exported_prompts = Arc::new(ExportedPrompts::new(theater_client.clone(), prompt_manager.clone()));
+        let exported_tools = Arc::new(
+            ExportedTools::new(theater_client.clone(), dispatcher.clone()).with_prompts(exported_prompts)
+        );
+
+        if let Some(actor_id) = proxy_actor {
+            // The proxied actor's tools are the only tools, with no lifecycle control
+            let tools = exported_tools.proxy_actor(actor_id).await?;
+            info!("Proxying actor {} with tools {:?}", actor_id, tools);
+        } else {
+            // Only offer tools the Theater server can back
+            actor_tools.clone().register_tools(&dispatcher);
+            Arc::new(PlaybookPrompts::new(event_history.clone())).register_prompts(&prompt_manager);
+            if features.messages {
+                message_tools.register_tools(&dispatcher);
+                exported_tools.clone().register_tools();
+                Arc::new(ConverseTools::new(theater_client.clone(), conversations)).register_tools(&dispatcher);
+                Arc::new(ScheduleTools::new(theater_client.clone(), scheduler.clone())).register_tools(&dispatcher);
+            }
+            if features.channels {
+                channel_tools.register_tools(&dispatcher);
+            }
+            if features.actor_events {
+                event_tools.register_tools(&dispatcher);
+            }
+            if features.messages && features.channels {
+                plan_tools.register_tools(&dispatcher);
+            }
+            if !config.actors.profiles.is_empty() {
+                let mut profile_tools = ProfileTools::new(actor_tools, config.actors.profiles.clone());
+                if features.messages {
+                    profile_tools = profile_tools.with_exported_tools(exported_tools.clone());
+                }
+                Arc::new(profile_tools).register_tools(&dispatcher);
+            }
+
+            // Everything the session touched, in one resource hosts can pin
+            let mut workspace = WorkspaceResources::new(
+                session.clone(),
+                actor_registry.clone(),
+                channel_registry.clone(),
+                event_stream.clone(),
+                calls,
+            );
+            if features.messages {
+                workspace = workspace.with_exported_tools(exported_tools.clone());
+            }
+            Arc::new(workspace).register_resources(&resource_manager);
+        }
+
+        // Tools and resources added by the embedding crate
+        let context = ServerContext {
+            config: config.clone(),
+            theater_client: theater_client.clone(),
+            features,
+            session: session.clone(),
+            actor_registry: actor_registry.clone(),
+            channel_registry: channel_registry.clone(),
+            event_stream: event_stream.clone(),
+            event_history: event_history.clone(),
+            causality: causality.clone(),
+        };
+        let registrar = Registrar {
+            tools: &dispatcher,
+            resources: &resource_manager,
+        };
+        for extension in extensions {
+            extension(&context, &registrar)?;
+        }
+
+        // Pick up the actors, aliases and channels of the previous run, and
+        // keep saving them for the next one
+        let server_state = match &config.state.path {
+            Some(path) if proxy_actor.is_none() => {
+                let mut server_state = ServerState::new(
+                    StateStore::new(path),
+                    actor_registry.clone(),
+                    channel_registry.clone(),
+                    session.id.clone(),
+                );
+                if features.messages {
+                    server_state = server_state
+                        .with_exported_tools(exported_tools.clone())
+                        .with_scheduler(scheduler.clone());
+                }
+                let server_state = Arc::new(server_state);
+                server_state.restore().await?;
+                Some(server_state)
+            }
+            _ => None,
+        };
+        let state_saver = server_state
+            .clone()
+            .map(|server_state| server_state.spawn(Duration::from_secs(config.state.save_interval_secs)));
+        let scheduler = (features.messages && proxy_actor.is_none()).then(|| scheduler.spawn(DEFAULT_TICK));
+
+        // Theater-specific extensions, negotiated during initialize
+        let mut experimental = ExperimentalCapabilities::new();
+        if !features.subscriptions || proxy_actor.is_some() {
+            experimental = experimental.without(EVENT_STREAMING);
+        }
+        if !features.channels || proxy_actor.is_some() {
+            experimental = experimental.without(CHANNEL_INBOX);
+        }
+        if proxy_actor.is_some() {
+            experimental = experimental.without(STATE_DIFFS);
+        }
+        let experimental = Arc::new(experimental);
+        let negotiate_experimental = experimental.clone();
+
+        // Tell clients when resources they subscribed to change, polling
+        // them and, where Theater streams events, checking on new events
+        let mut resource_watcher = ResourceWatcher::new(theater_client.clone(), resource_manager.clone(), experimental.clone())
+            .with_intervals(config.resources.poll_intervals())
+            .with_session(&session.id)
+            .with_channel_registry(channel_registry.clone());
+        if features.subscriptions {
+            resource_watcher = resource_watcher.with_event_stream(event_stream.clone());
+        }
+        let resource_watcher = Some(Arc::new(resource_watcher).spawn());
+
+        // Handle failures of the actors this server started, as they stream in
+        let failure_watcher = (features.subscriptions && proxy_actor.is_none()).then(|| {
+            Arc::new(
+                FailureWatcher::new(
+                    config.actors.failures.clone(),
+                    theater_client.clone(),
+                    actor_registry.clone(),
+                    event_stream.clone(),
+                )
+                .with_resource_manager(resource_manager.clone()),
+            )
+            .spawn()
+        });
 
         // Create the MCP server
         let server = ServerBuilder::new("theater-mcp", "0.1.0")
-            .with_transport(transport)
+            .with_transport(ClientLogTransport::new(transport, client_log.clone()))
             .with_tool_manager(tool_manager)
             .with_resource_manager(resource_manager)
+            .with_prompt_manager(prompt_manager)
+            .with_experimental_capabilities(experimental.advertised())
+            .on_initialize(move |client_capabilities: &ClientCapabilities| {
+                let requested = client_capabilities
+                    .experimental
+                    .as_ref()
+                    .and_then(|e| serde_json::to_value(e).ok());
+                let enabled = negotiate_experimental.negotiate(requested.as_ref());
+                info!("Negotiated experimental capabilities: {:?}", enabled);
+            })
             .build()?;
 
         info!("Theater MCP server created");
         Ok(Self { 
-            server,
+            server: Some(server),
+            experimental,
+            channel_registry,
+            actor_registry,
+            event_stream,
+            client_log,
+            session,
+            presence: Presence::new(),
+            channel_grace_period: Duration::from_secs(config.channels.session_grace_period_secs),
+            actor_grace_period: Duration::from_secs(config.actors.session_grace_period_secs),
             theater_heartbeat: Some(heartbeat),
+            event_recorder,
+            event_sinks,
+            resource_watcher,
+            failure_watcher,
+            health_listener,
+            connection_monitor,
+            server_state,
+            state_saver,
+            scheduler,
+            #[cfg(feature = "embedded-theater")]
+            embedded_theater,
+        })
+    }
+
+    /// Build the event history, opening the event store if one is configured
+    fn event_history(theater_client: Arc<TheaterClient>, config: &ServerConfig) -> Result<EventHistory> {
+        let history = EventHistory::new(theater_client);
+
+        match &config.events.store_path {
+            #[cfg(feature = "event-store")]
+            Some(path) => {
+                let store = crate::events::EventStore::open(path)?;
+                info!("Archiving events in {}", path.display());
+                Ok(history.with_store(Arc::new(store)))
+            }
+            #[cfg(not(feature = "event-store"))]
+            Some(_) => Err(anyhow::anyhow!(
+                "An event store was configured, but this build lacks the `event-store` feature"
+            )),
+            None => Ok(history),
+        }
+    }
+
+    /// Start exporting events over OTLP if a collector is configured
+    fn otlp_exporter(
+        config: &ServerConfig,
+        event_stream: &Arc<EventStreamHub>,
+    ) -> Result<Option<tokio::task::JoinHandle<()>>> {
+        match &config.events.otlp_endpoint {
+            #[cfg(feature = "otlp")]
+            Some(endpoint) => Ok(Some(crate::events::otlp::spawn_otlp_exporter(
+                endpoint,
+                &config.events.otlp_filter,
+                event_stream,
+            )?)),
+            #[cfg(not(feature = "otlp"))]
+            Some(_) => {
+                let _ = event_stream;
+                Err(anyhow::anyhow!(
+                    "An OTLP endpoint was configured, but this build lacks the `otlp` feature"
+                ))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Experimental capabilities offered to and negotiated with the client
+    pub fn experimental_capabilities(&self) -> Arc<ExperimentalCapabilities> {
+        self.experimental.clone()
+    }
+
+    /// Registry of channels opened through this server
+    pub fn channel_registry(&self) -> Arc<ChannelRegistry> {
+        self.channel_registry.clone()
+    }
+
+    /// Registry of actors started through this server
+    pub fn actor_registry(&self) -> Arc<ActorRegistry> {
+        self.actor_registry.clone()
+    }
+
+    /// Live actor event streams
+    pub fn event_stream(&self) -> Arc<EventStreamHub> {
+        self.event_stream.clone()
+    }
+
+    /// Log messages sent to the client as MCP notifications
+    pub fn client_log(&self) -> Arc<ClientLog> {
+        self.client_log.clone()
+    }
+
+    /// Follow whether the session's client is attached, as reported by a
+    /// transport it can detach from and come back to
+    pub fn with_presence(mut self, presence: Presence) -> Self {
+        self.presence = presence;
+        self
+    }
+
+    /// Close a detached session's channels and stop its owned actors once
+    /// their grace periods expire, unless the client comes back first
+    fn spawn_grace_monitor(&self, releases: Arc<PendingReleases>) -> tokio::task::JoinHandle<()> {
+        let mut presence = self.presence.subscribe();
+        let channel_registry = self.channel_registry.clone();
+        let actor_registry = self.actor_registry.clone();
+        let session_id = self.session.id.clone();
+        let (channel_grace_period, actor_grace_period) = (self.channel_grace_period, self.actor_grace_period);
+        tokio::spawn(async move {
+            while presence.changed().await.is_ok() {
+                if *presence.borrow_and_update() {
+                    if releases.cancel() {
+                        info!("Client of session {} came back; keeping its channels and actors", session_id);
+                    }
+                    continue;
+                }
+
+                info!(
+                    "Client of session {} detached; closing its channels in {}s and stopping its owned actors in {}s unless it comes back",
+                    session_id,
+                    channel_grace_period.as_secs(),
+                    actor_grace_period.as_secs()
+                );
+                let (registry, id) = (channel_registry.clone(), session_id.clone());
+                releases.schedule(channel_grace_period, async move { registry.close_session(&id).await });
+                let (registry, id) = (actor_registry.clone(), session_id.clone());
+                releases.schedule(actor_grace_period, async move { registry.stop_session(&id).await });
+            }
         })
     }
 
     /// Run the server (blocking)
-    pub async fn run(self) -> Result<()> {
-        info!("Starting Theater MCP server");
-        self.server.run().await
+    pub async fn run(mut self) -> Result<()> {
+        info!("Starting Theater MCP server (session {})", self.session.id);
+        let server = self
+            .server
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Server is already running"))?;
+        let releases = Arc::new(PendingReleases::default());
+        let grace_monitor = self.spawn_grace_monitor(releases.clone());
+        // A signal ends the session like the transport closing, so owned
+        // actors are stopped rather than left running after the process exits
+        let result = tokio::select! {
+            result = server.run() => result,
+            signal = shutdown_signal() => {
+                info!("Received {}; shutting down", signal);
+                Ok(())
+            }
+        };
+
+        // Record what is still open before the session's cleanup closes it,
+        // so the next run can reopen it
+        if let Some(server_state) = &self.server_state {
+            if let Err(e) = server_state.save() {
+                warn!("Failed to save server state: {}", e);
+            }
+        }
+
+        // The session ends when the transport closes; release its channels
+        // and stop the actors it owns now rather than after a grace period
+        grace_monitor.abort();
+        releases.cancel();
+        info!("Session {} ended", self.session.id);
+        self.channel_registry.close_session(&self.session.id).await;
+        self.actor_registry.stop_session(&self.session.id).await;
+
+        // Server shutdown: nothing opened through us should outlive the process
+        self.channel_registry.close_all().await;
+        self.event_stream.shutdown();
+
+        result
     }
 }
 
+/// Wait for SIGINT or, on Unix, SIGTERM, returning the signal's name
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => return "SIGINT",
+                    _ = terminate.recv() => return "SIGTERM",
+                }
+            }
+            Err(e) => warn!("Could not listen for SIGTERM: {}", e),
+        }
+    }
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!("Could not listen for SIGINT: {}", e);
+        std::future::pending::<()>().await;
+    }
+    "SIGINT"
+}
+
 impl Drop for TheaterMcpServer {
     fn drop(&mut self) {
         // Cleanup heartbeat task if server is dropped
@@ -87,5 +649,37 @@ impl Drop for TheaterMcpServer {
             warn!("Aborting Theater connection heartbeat");
             heartbeat.abort();
         }
+        
+        if let Some(recorder) = self.event_recorder.take() {
+            recorder.abort();
+        }
+        
+        for sink in self.event_sinks.drain(..) {
+            sink.abort();
+        }
+
+        if let Some(resource_watcher) = self.resource_watcher.take() {
+            resource_watcher.abort();
+        }
+
+        if let Some(failure_watcher) = self.failure_watcher.take() {
+            failure_watcher.abort();
+        }
+
+        if let Some(health_listener) = self.health_listener.take() {
+            health_listener.abort();
+        }
+
+        if let Some(connection_monitor) = self.connection_monitor.take() {
+            connection_monitor.abort();
+        }
+
+        if let Some(state_saver) = self.state_saver.take() {
+            state_saver.abort();
+        }
+
+        if let Some(scheduler) = self.scheduler.take() {
+            scheduler.abort();
+        }
     }
 }
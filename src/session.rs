@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Identifier of an MCP session
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId(String);
+
+impl SessionId {
+    /// Generate a fresh session ID
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for SessionId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An MCP session connected to this server
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: SessionId,
+    pub started_at: DateTime<Utc>,
+}
+
+impl Session {
+    /// Start a new session
+    pub fn new() -> Self {
+        Self {
+            id: SessionId::new(),
+            started_at: Utc::now(),
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a session's client is attached.
+///
+/// Transports whose clients can drop off and come back to the session, such
+/// as SSE, report it here; on the others the client stays attached until
+/// the session ends.
+#[derive(Debug, Clone)]
+pub struct Presence(Arc<watch::Sender<bool>>);
+
+impl Presence {
+    /// Presence of a session whose client is attached
+    pub fn new() -> Self {
+        Self(Arc::new(watch::channel(true).0))
+    }
+
+    pub fn is_attached(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    pub fn set_attached(&self, attached: bool) {
+        self.0.send_replace(attached);
+    }
+
+    /// Watch the client detach and come back
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.0.subscribe()
+    }
+}
+
+impl Default for Presence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cleanups of a session whose client detached, each run once its grace
+/// period expires unless the client comes back first
+#[derive(Debug, Default)]
+pub struct PendingReleases(Mutex<Vec<JoinHandle<()>>>);
+
+impl PendingReleases {
+    /// Run `release` after `grace`, unless cancelled first
+    pub fn schedule<F>(&self, grace: Duration, release: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let task = tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            release.await;
+        });
+        let mut pending = self.0.lock().unwrap();
+        pending.retain(|task| !task.is_finished());
+        pending.push(task);
+    }
+
+    /// Cancel every release still waiting, returning whether there were any
+    pub fn cancel(&self) -> bool {
+        let mut cancelled = false;
+        for task in self.0.lock().unwrap().drain(..) {
+            if !task.is_finished() {
+                task.abort();
+                cancelled = true;
+            }
+        }
+        cancelled
+    }
+}
@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::actors::{ActorRecord, ActorRegistry};
+use crate::channels::{ChannelRecord, ChannelRegistry, ChannelState};
+use crate::schedule::{ScheduledMessage, Scheduler};
+use crate::session::SessionId;
+use crate::tools::ExportedTools;
+
+/// Format version of the state file; files of other versions are ignored
+const STATE_VERSION: u32 = 1;
+
+/// What the server keeps track of between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersistedState {
+    pub version: u32,
+    /// Actors started through the server
+    pub actors: Vec<ActorRecord>,
+    /// Actor behind each alias whose exported tools are registered
+    pub aliases: BTreeMap<String, String>,
+    /// Channels that were open, with their MCP-facing IDs
+    pub channels: Vec<ChannelRecord>,
+    /// Messages waiting to be sent by the scheduler
+    pub schedules: Vec<ScheduledMessage>,
+}
+
+/// A small JSON file holding the persisted state
+pub struct StateStore {
+    path: PathBuf,
+}
+
+impl StateStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read the saved state, or nothing if the file doesn't exist yet
+    pub fn load(&self) -> Result<Option<PersistedState>> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read server state {}", self.path.display()))
+            }
+        };
+        let state = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid server state {}", self.path.display()))?;
+        Ok(Some(state))
+    }
+
+    /// Replace the saved state. The file is written beside the old one and
+    /// renamed over it, so a crash mid-write leaves the previous state intact.
+    pub fn save(&self, state: &PersistedState) -> Result<()> {
+        let temp_path = self.path.with_extension("tmp");
+        std::fs::write(&temp_path, serde_json::to_vec_pretty(state)?)
+            .with_context(|| format!("Failed to write server state {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &self.path)
+            .with_context(|| format!("Failed to replace server state {}", self.path.display()))
+    }
+}
+
+/// Saves the actors, aliases, channels and scheduled messages the server
+/// manages, and picks them up again when it restarts.
+///
+/// On restore, actors that are still running are tracked again, aliases are
+/// re-registered from their actors, and channels are reopened under their
+/// old IDs. All of them then belong to the current session, as if it had
+/// started or opened them.
+pub struct ServerState {
+    store: StateStore,
+    actor_registry: Arc<ActorRegistry>,
+    channel_registry: Arc<ChannelRegistry>,
+    exported_tools: Option<Arc<ExportedTools>>,
+    scheduler: Option<Arc<Scheduler>>,
+    session_id: SessionId,
+    // Last state written, to skip saves when nothing changed
+    last_saved: Mutex<Option<String>>,
+}
+
+impl ServerState {
+    pub fn new(
+        store: StateStore,
+        actor_registry: Arc<ActorRegistry>,
+        channel_registry: Arc<ChannelRegistry>,
+        session_id: SessionId,
+    ) -> Self {
+        Self {
+            store,
+            actor_registry,
+            channel_registry,
+            exported_tools: None,
+            scheduler: None,
+            session_id,
+            last_saved: Mutex::new(None),
+        }
+    }
+
+    /// Also persist the aliases of actor-exported tools
+    pub fn with_exported_tools(mut self, exported_tools: Arc<ExportedTools>) -> Self {
+        self.exported_tools = Some(exported_tools);
+        self
+    }
+
+    /// Also persist scheduled messages
+    pub fn with_scheduler(mut self, scheduler: Arc<Scheduler>) -> Self {
+        self.scheduler = Some(scheduler);
+        self
+    }
+
+    /// The state as it is now
+    pub fn snapshot(&self) -> PersistedState {
+        let mut actors = self.actor_registry.list();
+        actors.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        PersistedState {
+            version: STATE_VERSION,
+            actors,
+            aliases: self.exported_tools.as_ref().map(|tools| tools.aliases()).unwrap_or_default(),
+            // Invalidated channels are gone on the Theater side
+            channels: self
+                .channel_registry
+                .list()
+                .into_iter()
+                .filter(|record| record.state == ChannelState::Open)
+                .collect(),
+            schedules: self.scheduler.as_ref().map(|scheduler| scheduler.list()).unwrap_or_default(),
+        }
+    }
+
+    /// Take over what an earlier run saved, dropping whatever no longer exists
+    pub async fn restore(&self) -> Result<()> {
+        let Some(state) = self.store.load()? else {
+            return Ok(());
+        };
+        if state.version != STATE_VERSION {
+            warn!(
+                "Ignoring server state {} of version {} (expected {})",
+                self.store.path().display(),
+                state.version,
+                STATE_VERSION
+            );
+            return Ok(());
+        }
+
+        let actors = self.actor_registry.restore(state.actors, &self.session_id).await?;
+
+        let mut aliases = 0;
+        if let Some(exported_tools) = &self.exported_tools {
+            for (alias, actor_id) in &state.aliases {
+                match exported_tools.restore_alias(alias, actor_id).await {
+                    Ok(_) => aliases += 1,
+                    Err(e) => warn!("Could not restore alias {} of actor {}: {}", alias, actor_id, e),
+                }
+            }
+        }
+
+        let mut channels = 0;
+        for record in state.channels {
+            let channel_id = record.channel_id.clone();
+            match self.channel_registry.restore(record, &self.session_id).await {
+                Ok(()) => channels += 1,
+                Err(e) => warn!("Could not reopen channel {}: {}", channel_id, e),
+            }
+        }
+
+        let schedules = match &self.scheduler {
+            Some(scheduler) => scheduler.restore(state.schedules),
+            None => 0,
+        };
+
+        info!(
+            "Restored {} actors, {} aliases, {} channels and {} scheduled messages from {}",
+            actors,
+            aliases,
+            channels,
+            schedules,
+            self.store.path().display()
+        );
+        Ok(())
+    }
+
+    /// Write the current state if it changed since the last save
+    pub fn save(&self) -> Result<()> {
+        let state = self.snapshot();
+        let serialized = serde_json::to_string(&state)?;
+        let mut last_saved = self.last_saved.lock().unwrap();
+        if last_saved.as_deref() == Some(serialized.as_str()) {
+            return Ok(());
+        }
+        self.store.save(&state)?;
+        debug!("Saved server state to {}", self.store.path().display());
+        *last_saved = Some(serialized);
+        Ok(())
+    }
+
+    /// Save the state at this interval until the task is aborted
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                if let Err(e) = self.save() {
+                    warn!("Failed to save server state: {}", e);
+                }
+            }
+        })
+    }
+}
@@ -2,211 +2,338 @@ use anyhow::{anyhow, Result};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
-use tokio::sync::Mutex;
-use tracing::{trace, warn, error, info};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch, Semaphore};
+use tracing::{debug, trace, warn, error, info};
 
 use theater::id::TheaterId;
 use theater::theater_server::{ManagementCommand, ManagementResponse};
-use theater::messages::ChannelParticipant;
+use theater::messages::{ActorStatus, ChannelParticipant};
 use theater::chain::ChainEvent;
 
+use crate::theater::connector::Connector;
+use crate::theater::framing::{read_frame, write_frame};
+use crate::theater::limits::{check_size, SizeLimits};
+use crate::theater::pool::{ConnectionPool, PipelinedConnection, DEFAULT_POOL_SIZE};
+use crate::theater::redact::Redaction;
 use crate::theater::types::TheaterError;
 
+/// Commands that may wait for the Theater server to come back
+pub const DEFAULT_RECONNECT_QUEUE: usize = 256;
+
+/// How long a command waits for the Theater server to come back
+pub const DEFAULT_RECONNECT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Longest wait between reconnect attempts for a queued command
+const MAX_RECONNECT_BACKOFF_MS: u64 = 5000;
+
+/// Messages buffered between a channel's connection and its consumer
+const CHANNEL_STREAM_CAPACITY: usize = 64;
+
+/// Events buffered between a subscription's connection and its consumer
+const EVENT_STREAM_CAPACITY: usize = 256;
+
+/// A live subscription to an actor's events on its own connection
+#[derive(Debug)]
+pub struct EventSubscription {
+    pub actor_id: TheaterId,
+    /// Events as the actor records them; ends when the subscription ends
+    pub events: mpsc::Receiver<ChainEvent>,
+}
+
+/// Something that happened on a channel opened by this client
+#[derive(Debug)]
+pub enum ChannelEvent {
+    /// The other participant sent us a message
+    Message(Vec<u8>),
+    /// The channel was closed on the Theater side
+    Closed,
+}
+
+/// A channel opened on its own connection to the Theater server
+#[derive(Debug)]
+pub struct ChannelStream {
+    pub channel_id: String,
+    /// Events on the channel; ends without `Closed` if the connection drops
+    pub messages: mpsc::Receiver<ChannelEvent>,
+    /// Task reading the channel's dedicated connection; aborting it closes
+    /// the connection
+    pub reader: tokio::task::JoinHandle<()>,
+}
+
 /// Client for connecting to and interacting with a Theater server
 /// with automatic reconnection capabilities
 #[derive(Debug)]
 pub struct TheaterClient {
-    connection: Arc<Mutex<Option<TcpStream>>>,
-    address: SocketAddr,
-    is_connecting: Arc<AtomicBool>,
+    pool: ConnectionPool,
+    connector: Connector,
+    // Set when a connection breaks, so the next successful connect counts as a reconnect
+    connection_lost: AtomicBool,
+    // Whether the server is reachable, changing only when a connection breaks or comes back
+    connected: watch::Sender<bool>,
+    // Incremented every time a new connection replaces a broken one
+    generation: watch::Sender<u64>,
+    limits: SizeLimits,
+    // Commands waiting for the server to come back, and how long they wait
+    reconnect_queue: Semaphore,
+    reconnect_window: Duration,
+    // Applied to commands and responses before they are logged
+    redaction: Arc<Redaction>,
 }
 
 impl TheaterClient {
     /// Connect to a Theater server at the given address
     pub async fn connect(addr: SocketAddr) -> Result<Self> {
-        let stream = TcpStream::connect(addr)
-            .await
-            .map_err(|e| anyhow!("Failed to connect to Theater server: {}", e))?;
+        Self::connect_with_pool_size(addr, DEFAULT_POOL_SIZE).await
+    }
+    
+    /// Connect to a Theater server, running up to `pool_size` commands concurrently
+    pub async fn connect_with_pool_size(addr: SocketAddr, pool_size: usize) -> Result<Self> {
+        Self::connect_with(Connector::plain(addr), pool_size).await
+    }
+    
+    /// Connect to a Theater server through the given connector, e.g. one
+    /// using TLS with a client certificate
+    pub async fn connect_with(connector: Connector, pool_size: usize) -> Result<Self> {
+        let stream = connector.connect().await?;
 
-        info!("Connected to Theater server at {}", addr);
+        info!("Connected to Theater server at {}", connector.address());
         
         Ok(Self {
-            connection: Arc::new(Mutex::new(Some(stream))),
-            address: addr,
-            is_connecting: Arc::new(AtomicBool::new(false)),
+            pool: ConnectionPool::new(connector.clone(), pool_size, stream),
+            connector,
+            connection_lost: AtomicBool::new(false),
+            connected: watch::channel(true).0,
+            generation: watch::channel(0).0,
+            limits: SizeLimits::default(),
+            reconnect_queue: Semaphore::new(DEFAULT_RECONNECT_QUEUE),
+            reconnect_window: DEFAULT_RECONNECT_WINDOW,
+            redaction: Arc::new(Redaction::default()),
         })
     }
     
-    /// Ensure that we have a valid connection to the Theater server
-    async fn ensure_connected(&self) -> Result<()> {
-        let mut connection_guard = self.connection.lock().await;
-        
-        // If we already have a connection, check if it's still valid
-        if let Some(conn) = &mut *connection_guard {
-            // Try a small write to test connection (0-length write is a good way to test)
-            if let Err(e) = conn.write_all(&[0; 0]).await {
-                warn!("Connection test failed: {}. Will attempt to reconnect.", e);
-                // Connection is broken, clear it
-                *connection_guard = None;
-            }
-        }
-        
-        // If connection is None, create a new connection
-        if connection_guard.is_none() {
-            // Use atomic flag to prevent multiple reconnection attempts
-            if !self.is_connecting.swap(true, Ordering::SeqCst) {
-                // Try to establish a new connection
-                match TcpStream::connect(self.address).await {
-                    Ok(stream) => {
-                        *connection_guard = Some(stream);
-                        info!("Successfully reconnected to Theater server at {}", self.address);
-                    },
-                    Err(e) => {
-                        error!("Failed to reconnect to Theater server: {}", e);
-                        self.is_connecting.store(false, Ordering::SeqCst);
-                        return Err(anyhow!("Failed to connect to Theater server: {}", e));
-                    }
-                }
-                self.is_connecting.store(false, Ordering::SeqCst);
-            } else {
-                // Another thread is already trying to connect
-                return Err(anyhow!("Connection attempt already in progress"));
-            }
+    /// Apply size limits to frames, actor state and message payloads
+    pub fn with_limits(mut self, limits: SizeLimits) -> Self {
+        self.pool = self.pool.with_max_frame_bytes(limits.max_frame_bytes);
+        self.limits = limits;
+        self
+    }
+    
+    /// Hold up to `max_queued` commands for up to `window` while the server is
+    /// unreachable; commands beyond the limit fail immediately
+    pub fn with_reconnect_queue(mut self, max_queued: usize, window: Duration) -> Self {
+        self.reconnect_queue = Semaphore::new(max_queued);
+        self.reconnect_window = window;
+        self
+    }
+    
+    /// Mask secrets and truncate payloads when logging commands and responses
+    pub fn with_redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = Arc::new(redaction);
+        self
+    }
+    
+    /// Size limits applied to data exchanged with the server
+    pub fn limits(&self) -> &SizeLimits {
+        &self.limits
+    }
+    
+    /// Current connection generation.
+    ///
+    /// State tied to a connection (such as open channels) was created under a
+    /// specific generation and may be gone once the generation changes.
+    pub fn connection_generation(&self) -> u64 {
+        *self.generation.borrow()
+    }
+    
+    /// Whether the last attempt to reach the Theater server succeeded.
+    ///
+    /// After a failure this stays false until a command (or the heartbeat)
+    /// reconnects.
+    pub fn is_connected(&self) -> bool {
+        !self.connection_lost.load(Ordering::SeqCst)
+    }
+    
+    /// Watch for reconnects to the Theater server
+    pub fn subscribe_reconnects(&self) -> watch::Receiver<u64> {
+        self.generation.subscribe()
+    }
+    
+    /// Watch for the Theater server becoming unreachable and coming back
+    pub fn subscribe_connection(&self) -> watch::Receiver<bool> {
+        self.connected.subscribe()
+    }
+    
+    /// Note that the server can't be reached until a new connection succeeds
+    fn mark_lost(&self) {
+        if !self.connection_lost.swap(true, Ordering::SeqCst) {
+            self.connected.send_replace(false);
         }
-        
-        Ok(())
+    }
+    
+    /// Maximum number of connections commands are spread over
+    pub fn max_concurrent_commands(&self) -> usize {
+        self.pool.size()
     }
 
-    /// Send a command to the Theater server and receive a response
-    /// With automatic reconnection on failure
+    /// Send a command to the Theater server and receive a response.
+    ///
+    /// If the server can't be reached, the command waits for it to come back
+    /// rather than failing straight away: it joins a bounded queue of waiting
+    /// commands and is retried as soon as any command reconnects, or with
+    /// backoff otherwise, until the reconnect window runs out.
+    ///
+    /// Only commands that never reached the server, or that only read, are
+    /// retried. A command that changes something and lost its response may
+    /// already have been carried out, so resending it could start a second
+    /// actor or deliver a message twice.
     async fn send_command(&self, command: ManagementCommand) -> Result<ManagementResponse> {
-        let max_attempts = 3;
+        let deadline = tokio::time::Instant::now() + self.reconnect_window;
         let mut backoff_ms = 500; // Start with 500ms backoff
+        let mut attempt = 1;
+        let mut queue_slot = None;
         
-        for attempt in 1..=max_attempts {
-            // Ensure we have a connection before proceeding
-            if let Err(e) = self.ensure_connected().await {
-                if attempt == max_attempts {
-                    return Err(anyhow!("Failed to establish connection after {} attempts: {}", max_attempts, e));
-                }
-                
-                // Wait before retrying with exponential backoff
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms *= 2; // Exponential backoff
-                continue;
-            }
-            
-            // Create message frame
-            let message = serde_json::to_vec(&command)?;
-            let len = message.len() as u32;
-            let len_bytes = len.to_be_bytes();
-            
-            trace!("Sending command (attempt {}/{}): {:?}", attempt, max_attempts, command);
-            
-            // Get connection lock - we know it's Some because ensure_connected succeeded
-            let mut connection_guard = self.connection.lock().await;
-            let connection = connection_guard.as_mut().unwrap();
-            
-            // Send the length prefix
-            if let Err(e) = connection.write_all(&len_bytes).await {
-                warn!("Failed to send length prefix: {}", e);
-                // Mark connection as broken
-                *connection_guard = None;
-                
-                if attempt == max_attempts {
-                    return Err(anyhow!("Failed to send message after {} attempts: {}", max_attempts, e));
-                }
-                
-                // Wait before retrying
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms *= 2;
-                continue;
-            }
-            
-            // Send the message payload
-            if let Err(e) = connection.write_all(&message).await {
-                warn!("Failed to send message payload: {}", e);
-                // Mark connection as broken
-                *connection_guard = None;
-                
-                if attempt == max_attempts {
-                    return Err(anyhow!("Failed to send message payload after {} attempts: {}", max_attempts, e));
-                }
-                
-                // Wait before retrying
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms *= 2;
-                continue;
-            }
-            
-            // Read response length
-            let mut len_buf = [0u8; 4];
-            if let Err(e) = connection.read_exact(&mut len_buf).await {
-                warn!("Failed to read response length: {}", e);
-                // Mark connection as broken
-                *connection_guard = None;
-                
-                if attempt == max_attempts {
-                    return Err(anyhow!("Failed to read response length after {} attempts: {}", max_attempts, e));
-                }
-                
-                // Wait before retrying
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms *= 2;
-                continue;
-            }
-            
-            let len = u32::from_be_bytes(len_buf) as usize;
+        loop {
+            // Subscribe before trying so a reconnect by another command isn't missed
+            let mut reconnects = self.generation.subscribe();
             
-            // Read response
-            let mut response_buf = vec![0u8; len];
-            if let Err(e) = connection.read_exact(&mut response_buf).await {
-                warn!("Failed to read response payload: {}", e);
-                // Mark connection as broken
-                *connection_guard = None;
-                
-                if attempt == max_attempts {
-                    return Err(anyhow!("Failed to read response payload after {} attempts: {}", max_attempts, e));
-                }
-                
-                // Wait before retrying
-                tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                backoff_ms *= 2;
-                continue;
-            }
-            
-            // Parse response
-            let response: ManagementResponse = match serde_json::from_slice(&response_buf) {
-                Ok(resp) => resp,
-                Err(e) => {
-                    warn!("Failed to parse response: {}", e);
+            trace!("Sending command (attempt {}): {}", attempt, self.redaction.show(&command));
+            let error = match self.try_command(&command).await {
+                Ok(response) => {
+                    trace!("Received response: {}", self.redaction.show(&response));
                     
-                    if attempt == max_attempts {
-                        return Err(anyhow!("Failed to parse response after {} attempts: {}", max_attempts, e));
+                    // Check for error
+                    if let ManagementResponse::Error { message } = &response {
+                        return Err(TheaterError::ServerError(message.clone()).into());
                     }
                     
-                    // Wait before retrying
-                    tokio::time::sleep(tokio::time::Duration::from_millis(backoff_ms)).await;
-                    backoff_ms *= 2;
-                    continue;
+                    // Success!
+                    return Ok(response);
+                }
+                Err(e) if is_bad_response(&e) => {
+                    // The server is fine and retrying would only fetch the same response
+                    return Err(e);
+                }
+                Err(e) if !is_unsent(&e) && !is_read_only(&command) => {
+                    return Err(TheaterError::ConnectionError(format!(
+                        "lost the Theater connection waiting for the response, so the command may or may not \
+                         have been carried out; check before repeating it: {:#}",
+                        e
+                    ))
+                    .into());
                 }
+                Err(e) => e,
             };
             
-            trace!("Received response: {:?}", response);
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(TheaterError::ConnectionError(format!(
+                    "Theater server unreachable after {} attempts over {}s: {:#}",
+                    attempt,
+                    self.reconnect_window.as_secs(),
+                    error
+                ))
+                .into());
+            }
             
-            // Check for error
-            if let ManagementResponse::Error { message } = &response {
-                return Err(TheaterError::ServerError(message.clone()).into());
+            // Hold a place in the reconnect queue until the command goes through
+            if queue_slot.is_none() {
+                let slot = self.reconnect_queue.try_acquire().map_err(|_| {
+                    TheaterError::ConnectionError(format!(
+                        "Theater server is unavailable and too many commands are already waiting for it: {:#}",
+                        error
+                    ))
+                })?;
+                info!("Theater server unavailable ({:#}), holding command until it reconnects", error);
+                queue_slot = Some(slot);
             }
             
-            // Success!
-            return Ok(response);
+            // Wait for another command to reconnect, or retry after the backoff
+            let wait = tokio::time::Duration::from_millis(backoff_ms).min(deadline - now);
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                _ = reconnects.changed() => {}
+            }
+            backoff_ms = (backoff_ms * 2).min(MAX_RECONNECT_BACKOFF_MS); // Exponential backoff
+            attempt += 1;
         }
+    }
+    
+    /// Make a single attempt at a command on a pooled connection, alongside
+    /// any other commands in flight on it.
+    ///
+    /// A reused connection may have been closed by the server while it sat
+    /// idle, which says nothing about the server itself, so a command failing
+    /// on one is tried once more on a new connection before the server is
+    /// considered lost, if it is safe to repeat.
+    async fn try_command(&self, command: &ManagementCommand) -> Result<ManagementResponse> {
+        // Check out a connection, opening a new one if none are idle
+        let acquired = self.pool.acquire().await;
+        let (connection, fresh) = self.note_connect(acquired).map_err(|e| e.context(Unsent))?;
+        
+        let result = match self.exchange(&connection, command).await {
+            Err(e) if !fresh && !is_bad_response(&e) && (is_unsent(&e) || is_read_only(command)) => {
+                debug!("Reused Theater connection failed ({:#}); retrying on a new connection", e);
+                let acquired = self.pool.acquire_new().await.map(|connection| (connection, true));
+                let (connection, _) = self.note_connect(acquired).map_err(|e| e.context(Unsent))?;
+                self.exchange(&connection, command).await
+            }
+            result => result,
+        };
         
-        // This should not be reached due to the returns inside the loop
-        Err(anyhow!("Failed to send command after maximum attempts"))
+        match result {
+            Err(e) if !is_bad_response(&e) => {
+                warn!("Command failed on Theater connection: {:#}", e);
+                // Stop using the other connections too; they likely share the broken one's fate
+                self.pool.clear().await;
+                self.mark_lost();
+                Err(e)
+            }
+            // An oversized or undecodable response says nothing about the connection
+            result => result,
+        }
+    }
+    
+    /// Track the outcome of checking out a connection: a failed connect
+    /// means the server is lost, and a new connection after that is a reconnect
+    fn note_connect(
+        &self,
+        acquired: Result<(Arc<PipelinedConnection>, bool)>,
+    ) -> Result<(Arc<PipelinedConnection>, bool)> {
+        match acquired {
+            Ok((connection, fresh)) => {
+                if fresh && self.connection_lost.swap(false, Ordering::SeqCst) {
+                    self.generation.send_modify(|generation| *generation += 1);
+                    self.connected.send_replace(true);
+                    info!("Successfully reconnected to Theater server at {}", self.connector.address());
+                }
+                Ok((connection, fresh))
+            }
+            Err(e) => {
+                error!("Failed to connect to Theater server: {}", e);
+                self.mark_lost();
+                Err(e)
+            }
+        }
+    }
+    
+    /// Write a command and wait for its response. The connection isn't held
+    /// while waiting, so other commands can be written to it meanwhile.
+    async fn exchange(&self, connection: &PipelinedConnection, command: &ManagementCommand) -> Result<ManagementResponse> {
+        let pending = connection.submit(command).await.map_err(|e| e.context(Unsent))?;
+        pending.response().await
+    }
+    
+    /// Send a command on a dedicated connection and read one response, for
+    /// probing what the server supports without risking a pooled connection.
+    ///
+    /// The response is returned as raw JSON, since a server of another release
+    /// may answer with responses this build doesn't know. Returns `None` if the
+    /// server closed the connection instead of answering.
+    pub async fn probe_command(&self, command: &ManagementCommand) -> Result<Option<serde_json::Value>> {
+        let mut stream = self.connector.connect().await?;
+        write_frame(&mut stream, command).await?;
+        Ok(read_frame::<_, serde_json::Value>(&mut stream, self.limits.max_frame_bytes).await.ok())
     }
     
     /// Start a heartbeat process to periodically check connection
@@ -250,6 +377,9 @@ impl TheaterClient {
         manifest: &str,
         initial_state: Option<&[u8]>,
     ) -> Result<TheaterId> {
+        if let Some(state) = initial_state {
+            check_size("Initial actor state", state.len(), self.limits.max_state_bytes)?;
+        }
         let initial_state_vec = initial_state.map(|s| s.to_vec());
         
         let command = ManagementCommand::StartActor {
@@ -293,11 +423,53 @@ impl TheaterClient {
         }
     }
 
-    /// Check if an actor exists
+    /// Swap a running actor's component for the one at `component`, keeping its state
+    pub async fn update_actor_component(&self, actor_id: &TheaterId, component: &str) -> Result<()> {
+        let command = ManagementCommand::UpdateActorComponent {
+            id: actor_id.clone(),
+            component: component.to_string(),
+        };
+        
+        let response = self.send_command(command).await?;
+        
+        match response {
+            ManagementResponse::ActorComponentUpdated { id: _ } => Ok(()),
+            _ => Err(anyhow!("Unexpected response type: {:?}", response)),
+        }
+    }
+
+    /// Get the status of an actor
+    pub async fn get_actor_status(&self, actor_id: &TheaterId) -> Result<ActorStatus> {
+        let command = ManagementCommand::GetActorStatus {
+            id: actor_id.clone(),
+        };
+        
+        let response = self.send_command(command).await?;
+        
+        match response {
+            ManagementResponse::ActorStatus { id: _, status } => Ok(status),
+            _ => Err(anyhow!("Unexpected response type: {:?}", response)),
+        }
+    }
+
+    /// An actor's status in upper case, e.g. `RUNNING`, or `UNKNOWN` if it can't be read
+    pub async fn actor_status_name(&self, actor_id: &TheaterId) -> String {
+        match self.get_actor_status(actor_id).await {
+            Ok(status) => format!("{:?}", status).to_uppercase(),
+            Err(e) => {
+                debug!("Failed to get status of actor {}: {}", actor_id, e);
+                "UNKNOWN".to_string()
+            }
+        }
+    }
+
+        /// Check if an actor exists
     pub async fn actor_exists(&self, actor_id: &TheaterId) -> Result<bool> {
         // Try to get the actor's state to determine if it exists
         match self.get_actor_state(actor_id).await {
             Ok(_) => Ok(true),
+            // Only a running actor has a state to be too large
+            Err(e) if is_too_large(&e) => Ok(true),
             Err(_) => Ok(false),
         }
     }
@@ -311,7 +483,12 @@ impl TheaterClient {
         let response = self.send_command(command).await?;
         
         match response {
-            ManagementResponse::ActorState { id: _, state } => Ok(state),
+            ManagementResponse::ActorState { id: _, state } => {
+                if let Some(state) = &state {
+                    check_size("Actor state", state.len(), self.limits.max_state_bytes)?;
+                }
+                Ok(state)
+            }
             _ => Err(anyhow!("Unexpected response type: {:?}", response)),
         }
     }
@@ -332,6 +509,8 @@ impl TheaterClient {
 
     /// Send a one-way message to an actor
     pub async fn send_message(&self, actor_id: &TheaterId, data: &[u8]) -> Result<()> {
+        check_size("Message", data.len(), self.limits.max_message_bytes)?;
+        
         let command = ManagementCommand::SendActorMessage {
             id: actor_id.clone(),
             data: data.to_vec(),
@@ -347,6 +526,8 @@ impl TheaterClient {
 
     /// Send a request to an actor and receive a response
     pub async fn request_message(&self, actor_id: &TheaterId, data: &[u8]) -> Result<Vec<u8>> {
+        check_size("Request", data.len(), self.limits.max_message_bytes)?;
+        
         let command = ManagementCommand::RequestActorMessage {
             id: actor_id.clone(),
             data: data.to_vec(),
@@ -355,37 +536,114 @@ impl TheaterClient {
         let response = self.send_command(command).await?;
         
         match response {
-            ManagementResponse::RequestedMessage { id: _, message } => Ok(message),
+            ManagementResponse::RequestedMessage { id: _, message } => {
+                check_size("Actor response", message.len(), self.limits.max_message_bytes)?;
+                Ok(message)
+            }
             _ => Err(anyhow!("Unexpected response type: {:?}", response)),
         }
     }
 
-    /// Open a channel to an actor
+    /// Send a JSON request to an actor and parse its JSON reply, for the
+    /// contracts actors answer to export tools, resources and prompts
+    pub async fn request_json(
+        &self,
+        actor_id: &TheaterId,
+        request: &serde_json::Value,
+        timeout: Duration,
+    ) -> Result<serde_json::Value> {
+        let data = serde_json::to_vec(request)?;
+        let reply = tokio::time::timeout(timeout, self.request_message(actor_id, &data))
+            .await
+            .map_err(|_| TheaterError::Timeout(format!(
+                "actor {} did not answer within {} ms",
+                actor_id,
+                timeout.as_millis()
+            )))??;
+        
+        serde_json::from_slice(&reply)
+            .map_err(|e| anyhow!("Actor {} sent a reply that is not JSON: {}", actor_id, e))
+    }
+
+    /// Open a channel to a participant (an actor or an external endpoint).
+    ///
+    /// The channel is opened on a dedicated connection so that messages the
+    /// participant sends back can be read without interleaving with command
+    /// responses on the shared connection.
     pub async fn open_channel(
         &self,
-        actor_id: &str,
+        participant: ChannelParticipant,
         initial_message: Option<&[u8]>,
-    ) -> Result<String> {
-        // Parse actor ID string to TheaterId
-        let actor_id = TheaterId::parse(actor_id)?;
-        let actor_participant = ChannelParticipant::Actor(actor_id);
+    ) -> Result<ChannelStream> {
         let initial_data = initial_message.map(|m| m.to_vec()).unwrap_or_default();
+        check_size("Initial channel message", initial_data.len(), self.limits.max_message_bytes)?;
         
         let command = ManagementCommand::OpenChannel {
-            actor_id: actor_participant,
+            actor_id: participant,
             initial_message: initial_data,
         };
         
-        let response = self.send_command(command).await?;
+        let mut stream = self.connector.connect().await?;
         
-        match response {
-            ManagementResponse::ChannelOpened { channel_id, actor_id: _ } => Ok(channel_id),
-            _ => Err(anyhow!("Unexpected response type: {:?}", response)),
-        }
+        trace!("Opening channel on dedicated connection: {}", self.redaction.show(&command));
+        write_frame(&mut stream, &command).await?;
+        
+        let max_frame = self.limits.max_frame_bytes;
+        let channel_id = match read_frame::<_, ManagementResponse>(&mut stream, max_frame).await? {
+            ManagementResponse::ChannelOpened { channel_id, actor_id: _ } => channel_id,
+            ManagementResponse::Error { message } => {
+                return Err(TheaterError::ServerError(message).into());
+            }
+            response => return Err(anyhow!("Unexpected response type: {:?}", response)),
+        };
+        
+        let (tx, rx) = mpsc::channel(CHANNEL_STREAM_CAPACITY);
+        let reader_channel_id = channel_id.clone();
+        let redaction = self.redaction.clone();
+        let reader = tokio::spawn(async move {
+            loop {
+                let response = match read_frame::<_, ManagementResponse>(&mut stream, max_frame).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        debug!("Channel {} connection ended: {}", reader_channel_id, e);
+                        break;
+                    }
+                };
+                
+                match response {
+                    ManagementResponse::ChannelMessage { channel_id, message } => {
+                        if tx.send(ChannelEvent::Message(message)).await.is_err() {
+                            // Receiver dropped: the channel is no longer wanted
+                            trace!("Dropping reader for channel {}", channel_id);
+                            break;
+                        }
+                    }
+                    ManagementResponse::ChannelClosed { channel_id } => {
+                        debug!("Channel {} closed by Theater", channel_id);
+                        let _ = tx.send(ChannelEvent::Closed).await;
+                        break;
+                    }
+                    ManagementResponse::Error { message } => {
+                        warn!("Theater error on channel {}: {}", reader_channel_id, message);
+                    }
+                    other => {
+                        trace!("Ignoring frame on channel {}: {}", reader_channel_id, redaction.show(&other));
+                    }
+                }
+            }
+        });
+        
+        Ok(ChannelStream {
+            channel_id,
+            messages: rx,
+            reader,
+        })
     }
 
     /// Send a message on an open channel
     pub async fn send_on_channel(&self, channel_id: &str, message: &[u8]) -> Result<()> {
+        check_size("Channel message", message.len(), self.limits.max_message_bytes)?;
+        
         let command = ManagementCommand::SendOnChannel {
             channel_id: channel_id.to_string(),
             message: message.to_vec(),
@@ -412,4 +670,108 @@ impl TheaterClient {
             _ => Err(anyhow!("Unexpected response type: {:?}", response)),
         }
     }
-}
\ No newline at end of file
+    /// Subscribe to an actor's events.
+    ///
+    /// Like channels, subscriptions use a dedicated connection since the
+    /// Theater server pushes events on the connection that subscribed.
+    pub async fn subscribe_to_actor(&self, actor_id: &TheaterId) -> Result<EventSubscription> {
+        let command = ManagementCommand::SubscribeToActor {
+            id: actor_id.clone(),
+        };
+        
+        let mut stream = self.connector.connect().await?;
+        
+        trace!("Subscribing on dedicated connection: {}", self.redaction.show(&command));
+        write_frame(&mut stream, &command).await?;
+        
+        let max_frame = self.limits.max_frame_bytes;
+        match read_frame::<_, ManagementResponse>(&mut stream, max_frame).await? {
+            ManagementResponse::Subscribed { id: _, subscription_id } => {
+                debug!("Subscribed to actor {} ({})", actor_id, subscription_id);
+            }
+            ManagementResponse::Error { message } => {
+                return Err(TheaterError::ServerError(message).into());
+            }
+            response => return Err(anyhow!("Unexpected response type: {:?}", response)),
+        }
+        
+        let (tx, rx) = mpsc::channel(EVENT_STREAM_CAPACITY);
+        let reader_actor_id = actor_id.clone();
+        let redaction = self.redaction.clone();
+        tokio::spawn(async move {
+            loop {
+                let response = match read_frame::<_, ManagementResponse>(&mut stream, max_frame).await {
+                    Ok(response) => response,
+                    Err(e) => {
+                        debug!("Event subscription for {} ended: {}", reader_actor_id, e);
+                        break;
+                    }
+                };
+                
+                match response {
+                    ManagementResponse::ActorEvent { event } => {
+                        if tx.send(event).await.is_err() {
+                            // Receiver dropped: nobody is listening anymore
+                            break;
+                        }
+                    }
+                    ManagementResponse::Error { message } => {
+                        warn!("Theater error on subscription for {}: {}", reader_actor_id, message);
+                    }
+                    other => {
+                        trace!("Ignoring frame on subscription for {}: {}", reader_actor_id, redaction.show(&other));
+                    }
+                }
+            }
+        });
+        
+        Ok(EventSubscription {
+            actor_id: actor_id.clone(),
+            events: rx,
+        })
+    }
+}
+
+/// Whether an error is an oversized response rather than a connection problem
+fn is_too_large(error: &anyhow::Error) -> bool {
+    matches!(error.downcast_ref::<TheaterError>(), Some(TheaterError::TooLarge(..)))
+}
+
+/// Whether Theater reported that the thing a command named doesn't exist
+pub fn is_not_found(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<TheaterError>(),
+        Some(TheaterError::ServerError(message)) if message.to_lowercase().contains("not found")
+    )
+}
+
+/// Whether the server answered, but with a response that can't be used
+fn is_bad_response(error: &anyhow::Error) -> bool {
+    is_too_large(error) || matches!(error.downcast_ref::<TheaterError>(), Some(TheaterError::SerializationError(_)))
+}
+
+/// Marks an error from before a command was completely written, so the
+/// server can't have acted on it
+#[derive(Debug)]
+struct Unsent;
+
+impl std::fmt::Display for Unsent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "command was not sent")
+    }
+}
+
+fn is_unsent(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<Unsent>().is_some()
+}
+
+/// Whether a command only reads, so repeating it is harmless
+fn is_read_only(command: &ManagementCommand) -> bool {
+    matches!(
+        command,
+        ManagementCommand::ListActors
+            | ManagementCommand::GetActorStatus { .. }
+            | ManagementCommand::GetActorState { .. }
+            | ManagementCommand::GetActorEvents { .. }
+    )
+}
@@ -0,0 +1,116 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use theater::id::TheaterId;
+use theater::theater_server::{ManagementCommand, ManagementResponse};
+
+use crate::theater::client::TheaterClient;
+use crate::theater::TheaterIdExt;
+
+/// How long each compatibility check waits for the server
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest excerpt of an undecodable response quoted in errors
+const MAX_QUOTED_BYTES: usize = 200;
+
+/// What to do when the Theater server answers in a way this build can't decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityMode {
+    /// Refuse to start
+    #[default]
+    Strict,
+    /// Log the problem and start anyway; affected tools fail when called
+    Warn,
+}
+
+impl std::str::FromStr for CompatibilityMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "strict" => Ok(CompatibilityMode::Strict),
+            "warn" => Ok(CompatibilityMode::Warn),
+            other => Err(anyhow!(
+                "Unknown compatibility mode '{}' (expected 'strict' or 'warn')",
+                other
+            )),
+        }
+    }
+}
+
+/// Check that the Theater server speaks the management protocol this build
+/// was compiled against.
+///
+/// Sends `ListActors` and a `GetActorStatus` for an actor that doesn't exist,
+/// and checks that both answers decode as this build's responses. A server
+/// from another Theater release fails here with the response it sent,
+/// rather than with "unexpected response type" errors later in a session.
+pub async fn check_compatibility(client: &TheaterClient, mode: CompatibilityMode) -> Result<()> {
+    let missing = TheaterId::from_str(&Uuid::new_v4().to_string())?;
+    let checks = [
+        ("ListActors", ManagementCommand::ListActors),
+        ("GetActorStatus", ManagementCommand::GetActorStatus { id: missing }),
+    ];
+
+    let mut problems = Vec::new();
+    for (name, command) in checks {
+        let reply = match tokio::time::timeout(CHECK_TIMEOUT, client.probe_command(&command)).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(e)) => return Err(anyhow!("Could not reach the Theater server to check compatibility: {}", e)),
+            Err(_) => {
+                problems.push(format!("did not answer {} within {:?}", name, CHECK_TIMEOUT));
+                continue;
+            }
+        };
+        if let Err(problem) = check_response(name, reply.as_ref()) {
+            problems.push(problem);
+        }
+    }
+
+    if problems.is_empty() {
+        info!("Theater server speaks a compatible management protocol");
+        return Ok(());
+    }
+
+    let message = format!(
+        "The Theater server is not compatible with this build of theater-mcp, which was built against a different Theater release: it {}. Rebuild theater-mcp against the Theater release the server runs, or start it with the compatibility mode set to 'warn' to continue anyway.",
+        problems.join("; it ")
+    );
+    match mode {
+        CompatibilityMode::Strict => Err(anyhow!(message)),
+        CompatibilityMode::Warn => {
+            warn!("{}", message);
+            Ok(())
+        }
+    }
+}
+
+/// Check that the raw reply to a command decodes as one of this build's responses
+pub(crate) fn check_response(command: &str, reply: Option<&Value>) -> std::result::Result<(), String> {
+    let Some(reply) = reply else {
+        return Err(format!(
+            "closed the connection instead of answering {}, so it may not be a Theater server or frames messages differently",
+            command
+        ));
+    };
+
+    match serde_json::from_value::<ManagementResponse>(reply.clone()) {
+        Ok(ManagementResponse::ActorList { .. }) if command == "ListActors" => Ok(()),
+        Ok(_) if command != "ListActors" => Ok(()),
+        Ok(other) => Err(format!("answered {} with an unexpected response: {:?}", command, other)),
+        Err(e) => {
+            let mut quoted = reply.to_string();
+            if quoted.len() > MAX_QUOTED_BYTES {
+                let end = (0..=MAX_QUOTED_BYTES).rev().find(|i| quoted.is_char_boundary(*i)).unwrap_or(0);
+                quoted.truncate(end);
+                quoted.push_str("...");
+            }
+            Err(format!("answered {} with a response this build can't decode ({}): {}", command, e, quoted))
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+
+/// TLS settings for connections to the Theater server.
+///
+/// The server is verified against `ca_file`. Setting `cert_file` and
+/// `key_file` also presents a client certificate, for servers that require
+/// mutual TLS.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// PEM file of the CA certificates the Theater server's certificate must chain to
+    pub ca_file: Option<PathBuf>,
+    /// PEM file of the client certificate chain presented to the server
+    pub cert_file: Option<PathBuf>,
+    /// PEM file of the client certificate's private key
+    pub key_file: Option<PathBuf>,
+    /// Name to verify the server certificate against; defaults to the address's IP
+    pub server_name: Option<String>,
+}
+
+/// A connection to the Theater server, over plain TCP or TLS
+#[derive(Debug)]
+pub enum TheaterStream {
+    Tcp(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<tokio_rustls::client::TlsStream<TcpStream>>),
+}
+
+/// Opens connections to the Theater server
+#[derive(Clone)]
+pub struct Connector {
+    address: SocketAddr,
+    #[cfg(feature = "tls")]
+    tls: Option<tls::TlsSettings>,
+}
+
+impl Connector {
+    /// Connect over plain TCP
+    pub fn plain(address: SocketAddr) -> Self {
+        Self {
+            address,
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Connect over TLS, presenting a client certificate if one is configured
+    #[cfg(feature = "tls")]
+    pub fn tls(address: SocketAddr, config: &TlsConfig) -> Result<Self> {
+        Ok(Self {
+            address,
+            tls: Some(tls::TlsSettings::load(address, config)?),
+        })
+    }
+
+    /// Connect over TLS (unavailable: built without the `tls` feature)
+    #[cfg(not(feature = "tls"))]
+    pub fn tls(_address: SocketAddr, _config: &TlsConfig) -> Result<Self> {
+        Err(anyhow!(
+            "TLS to the Theater server was configured, but this build lacks the `tls` feature"
+        ))
+    }
+
+    /// Address of the Theater server
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Open a new connection
+    pub async fn connect(&self) -> Result<TheaterStream> {
+        let stream = TcpStream::connect(self.address)
+            .await
+            .map_err(|e| anyhow!("Failed to connect to Theater server: {}", e))?;
+
+        #[cfg(feature = "tls")]
+        if let Some(tls) = &self.tls {
+            let stream = tls
+                .connector
+                .connect(tls.server_name.clone(), stream)
+                .await
+                .map_err(|e| anyhow!("TLS handshake with Theater server failed: {}", e))?;
+            return Ok(TheaterStream::Tls(Box::new(stream)));
+        }
+
+        Ok(TheaterStream::Tcp(stream))
+    }
+}
+
+impl std::fmt::Debug for Connector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Connector");
+        debug.field("address", &self.address);
+        #[cfg(feature = "tls")]
+        debug.field("tls", &self.tls.is_some());
+        debug.finish()
+    }
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use anyhow::{anyhow, Context, Result};
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::net::SocketAddr;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tokio_rustls::rustls::crypto::ring::default_provider;
+    use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+    use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+    use tokio_rustls::TlsConnector;
+
+    use super::TlsConfig;
+
+    #[derive(Clone)]
+    pub(super) struct TlsSettings {
+        pub(super) connector: TlsConnector,
+        pub(super) server_name: ServerName<'static>,
+    }
+
+    impl TlsSettings {
+        pub(super) fn load(address: SocketAddr, config: &TlsConfig) -> Result<Self> {
+            let ca_file = config
+                .ca_file
+                .as_deref()
+                .ok_or_else(|| anyhow!("TLS to the Theater server needs a CA file to verify it"))?;
+            let mut roots = RootCertStore::empty();
+            for cert in read_certs(ca_file)? {
+                roots.add(cert)?;
+            }
+
+            let builder = ClientConfig::builder_with_provider(Arc::new(default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_root_certificates(roots);
+            let client_config = match (&config.cert_file, &config.key_file) {
+                (Some(cert_file), Some(key_file)) => {
+                    builder.with_client_auth_cert(read_certs(cert_file)?, read_key(key_file)?)?
+                }
+                (None, None) => builder.with_no_client_auth(),
+                _ => return Err(anyhow!("A client certificate needs both a cert file and a key file")),
+            };
+
+            let server_name = match &config.server_name {
+                Some(name) => ServerName::try_from(name.clone())?,
+                None => ServerName::from(address.ip()),
+            };
+
+            Ok(Self {
+                connector: TlsConnector::from(Arc::new(client_config)),
+                server_name,
+            })
+        }
+    }
+
+    fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        rustls_pemfile::certs(&mut BufReader::new(file))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Invalid certificates in {}", path.display()))
+    }
+
+    fn read_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        rustls_pemfile::private_key(&mut BufReader::new(file))
+            .with_context(|| format!("Invalid private key in {}", path.display()))?
+            .ok_or_else(|| anyhow!("No private key found in {}", path.display()))
+    }
+}
+
+impl AsyncRead for TheaterStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TheaterStream::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(feature = "tls")]
+            TheaterStream::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for TheaterStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            TheaterStream::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(feature = "tls")]
+            TheaterStream::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TheaterStream::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(feature = "tls")]
+            TheaterStream::Tls(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            TheaterStream::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            #[cfg(feature = "tls")]
+            TheaterStream::Tls(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! A Theater runtime hosted inside this process.
+//!
+//! Theater only serves its management protocol over TCP, so the embedded
+//! server listens on a free loopback port and the regular client connects to
+//! it there. Nothing has to be launched separately, which is all single-binary
+//! demos and tests need.
+
+use anyhow::{anyhow, Result};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use theater::theater_server::TheaterServer;
+
+/// How long to wait for the embedded server to start accepting connections
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A Theater server running on this process's runtime, stopped when dropped
+pub struct EmbeddedTheater {
+    address: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl EmbeddedTheater {
+    /// Start a Theater server on a free loopback port and wait until it accepts connections
+    pub async fn start() -> Result<Self> {
+        // Theater binds its own listener, so find a free port for it first
+        let address = std::net::TcpListener::bind("127.0.0.1:0")?.local_addr()?;
+
+        let server = TheaterServer::new(address).await?;
+        let task = tokio::spawn(async move {
+            if let Err(e) = server.run().await {
+                error!("Embedded Theater server stopped: {}", e);
+            }
+        });
+
+        let ready = async {
+            while TcpStream::connect(address).await.is_err() {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        };
+        if tokio::time::timeout(STARTUP_TIMEOUT, ready).await.is_err() {
+            task.abort();
+            return Err(anyhow!(
+                "Embedded Theater server did not start listening on {} within {:?}",
+                address,
+                STARTUP_TIMEOUT
+            ));
+        }
+
+        info!("Started embedded Theater server on {}", address);
+        Ok(Self { address, task })
+    }
+
+    /// Loopback address the embedded server listens on
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl Drop for EmbeddedTheater {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
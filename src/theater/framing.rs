@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::theater::limits::check_size;
+use crate::theater::types::TheaterError;
+
+/// Write a length-prefixed JSON frame.
+///
+/// The value is serialized straight into the frame buffer behind a
+/// placeholder length, so each frame is built and written in one piece.
+/// Byte fields are encoded however the Theater protocol types declare them.
+pub async fn write_frame<W, T>(writer: &mut W, value: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut frame = vec![0u8; 4];
+    serde_json::to_writer(&mut frame, value)?;
+    let len = u32::try_from(frame.len() - 4)
+        .map_err(|_| anyhow!("Frame too large to send: {} bytes", frame.len() - 4))?;
+    frame[..4].copy_from_slice(&len.to_be_bytes());
+
+    writer.write_all(&frame).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read a length-prefixed JSON frame, refusing frames over `max_len` bytes
+/// before allocating for them
+pub async fn read_frame<R, T>(reader: &mut R, max_len: usize) -> Result<T>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut len_buf = [0u8; 4];
+    reader
+        .read_exact(&mut len_buf)
+        .await
+        .map_err(|e| anyhow!("Failed to read frame length: {}", e))?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    check_size("Theater response frame", len, max_len)?;
+
+    let mut frame = vec![0u8; len];
+    reader
+        .read_exact(&mut frame)
+        .await
+        .map_err(|e| anyhow!("Failed to read frame payload: {}", e))?;
+
+    // The whole frame was read, so the stream is still in step
+    serde_json::from_slice(&frame)
+        .map_err(|e| TheaterError::SerializationError(format!("Failed to parse frame: {}", e)).into())
+}
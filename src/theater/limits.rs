@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::theater::types::TheaterError;
+
+/// Size limits on data exchanged with the Theater server.
+///
+/// Frames are length-prefixed, so without a cap a single corrupt or hostile
+/// prefix could make the server allocate gigabytes before reading anything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SizeLimits {
+    /// Largest response frame read from the Theater server
+    pub max_frame_bytes: usize,
+    /// Largest actor state sent at start or returned by get_actor_state
+    pub max_state_bytes: usize,
+    /// Largest message payload sent to or received from an actor
+    pub max_message_bytes: usize,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            max_frame_bytes: 64 * 1024 * 1024,
+            max_state_bytes: 16 * 1024 * 1024,
+            max_message_bytes: 4 * 1024 * 1024,
+        }
+    }
+}
+
+/// Fail with [`TheaterError::TooLarge`] if `size` exceeds `limit`
+pub fn check_size(what: &str, size: usize, limit: usize) -> Result<(), TheaterError> {
+    if size > limit {
+        return Err(TheaterError::TooLarge(what.to_string(), size, limit));
+    }
+    Ok(())
+}
@@ -1,5 +1,14 @@
 // Original implementations
 pub mod client;
+pub mod compat;
+pub mod connector;
+#[cfg(feature = "embedded-theater")]
+pub mod embedded;
+pub mod framing;
+pub mod limits;
+pub mod pool;
+pub mod probe;
+pub mod redact;
 pub mod types;
 
 // Tests
@@ -12,6 +21,14 @@ pub use theater::chain::ChainEvent;
 pub use theater::id::TheaterId;
 pub use theater::messages::ActorStatus;
 
+pub use compat::CompatibilityMode;
+pub use connector::{Connector, TlsConfig};
+#[cfg(feature = "embedded-theater")]
+pub use embedded::EmbeddedTheater;
+pub use limits::SizeLimits;
+pub use probe::TheaterFeatures;
+pub use redact::Redaction;
+
 // Re-export our extension trait
 pub use types::{TheaterError, TheaterIdExt};
 
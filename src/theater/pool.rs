@@ -0,0 +1,309 @@
+use anyhow::{anyhow, Result};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{ReadHalf, WriteHalf};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use theater::theater_server::{ManagementCommand, ManagementResponse};
+
+use crate::theater::connector::{Connector, TheaterStream};
+use crate::theater::framing::{read_frame, write_frame};
+use crate::theater::limits::SizeLimits;
+use crate::theater::types::TheaterError;
+
+/// Connections kept to the Theater server for commands
+pub const DEFAULT_POOL_SIZE: usize = 8;
+
+/// How long a connection may sit idle before it is closed rather than reused
+pub const DEFAULT_MAX_IDLE: Duration = Duration::from_secs(60);
+
+/// A bounded pool of pipelined command connections to the Theater server.
+///
+/// Theater answers commands in order on each connection and doesn't tag
+/// responses, so each connection keeps the commands written to it in a
+/// FIFO and a background task hands every response to the oldest one.
+/// Commands therefore never wait for another command's response before
+/// being written. A slow command still holds up the responses behind it on
+/// its connection, so idle connections are preferred, new ones are opened
+/// up to the pool size, and only then do commands queue behind the least
+/// busy connection.
+///
+/// Connections idle for longer than the pool's maximum idle time are closed
+/// when next looked at instead of reused, since servers, proxies and NAT
+/// tables tend to drop quiet connections without the client noticing.
+#[derive(Debug)]
+pub struct ConnectionPool {
+    connector: Connector,
+    // The connection made while connecting, until the first command uses it
+    initial: Mutex<Option<(TheaterStream, Instant)>>,
+    connections: tokio::sync::Mutex<Vec<Arc<PipelinedConnection>>>,
+    size: usize,
+    max_idle: Duration,
+    max_frame_bytes: usize,
+}
+
+/// A command connection that carries several commands at once.
+///
+/// Dropping it stops its reader, closing the connection.
+#[derive(Debug)]
+pub struct PipelinedConnection {
+    writer: tokio::sync::Mutex<WriteHalf<TheaterStream>>,
+    shared: Arc<Shared>,
+    reader: JoinHandle<()>,
+}
+
+/// A written command waiting for its response
+pub struct PendingResponse(oneshot::Receiver<Result<ManagementResponse>>);
+
+/// State shared between a connection's writers and its reader
+#[derive(Debug)]
+struct Shared {
+    queue: Mutex<Queue>,
+}
+
+#[derive(Debug)]
+struct Queue {
+    // Commands written and not yet answered, oldest first
+    waiting: VecDeque<oneshot::Sender<Result<ManagementResponse>>>,
+    closed: bool,
+    last_used: Instant,
+}
+
+impl ConnectionPool {
+    /// Create a pool, seeded with an already established connection
+    pub fn new(connector: Connector, size: usize, initial: TheaterStream) -> Self {
+        Self {
+            connector,
+            initial: Mutex::new(Some((initial, Instant::now()))),
+            connections: tokio::sync::Mutex::new(Vec::new()),
+            size: size.max(1),
+            max_idle: DEFAULT_MAX_IDLE,
+            max_frame_bytes: SizeLimits::default().max_frame_bytes,
+        }
+    }
+
+    /// Close connections that were idle longer than this instead of reusing them
+    pub fn with_max_idle(mut self, max_idle: Duration) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Refuse responses larger than this, closing the connection they came on
+    pub fn with_max_frame_bytes(mut self, max_frame_bytes: usize) -> Self {
+        self.max_frame_bytes = max_frame_bytes;
+        self
+    }
+
+    /// Maximum number of connections
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Open connections with no commands in flight
+    pub async fn idle_count(&self) -> usize {
+        self.connections
+            .lock()
+            .await
+            .iter()
+            .filter(|connection| connection.in_flight() == 0)
+            .count()
+    }
+
+    /// Pick a connection for a command: an idle one, else a new one while
+    /// the pool has room, else the one with the fewest commands in flight.
+    /// Returns whether the connection is newly opened.
+    pub async fn acquire(&self) -> Result<(Arc<PipelinedConnection>, bool)> {
+        let mut connections = self.connections.lock().await;
+        connections.retain(|connection| connection.is_usable(self.max_idle));
+
+        if let Some(idle) = connections.iter().find(|connection| connection.in_flight() == 0) {
+            return Ok((idle.clone(), false));
+        }
+
+        if connections.len() < self.size {
+            let (stream, fresh) = match self.take_initial() {
+                Some(stream) => (stream, false),
+                None => (self.connect().await?, true),
+            };
+            let connection = Arc::new(PipelinedConnection::new(stream, self.max_frame_bytes));
+            connections.push(connection.clone());
+            return Ok((connection, fresh));
+        }
+
+        connections
+            .iter()
+            .min_by_key(|connection| connection.in_flight())
+            .cloned()
+            .map(|connection| (connection, false))
+            .ok_or_else(|| anyhow!("Theater connection pool is empty"))
+    }
+
+    /// Open a new connection, e.g. to retry a command after a reused
+    /// connection turned out to be dead. It joins the pool if there's room.
+    pub async fn acquire_new(&self) -> Result<Arc<PipelinedConnection>> {
+        let connection = Arc::new(PipelinedConnection::new(self.connect().await?, self.max_frame_bytes));
+        let mut connections = self.connections.lock().await;
+        connections.retain(|connection| connection.is_usable(self.max_idle));
+        if connections.len() < self.size {
+            connections.push(connection.clone());
+        }
+        Ok(connection)
+    }
+
+    /// Stop handing out the open connections, e.g. after the server went
+    /// away. Commands already in flight on them still get their responses.
+    pub async fn clear(&self) {
+        self.connections.lock().await.clear();
+        self.initial.lock().unwrap().take();
+    }
+
+    async fn connect(&self) -> Result<TheaterStream> {
+        debug!("Opening pooled connection to Theater server at {}", self.connector.address());
+        self.connector.connect().await
+    }
+
+    /// The seeded connection, unless it was idle too long
+    fn take_initial(&self) -> Option<TheaterStream> {
+        let (stream, since) = self.initial.lock().unwrap().take()?;
+        if since.elapsed() > self.max_idle {
+            debug!("Closed the initial Theater connection, idle for over {:?}", self.max_idle);
+            return None;
+        }
+        Some(stream)
+    }
+}
+
+impl PipelinedConnection {
+    /// Start reading responses from a connection
+    pub fn new(stream: TheaterStream, max_frame_bytes: usize) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue {
+                waiting: VecDeque::new(),
+                closed: false,
+                last_used: Instant::now(),
+            }),
+        });
+        Self {
+            writer: tokio::sync::Mutex::new(writer),
+            shared: shared.clone(),
+            reader: tokio::spawn(read_responses(reader, shared, max_frame_bytes)),
+        }
+    }
+
+    /// Write a command, returning once it is on the wire.
+    ///
+    /// An error means the command was not (completely) written, so the
+    /// server can't have acted on it.
+    pub async fn submit(&self, command: &ManagementCommand) -> Result<PendingResponse> {
+        let (sender, receiver) = oneshot::channel();
+        let mut writer = self.writer.lock().await;
+
+        // Queue before writing, under the write lock, so the queue is in the
+        // order commands go out and the response can't arrive unclaimed
+        {
+            let mut queue = self.shared.queue.lock().unwrap();
+            if queue.closed {
+                return Err(anyhow!("Theater connection is closed"));
+            }
+            queue.waiting.push_back(sender);
+            queue.last_used = Instant::now();
+        }
+
+        // A write abandoned halfway leaves the connection mid-frame
+        let mut guard = CloseUnlessWritten { shared: &self.shared, written: false };
+        write_frame(&mut *writer, command).await?;
+        guard.written = true;
+        Ok(PendingResponse(receiver))
+    }
+
+    /// Commands written and not yet answered
+    pub fn in_flight(&self) -> usize {
+        self.shared.queue.lock().unwrap().waiting.len()
+    }
+
+    /// Whether the connection can take more commands
+    fn is_usable(&self, max_idle: Duration) -> bool {
+        let queue = self.shared.queue.lock().unwrap();
+        !queue.closed && (!queue.waiting.is_empty() || queue.last_used.elapsed() <= max_idle)
+    }
+}
+
+impl Drop for PipelinedConnection {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+impl PendingResponse {
+    /// Wait for the command's response
+    pub async fn response(self) -> Result<ManagementResponse> {
+        self.0
+            .await
+            .unwrap_or_else(|_| Err(anyhow!("Theater connection closed before the response arrived")))
+    }
+}
+
+impl Shared {
+    /// Refuse new commands and fail the ones still waiting
+    fn close(&self, reason: &str) {
+        let waiting = {
+            let mut queue = self.queue.lock().unwrap();
+            queue.closed = true;
+            std::mem::take(&mut queue.waiting)
+        };
+        for sender in waiting {
+            let _ = sender.send(Err(anyhow!("{}", reason)));
+        }
+    }
+}
+
+/// Closes the connection unless the command finished writing, e.g. when
+/// the write fails or the command is cancelled partway through
+struct CloseUnlessWritten<'a> {
+    shared: &'a Shared,
+    written: bool,
+}
+
+impl Drop for CloseUnlessWritten<'_> {
+    fn drop(&mut self) {
+        if !self.written {
+            self.shared.close("Theater connection closed after a command failed to write");
+        }
+    }
+}
+
+/// Hand each response to the oldest command waiting on the connection
+async fn read_responses(mut reader: ReadHalf<TheaterStream>, shared: Arc<Shared>, max_frame_bytes: usize) {
+    loop {
+        let result = read_frame::<_, ManagementResponse>(&mut reader, max_frame_bytes).await;
+        // A response read whole that didn't parse leaves the stream in step
+        let in_step = match &result {
+            Ok(_) => true,
+            Err(e) => matches!(e.downcast_ref::<TheaterError>(), Some(TheaterError::SerializationError(_))),
+        };
+
+        let waiting = {
+            let mut queue = shared.queue.lock().unwrap();
+            queue.last_used = Instant::now();
+            queue.waiting.pop_front()
+        };
+        match waiting {
+            Some(sender) => {
+                let _ = sender.send(result);
+            }
+            None if result.is_ok() => {
+                debug!("Theater sent a response no command was waiting for; closing the connection");
+                break;
+            }
+            None => break,
+        }
+        if !in_step {
+            break;
+        }
+    }
+    shared.close("Theater connection closed before the response arrived");
+}
@@ -0,0 +1,115 @@
+use anyhow::Result;
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use theater::id::TheaterId;
+use theater::messages::ChannelParticipant;
+use theater::theater_server::ManagementCommand;
+
+use crate::theater::client::TheaterClient;
+use crate::theater::TheaterIdExt;
+
+/// How long a single probe waits for the server before assuming the command works
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Groups of management commands the connected Theater server supports.
+///
+/// Older Theater releases lack some commands. Probing for them at startup
+/// lets the server leave out tools and resources that could only fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TheaterFeatures {
+    /// `GetActorEvents`: event history resources and tools
+    pub actor_events: bool,
+    /// `SendActorMessage` and `RequestActorMessage`: message tools
+    pub messages: bool,
+    /// `OpenChannel` and friends: channel tools
+    pub channels: bool,
+    /// `SubscribeToActor`: live event streaming
+    pub subscriptions: bool,
+}
+
+impl Default for TheaterFeatures {
+    fn default() -> Self {
+        Self {
+            actor_events: true,
+            messages: true,
+            channels: true,
+            subscriptions: true,
+        }
+    }
+}
+
+impl TheaterFeatures {
+    /// Try each command group against the server with an actor ID that
+    /// doesn't exist. A supported command fails with "not found" or similar;
+    /// an unsupported one is rejected as unknown or drops the connection.
+    pub async fn probe(client: &TheaterClient) -> Self {
+        let missing = || TheaterId::from_str(&Uuid::new_v4().to_string()).ok();
+        let Some(id) = missing() else {
+            return Self::default();
+        };
+
+        let (actor_events, messages, channels, subscriptions) = tokio::join!(
+            supports(client, ManagementCommand::GetActorEvents { id: id.clone() }),
+            supports(client, ManagementCommand::SendActorMessage { id: id.clone(), data: Vec::new() }),
+            supports(client, ManagementCommand::OpenChannel {
+                actor_id: ChannelParticipant::Actor(id.clone()),
+                initial_message: Vec::new(),
+            }),
+            supports(client, ManagementCommand::SubscribeToActor { id }),
+        );
+        let features = Self {
+            actor_events,
+            messages,
+            channels,
+            subscriptions,
+        };
+
+        if features == Self::default() {
+            info!("Theater server supports every probed command");
+        } else {
+            warn!("Theater server lacks some commands; related tools are disabled: {:?}", features);
+        }
+        features
+    }
+}
+
+/// Whether the server understood a probe command
+async fn supports(client: &TheaterClient, command: ManagementCommand) -> bool {
+    let name = format!("{:?}", command);
+    let name = name.split([' ', '{', '(']).next().unwrap_or_default().to_string();
+    let outcome: Result<Option<serde_json::Value>> =
+        match tokio::time::timeout(PROBE_TIMEOUT, client.probe_command(&command)).await {
+            Ok(outcome) => outcome,
+            // Slow, but not refused
+            Err(_) => return true,
+        };
+
+    match outcome {
+        Ok(Some(response)) => match response["Error"]["message"].as_str() {
+            Some(message) if is_unknown_command(message) => {
+                debug!("Theater server does not support {}: {}", name, message);
+                false
+            }
+            _ => true,
+        },
+        Ok(None) => {
+            debug!("Theater server closed the connection on {}", name);
+            false
+        }
+        Err(e) => {
+            // Couldn't reach the server at all; assume support rather than disabling tools
+            debug!("Could not probe {}: {}", name, e);
+            true
+        }
+    }
+}
+
+fn is_unknown_command(message: &str) -> bool {
+    let message = message.to_lowercase();
+    ["unknown variant", "unknown command", "unsupported", "not supported", "not implemented"]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// What replaces the value of a masked field
+const MASK: &str = "[REDACTED]";
+
+/// Masks secrets and truncates large payloads in logged commands and responses.
+///
+/// Values are rendered as JSON. Fields whose name contains one of `fields`
+/// (ignoring case) are masked wherever they appear, including inside byte
+/// payloads that hold JSON, such as message data and initial state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Redaction {
+    /// Field names to mask, matched case-insensitively anywhere in the name
+    pub fields: Vec<String>,
+    /// Byte payloads and strings longer than this are cut short
+    pub max_logged_bytes: usize,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self {
+            fields: ["password", "secret", "token", "api_key", "apikey", "authorization", "credential", "private_key"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            max_logged_bytes: 256,
+        }
+    }
+}
+
+impl Redaction {
+    /// Wrap a value so it is redacted when formatted, only if it is logged
+    pub fn show<'a, T: Serialize>(&'a self, value: &'a T) -> Redacted<'a, T> {
+        Redacted { redaction: self, value }
+    }
+
+    /// Redact a JSON value
+    pub fn redact(&self, value: Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.into_iter()
+                    .map(|(key, value)| {
+                        if self.is_secret(&key) {
+                            (key, Value::String(MASK.to_string()))
+                        } else {
+                            (key, self.redact(value))
+                        }
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => match as_bytes(&items) {
+                Some(bytes) => Value::String(self.describe_bytes(&bytes)),
+                None => Value::Array(items.into_iter().map(|item| self.redact(item)).collect()),
+            },
+            Value::String(text) => Value::String(self.truncate(&text)),
+            other => other,
+        }
+    }
+
+    fn is_secret(&self, key: &str) -> bool {
+        let key = key.to_lowercase();
+        self.fields.iter().any(|field| key.contains(&field.to_lowercase()))
+    }
+
+    /// Render a byte payload, redacting it first if it holds JSON
+    fn describe_bytes(&self, bytes: &[u8]) -> String {
+        let preview = match serde_json::from_slice::<Value>(bytes) {
+            Ok(json) => self.redact(json).to_string(),
+            Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+        };
+        format!("<{} bytes: {}>", bytes.len(), self.truncate(&preview))
+    }
+
+    fn truncate(&self, text: &str) -> String {
+        if text.len() <= self.max_logged_bytes {
+            return text.to_string();
+        }
+        let mut end = self.max_logged_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}... ({} bytes)", &text[..end], text.len())
+    }
+}
+
+/// Byte payloads serialize as arrays of numbers
+fn as_bytes(items: &[Value]) -> Option<Vec<u8>> {
+    if items.is_empty() {
+        return None;
+    }
+    items
+        .iter()
+        .map(|item| item.as_u64().and_then(|n| u8::try_from(n).ok()))
+        .collect()
+}
+
+/// A value formatted through a [`Redaction`]
+pub struct Redacted<'a, T> {
+    redaction: &'a Redaction,
+    value: &'a T,
+}
+
+impl<T: Serialize> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match serde_json::to_value(self.value) {
+            Ok(value) => write!(f, "{}", self.redaction.redact(value)),
+            Err(_) => write!(f, "<unserializable>"),
+        }
+    }
+}
@@ -25,6 +25,38 @@ pub enum TheaterError {
     /// Channel not found
     #[error("Channel not found: {0}")]
     ChannelNotFound(String),
+    
+    /// Channel lost with the Theater connection and could not be re-established
+    #[error("Channel {0} was lost when the Theater connection dropped and could not be reopened ({1}). Open a new channel with open_channel.")]
+    ChannelInvalidated(String, String),
+    
+    /// The calling session is not allowed to perform the operation
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    
+    /// The tool policy requires the user to approve the call first
+    #[error("{0} requires confirmation: ask the user to approve this call, then repeat it with \"confirmed\": true")]
+    ConfirmationRequired(String),
+    
+    /// Starting another actor would exceed the configured actor quota
+    #[error("Actor quota exceeded: {0}. Stop actors that are no longer needed with stop_actor")]
+    QuotaExceeded(String),
+    
+    /// A manifest failed validation before it was sent to Theater
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(String),
+    
+    /// Data exceeded a configured size limit
+    #[error("{0} is {1} bytes, over the {2} byte limit")]
+    TooLarge(String, usize, usize),
+    
+    /// A request was rejected to shed load; the caller may retry after the given milliseconds
+    #[error("Server busy: {0}. Retry after {1} ms")]
+    RateLimited(String, u64),
+    
+    /// An operation did not complete in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
 }
 
 /// Actor status (re-exported from Theater)
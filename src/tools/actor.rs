@@ -1,131 +1,319 @@
 use anyhow::{anyhow, Result};
-use mcp_protocol::types::tool::{Tool, ToolCallResult, ToolContent};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
 use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing::{error, warn};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::warn;
 
 use theater::id::TheaterId;
+use crate::actors::ActorRegistry;
+use crate::events::filter::glob_match;
+use crate::events::{CausalityTracker, EventStreamHub};
+use crate::logging::{ClientLog, ClientLogLevel};
+use crate::session::SessionId;
 use crate::theater::client::TheaterClient;
+use crate::theater::limits::check_size;
 use crate::theater::TheaterIdExt;
-use crate::tools::utils::register_async_tool;
+use crate::tools::confirm::ConfirmationTokens;
+use crate::tools::manifest::{
+    component_problem, is_toml_path, manifest_problems, validate_manifest_content, ManifestAllowlist,
+};
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Maximum post-start tasks running at once across all actor starts
+const POST_START_CONCURRENCY: usize = 4;
+
+/// How long start_actor waits for post-start tasks before reporting them as incomplete
+const POST_START_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Actors list_actors returns when no limit is given
+const DEFAULT_LIST_LIMIT: usize = 50;
 
 pub struct ActorTools {
     theater_client: Arc<TheaterClient>,
-    resource_manager: Option<Arc<mcp_server::resources::ResourceManager>>,
-    actor_resources: Option<Arc<crate::resources::ActorResources>>,
-    event_resources: Option<Arc<crate::resources::EventResources>>,
+    actor_registry: Arc<ActorRegistry>,
+    session_id: SessionId,
+    causality: Option<Arc<CausalityTracker>>,
+    event_stream: Option<Arc<EventStreamHub>>,
+    post_start_permits: Arc<Semaphore>,
+    manifest_allowlist: Option<ManifestAllowlist>,
+    owned_by_default: bool,
+    confirmations: ConfirmationTokens,
+    client_log: Option<Arc<ClientLog>>,
 }
 
 impl ActorTools {
-    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+    pub fn new(
+        theater_client: Arc<TheaterClient>,
+        actor_registry: Arc<ActorRegistry>,
+        session_id: SessionId,
+    ) -> Self {
         Self {
             theater_client,
-            resource_manager: None,
-            actor_resources: None,
-            event_resources: None,
+            actor_registry,
+            session_id,
+            causality: None,
+            event_stream: None,
+            post_start_permits: Arc::new(Semaphore::new(POST_START_CONCURRENCY)),
+            manifest_allowlist: None,
+            owned_by_default: false,
+            confirmations: ConfirmationTokens::new(),
+            client_log: None,
         }
     }
     
-    /// Helper method to handle Theater connection errors
-    fn handle_connection_error<T>(&self, result: Result<T>, context: &str) -> Result<T> {
-        match result {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("connect") || error_msg.contains("connection") || 
-                   error_msg.contains("read") || error_msg.contains("write") {
-                    // This is likely a connection issue
-                    warn!("Theater connection issue during {}: {}. Will attempt reconnection on next request.", context, error_msg);
-                    Err(anyhow!("Theater server connection issue: {}. The server will attempt to reconnect on the next request.", error_msg))
-                } else {
-                    // Other type of error
-                    Err(e)
+    /// Tag actor starts so the events they cause can be traced back to the tool call
+    pub fn with_causality(mut self, causality: Arc<CausalityTracker>) -> Self {
+        self.causality = Some(causality);
+        self
+    }
+    
+    /// Stop actors when the session that started them ends, unless a
+    /// start_actor call sets `owned` to false
+    pub fn with_owned_by_default(mut self, owned: bool) -> Self {
+        self.owned_by_default = owned;
+        self
+    }
+    
+    /// Tell the client about actors as they start
+    pub fn with_client_log(mut self, client_log: Arc<ClientLog>) -> Self {
+        self.client_log = Some(client_log);
+        self
+    }
+    
+    /// Only start actors from manifests in the allowed locations
+    pub fn with_manifest_allowlist(mut self, allowlist: ManifestAllowlist) -> Self {
+        self.manifest_allowlist = Some(allowlist);
+        self
+    }
+    
+    /// Start streaming events from new actors right away, for event sinks that
+    /// would otherwise only pick them up on their next discovery pass
+    pub fn with_event_stream(mut self, event_stream: Arc<EventStreamHub>) -> Self {
+        self.event_stream = Some(event_stream);
+        self
+    }
+    
+    /// Run follow-up work for a newly started actor, returning a warning for each
+    /// task that failed or didn't finish in time. The actor is running either way.
+    async fn post_start(&self, actor_id: &TheaterId) -> Vec<String> {
+        let mut tasks: JoinSet<Result<()>> = JoinSet::new();
+        
+        // Confirm the actor survived startup
+        let client = self.theater_client.clone();
+        let id = actor_id.clone();
+        let permits = self.post_start_permits.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await?;
+            if !client.actor_exists(&id).await? {
+                return Err(anyhow!("actor stopped immediately after starting"));
+            }
+            Ok(())
+        });
+        
+        if let Some(event_stream) = &self.event_stream {
+            let event_stream = event_stream.clone();
+            let id = actor_id.as_string();
+            let permits = self.post_start_permits.clone();
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await?;
+                event_stream.watch(&id).await
+                    .map_err(|e| anyhow!("could not stream events: {}", e))
+            });
+        }
+        
+        let mut warnings = Vec::new();
+        let collected = tokio::time::timeout(POST_START_TIMEOUT, async {
+            while let Some(joined) = tasks.join_next().await {
+                match joined {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => warnings.push(e.to_string()),
+                    Err(e) => warnings.push(format!("post-start task failed: {}", e)),
                 }
             }
+        })
+        .await;
+        
+        if collected.is_err() {
+            warnings.push(format!(
+                "{} post-start task(s) did not finish within {}s",
+                tasks.len(),
+                POST_START_TIMEOUT.as_secs()
+            ));
+            // Dropping the set aborts whatever is still running
         }
+        
+        for warning in &warnings {
+            warn!("After starting actor {}: {}", actor_id, warning);
+        }
+        warnings
     }
     
-    pub fn with_resources(
-        mut self,
-        resource_manager: Arc<mcp_server::resources::ResourceManager>,
-        actor_resources: Arc<crate::resources::ActorResources>,
-        event_resources: Arc<crate::resources::EventResources>,
-    ) -> Self {
-        self.resource_manager = Some(resource_manager);
-        self.actor_resources = Some(actor_resources);
-        self.event_resources = Some(event_resources);
-        self
+    /// List running actors a page at a time, in ID order, fetching statuses
+    /// only for the actors on the page
+    pub async fn list_actors(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract optional offset and limit
+        let offset = args.get("offset").and_then(|v| v.as_u64()).unwrap_or(0) as usize;
+        let limit = args.get("limit").and_then(|v| v.as_u64()).map(|l| l as usize).unwrap_or(DEFAULT_LIST_LIMIT);
+        if limit == 0 {
+            return Err(anyhow!("limit must be at least 1"));
+        }
+        
+        // Get the page of actors
+        let mut actor_ids = self.theater_client.list_actors().await?;
+        actor_ids.sort_by_key(|id| id.as_string());
+        let total = actor_ids.len();
+        let page = actor_ids.iter().skip(offset).take(limit);
+        let actors = futures::future::join_all(page.map(|id| async move {
+            json!({
+                "id": id.as_string(),
+                "status": self.theater_client.actor_status_name(id).await,
+                "uri": format!("theater://actor/{}", id)
+            })
+        }))
+        .await;
+        
+        // Create result
+        let mut result_json = json!({
+            "actors": actors,
+            "returned": actors.len(),
+            "offset": offset,
+            "total": total
+        });
+        if offset.saturating_add(limit) < total {
+            result_json["next_offset"] = json!(offset + limit);
+        }
+        
+        ToolResult::ok(result_json).into_call_result()
     }
     
     pub async fn start_actor(&self, args: Value) -> Result<ToolCallResult> {
-        // Extract manifest path
-        let manifest = args["manifest"].as_str()
-            .ok_or_else(|| anyhow!("Missing manifest parameter"))?;
+        // Extract the manifest path, or inline manifest content
+        let manifest = match (args["manifest"].as_str(), args["manifest_content"].as_str()) {
+            (Some(_), Some(_)) => return Err(anyhow!("Pass either manifest or manifest_content, not both")),
+            (Some(manifest), None) => {
+                // Theater also takes manifest content in place of a path
+                if manifest.contains('\n') {
+                    validate_manifest_content(manifest)?;
+                }
+                match &self.manifest_allowlist {
+                    Some(allowlist) => allowlist.check(manifest)?,
+                    None => manifest.to_string(),
+                }
+            }
+            (None, Some(content)) => {
+                if self.manifest_allowlist.is_some() {
+                    return Err(ManifestAllowlist::inline_refused().into());
+                }
+                validate_manifest_content(content)?;
+                content.to_string()
+            }
+            (None, None) => return Err(anyhow!("Missing manifest parameter")),
+        };
             
         // Extract optional initial state
         let initial_state = if let Some(state) = args.get("initial_state") {
             // Convert to JSON bytes
             let state_bytes = serde_json::to_vec(state)?;
+            check_size("initial_state", state_bytes.len(), self.theater_client.limits().max_state_bytes)?;
             Some(state_bytes)
         } else {
             None
         };
         
+        // Owned actors are stopped when this session ends
+        let owned = args["owned"].as_bool().unwrap_or(self.owned_by_default);
+        
+        // Hold a place in the session's actor quota while the actor starts
+        let reservation = self.actor_registry.reserve(&self.session_id).await?;
+        
         // Start the actor and capture any errors for better debugging
+        let started_at = chrono::Utc::now();
         let actor_id = match initial_state {
             Some(ref bytes) => {
-                self.handle_connection_error(
-                    self.theater_client.start_actor(manifest, Some(bytes.as_slice())).await,
-                    "actor start"
-                )?
+                self.theater_client.start_actor(&manifest, Some(bytes.as_slice())).await?
             },
             None => {
-                self.handle_connection_error(
-                    self.theater_client.start_actor(manifest, None).await,
-                    "actor start"
-                )?
+                self.theater_client.start_actor(&manifest, None).await?
             },
         };
         
-        // Register resources for this actor if resource managers are available
         let actor_id_str = actor_id.as_string();
-        if let (Some(rm), Some(ar), Some(er)) = (
-            &self.resource_manager,
-            &self.actor_resources,
-            &self.event_resources
-        ) {
-            // Prepare resource registration
-            let actor_resources_fut = ar.clone().register_actor_resources(actor_id_str.clone(), rm.clone());
-            let event_resources_fut = er.clone().register_actor_events(actor_id_str.clone(), rm.clone());
-            
-            // Execute them in parallel
-            tokio::spawn(async move {
-                if let Err(e) = actor_resources_fut.await {
-                    error!("Error registering actor resources: {}", e);
-                    // Continue anyway, don't fail the actor start
-                }
-                
-                if let Err(e) = event_resources_fut.await {
-                    error!("Error registering event resources: {}", e);
-                    // Continue anyway, don't fail the actor start
-                }
-            });
+        reservation.commit(&actor_id_str, &manifest, owned);
+        let warnings = self.post_start(&actor_id).await;
+        let status = self.theater_client.actor_status_name(&actor_id).await;
+        
+        if let Some(client_log) = &self.client_log {
+            // Inline content would swamp the message, so only paths are named
+            let source = if manifest.contains('\n') { "inline manifest" } else { manifest.as_str() };
+            client_log.log(ClientLogLevel::Info, "actors", json!({
+                "message": format!("Started actor {} from {}", actor_id_str, source),
+                "actor_id": actor_id_str,
+                "status": status
+            }))
+            .await;
         }
         
         // Create result
         let result_json = json!({
             "actor_id": actor_id_str,
-            "status": "RUNNING"
+            "status": status,
+            "owned": owned
         });
+        let mut result = ToolResult::ok(result_json).with_warnings(warnings);
+        if let Some(causality) = &self.causality {
+            let operation = causality.record_since("start_actor", &actor_id_str, started_at);
+            result.data["operation_id"] = json!(operation.id);
+            result = result.with_resource(CausalityTracker::caused_events_uri(&operation));
+        }
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: serde_json::to_string(&result_json)? 
+        result.into_call_result()
+    }
+    
+    /// Check a manifest the way start_actor would, and that its component
+    /// exists, without starting anything
+    pub async fn validate_manifest(&self, args: Value) -> Result<ToolCallResult> {
+        // Read the manifest, noting where its relative paths start from
+        let (source, content, base) = match (args["manifest"].as_str(), args["manifest_content"].as_str()) {
+            (Some(_), Some(_)) => return Err(anyhow!("Pass either manifest or manifest_content, not both")),
+            (Some(path), None) => {
+                // Only manifests are read, so arbitrary files can't be probed
+                // through the diagnostics
+                if !is_toml_path(path) {
+                    return Err(anyhow!("Manifest {} is not a .toml file", path));
                 }
-            ],
-            is_error: Some(false),
-        })
+                let path = match &self.manifest_allowlist {
+                    Some(allowlist) => allowlist.check(path)?,
+                    None => path.to_string(),
+                };
+                let content = tokio::fs::read_to_string(&path).await
+                    .map_err(|e| anyhow!("Could not read manifest {}: {}", path, e))?;
+                let base = Path::new(&path).parent().map(Path::to_path_buf).unwrap_or_default();
+                (path, content, base)
+            }
+            (None, Some(content)) => ("inline".to_string(), content.to_string(), PathBuf::new()),
+            (None, None) => return Err(anyhow!("Missing manifest parameter")),
+        };
+        
+        // Only look for the component once the manifest itself is sound
+        let mut diagnostics = manifest_problems(&content);
+        if diagnostics.is_empty() {
+            diagnostics.extend(component_problem(&content, &base));
+        }
+        
+        // Create result
+        let result_json = json!({
+            "manifest": source,
+            "valid": diagnostics.is_empty(),
+            "diagnostics": diagnostics
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
     }
     
     pub async fn stop_actor(&self, args: Value) -> Result<ToolCallResult> {
@@ -136,11 +324,9 @@ impl ActorTools {
         // Convert to TheaterId
         let theater_id = TheaterId::from_str(actor_id_str)?;
             
-        // Stop the actor with connection error handling
-        self.handle_connection_error(
-            self.theater_client.stop_actor(&theater_id).await,
-            "actor stop"
-        )?;
+        // Stop the actor
+        self.theater_client.stop_actor(&theater_id).await?;
+        self.actor_registry.forget(actor_id_str);
         
         // Create result
         let result_json = json!({
@@ -148,14 +334,109 @@ impl ActorTools {
             "status": "STOPPED"
         });
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: serde_json::to_string(&result_json)? 
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    /// Stop every running actor, in two steps: without a `confirmation_token`
+    /// this only previews the actors and issues a token; with one it stops
+    /// the previewed actors
+    pub async fn stop_all_actors(&self, args: Value) -> Result<ToolCallResult> {
+        let result_json = match args["confirmation_token"].as_str() {
+            None => {
+                let actor_ids: Vec<String> = self.theater_client.list_actors().await?
+                .iter()
+                .map(|id| id.as_string())
+                .collect();
+                
+                if actor_ids.is_empty() {
+                    json!({ "actors": [], "count": 0 })
+                } else {
+                    let token = self.confirmations.issue("stop_all_actors", actor_ids.clone());
+                    json!({
+                        "confirmation_required": true,
+                        "actors": actor_ids,
+                        "count": actor_ids.len(),
+                        "confirmation_token": token,
+                        "expires_in_secs": self.confirmations.ttl().as_secs(),
+                        "message": "Nothing was stopped. Call stop_all_actors again with this confirmation_token to stop these actors."
+                    })
                 }
-            ],
-            is_error: Some(false),
-        })
+            }
+            Some(token) => {
+                let actor_ids = self.confirmations.redeem(token, "stop_all_actors")?;
+                let results = self.stop_each(&actor_ids).await;
+                json!({ "results": results, "count": results.len() })
+            }
+        };
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    /// Stop the actors listed in `actor_ids`, or those started from a
+    /// manifest matching the `manifest` pattern, all at once
+    pub async fn stop_actors(&self, args: Value) -> Result<ToolCallResult> {
+        let actor_ids = self.selected_actors(&args)?;
+        let results = self.stop_each(&actor_ids).await;
+        batch_result(results)
+    }
+    
+    /// Restart the actors listed in `actor_ids`, or those started from a
+    /// manifest matching the `manifest` pattern, all at once
+    pub async fn restart_actors(&self, args: Value) -> Result<ToolCallResult> {
+        let actor_ids = self.selected_actors(&args)?;
+        let results = futures::future::join_all(actor_ids.iter().map(|actor_id| async move {
+            let result = match TheaterId::from_str(actor_id) {
+                Ok(theater_id) => self.theater_client.restart_actor(&theater_id).await.map(|()| theater_id),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(theater_id) => {
+                    self.actor_registry.clear_failure(actor_id);
+                    json!({ "actor_id": actor_id, "status": self.theater_client.actor_status_name(&theater_id).await })
+                }
+                Err(e) => json!({ "actor_id": actor_id, "error": e.to_string() }),
+            }
+        }))
+        .await;
+        batch_result(results)
+    }
+    
+    /// Actors a batch tool applies to: the `actor_ids` given, or the actors
+    /// started through this server whose manifest matches `manifest`
+    fn selected_actors(&self, args: &Value) -> Result<Vec<String>> {
+        match (args.get("actor_ids").and_then(|v| v.as_array()), args["manifest"].as_str()) {
+            (Some(_), Some(_)) => Err(anyhow!("Pass either actor_ids or manifest, not both")),
+            (Some(ids), None) => ids.iter()
+                .map(|id| id.as_str().map(str::to_string).ok_or_else(|| anyhow!("actor_ids must be strings")))
+                .collect(),
+            (None, Some(pattern)) => {
+                let mut actor_ids: Vec<String> = self.actor_registry.list().into_iter()
+                    .filter(|record| glob_match(pattern, &record.manifest))
+                    .map(|record| record.actor_id)
+                    .collect();
+                actor_ids.sort();
+                Ok(actor_ids)
+            }
+            (None, None) => Err(anyhow!("Missing actor_ids or manifest parameter")),
+        }
+    }
+    
+    /// Stop actors concurrently, with a result for each
+    async fn stop_each(&self, actor_ids: &[String]) -> Vec<Value> {
+        futures::future::join_all(actor_ids.iter().map(|actor_id| async move {
+            let result = match TheaterId::from_str(actor_id) {
+                Ok(theater_id) => self.theater_client.stop_actor(&theater_id).await,
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(()) => {
+                    self.actor_registry.forget(actor_id);
+                    json!({ "actor_id": actor_id, "status": "STOPPED" })
+                }
+                Err(e) => json!({ "actor_id": actor_id, "error": e.to_string() }),
+            }
+        }))
+        .await
     }
     
     pub async fn restart_actor(&self, args: Value) -> Result<ToolCallResult> {
@@ -166,33 +447,117 @@ impl ActorTools {
         // Convert to TheaterId
         let theater_id = TheaterId::from_str(actor_id_str)?;
             
-        // Restart the actor with connection error handling
-        self.handle_connection_error(
-            self.theater_client.restart_actor(&theater_id).await,
-            "actor restart"
-        )?;
+        // Restart the actor
+        self.theater_client.restart_actor(&theater_id).await?;
+        self.actor_registry.clear_failure(actor_id_str);
         
         // Create result
         let result_json = json!({
             "actor_id": actor_id_str,
-            "status": "RUNNING"
+            "status": self.theater_client.actor_status_name(&theater_id).await
         });
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: serde_json::to_string(&result_json)? 
-                }
-            ],
-            is_error: Some(false),
-        })
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn update_actor(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+            
+        // Extract the new component
+        let component = args["component"].as_str()
+            .ok_or_else(|| anyhow!("Missing component parameter"))?;
+        let component = match &self.manifest_allowlist {
+            Some(allowlist) => allowlist.check(component)?,
+            None => component.to_string(),
+        };
+            
+        // Convert to TheaterId
+        let theater_id = TheaterId::from_str(actor_id_str)?;
+            
+        // Swap the component
+        self.theater_client.update_actor_component(&theater_id, &component).await?;
+        
+        // Create result
+        let result_json = json!({
+            "actor_id": actor_id_str,
+            "component": component,
+            "status": self.theater_client.actor_status_name(&theater_id).await
+        });
+        
+        ToolResult::ok(result_json)
+            .with_resource(format!("theater://actor/{}", actor_id_str))
+            .into_call_result()
+    }
+    
+    pub async fn get_actor_state(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+            
+        // Convert to TheaterId
+        let theater_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Get the state
+        let state = self.theater_client.get_actor_state(&theater_id).await?;
+        
+        // Same shapes as theater://actor/{actor_id}/state: JSON state as is,
+        // anything else as base64
+        let state = match state {
+            Some(bytes) => serde_json::from_slice(&bytes)
+                .unwrap_or_else(|_| json!({ "_raw_state_base64": BASE64.encode(&bytes) })),
+            None => json!({ "_state": "empty" }),
+        };
+        
+        // Create result
+        let result_json = json!({
+            "actor_id": actor_id_str,
+            "state": state
+        });
+        
+        ToolResult::ok(result_json)
+            .with_resource(format!("theater://actor/{}/state", actor_id_str))
+            .into_call_result()
     }
     
     /// Register the tools with the MCP tool manager
     pub fn register_tools(
         self: Arc<Self>,
-        tool_manager: &Arc<mcp_server::tools::ToolManager>,
+        dispatcher: &ToolDispatcher,
     ) {
+        // Register the list_actors tool
+        let list_actors_tool = Tool {
+            name: "list_actors".to_string(),
+            description: Some("List running actors with their status, a page at a time".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "offset": {
+                        "type": "integer",
+                        "description": "How many actors to skip, in ID order (default 0)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Most actors to return (default 50)"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            list_actors_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.list_actors(args).await
+                }
+            },
+        );
+        
         // Register the start_actor tool
         let start_actor_tool = Tool {
             name: "start_actor".to_string(),
@@ -202,21 +567,28 @@ impl ActorTools {
                 "properties": {
                     "manifest": {
                         "type": "string",
-                        "description": "Path to the actor manifest or manifest content"
+                        "description": "Path to the actor manifest"
+                    },
+                    "manifest_content": {
+                        "type": "string",
+                        "description": "TOML manifest to start the actor from; pass this or manifest"
                     },
                     "initial_state": {
                         "type": "object",
                         "description": "Optional initial state for the actor"
+                    },
+                    "owned": {
+                        "type": "boolean",
+                        "description": "Stop the actor when this MCP session ends"
                     }
-                },
-                "required": ["manifest"]
+                }
             }),
             annotations: None,
         };
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             start_actor_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -226,6 +598,38 @@ impl ActorTools {
             },
         );
         
+        // Register the validate_manifest tool
+        let validate_manifest_tool = Tool {
+            name: "validate_manifest".to_string(),
+            description: Some("Check an actor manifest and its component without starting the actor".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "manifest": {
+                        "type": "string",
+                        "description": "Path to the actor manifest"
+                    },
+                    "manifest_content": {
+                        "type": "string",
+                        "description": "TOML manifest to check; pass this or manifest"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            validate_manifest_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.validate_manifest(args).await
+                }
+            },
+        );
+        
         // Register the stop_actor tool
         let stop_actor_tool = Tool {
             name: "stop_actor".to_string(),
@@ -245,7 +649,7 @@ impl ActorTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             stop_actor_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -274,7 +678,7 @@ impl ActorTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             restart_actor_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -283,5 +687,175 @@ impl ActorTools {
                 }
             },
         );
+        
+        // Register the stop_actors tool
+        let stop_actors_tool = Tool {
+            name: "stop_actors".to_string(),
+            description: Some("Stop several actors at once, by ID or manifest pattern, with a result for each".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "IDs of the actors to stop"
+                    },
+                    "manifest": {
+                        "type": "string",
+                        "description": "Instead of actor_ids, stop the actors started through this server from manifests matching this * pattern"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            stop_actors_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.stop_actors(args).await
+                }
+            },
+        );
+        
+        // Register the restart_actors tool
+        let restart_actors_tool = Tool {
+            name: "restart_actors".to_string(),
+            description: Some("Restart several actors at once, by ID or manifest pattern, with a result for each".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "IDs of the actors to restart"
+                    },
+                    "manifest": {
+                        "type": "string",
+                        "description": "Instead of actor_ids, restart the actors started through this server from manifests matching this * pattern"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            restart_actors_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.restart_actors(args).await
+                }
+            },
+        );
+        
+        // Register the update_actor tool
+        let update_actor_tool = Tool {
+            name: "update_actor".to_string(),
+            description: Some("Hot-swap a running actor's WASM component, keeping its ID and state".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor to update"
+                    },
+                    "component": {
+                        "type": "string",
+                        "description": "Path or URL of the new WASM component"
+                    }
+                },
+                "required": ["actor_id", "component"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            update_actor_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.update_actor(args).await
+                }
+            },
+        );
+        
+        // Register the get_actor_state tool
+        let get_actor_state_tool = Tool {
+            name: "get_actor_state".to_string(),
+            description: Some("Get an actor's current state: parsed JSON when the state is JSON, otherwise base64 under _raw_state_base64".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            get_actor_state_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.get_actor_state(args).await
+                }
+            },
+        );
+        
+        // Register the stop_all_actors tool
+        let stop_all_actors_tool = Tool {
+            name: "stop_all_actors".to_string(),
+            description: Some("Stop every running actor. The first call only previews the actors and returns a confirmation_token; call again with the token to stop them".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "confirmation_token": {
+                        "type": "string",
+                        "description": "Token from a previous preview call, to stop the actors it listed"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            stop_all_actors_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.stop_all_actors(args).await
+                }
+            },
+        );
     }
-}
\ No newline at end of file
+}
+
+/// Result of a batch tool, with a warning when some actors failed
+fn batch_result(results: Vec<Value>) -> Result<ToolCallResult> {
+    let failed = results.iter().filter(|result| result.get("error").is_some()).count();
+    let mut result = ToolResult::ok(json!({
+        "results": results,
+        "count": results.len(),
+        "failed": failed
+    }));
+    if failed > 0 {
+        result = result.with_warning(format!("{} of {} actors failed; see their results", failed, results.len()));
+    }
+    result.into_call_result()
+}
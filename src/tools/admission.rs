@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::session::SessionId;
+use crate::theater::types::TheaterError;
+
+/// Retry hint given when every concurrent call slot is taken
+const BUSY_RETRY_AFTER: Duration = Duration::from_millis(500);
+
+/// Limits on how many tool calls are accepted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionLimits {
+    /// Tool calls allowed to run at once across all sessions
+    pub max_concurrent_calls: usize,
+    /// Sustained tool calls per second allowed for each session
+    pub calls_per_second: f64,
+    /// Calls a session can make in a burst before being throttled
+    pub burst: u32,
+}
+
+impl Default for AdmissionLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_calls: 32,
+            calls_per_second: 10.0,
+            burst: 20,
+        }
+    }
+}
+
+/// A token bucket refilled continuously at a fixed rate
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Create a full bucket
+    pub fn new(capacity: u32, refill_per_sec: f64, now: Instant) -> Self {
+        Self {
+            capacity: capacity.max(1) as f64,
+            refill_per_sec,
+            tokens: capacity.max(1) as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Take a token, or return how long until one is available
+    pub fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.refill_per_sec <= 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec))
+    }
+}
+
+/// Decides whether a tool call may run.
+///
+/// Each session draws from its own token bucket, and all sessions share a
+/// cap on calls in flight. Calls over either limit are rejected straight
+/// away with a retry-after hint rather than queued, so a flood of requests
+/// can't pile up on the Theater connection or in memory.
+#[derive(Debug)]
+pub struct Admission {
+    limits: AdmissionLimits,
+    buckets: Mutex<HashMap<SessionId, TokenBucket>>,
+    in_flight: Arc<Semaphore>,
+}
+
+impl Admission {
+    pub fn new(limits: AdmissionLimits) -> Self {
+        let in_flight = Arc::new(Semaphore::new(limits.max_concurrent_calls.max(1)));
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+            in_flight,
+        }
+    }
+
+    /// Admit a call from a session. The returned permit holds a concurrency
+    /// slot until it is dropped.
+    pub fn admit(&self, session: &SessionId) -> Result<OwnedSemaphorePermit, TheaterError> {
+        let now = Instant::now();
+        {
+            let mut buckets = self.buckets.lock().unwrap();
+            let bucket = buckets
+                .entry(session.clone())
+                .or_insert_with(|| TokenBucket::new(self.limits.burst, self.limits.calls_per_second, now));
+            if let Err(retry_after) = bucket.try_take(now) {
+                return Err(TheaterError::RateLimited(
+                    "this session is over its tool call rate".to_string(),
+                    retry_after.as_millis().min(u64::MAX as u128) as u64,
+                ));
+            }
+        }
+
+        self.in_flight.clone().try_acquire_owned().map_err(|_| {
+            TheaterError::RateLimited(
+                format!("{} tool calls are already running", self.limits.max_concurrent_calls),
+                BUSY_RETRY_AFTER.as_millis() as u64,
+            )
+        })
+    }
+}
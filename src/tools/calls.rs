@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::tools::result::ToolStatus;
+
+/// Tool calls kept by a [`ToolCallLog`]
+pub const RECENT_CALLS: usize = 20;
+
+/// A finished tool call
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolCall {
+    pub tool: String,
+    pub at: DateTime<Utc>,
+    pub status: ToolStatus,
+    pub elapsed_ms: u64,
+    /// Why the call failed, if it returned an error rather than a result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The most recent tool calls of the session, oldest first
+pub struct ToolCallLog {
+    capacity: usize,
+    calls: Mutex<VecDeque<ToolCall>>,
+}
+
+impl ToolCallLog {
+    pub fn new() -> Self {
+        Self {
+            capacity: RECENT_CALLS,
+            calls: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Keep this many calls
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn record(&self, call: ToolCall) {
+        let mut calls = self.calls.lock().unwrap();
+        calls.push_back(call);
+        while calls.len() > self.capacity {
+            calls.pop_front();
+        }
+    }
+
+    pub fn recent(&self) -> Vec<ToolCall> {
+        self.calls.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl Default for ToolCallLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,84 +1,87 @@
 use anyhow::{anyhow, Result};
-use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
-use mcp_protocol::types::tool::{Tool, ToolCallResult, ToolContent};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
 use serde_json::{json, Value};
 use std::sync::Arc;
-use tracing::warn;
+use std::time::Duration;
 
+use crate::channels::{ChannelRegistry, ChannelState, ParticipantRef};
+use crate::encoding::PayloadEncoding;
+use crate::session::SessionId;
 use crate::theater::client::TheaterClient;
-use crate::tools::utils::register_async_tool;
+use crate::theater::limits::check_size;
+use crate::tools::confirm::ConfirmationTokens;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Default time to wait for a reply in `channel_request`
+const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+/// Default number of messages returned by `receive_channel_message`
+const DEFAULT_RECEIVE_MAX: usize = 10;
+
+/// Longest `receive_channel_message` may wait for a message
+const MAX_RECEIVE_WAIT_MS: u64 = 60_000;
 
 pub struct ChannelTools {
     theater_client: Arc<TheaterClient>,
+    channel_registry: Arc<ChannelRegistry>,
+    session_id: SessionId,
+    confirmations: ConfirmationTokens,
 }
 
 impl ChannelTools {
-    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
-        Self { theater_client }
-    }
-    
-    /// Helper method to handle Theater connection errors
-    fn handle_connection_error<T>(&self, result: Result<T>, context: &str) -> Result<T> {
-        match result {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("connect") || error_msg.contains("connection") || 
-                   error_msg.contains("read") || error_msg.contains("write") {
-                    // This is likely a connection issue
-                    warn!("Theater connection issue during {}: {}. Will attempt reconnection on next request.", context, error_msg);
-                    Err(anyhow!("Theater server connection issue: {}. The server will attempt to reconnect on the next request.", error_msg))
-                } else {
-                    // Other type of error
-                    Err(e)
-                }
-            }
+    pub fn new(
+        theater_client: Arc<TheaterClient>,
+        channel_registry: Arc<ChannelRegistry>,
+        session_id: SessionId,
+    ) -> Self {
+        Self {
+            theater_client,
+            channel_registry,
+            session_id,
+            confirmations: ConfirmationTokens::new(),
         }
     }
     
     pub async fn open_channel(&self, args: Value) -> Result<ToolCallResult> {
-        // Extract actor ID
-        let actor_id = args["actor_id"].as_str()
-            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        // Extract the participant (actor_id or an explicit participant object)
+        let participant = ParticipantRef::from_args(&args)?;
+            
+        // Encoding used for every message on this channel
+        let encoding = PayloadEncoding::from_args(&args, PayloadEncoding::Base64)?;
             
         // Extract optional initial message
-        let initial_message = if let Some(msg) = args.get("initial_message") {
-            if let Some(msg_str) = msg.as_str() {
-                let msg_data = BASE64.decode(msg_str)?;
-                Some(msg_data)
-            } else {
-                None
-            }
-        } else {
-            None
+        let initial_message = match args.get("initial_message") {
+            Some(msg) if !msg.is_null() => Some(encoding.encode(msg)?),
+            _ => None,
         };
+        if let Some(message) = &initial_message {
+            check_size("initial_message", message.len(), self.theater_client.limits().max_message_bytes)?;
+        }
         
-        // Open the channel with connection error handling
-        let channel_id = match initial_message {
-            Some(msg) => self.handle_connection_error(
-                self.theater_client.open_channel(actor_id, Some(&msg)).await,
-                &format!("channel open to {}", actor_id)
-            )?,
-            None => self.handle_connection_error(
-                self.theater_client.open_channel(actor_id, None).await,
-                &format!("channel open to {}", actor_id)
-            )?,
-        };
+        // Open the channel; the registry tracks
+        // it for the session that opened it and buffers inbound messages
+        let record = self.channel_registry
+            .open(participant.clone(), encoding, initial_message.as_deref(), &self.session_id)
+            .await?;
+        let channel_id = record.channel_id;
+        
+        // Optionally let other sessions use the channel
+        let shared = args.get("shared").and_then(|v| v.as_bool()).unwrap_or(false);
+        if shared {
+            self.channel_registry.set_shared(&channel_id, &self.session_id, true)?;
+        }
         
         // Create result
         let response_json = json!({
             "channel_id": channel_id,
-            "actor_id": actor_id
+            "actor_id": participant.actor_id(),
+            "participant": participant,
+            "encoding": encoding,
+            "shared": shared
         });
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: format!("{{\"json\":{}}}", serde_json::to_string(&response_json)?) 
-                }
-            ],
-            is_error: Some(false),
-        })
+        ToolResult::ok(response_json).into_call_result()
     }
     
     pub async fn send_on_channel(&self, args: Value) -> Result<ToolCallResult> {
@@ -86,18 +89,23 @@ impl ChannelTools {
         let channel_id = args["channel_id"].as_str()
             .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
             
+        // Only the owning session may use an unshared channel
+        self.channel_registry.check_access(channel_id, &self.session_id)?;
+            
         // Extract message data
-        let message_b64 = args["message"].as_str()
+        let message_arg = args.get("message")
             .ok_or_else(|| anyhow!("Missing message parameter"))?;
             
-        // Decode message data
-        let message = BASE64.decode(message_b64)?;
+        // Encode message data using the channel's configured encoding
+        let message = self.channel_registry.encoding(channel_id).encode(message_arg)?;
+        check_size("message", message.len(), self.theater_client.limits().max_message_bytes)?;
+        
+        // Map to the Theater channel, reopening it if the connection was re-established
+        let theater_channel_id = self.channel_registry.resolve(channel_id).await?;
         
-        // Send on the channel with connection error handling
-        self.handle_connection_error(
-            self.theater_client.send_on_channel(channel_id, &message).await,
-            &format!("channel send on {}", channel_id)
-        )?;
+        // Send on the channel
+        self.theater_client.send_on_channel(&theater_channel_id, &message).await?;
+        self.channel_registry.record_sent(channel_id, message.len());
         
         // Create result
         let response_json = json!({
@@ -105,14 +113,7 @@ impl ChannelTools {
             "channel_id": channel_id
         });
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: format!("{{\"json\":{}}}", serde_json::to_string(&response_json)?) 
-                }
-            ],
-            is_error: Some(false),
-        })
+        ToolResult::ok(response_json).into_call_result()
     }
     
     pub async fn close_channel(&self, args: Value) -> Result<ToolCallResult> {
@@ -120,11 +121,7 @@ impl ChannelTools {
         let channel_id = args["channel_id"].as_str()
             .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
             
-        // Close the channel with connection error handling
-        self.handle_connection_error(
-            self.theater_client.close_channel(channel_id).await,
-            &format!("channel close {}", channel_id)
-        )?;
+        self.close(channel_id).await?;
         
         // Create result
         let response_json = json!({
@@ -132,25 +129,220 @@ impl ChannelTools {
             "channel_id": channel_id
         });
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: format!("{{\"json\":{}}}", serde_json::to_string(&response_json)?) 
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    /// Close a channel this session may use and stop tracking it
+    async fn close(&self, channel_id: &str) -> Result<()> {
+        // Only the owning session may use an unshared channel
+        self.channel_registry.check_access(channel_id, &self.session_id)?;
+            
+        // Invalidated channels are already gone on the Theater side
+        let theater_channel_id = match self.channel_registry.get(channel_id) {
+            Some(record) if record.state == ChannelState::Invalidated => None,
+            Some(record) => Some(record.theater_channel_id),
+            None => Some(channel_id.to_string()),
+        };
+        
+        // Close the channel
+        if let Some(theater_channel_id) = theater_channel_id {
+            self.theater_client.close_channel(&theater_channel_id).await?;
+        }
+        
+        // Stop tracking the channel
+        self.channel_registry.mark_closed(channel_id);
+        Ok(())
+    }
+    
+    /// Close every channel this session opened, in two steps: without a
+    /// `confirmation_token` this only previews the channels and issues a
+    /// token; with one it closes the previewed channels
+    pub async fn close_all_channels(&self, args: Value) -> Result<ToolCallResult> {
+        let response_json = match args["confirmation_token"].as_str() {
+            None => {
+                let channels: Vec<Value> = self
+                    .channel_registry
+                    .list_for_session(&self.session_id)
+                    .into_iter()
+                    .filter(|record| record.state != ChannelState::Closed)
+                    .map(|record| json!({
+                        "channel_id": record.channel_id,
+                        "participant": record.participant.to_string(),
+                        "state": record.state
+                    }))
+                    .collect();
+                
+                if channels.is_empty() {
+                    json!({ "channels": [], "count": 0 })
+                } else {
+                    let channel_ids = channels
+                        .iter()
+                        .filter_map(|channel| channel["channel_id"].as_str().map(String::from))
+                        .collect();
+                    let token = self.confirmations.issue("close_all_channels", channel_ids);
+                    json!({
+                        "confirmation_required": true,
+                        "channels": channels,
+                        "count": channels.len(),
+                        "confirmation_token": token,
+                        "expires_in_secs": self.confirmations.ttl().as_secs(),
+                        "message": "Nothing was closed. Call close_all_channels again with this confirmation_token to close these channels."
+                    })
+                }
+            }
+            Some(token) => {
+                let channel_ids = self.confirmations.redeem(token, "close_all_channels")?;
+                let mut results = Vec::with_capacity(channel_ids.len());
+                for channel_id in channel_ids {
+                    results.push(match self.close(&channel_id).await {
+                        Ok(()) => json!({ "channel_id": channel_id, "success": true }),
+                        Err(e) => json!({ "channel_id": channel_id, "error": e.to_string() }),
+                    });
                 }
-            ],
-            is_error: Some(false),
-        })
+                json!({ "results": results, "count": results.len() })
+            }
+        };
+        
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    pub async fn channel_request(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract channel ID
+        let channel_id = args["channel_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
+            
+        // Only the owning session may use an unshared channel
+        self.channel_registry.check_access(channel_id, &self.session_id)?;
+            
+        // Extract message data
+        let message_arg = args.get("message")
+            .ok_or_else(|| anyhow!("Missing message parameter"))?;
+            
+        // Extract optional timeout
+        let timeout_ms = args.get("timeout_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT_MS);
+            
+        // Encode message data using the channel's configured encoding
+        let encoding = self.channel_registry.encoding(channel_id);
+        let message = encoding.encode(message_arg)?;
+        check_size("message", message.len(), self.theater_client.limits().max_message_bytes)?;
+        
+        // Send and wait for the reply
+        let reply = self.channel_registry
+            .request(channel_id, &message, Duration::from_millis(timeout_ms))
+            .await?;
+        
+        // Create result
+        let response_json = json!({
+            "channel_id": channel_id,
+            "reply": encoding.decode(&reply.data),
+            "received_at": reply.received_at.to_rfc3339()
+        });
+        
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    pub async fn receive_channel_message(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract channel ID
+        let channel_id = args["channel_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
+            
+        // Only the owning session may use an unshared channel
+        self.channel_registry.check_access(channel_id, &self.session_id)?;
+        
+        // Extract optional count and wait
+        let max_messages = args.get("max_messages")
+            .and_then(|v| v.as_u64())
+            .map(|m| (m as usize).max(1))
+            .unwrap_or(DEFAULT_RECEIVE_MAX);
+        let wait_ms = args.get("wait_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            .min(MAX_RECEIVE_WAIT_MS);
+        
+        let (messages, rejected) = self.channel_registry
+            .receive(channel_id, max_messages, Duration::from_millis(wait_ms))
+            .await?;
+        
+        // Decode messages using the channel's configured encoding
+        let encoding = self.channel_registry.encoding(channel_id);
+        let messages: Vec<Value> = messages
+            .iter()
+            .map(|message| json!({
+                "seq": message.seq,
+                "received_at": message.received_at.to_rfc3339(),
+                "data": encoding.decode(&message.data)
+            }))
+            .collect();
+        let remaining = self.channel_registry.inbox(channel_id).map_or(0, |inbox| inbox.len());
+        
+        // Create result
+        let response_json = json!({
+            "channel_id": channel_id,
+            "messages": messages,
+            "remaining": remaining,
+            "rejected": rejected
+        });
+        
+        // With the reject policy, overflow since the last read is an error
+        if rejected > 0 {
+            return ToolResult::error(response_json)
+                .with_warning(format!("{} messages were rejected because the channel's inbox was full", rejected))
+                .into_call_result();
+        }
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    pub async fn share_channel(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract channel ID
+        let channel_id = args["channel_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
+            
+        let shared = args.get("shared").and_then(|v| v.as_bool()).unwrap_or(true);
+        
+        let record = self.channel_registry.set_shared(channel_id, &self.session_id, shared)?;
+        
+        // Create result
+        let response_json = json!({
+            "channel_id": channel_id,
+            "shared": record.shared
+        });
+        
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    pub async fn channel_status(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract channel ID
+        let channel_id = args["channel_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing channel_id parameter"))?;
+            
+        let response_json = self.channel_registry.status(channel_id)?;
+        
+        ToolResult::ok(response_json).into_call_result()
+    }
+    
+    pub async fn list_channels(&self, _args: Value) -> Result<ToolCallResult> {
+        let channels = self.channel_registry.list();
+        
+        // Create result
+        let response_json = json!({
+            "channels": channels,
+            "total": channels.len()
+        });
+        
+        ToolResult::ok(response_json).into_call_result()
     }
     
     /// Register the tools with the MCP tool manager
     pub fn register_tools(
         self: Arc<Self>,
-        tool_manager: &Arc<mcp_server::tools::ToolManager>,
+        dispatcher: &ToolDispatcher,
     ) {
         // Register the open_channel tool
         let open_channel_tool = Tool {
             name: "open_channel".to_string(),
-            description: Some("Open a communication channel to an actor".to_string()),
+            description: Some("Open a communication channel to an actor or external participant".to_string()),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -158,19 +350,42 @@ impl ChannelTools {
                         "type": "string",
                         "description": "ID of the actor to open a channel with"
                     },
-                    "initial_message": {
+                    "participant": {
+                        "type": "object",
+                        "description": "Channel participant; use instead of actor_id for non-actor endpoints",
+                        "properties": {
+                            "type": {
+                                "type": "string",
+                                "enum": ["actor", "external"],
+                                "description": "Kind of participant"
+                            },
+                            "id": {
+                                "type": "string",
+                                "description": "Actor ID or external participant identifier"
+                            }
+                        },
+                        "required": ["type", "id"]
+                    },
+                    "encoding": {
                         "type": "string",
-                        "description": "Initial message data (base64 encoded)"
+                        "enum": ["utf8", "json", "base64", "msgpack", "cbor"],
+                        "description": "Encoding of messages on this channel (default: base64)"
+                    },
+                    "initial_message": {
+                        "description": "Initial message data, in the channel's encoding"
+                    },
+                    "shared": {
+                        "type": "boolean",
+                        "description": "Allow other MCP sessions to use this channel (default: false)"
                     }
-                },
-                "required": ["actor_id"]
+                }
             }),
             annotations: None,
         };
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             open_channel_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -192,8 +407,7 @@ impl ChannelTools {
                         "description": "ID of the channel"
                     },
                     "message": {
-                        "type": "string",
-                        "description": "Message data (base64 encoded)"
+                        "description": "Message data, in the encoding the channel was opened with (base64 by default)"
                     }
                 },
                 "required": ["channel_id", "message"]
@@ -203,7 +417,7 @@ impl ChannelTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             send_on_channel_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -232,7 +446,7 @@ impl ChannelTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             close_channel_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -241,5 +455,191 @@ impl ChannelTools {
                 }
             },
         );
+        
+        // Register the close_all_channels tool
+        let close_all_channels_tool = Tool {
+            name: "close_all_channels".to_string(),
+            description: Some("Close every channel this session opened. The first call only previews the channels and returns a confirmation_token; call again with the token to close them".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "confirmation_token": {
+                        "type": "string",
+                        "description": "Token from a previous preview call, to close the channels it listed"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            close_all_channels_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.close_all_channels(args).await
+                }
+            },
+        );
+        
+        // Register the channel_request tool
+        let channel_request_tool = Tool {
+            name: "channel_request".to_string(),
+            description: Some("Send a message on a channel and wait for the next message received on it as the reply".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "channel_id": {
+                        "type": "string",
+                        "description": "ID of the channel"
+                    },
+                    "message": {
+                        "description": "Message data, in the encoding the channel was opened with (base64 by default)"
+                    },
+                    "timeout_ms": {
+                        "type": "integer",
+                        "description": "How long to wait for a reply in milliseconds (default: 30000)"
+                    }
+                },
+                "required": ["channel_id", "message"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            channel_request_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.channel_request(args).await
+                }
+            },
+        );
+        
+        // Register the receive_channel_message tool
+        let receive_channel_message_tool = Tool {
+            name: "receive_channel_message".to_string(),
+            description: Some("Take messages received on a channel from its inbox, oldest first, optionally waiting for one to arrive".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "channel_id": {
+                        "type": "string",
+                        "description": "ID of the channel"
+                    },
+                    "max_messages": {
+                        "type": "integer",
+                        "description": "Most messages to take (default 10)"
+                    },
+                    "wait_ms": {
+                        "type": "integer",
+                        "description": "If the inbox is empty, wait up to this long for a message (default 0, at most 60000)"
+                    }
+                },
+                "required": ["channel_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            receive_channel_message_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.receive_channel_message(args).await
+                }
+            },
+        );
+        
+        // Register the share_channel tool
+        let share_channel_tool = Tool {
+            name: "share_channel".to_string(),
+            description: Some("Allow or disallow other MCP sessions to use a channel opened by this session".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "channel_id": {
+                        "type": "string",
+                        "description": "ID of the channel"
+                    },
+                    "shared": {
+                        "type": "boolean",
+                        "description": "Whether other sessions may use the channel (default: true)"
+                    }
+                },
+                "required": ["channel_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            share_channel_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.share_channel(args).await
+                }
+            },
+        );
+        
+        // Register the channel_status tool
+        let channel_status_tool = Tool {
+            name: "channel_status".to_string(),
+            description: Some("Get traffic statistics and health for a channel".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "channel_id": {
+                        "type": "string",
+                        "description": "ID of the channel"
+                    }
+                },
+                "required": ["channel_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            channel_status_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.channel_status(args).await
+                }
+            },
+        );
+        
+        // Register the list_channels tool
+        let list_channels_tool = Tool {
+            name: "list_channels".to_string(),
+            description: Some("List channels opened through this server".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            list_channels_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.list_channels(args).await
+                }
+            },
+        );
     }
 }
\ No newline at end of file
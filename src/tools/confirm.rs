@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::theater::types::TheaterError;
+
+/// How long a confirmation token can be redeemed after it is issued
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(60);
+
+struct PendingConfirmation {
+    operation: String,
+    targets: Vec<String>,
+    expires_at: Instant,
+}
+
+/// Single-use tokens gating bulk destructive operations.
+///
+/// A first call previews what the operation would affect and gets a token;
+/// only a second call presenting the token acts, and only on the previewed
+/// targets. A single hallucinated call can't wipe anything out.
+pub struct ConfirmationTokens {
+    pending: Mutex<HashMap<String, PendingConfirmation>>,
+    ttl: Duration,
+}
+
+impl ConfirmationTokens {
+    pub fn new() -> Self {
+        Self::with_ttl(CONFIRMATION_TTL)
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            pending: Mutex::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// How long issued tokens stay valid
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Issue a token for performing `operation` on `targets`
+    pub fn issue(&self, operation: &str, targets: Vec<String>) -> String {
+        let now = Instant::now();
+        let token = Uuid::new_v4().to_string();
+        let mut pending = self.pending.lock().unwrap();
+        pending.retain(|_, confirmation| confirmation.expires_at > now);
+        pending.insert(
+            token.clone(),
+            PendingConfirmation {
+                operation: operation.to_string(),
+                targets,
+                expires_at: now + self.ttl,
+            },
+        );
+        token
+    }
+
+    /// Use up a token, returning the targets it was issued for
+    pub fn redeem(&self, token: &str, operation: &str) -> Result<Vec<String>, TheaterError> {
+        let invalid = || {
+            TheaterError::PermissionDenied(format!(
+                "confirmation token is unknown, expired or already used; call {} without a token for a new preview",
+                operation
+            ))
+        };
+
+        let mut pending = self.pending.lock().unwrap();
+        match pending.get(token) {
+            Some(confirmation) if confirmation.operation != operation => Err(invalid()),
+            Some(confirmation) if confirmation.expires_at <= Instant::now() => {
+                pending.remove(token);
+                Err(invalid())
+            }
+            Some(_) => Ok(pending.remove(token).map(|c| c.targets).unwrap_or_default()),
+            None => Err(invalid()),
+        }
+    }
+}
+
+impl Default for ConfirmationTokens {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use theater::id::TheaterId;
+use crate::conversations::{transcript_uri, ConversationStore, Exchange};
+use crate::encoding::PayloadEncoding;
+use crate::theater::client::TheaterClient;
+use crate::theater::limits::check_size;
+use crate::theater::TheaterIdExt;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Exchanges sent along with a message when the actor asks for context
+const DEFAULT_CONTEXT_EXCHANGES: usize = 10;
+
+/// Chat-style exchanges with an actor over request_message.
+///
+/// The server numbers the exchanges of each conversation and keeps the most
+/// recent ones, readable as `theater://conversation/{conversation_id}`.
+/// Messages are sent as they are, or with `with_context` wrapped with the
+/// conversation's recent history for actors that don't keep their own:
+///
+/// ```json
+/// {"conversation_id": "...", "seq": 3, "message": "...",
+///  "history": [{"seq": 1, "message": "...", "reply": "..."}, ...]}
+/// ```
+pub struct ConverseTools {
+    theater_client: Arc<TheaterClient>,
+    conversations: Arc<ConversationStore>,
+}
+
+impl ConverseTools {
+    pub fn new(theater_client: Arc<TheaterClient>, conversations: Arc<ConversationStore>) -> Self {
+        Self {
+            theater_client,
+            conversations,
+        }
+    }
+
+    pub async fn converse_with_actor(&self, args: Value) -> Result<ToolCallResult> {
+        let message = args.get("message")
+            .filter(|message| !message.is_null())
+            .ok_or_else(|| anyhow!("Missing message parameter"))?;
+
+        // Continue a conversation, or start one with the actor
+        let conversation = match args["conversation_id"].as_str() {
+            Some(conversation_id) => self.conversations.get(conversation_id)
+                .ok_or_else(|| anyhow!("Unknown conversation {}", conversation_id))?,
+            None => {
+                let actor_id = args["actor_id"].as_str()
+                    .ok_or_else(|| anyhow!("Missing actor_id or conversation_id parameter"))?;
+                let encoding = PayloadEncoding::from_args(&args, PayloadEncoding::Utf8)?;
+                self.conversations.start(actor_id, encoding)
+            }
+        };
+        let conversation_id = conversation.conversation_id.as_str();
+        let theater_id = TheaterId::from_str(&conversation.actor_id)?;
+        let seq = self.conversations.next_seq(conversation_id)
+            .ok_or_else(|| anyhow!("Conversation {} has ended", conversation_id))?;
+
+        // Context goes as JSON whatever the conversation's encoding
+        let (encoding, request) = match args["with_context"].as_bool().unwrap_or(false) {
+            true => {
+                let keep = args["context_exchanges"].as_u64()
+                    .map_or(DEFAULT_CONTEXT_EXCHANGES, |n| n as usize);
+                let history: Vec<Value> = conversation.exchanges.iter()
+                    .skip(conversation.exchanges.len().saturating_sub(keep))
+                    .map(|exchange| json!({ "seq": exchange.seq, "message": exchange.message, "reply": exchange.reply }))
+                    .collect();
+                (PayloadEncoding::Json, json!({
+                    "conversation_id": conversation_id,
+                    "seq": seq,
+                    "message": message,
+                    "history": history
+                }))
+            }
+            false => (conversation.encoding, message.clone()),
+        };
+        let data = encoding.encode(&request)?;
+        check_size("message", data.len(), self.theater_client.limits().max_message_bytes)?;
+
+        let sent_at = Utc::now();
+        let reply_data = self.theater_client.request_message(&theater_id, &data).await?;
+        let reply = encoding.decode(&reply_data);
+
+        self.conversations.record(conversation_id, Exchange {
+            seq,
+            sent_at,
+            message: message.clone(),
+            reply: reply.clone(),
+        });
+
+        // Create result
+        let mut result_json = json!({
+            "conversation_id": conversation_id,
+            "actor_id": conversation.actor_id,
+            "seq": seq,
+            "reply": reply
+        });
+        if let Ok(text) = std::str::from_utf8(&reply_data) {
+            result_json["text"] = json!(text);
+        }
+
+        ToolResult::ok(result_json)
+            .with_resource(transcript_uri(conversation_id))
+            .into_call_result()
+    }
+
+    pub async fn end_conversation(&self, args: Value) -> Result<ToolCallResult> {
+        let conversation_id = args["conversation_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing conversation_id parameter"))?;
+        let conversation = self.conversations.end(conversation_id)
+            .ok_or_else(|| anyhow!("Unknown conversation {}", conversation_id))?;
+
+        // Create result
+        let result_json = json!({
+            "conversation_id": conversation_id,
+            "actor_id": conversation.actor_id,
+            "exchanges": conversation.next_seq - 1
+        });
+
+        ToolResult::ok(result_json).into_call_result()
+    }
+
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(
+        self: Arc<Self>,
+        dispatcher: &ToolDispatcher,
+    ) {
+        // Register the converse_with_actor tool
+        let converse_tool = Tool {
+            name: "converse_with_actor".to_string(),
+            description: Some("Send the next message of a conversation with a chat-style actor and get its reply as text; the server numbers the exchanges and keeps the transcript".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "Actor to start a conversation with"
+                    },
+                    "conversation_id": {
+                        "type": "string",
+                        "description": "Conversation to continue, from an earlier reply"
+                    },
+                    "message": {
+                        "description": "Message to send, in the conversation's encoding"
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "enum": ["utf8", "json", "base64", "msgpack", "cbor"],
+                        "description": "Encoding of messages and replies, set when the conversation starts (default utf8)"
+                    },
+                    "with_context": {
+                        "type": "boolean",
+                        "description": "Send the message as JSON together with the conversation's recent history"
+                    },
+                    "context_exchanges": {
+                        "type": "integer",
+                        "description": "How many recent exchanges with_context sends (default 10)"
+                    }
+                },
+                "required": ["message"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            converse_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.converse_with_actor(args).await
+                }
+            },
+        );
+
+        // Register the end_conversation tool
+        let end_conversation_tool = Tool {
+            name: "end_conversation".to_string(),
+            description: Some("End a conversation and discard its transcript".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "conversation_id": {
+                        "type": "string",
+                        "description": "Conversation to end"
+                    }
+                },
+                "required": ["conversation_id"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            end_conversation_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.end_conversation(args).await
+                }
+            },
+        );
+    }
+}
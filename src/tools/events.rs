@@ -0,0 +1,618 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+use theater::chain::ChainEvent;
+use theater::id::TheaterId;
+use crate::encoding::CompactEvent;
+use crate::events::search::EventSearch;
+use crate::events::summary::summarize_chain;
+use crate::events::filter::parse_time;
+use crate::events::history::events_after;
+use crate::events::{aggregate, verify, EventFilter, EventHistory, EventStreamHub};
+use crate::theater::TheaterIdExt;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Default number of events returned by event queries
+const DEFAULT_EVENT_LIMIT: usize = 100;
+
+/// Default number of matches returned by a search
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Longest a tail request may wait for new events
+const MAX_TAIL_WAIT_MS: u64 = 60_000;
+
+pub struct EventTools {
+    history: Arc<EventHistory>,
+    event_stream: Arc<EventStreamHub>,
+}
+
+impl EventTools {
+    pub fn new(history: Arc<EventHistory>, event_stream: Arc<EventStreamHub>) -> Self {
+        Self { history, event_stream }
+    }
+    
+    pub async fn aggregate_events(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract optional actor IDs; default to all known actors
+        let actor_ids = actor_ids_arg(&args)?;
+        
+        // Extract optional filter expression
+        let filter = match args.get("filter").and_then(|v| v.as_str()) {
+            Some(expr) => EventFilter::parse(expr)?,
+            None => EventFilter::all(),
+        };
+        
+        // Extract optional limit
+        let limit = args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_EVENT_LIMIT);
+        
+        // Merge the chains
+        let events = aggregate::collect_merged(&self.history, actor_ids, &filter).await?;
+        let total = events.len();
+        let events = aggregate::latest(events, limit);
+        
+        // Create result
+        let result_json = json!({
+            "events": events,
+            "returned": events.len(),
+            "total": total
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn get_chain_head(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let actor_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Get the chain
+        let events = self.history.actor_events(&actor_id).await?;
+        
+        // Create result
+        let head = events.last();
+        let result_json = json!({
+            "actor_id": actor_id_str,
+            "head": head.map(|e| verify::to_hex(&e.hash)),
+            "event_count": events.len(),
+            "head_event_type": head.map(|e| e.event_type.clone()),
+            "head_timestamp": head.map(|e| e.timestamp)
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn verify_actor_chain(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let actor_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Get the chain
+        let events = self.history.actor_events(&actor_id).await?;
+        
+        // Recompute hashes and check parent links
+        let verification = verify::verify_chain(&events);
+        let mut result_json = serde_json::to_value(&verification)?;
+        result_json["actor_id"] = json!(actor_id_str);
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn get_actor_events(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let actor_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Extract optional event type, start time and limit
+        let mut filter = EventFilter::all();
+        if let Some(event_type) = args.get("event_type").and_then(|v| v.as_str()) {
+            filter = filter.with_event_type(event_type);
+        }
+        let since = match args.get("since") {
+            Some(Value::String(since)) => Some(parse_time(since)?),
+            Some(Value::Number(since)) => Some(parse_time(&since.to_string())?),
+            Some(Value::Null) | None => None,
+            Some(other) => return Err(anyhow!("Invalid since parameter: {}", other)),
+        };
+        if let Some(since) = since {
+            filter = filter.with_since(since);
+        }
+        let limit = args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_EVENT_LIMIT);
+        
+        // Get the chain
+        let events = self.history.actor_events(&actor_id).await?;
+        
+        // Keep the latest matching events, in chain order
+        let matching: Vec<&ChainEvent> = events.iter().filter(|e| filter.matches(e)).collect();
+        let total = matching.len();
+        let returned = &matching[total.saturating_sub(limit)..];
+        
+        // Create result
+        let result_json = json!({
+            "actor_id": actor_id_str,
+            "events": returned.iter().copied().map(CompactEvent).collect::<Vec<_>>(),
+            "returned": returned.len(),
+            "total": total
+        });
+        
+        let mut result = ToolResult::ok(result_json);
+        if total > returned.len() {
+            result = result.with_warning(format!(
+                "{} matching events were left out; raise limit or narrow since to see them",
+                total - returned.len()
+            ));
+        }
+        result.into_call_result()
+    }
+    
+    pub async fn tail_actor_events(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let actor_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Extract optional cursor, wait and limit
+        let cursor = args.get("cursor").and_then(|v| v.as_str());
+        let wait_ms = args.get("wait_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0)
+            .min(MAX_TAIL_WAIT_MS);
+        let limit = args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_EVENT_LIMIT);
+        
+        // Subscribe before fetching so an event can't slip in between the fetch and the wait
+        let mut live = if wait_ms > 0 {
+            match self.event_stream.subscribe(actor_id_str).await {
+                Ok(live) => Some(live),
+                Err(e) => {
+                    debug!("Could not stream events for {}; not waiting: {}", actor_id_str, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        
+        // Get the chain
+        let mut events = self.history.actor_events(&actor_id).await?;
+        
+        // Nothing new yet: wait for the next live event, then re-read the chain
+        if events_after(&events, cursor)?.is_empty() {
+            if let Some(live) = live.as_mut() {
+                if let Ok(Ok(_)) = tokio::time::timeout(Duration::from_millis(wait_ms), live.recv()).await {
+                    events = self.history.actor_events(&actor_id).await?;
+                }
+            }
+        }
+        
+        let new_events = events_after(&events, cursor)?;
+        let has_more = new_events.len() > limit;
+        let returned = &new_events[..new_events.len().min(limit)];
+        
+        // The next cursor is the last event returned, or the old one if nothing is new
+        let next_cursor = match returned.last() {
+            Some(event) => Some(verify::to_hex(&event.hash)),
+            None => cursor.map(|c| c.to_string()),
+        };
+        
+        // Create result
+        let result_json = json!({
+            "actor_id": actor_id_str,
+            "events": returned.iter().map(CompactEvent).collect::<Vec<_>>(),
+            "cursor": next_cursor,
+            "has_more": has_more
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn search_events(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract optional query and filter; at least one is required
+        let query = args.get("query").and_then(|v| v.as_str()).map(|q| q.to_string());
+        let filter = match args.get("filter").and_then(|v| v.as_str()) {
+            Some(expr) => EventFilter::parse(expr)?,
+            None => EventFilter::all(),
+        };
+        if query.is_none() && filter.is_empty() {
+            return Err(anyhow!("Provide a query, a filter, or both"));
+        }
+        
+        // Extract optional actor IDs, context and limit
+        let actor_ids = actor_ids_arg(&args)?;
+        let context = args.get("context")
+            .and_then(|v| v.as_u64())
+            .map(|c| c as usize)
+            .unwrap_or(1);
+        let limit = args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_SEARCH_LIMIT);
+        
+        // Fetch the chains
+        let chains = aggregate::collect_chains(&self.history, actor_ids).await?;
+        
+        let search = EventSearch { query, filter, context };
+        let mut matches: Vec<_> = chains
+            .iter()
+            .flat_map(|(actor_id, events)| search.search_chain(actor_id, events))
+            .collect();
+        let total = matches.len();
+        matches.truncate(limit);
+        
+        // Create result
+        let result_json = json!({
+            "matches": matches,
+            "returned": matches.len(),
+            "total": total
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn get_event_rates(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract optional limit
+        let limit = args.get("limit")
+            .and_then(|v| v.as_u64())
+            .map(|l| l as usize)
+            .unwrap_or(DEFAULT_EVENT_LIMIT);
+        
+        // Rates are already sorted busiest first
+        let rates: Vec<Value> = self.event_stream.rates()
+            .into_iter()
+            .take(limit)
+            .map(|(actor_id, rate)| {
+                let mut entry = json!(rate);
+                entry["actor_id"] = json!(actor_id);
+                entry
+            })
+            .collect();
+        
+        // Create result
+        let result_json = json!({
+            "actors": rates
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    pub async fn summarize_actor_events(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let actor_id = TheaterId::from_str(actor_id_str)?;
+        
+        // Extract optional filter expression
+        let filter = match args.get("filter").and_then(|v| v.as_str()) {
+            Some(expr) => EventFilter::parse(expr)?,
+            None => EventFilter::all(),
+        };
+        
+        // Get the chain
+        let events = self.history.actor_events(&actor_id).await?;
+        let events: Vec<ChainEvent> = events.into_iter().filter(|e| filter.matches(e)).collect();
+        
+        // Create result
+        let mut result_json = serde_json::to_value(summarize_chain(&events))?;
+        result_json["actor_id"] = json!(actor_id_str);
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(
+        self: Arc<Self>,
+        dispatcher: &ToolDispatcher,
+    ) {
+        // Register the aggregate_events tool
+        let aggregate_events_tool = Tool {
+            name: "aggregate_events".to_string(),
+            description: Some("Merge the event chains of several actors into one timestamp-ordered feed".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Actors to include (default: all running actors)"
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Event filter expression, e.g. 'type:wasm* since:2025-04-01T00:00:00Z'"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of most recent events to return (default: 100)"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            aggregate_events_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.aggregate_events(args).await
+                }
+            },
+        );
+        
+        // Register the get_chain_head tool
+        let get_chain_head_tool = Tool {
+            name: "get_chain_head".to_string(),
+            description: Some("Get the hash of the latest event in an actor's chain".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            get_chain_head_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.get_chain_head(args).await
+                }
+            },
+        );
+        
+        // Register the verify_actor_chain tool
+        let verify_actor_chain_tool = Tool {
+            name: "verify_actor_chain".to_string(),
+            description: Some("Recompute an actor's event hashes and report tampering or missing events".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            verify_actor_chain_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.verify_actor_chain(args).await
+                }
+            },
+        );
+        
+        // Register the get_actor_events tool
+        let get_actor_events_tool = Tool {
+            name: "get_actor_events".to_string(),
+            description: Some("Get the latest events of an actor's chain, optionally only of one type or since a time".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    },
+                    "event_type": {
+                        "type": "string",
+                        "description": "Only events of this type; * matches any run of characters, e.g. *message*"
+                    },
+                    "since": {
+                        "type": ["string", "integer"],
+                        "description": "Only events at or after this time, as RFC 3339 or Unix seconds"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Most events to return, the latest first kept (default 100)"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            get_actor_events_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.get_actor_events(args).await
+                }
+            },
+        );
+        
+        // Register the tail_actor_events tool
+        let tail_actor_events_tool = Tool {
+            name: "tail_actor_events".to_string(),
+            description: Some("Get events newer than a cursor, waiting up to wait_ms for new ones if there are none yet".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    },
+                    "cursor": {
+                        "type": "string",
+                        "description": "Cursor returned by a previous call (omit to start from the beginning of the chain)"
+                    },
+                    "wait_ms": {
+                        "type": "integer",
+                        "description": "How long to wait for new events if there are none (default: 0, max: 60000)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of events to return (default: 100)"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            tail_actor_events_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.tail_actor_events(args).await
+                }
+            },
+        );
+        
+        // Register the search_events tool
+        let search_events_tool = Tool {
+            name: "search_events".to_string(),
+            description: Some("Search event history for text or payload field matches, returning each match with surrounding events".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Case-insensitive text to find in the event type, description or decoded payload"
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Event filter expression, e.g. 'data.counter<0'"
+                    },
+                    "actor_ids": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Actors to search (default: all known actors)"
+                    },
+                    "context": {
+                        "type": "integer",
+                        "description": "Events to include before and after each match (default: 1)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of matches to return (default: 20)"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            search_events_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.search_events(args).await
+                }
+            },
+        );
+        
+        // Register the get_event_rates tool
+        let get_event_rates_tool = Tool {
+            name: "get_event_rates".to_string(),
+            description: Some("Get rolling events per second and payload sizes for streamed actors, busiest first".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of actors to return (default: 100)"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            get_event_rates_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.get_event_rates(args).await
+                }
+            },
+        );
+        
+        // Register the summarize_actor_events tool
+        let summarize_actor_events_tool = Tool {
+            name: "summarize_actor_events".to_string(),
+            description: Some("Summarize an actor's event chain: counts by type, time range, and notable events such as lifecycle changes and errors".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor"
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Event filter expression limiting which events are summarized"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            summarize_actor_events_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.summarize_actor_events(args).await
+                }
+            },
+        );
+    }
+}
+
+/// Optional `actor_ids` argument
+fn actor_ids_arg(args: &Value) -> Result<Option<Vec<TheaterId>>> {
+    let Some(ids) = args.get("actor_ids").and_then(|v| v.as_array()) else {
+        return Ok(None);
+    };
+
+    ids.iter()
+        .map(|id| {
+            let id = id.as_str().ok_or_else(|| anyhow!("actor_ids must be strings"))?;
+            TheaterId::from_str(id)
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(Some)
+}
@@ -0,0 +1,335 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::tool::{Tool, ToolCallResult, ToolContent};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::prompts::ExportedPrompts;
+use crate::theater::client::TheaterClient;
+use crate::theater::types::TheaterIdExt;
+use crate::theater::TheaterId;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Prefix of the tools actors export, as `actor.{alias}.{tool}`
+pub const EXPORTED_TOOL_PREFIX: &str = "actor";
+
+/// How long an actor has to list its tools
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long an exported tool call may take
+const CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// An actor whose tools are registered under an alias
+#[derive(Debug, Clone)]
+struct ExportingActor {
+    actor_id: String,
+    tools: Vec<String>,
+    prompts: Vec<String>,
+}
+
+/// Tools exported by actors and proxied over request_message.
+///
+/// An actor opts in by answering two JSON requests:
+///
+/// - `{"mcp": "tools/list"}` with `{"tools": [{"name", "description", "inputSchema"}]}`
+/// - `{"mcp": "tools/call", "name": ..., "arguments": {...}}` with
+///   `{"content": [{"type": "text", "text": ...}], "isError": false}`, or
+///   any other JSON value, which is returned as the tool's text
+///
+/// `register_actor_tools` asks an actor for its tools and registers each as
+/// `actor.{alias}.{tool}`. Calls look the alias up when they run, so pointing
+/// an alias at a replacement actor keeps the registered tools working.
+/// With `with_prompts`, the actor's prompt templates are registered too.
+pub struct ExportedTools {
+    theater_client: Arc<TheaterClient>,
+    dispatcher: ToolDispatcher,
+    prompts: Option<Arc<ExportedPrompts>>,
+    exports: Mutex<BTreeMap<String, ExportingActor>>,
+}
+
+impl ExportedTools {
+    pub fn new(theater_client: Arc<TheaterClient>, dispatcher: ToolDispatcher) -> Self {
+        Self {
+            theater_client,
+            dispatcher,
+            prompts: None,
+            exports: Mutex::new(BTreeMap::new()),
+        }
+    }
+    
+    /// Also register the prompts actors declare
+    pub fn with_prompts(mut self, prompts: Arc<ExportedPrompts>) -> Self {
+        self.prompts = Some(prompts);
+        self
+    }
+    
+    /// Send a JSON request to an actor and parse its JSON reply
+    async fn request_json(&self, actor_id: &str, request: &Value, timeout: Duration) -> Result<Value> {
+        let theater_id = TheaterId::from_str(actor_id)?;
+        self.theater_client.request_json(&theater_id, request, timeout).await
+    }
+    
+    pub async fn register_actor_tools(self: &Arc<Self>, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID and alias
+        let actor_id = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        let alias = match args["alias"].as_str() {
+            Some(alias) => alias.to_string(),
+            None => actor_id.chars().take(8).collect(),
+        };
+        check_name("alias", &alias)?;
+        
+        let (registered, skipped, prompts) = self.export_actor(actor_id, &alias, true).await?;
+        
+        // Create result
+        let mut result_json = json!({
+            "actor_id": actor_id,
+            "alias": alias,
+            "tools": registered,
+            "prompts": prompts
+        });
+        if !skipped.is_empty() {
+            result_json["skipped"] = json!(skipped);
+        }
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    /// Offer the tools exported by an actor under their own names, as if
+    /// they were this server's tools, returning the names registered
+    pub async fn proxy_actor(self: &Arc<Self>, actor_id: &str) -> Result<Vec<String>> {
+        let (registered, skipped, _) = self.export_actor(actor_id, actor_id, false).await?;
+        if !skipped.is_empty() {
+            warn!("Skipped {} tools of proxied actor {}", skipped.len(), actor_id);
+        }
+        Ok(registered)
+    }
+    
+    /// Actor behind each alias, for persisting across restarts
+    pub fn aliases(&self) -> BTreeMap<String, String> {
+        self.exports.lock().unwrap()
+            .iter()
+            .map(|(alias, export)| (alias.clone(), export.actor_id.clone()))
+            .collect()
+    }
+    
+    /// Register an actor's tools under an alias recorded by an earlier run,
+    /// returning the tools registered
+    pub async fn restore_alias(self: &Arc<Self>, alias: &str, actor_id: &str) -> Result<Vec<String>> {
+        check_name("alias", alias)?;
+        let (registered, _, _) = self.export_actor(actor_id, alias, true).await?;
+        Ok(registered)
+    }
+    
+    /// Ask an actor for its tools and prompts and register them under the
+    /// alias, qualified as `actor.{alias}.{name}` or under their own names.
+    /// Returns the tools registered, the tools skipped and the prompts registered.
+    async fn export_actor(
+        self: &Arc<Self>,
+        actor_id: &str,
+        alias: &str,
+        qualified: bool,
+    ) -> Result<(Vec<String>, Vec<Value>, Vec<String>)> {
+        // Ask the actor which tools it offers
+        let listing = self
+            .request_json(actor_id, &json!({ "mcp": "tools/list" }), DISCOVERY_TIMEOUT)
+            .await
+            .map_err(|e| anyhow!("Actor {} did not list its tools: {}", actor_id, e))?;
+        let declared = listing["tools"]
+            .as_array()
+            .ok_or_else(|| anyhow!("Actor {} replied without a tools list: {}", actor_id, listing))?;
+        
+        let mut registered = Vec::new();
+        let mut skipped = Vec::new();
+        for declaration in declared {
+            match self.register_exported_tool(alias, declaration, qualified) {
+                Ok(name) => registered.push(name),
+                Err(e) => {
+                    warn!("Skipping tool exported by actor {}: {}", actor_id, e);
+                    skipped.push(json!({ "tool": declaration["name"], "error": e.to_string() }));
+                }
+            }
+        }
+        
+        info!("Registered {} tools of actor {} as {}", registered.len(), actor_id, alias);
+        
+        // Prompts are optional; an actor that doesn't declare any has none
+        let prompts = match &self.prompts {
+            Some(exported_prompts) => exported_prompts.register_actor(actor_id, alias, qualified).await?,
+            None => Vec::new(),
+        };
+        
+        self.exports.lock().unwrap().insert(alias.to_string(), ExportingActor {
+            actor_id: actor_id.to_string(),
+            tools: registered.clone(),
+            prompts: prompts.clone(),
+        });
+        Ok((registered, skipped, prompts))
+    }
+    
+    /// Register one declared tool under the alias, returning its full name
+    fn register_exported_tool(self: &Arc<Self>, alias: &str, declaration: &Value, qualified: bool) -> Result<String> {
+        let tool_name = declaration["name"].as_str()
+            .ok_or_else(|| anyhow!("tool declaration without a name"))?;
+        check_name("tool name", tool_name)?;
+        let input_schema = declaration.get("inputSchema")
+            .or_else(|| declaration.get("input_schema"))
+            .cloned()
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} }));
+        
+        let name = match qualified {
+            true => format!("{}.{}.{}", EXPORTED_TOOL_PREFIX, alias, tool_name),
+            false => tool_name.to_string(),
+        };
+        let tool = Tool {
+            name: name.clone(),
+            description: declaration["description"].as_str().map(|d| d.to_string()),
+            input_schema,
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        let alias = alias.to_string();
+        let tool_name = tool_name.to_string();
+        register_async_tool(
+            &self.dispatcher,
+            tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                let alias = alias.clone();
+                let tool_name = tool_name.clone();
+                async move {
+                    tools_self.call_exported_tool(&alias, &tool_name, args).await
+                }
+            },
+        );
+        Ok(name)
+    }
+    
+    /// Forward a call of an exported tool to the actor currently behind its alias
+    async fn call_exported_tool(&self, alias: &str, tool_name: &str, args: Value) -> Result<ToolCallResult> {
+        let actor_id = self.exports.lock().unwrap()
+            .get(alias)
+            .map(|export| export.actor_id.clone())
+            .ok_or_else(|| anyhow!("No actor is registered as {}", alias))?;
+        
+        let request = json!({ "mcp": "tools/call", "name": tool_name, "arguments": args });
+        let reply = self.request_json(&actor_id, &request, CALL_TIMEOUT).await?;
+        
+        // Pass MCP-shaped results through; anything else becomes the tool's text
+        let is_error = reply.get("isError").or_else(|| reply.get("is_error")).and_then(|v| v.as_bool());
+        let content = match reply["content"].as_array() {
+            Some(items) => items
+                .iter()
+                .map(|item| ToolContent::Text {
+                    text: match item["text"].as_str() {
+                        Some(text) => text.to_string(),
+                        None => item.to_string(),
+                    },
+                })
+                .collect(),
+            None => vec![ToolContent::Text { text: reply.to_string() }],
+        };
+        
+        Ok(ToolCallResult {
+            content,
+            is_error: Some(is_error.unwrap_or(false)),
+        })
+    }
+    
+    pub async fn list_actor_tools(&self, _args: Value) -> Result<ToolCallResult> {
+        let exports: Vec<Value> = self.exports.lock().unwrap()
+            .iter()
+            .map(|(alias, export)| json!({
+                "alias": alias,
+                "actor_id": export.actor_id,
+                "tools": export.tools,
+                "prompts": export.prompts
+            }))
+            .collect();
+        
+        // Create result
+        let result_json = json!({
+            "actors": exports,
+            "total": exports.len()
+        });
+        
+        ToolResult::ok(result_json).into_call_result()
+    }
+    
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(self: Arc<Self>) {
+        // Register the register_actor_tools tool
+        let register_actor_tools_tool = Tool {
+            name: "register_actor_tools".to_string(),
+            description: Some(
+                "Ask an actor for the tools and prompts it exports and offer them as actor.{alias}.{name}".to_string()
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor exporting tools"
+                    },
+                    "alias": {
+                        "type": "string",
+                        "description": "Name to register the tools and prompts under (letters, digits, _ and -); defaults to the start of the actor ID. Reusing an alias points its tools at this actor"
+                    }
+                },
+                "required": ["actor_id"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            &self.dispatcher,
+            register_actor_tools_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.register_actor_tools(args).await
+                }
+            },
+        );
+        
+        // Register the list_actor_tools tool
+        let list_actor_tools_tool = Tool {
+            name: "list_actor_tools".to_string(),
+            description: Some("List the actors whose tools and prompts are registered, by alias".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            &self.dispatcher,
+            list_actor_tools_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.list_actor_tools(args).await
+                }
+            },
+        );
+    }
+}
+
+/// Aliases and tool names become part of a tool name, so keep them to a safe alphabet
+pub(crate) fn check_name(what: &str, name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid {} '{}': use 1-64 letters, digits, '_' or '-'", what, name))
+    }
+}
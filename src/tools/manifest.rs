@@ -0,0 +1,198 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::events::filter::glob_match;
+use crate::theater::types::TheaterError;
+
+/// Locations `start_actor` may load manifests from.
+///
+/// Entries containing `*` are patterns matched against the manifest's
+/// canonical path (`*` also matches `/`); other entries are directories
+/// whose contents, at any depth, are allowed. Manifests are resolved
+/// through symlinks and `..` before checking, and inline manifest content
+/// is refused since it could point at any component on disk.
+#[derive(Debug, Clone)]
+pub struct ManifestAllowlist {
+    directories: Vec<PathBuf>,
+    patterns: Vec<String>,
+}
+
+impl ManifestAllowlist {
+    /// Build an allowlist, resolving its directories up front
+    pub fn new(entries: &[String]) -> Result<Self> {
+        let mut directories = Vec::new();
+        let mut patterns = Vec::new();
+        for entry in entries {
+            if entry.contains('*') {
+                patterns.push(entry.clone());
+            } else {
+                let directory = Path::new(entry)
+                    .canonicalize()
+                    .map_err(|e| anyhow!("Allowed manifest directory {} is not usable: {}", entry, e))?;
+                directories.push(directory);
+            }
+        }
+        Ok(Self { directories, patterns })
+    }
+
+    /// Check that a `start_actor` manifest argument is an allowed manifest
+    /// file, returning its canonical path.
+    ///
+    /// Callers must use the returned path rather than the argument, so a
+    /// relative path or a symlink can't resolve elsewhere afterwards.
+    pub fn check(&self, manifest: &str) -> Result<String, TheaterError> {
+        if manifest.contains('\n') {
+            return Err(Self::inline_refused());
+        }
+
+        let path = Path::new(manifest).canonicalize().map_err(|e| {
+            TheaterError::PermissionDenied(format!("manifest {} could not be resolved: {}", manifest, e))
+        })?;
+
+        let allowed = self.directories.iter().any(|directory| path.starts_with(directory))
+            || self.patterns.iter().any(|pattern| glob_match(pattern, &path.to_string_lossy()));
+        if !allowed {
+            return Err(TheaterError::PermissionDenied(format!(
+                "manifest {} is outside the allowed manifest locations",
+                path.display()
+            )));
+        }
+        path.into_os_string().into_string().map_err(|path| {
+            TheaterError::PermissionDenied(format!("manifest {} is not a UTF-8 path", path.to_string_lossy()))
+        })
+    }
+
+    /// The error for inline manifest content, which no allowlist admits
+    pub fn inline_refused() -> TheaterError {
+        TheaterError::PermissionDenied(
+            "inline manifests are not allowed; pass the path of a manifest in an allowed location".to_string(),
+        )
+    }
+}
+
+/// Manifest fields that must be present, all strings
+const REQUIRED_FIELDS: [&str; 3] = ["name", "version", "component_path"];
+
+/// Optional manifest fields that must be strings when present
+const OPTIONAL_STRING_FIELDS: [&str; 2] = ["description", "init_state"];
+
+/// A problem found in a manifest, and the field it concerns
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ManifestProblem {
+    /// Dotted path of the field, e.g. `handlers[0].type`; absent for syntax errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ManifestProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "{}: {}", field, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl ManifestProblem {
+    fn at(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: Some(field.into()),
+            message: message.into(),
+        }
+    }
+}
+
+/// Check a TOML manifest against the fields Theater expects, returning
+/// every problem found
+pub fn manifest_problems(content: &str) -> Vec<ManifestProblem> {
+    let table: toml::Table = match content.parse() {
+        Ok(table) => table,
+        Err(e) => {
+            let e: toml::de::Error = e;
+            return vec![ManifestProblem {
+                field: None,
+                message: syntax_error_message(content, &e),
+            }];
+        }
+    };
+
+    let mut problems = Vec::new();
+    for field in REQUIRED_FIELDS {
+        match table.get(field) {
+            Some(toml::Value::String(_)) => {}
+            Some(_) => problems.push(ManifestProblem::at(field, "must be a string")),
+            None => problems.push(ManifestProblem::at(field, "is required")),
+        }
+    }
+    for field in OPTIONAL_STRING_FIELDS {
+        if table.get(field).is_some_and(|value| !value.is_str()) {
+            problems.push(ManifestProblem::at(field, "must be a string"));
+        }
+    }
+    match table.get("handlers") {
+        None => {}
+        Some(toml::Value::Array(handlers)) => {
+            for (index, handler) in handlers.iter().enumerate() {
+                if !handler.get("type").is_some_and(|handler_type| handler_type.is_str()) {
+                    problems.push(ManifestProblem::at(
+                        format!("handlers[{}].type", index),
+                        "is required and must be a string",
+                    ));
+                }
+            }
+        }
+        Some(_) => problems.push(ManifestProblem::at("handlers", "must be an array of [[handlers]] tables")),
+    }
+    problems
+}
+
+/// Describe a TOML syntax error by its message and position only.
+///
+/// The parser's full error quotes the offending line, which would hand back
+/// pieces of whatever file the caller named.
+fn syntax_error_message(content: &str, error: &toml::de::Error) -> String {
+    let message = error.message().trim_end();
+    let Some(span) = error.span() else {
+        return message.to_string();
+    };
+    let before = &content[..span.start.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |last| last.chars().count()) + 1;
+    format!("{} at line {}, column {}", message, line, column)
+}
+
+/// Whether a manifest path names a TOML file
+pub fn is_toml_path(manifest: &str) -> bool {
+    Path::new(manifest).extension().is_some_and(|extension| extension == "toml")
+}
+
+/// Check that the component a manifest names exists, resolving a relative
+/// `component_path` against `base`. Component URLs aren't checked.
+pub fn component_problem(content: &str, base: &Path) -> Option<ManifestProblem> {
+    let table: toml::Table = content.parse().ok()?;
+    let component_path = table.get("component_path")?.as_str()?;
+    if component_path.contains("://") {
+        return None;
+    }
+    let component = base.join(component_path);
+    if component.is_file() {
+        return None;
+    }
+    Some(ManifestProblem::at(
+        "component_path",
+        format!("component {} does not exist", component.display()),
+    ))
+}
+
+/// Validate inline manifest content before it is sent to Theater
+pub fn validate_manifest_content(content: &str) -> Result<(), TheaterError> {
+    let problems = manifest_problems(content);
+    if problems.is_empty() {
+        return Ok(());
+    }
+    let problems: Vec<String> = problems.iter().map(|problem| problem.to_string()).collect();
+    Err(TheaterError::InvalidManifest(problems.join("; ")))
+}
@@ -1,44 +1,56 @@
 use anyhow::{anyhow, Result};
-use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
-use mcp_protocol::types::tool::{Tool, ToolCallResult, ToolContent};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
 use serde_json::{json, Value};
 use std::sync::Arc;
-use tracing::warn;
 
 use theater::id::TheaterId;
+use crate::encoding::PayloadEncoding;
+use crate::events::CausalityTracker;
 use crate::theater::client::TheaterClient;
+use crate::theater::limits::check_size;
 use crate::theater::TheaterIdExt;
-use crate::tools::utils::register_async_tool;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
 
 pub struct MessageTools {
     theater_client: Arc<TheaterClient>,
+    causality: Option<Arc<CausalityTracker>>,
 }
 
 impl MessageTools {
     pub fn new(theater_client: Arc<TheaterClient>) -> Self {
-        Self { theater_client }
+        Self {
+            theater_client,
+            causality: None,
+        }
     }
     
-    /// Helper method to handle Theater connection errors
-    fn handle_connection_error<T>(&self, result: Result<T>, context: &str) -> Result<T> {
-        match result {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("connect") || error_msg.contains("connection") || 
-                   error_msg.contains("read") || error_msg.contains("write") {
-                    // This is likely a connection issue
-                    warn!("Theater connection issue during {}: {}. Will attempt reconnection on next request.", context, error_msg);
-                    Err(anyhow!("Theater server connection issue: {}. The server will attempt to reconnect on the next request.", error_msg))
-                } else {
-                    // Other type of error
-                    Err(e)
-                }
-            }
-        }
+    /// Tag messages so the events they cause can be traced back to the tool call
+    pub fn with_causality(mut self, causality: Arc<CausalityTracker>) -> Self {
+        self.causality = Some(causality);
+        self
     }
     
     pub async fn send_message(&self, args: Value) -> Result<ToolCallResult> {
+        let encoding = PayloadEncoding::from_args(&args, PayloadEncoding::Base64)?;
+        self.send("send_message", &args, "data", encoding).await
+    }
+    
+    pub async fn send_json(&self, args: Value) -> Result<ToolCallResult> {
+        self.send("send_json", &args, "message", PayloadEncoding::Json).await
+    }
+    
+    pub async fn request_message(&self, args: Value) -> Result<ToolCallResult> {
+        let encoding = PayloadEncoding::from_args(&args, PayloadEncoding::Base64)?;
+        self.request("request_message", &args, "data", encoding).await
+    }
+    
+    pub async fn request_json(&self, args: Value) -> Result<ToolCallResult> {
+        self.request("request_json", &args, "message", PayloadEncoding::Json).await
+    }
+    
+    /// Send the payload in `data_param` as a one-way message
+    async fn send(&self, tool: &str, args: &Value, data_param: &str, encoding: PayloadEncoding) -> Result<ToolCallResult> {
         // Extract actor ID
         let actor_id_str = args["actor_id"].as_str()
             .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
@@ -47,35 +59,35 @@ impl MessageTools {
         let theater_id = TheaterId::from_str(actor_id_str)?;
             
         // Extract message data
-        let data_b64 = args["data"].as_str()
-            .ok_or_else(|| anyhow!("Missing data parameter"))?;
+        let data_arg = args.get(data_param)
+            .filter(|data| !data.is_null())
+            .ok_or_else(|| anyhow!("Missing {} parameter", data_param))?;
             
-        // Decode message data
-        let data = BASE64.decode(data_b64)?;
+        // Convert message data to bytes in the requested encoding
+        let data = encoding.encode(data_arg)?;
+        check_size("data", data.len(), self.theater_client.limits().max_message_bytes)?;
         
-        // Send the message with connection error handling
-        self.handle_connection_error(
-            self.theater_client.send_message(&theater_id, &data).await,
-            &format!("message send to {}", actor_id_str)
-        )?;
+        // Tag the operation before sending so its events are attributed to it
+        let operation = self.causality.as_ref().map(|c| c.record(tool, actor_id_str));
+        
+        // Send the message
+        self.theater_client.send_message(&theater_id, &data).await?;
         
         // Create result
-        let result_json = json!({
+        let mut result = ToolResult::ok(json!({
             "success": true,
             "actor_id": actor_id_str
-        });
+        }));
+        if let Some(operation) = operation {
+            result.data["operation_id"] = json!(operation.id);
+            result = result.with_resource(CausalityTracker::caused_events_uri(&operation));
+        }
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: serde_json::to_string(&result_json)? 
-                }
-            ],
-            is_error: Some(false),
-        })
+        result.into_call_result()
     }
     
-    pub async fn request_message(&self, args: Value) -> Result<ToolCallResult> {
+    /// Send the payload in `data_param` as a request and return the response
+    async fn request(&self, tool: &str, args: &Value, data_param: &str, encoding: PayloadEncoding) -> Result<ToolCallResult> {
         // Extract actor ID
         let actor_id_str = args["actor_id"].as_str()
             .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
@@ -84,41 +96,47 @@ impl MessageTools {
         let theater_id = TheaterId::from_str(actor_id_str)?;
             
         // Extract request data
-        let data_b64 = args["data"].as_str()
-            .ok_or_else(|| anyhow!("Missing data parameter"))?;
+        let data_arg = args.get(data_param)
+            .filter(|data| !data.is_null())
+            .ok_or_else(|| anyhow!("Missing {} parameter", data_param))?;
             
-        // Decode request data
-        let data = BASE64.decode(data_b64)?;
+        // Convert request data to bytes in the requested encoding
+        let data = encoding.encode(data_arg)?;
+        check_size("data", data.len(), self.theater_client.limits().max_message_bytes)?;
         
-        // Send the request and get response with connection error handling
-        let response_data = self.handle_connection_error(
-            self.theater_client.request_message(&theater_id, &data).await,
-            &format!("message request to {}", actor_id_str)
-        )?;
+        // Tag the operation before sending so its events are attributed to it
+        let operation = self.causality.as_ref().map(|c| c.record(tool, actor_id_str));
         
-        // Encode response data
-        let response_b64 = BASE64.encode(&response_data);
+        // Send the request and get response
+        let response_data = self.theater_client.request_message(&theater_id, &data).await?;
+        
+        // Decode response data with the request's encoding
+        let response = encoding.decode(&response_data);
         
         // Create result
-        let result_json = json!({
+        let raw_response = encoding == PayloadEncoding::Base64 || response.get("base64").is_some();
+        let mut result = ToolResult::ok(json!({
             "actor_id": actor_id_str,
-            "response": response_b64
-        });
+            "response": response
+        }));
+        // Spare clients decoding base64 when the response is readable text
+        if raw_response {
+            if let Ok(text) = std::str::from_utf8(&response_data) {
+                result.data["text"] = json!(text);
+            }
+        }
+        if let Some(operation) = operation {
+            result.data["operation_id"] = json!(operation.id);
+            result = result.with_resource(CausalityTracker::caused_events_uri(&operation));
+        }
         
-        Ok(ToolCallResult {
-            content: vec![
-                ToolContent::Text { 
-                    text: serde_json::to_string(&result_json)? 
-                }
-            ],
-            is_error: Some(false),
-        })
+        result.into_call_result()
     }
     
     /// Register the tools with the MCP tool manager
     pub fn register_tools(
         self: Arc<Self>,
-        tool_manager: &Arc<mcp_server::tools::ToolManager>,
+        dispatcher: &ToolDispatcher,
     ) {
         // Register the send_message tool
         let send_message_tool = Tool {
@@ -132,8 +150,12 @@ impl MessageTools {
                         "description": "ID of the actor to send the message to"
                     },
                     "data": {
+                        "description": "Message data, in the given encoding (base64 by default)"
+                    },
+                    "encoding": {
                         "type": "string",
-                        "description": "Message data (base64 encoded)"
+                        "enum": ["utf8", "json", "base64", "msgpack", "cbor"],
+                        "description": "How data is given: base64 bytes (default), utf8 text, or JSON sent as JSON, MessagePack or CBOR"
                     }
                 },
                 "required": ["actor_id", "data"]
@@ -143,7 +165,7 @@ impl MessageTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             send_message_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -165,8 +187,12 @@ impl MessageTools {
                         "description": "ID of the actor to send the request to"
                     },
                     "data": {
+                        "description": "Request data, in the given encoding (base64 by default)"
+                    },
+                    "encoding": {
                         "type": "string",
-                        "description": "Request data (base64 encoded)"
+                        "enum": ["utf8", "json", "base64", "msgpack", "cbor"],
+                        "description": "How data is given and the response returned: base64 bytes (default), utf8 text, or JSON sent as JSON, MessagePack or CBOR"
                     }
                 },
                 "required": ["actor_id", "data"]
@@ -176,7 +202,7 @@ impl MessageTools {
         
         let tools_self = self.clone();
         register_async_tool(
-            tool_manager,
+            dispatcher,
             request_message_tool,
             move |args| {
                 let tools_self = tools_self.clone();
@@ -185,5 +211,69 @@ impl MessageTools {
                 }
             },
         );
+        
+        // Register the send_json tool
+        let send_json_tool = Tool {
+            name: "send_json".to_string(),
+            description: Some("Send a JSON message to an actor, serialized as JSON bytes".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor to send the message to"
+                    },
+                    "message": {
+                        "description": "The JSON message"
+                    }
+                },
+                "required": ["actor_id", "message"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            send_json_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.send_json(args).await
+                }
+            },
+        );
+        
+        // Register the request_json tool
+        let request_json_tool = Tool {
+            name: "request_json".to_string(),
+            description: Some("Send a JSON request to an actor and receive its response parsed as JSON".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor to send the request to"
+                    },
+                    "message": {
+                        "description": "The JSON request"
+                    }
+                },
+                "required": ["actor_id", "message"]
+            }),
+            annotations: None,
+        };
+        
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            request_json_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.request_json(args).await
+                }
+            },
+        );
     }
 }
\ No newline at end of file
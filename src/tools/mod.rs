@@ -1,10 +1,39 @@
 mod actor;
+mod admission;
+mod calls;
 mod channel;
+mod confirm;
+mod converse;
+mod events;
+mod exported;
+mod manifest;
 mod message;
+mod plan;
+mod profile;
+mod result;
+mod schedule;
+mod policy;
 mod utils;
 
-pub use utils::register_async_tool;
+pub use utils::{register_async_tool, ToolDispatcher};
+
+pub use admission::{Admission, AdmissionLimits};
+pub use calls::{ToolCall, ToolCallLog, RECENT_CALLS};
+pub use confirm::{ConfirmationTokens, CONFIRMATION_TTL};
+pub use manifest::ManifestAllowlist;
+pub use policy::{ToolAction, ToolPolicy};
+pub use result::{ToolResult, ToolStatus};
 
 pub use actor::ActorTools;
 pub use channel::ChannelTools;
+pub use converse::ConverseTools;
+pub use events::EventTools;
+pub use exported::{ExportedTools, EXPORTED_TOOL_PREFIX};
+pub(crate) use exported::check_name;
 pub use message::MessageTools;
+pub use plan::PlanTools;
+pub use profile::ProfileTools;
+pub use schedule::ScheduleTools;
+
+#[cfg(test)]
+mod tests;
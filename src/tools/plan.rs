@@ -0,0 +1,328 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::tools::actor::ActorTools;
+use crate::tools::channel::ChannelTools;
+use crate::tools::message::MessageTools;
+use crate::tools::policy::{ToolPolicy, CONFIRMED_ARG};
+use crate::tools::result::{ToolResult, ToolStatus};
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Most steps a single plan may have
+const MAX_PLAN_STEPS: usize = 50;
+
+/// Tools a plan step may run
+const PLAN_OPS: &[&str] = &["start_actor", "open_channel", "send_message", "request_message", "send_on_channel"];
+
+/// A completed step that can be reversed if a later one fails
+enum Undo {
+    StopActor(String),
+    CloseChannel(String),
+}
+
+/// Runs an ordered list of actor, channel and message operations as one
+/// unit: if a step fails, the actors started and channels opened by earlier
+/// steps are stopped and closed again, in reverse order.
+///
+/// Steps name the tool to run in `op` and take that tool's arguments. A step
+/// with an `id` can be referred to by later steps: `"$id"` stands for the
+/// actor it started or channel it opened, and `"$id.field"` for any field of
+/// its result. Messages can't be unsent, so message steps are reported as
+/// not reversed.
+pub struct PlanTools {
+    actor_tools: Arc<ActorTools>,
+    message_tools: Arc<MessageTools>,
+    channel_tools: Arc<ChannelTools>,
+    policy: ToolPolicy,
+}
+
+impl PlanTools {
+    pub fn new(
+        actor_tools: Arc<ActorTools>,
+        message_tools: Arc<MessageTools>,
+        channel_tools: Arc<ChannelTools>,
+    ) -> Self {
+        Self {
+            actor_tools,
+            message_tools,
+            channel_tools,
+            policy: ToolPolicy::default(),
+        }
+    }
+
+    /// Apply the tool policy to every step, as if each were called directly
+    pub fn with_policy(mut self, policy: ToolPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub async fn run_plan(&self, args: Value) -> Result<ToolCallResult> {
+        let steps = args["steps"].as_array()
+            .ok_or_else(|| anyhow!("Missing steps parameter"))?;
+        let rollback = args["rollback"].as_bool().unwrap_or(true);
+        self.check_plan(steps)?;
+
+        let mut outputs: HashMap<String, Value> = HashMap::new();
+        let mut undo: Vec<(usize, Undo)> = Vec::new();
+        let mut completed = Vec::new();
+
+        for (index, step) in steps.iter().enumerate() {
+            let op = step["op"].as_str().unwrap_or_default();
+            let outcome = match substitute(&step_args(step), &outputs) {
+                Ok(step_args) => self.run_step(op, step_args).await,
+                Err(e) => Err(e),
+            };
+
+            match outcome {
+                Ok(result) => {
+                    match op {
+                        "start_actor" => undo.push((index, Undo::StopActor(string_field(&result, "actor_id")))),
+                        "open_channel" => undo.push((index, Undo::CloseChannel(string_field(&result, "channel_id")))),
+                        _ => {}
+                    }
+                    if let Some(id) = step["id"].as_str() {
+                        outputs.insert(id.to_string(), result.clone());
+                    }
+                    completed.push(json!({ "step": index, "op": op, "id": step["id"], "result": result }));
+                }
+                Err(e) => {
+                    warn!("Plan step {} ({}) failed: {}", index, op, e);
+                    let mut report = json!({
+                        "failed_step": index,
+                        "failed_op": op,
+                        "error": e.to_string(),
+                        "completed": completed
+                    });
+                    if rollback {
+                        let (rolled_back, rollback_errors) = self.roll_back(undo).await;
+                        report["rolled_back"] = json!(rolled_back);
+                        if !rollback_errors.is_empty() {
+                            report["rollback_errors"] = json!(rollback_errors);
+                        }
+                        let not_reversed: Vec<usize> = steps[..index].iter()
+                            .enumerate()
+                            .filter(|(_, step)| !matches!(step["op"].as_str(), Some("start_actor" | "open_channel")))
+                            .map(|(index, _)| index)
+                            .collect();
+                        if !not_reversed.is_empty() {
+                            report["not_reversed"] = json!(not_reversed);
+                        }
+                    }
+
+                    return ToolResult::error(report).into_call_result();
+                }
+            }
+        }
+
+        info!("Plan of {} steps completed", steps.len());
+        let result_json = json!({
+            "steps": completed
+        });
+
+        ToolResult::ok(result_json).into_call_result()
+    }
+
+    /// Refuse a plan with unknown or disallowed steps, or references to
+    /// steps that don't come earlier, before anything runs
+    fn check_plan(&self, steps: &[Value]) -> Result<()> {
+        if steps.is_empty() || steps.len() > MAX_PLAN_STEPS {
+            return Err(anyhow!("A plan needs between 1 and {} steps", MAX_PLAN_STEPS));
+        }
+
+        let mut ids: Vec<&str> = Vec::new();
+        for (index, step) in steps.iter().enumerate() {
+            let op = step["op"].as_str()
+                .ok_or_else(|| anyhow!("Step {} has no op", index))?;
+            if !PLAN_OPS.contains(&op) {
+                return Err(anyhow!("Step {} runs {}, but plans can only run {}", index, op, PLAN_OPS.join(", ")));
+            }
+            self.policy.check(op, step)
+                .map_err(|e| anyhow!("Step {} is refused: {}", index, e))?;
+
+            for reference in references(&step_args(step)) {
+                if !ids.contains(&reference) {
+                    return Err(anyhow!("Step {} refers to ${}, which no earlier step defines", index, reference));
+                }
+            }
+            if let Some(id) = step["id"].as_str() {
+                if ids.contains(&id) {
+                    return Err(anyhow!("Step {} reuses the id {}", index, id));
+                }
+                ids.push(id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Run one step's tool and return its JSON result
+    async fn run_step(&self, op: &str, args: Value) -> Result<Value> {
+        let result = match op {
+            "start_actor" => self.actor_tools.start_actor(args).await?,
+            "open_channel" => self.channel_tools.open_channel(args).await?,
+            "send_message" => self.message_tools.send_message(args).await?,
+            "request_message" => self.message_tools.request_message(args).await?,
+            "send_on_channel" => self.channel_tools.send_on_channel(args).await?,
+            other => return Err(anyhow!("Plans can't run {}", other)),
+        };
+        step_data(result)
+    }
+
+    /// Reverse completed steps, newest first
+    async fn roll_back(&self, undo: Vec<(usize, Undo)>) -> (Vec<usize>, Vec<Value>) {
+        let mut rolled_back = Vec::new();
+        let mut errors = Vec::new();
+        for (index, action) in undo.into_iter().rev() {
+            let outcome = match &action {
+                Undo::StopActor(actor_id) => {
+                    self.actor_tools.stop_actor(json!({ "actor_id": actor_id })).await
+                }
+                Undo::CloseChannel(channel_id) => {
+                    self.channel_tools.close_channel(json!({ "channel_id": channel_id })).await
+                }
+            };
+            match outcome.and_then(step_data) {
+                Ok(_) => rolled_back.push(index),
+                Err(e) => {
+                    warn!("Could not reverse plan step {}: {}", index, e);
+                    errors.push(json!({ "step": index, "error": e.to_string() }));
+                }
+            }
+        }
+        (rolled_back, errors)
+    }
+
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(
+        self: Arc<Self>,
+        dispatcher: &ToolDispatcher,
+    ) {
+        // Register the run_plan tool
+        let run_plan_tool = Tool {
+            name: "run_plan".to_string(),
+            description: Some(
+                "Run start_actor, open_channel, send_message, request_message and send_on_channel steps in order as one unit; if a step fails, actors started and channels opened by earlier steps are stopped and closed again".to_string()
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "steps": {
+                        "type": "array",
+                        "description": "Steps to run in order",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "op": {
+                                    "type": "string",
+                                    "enum": PLAN_OPS,
+                                    "description": "Tool to run; the step's other fields are its arguments"
+                                },
+                                "id": {
+                                    "type": "string",
+                                    "description": "Name later steps use to refer to this step: \"$id\" is the actor started or channel opened, \"$id.field\" any field of its result"
+                                }
+                            },
+                            "required": ["op"]
+                        }
+                    },
+                    "rollback": {
+                        "type": "boolean",
+                        "description": "Reverse completed steps if one fails (default true)"
+                    }
+                },
+                "required": ["steps"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            run_plan_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.run_plan(args).await
+                }
+            },
+        );
+    }
+}
+
+/// A step's tool arguments: everything but `op` and `id`
+fn step_args(step: &Value) -> Value {
+    let mut args = step.as_object().cloned().unwrap_or_default();
+    args.remove("op");
+    args.remove("id");
+    args.remove(CONFIRMED_ARG);
+    Value::Object(args)
+}
+
+/// The data of a step's result, or its failure as an error
+fn step_data(result: ToolCallResult) -> Result<Value> {
+    let envelope = ToolResult::from_call_result(&result)
+        .ok_or_else(|| anyhow!("Step returned no result"))?;
+    match envelope.status {
+        ToolStatus::Ok => Ok(envelope.data),
+        ToolStatus::Error => Err(anyhow!("{}", envelope.data)),
+    }
+}
+
+fn string_field(result: &Value, field: &str) -> String {
+    result[field].as_str().unwrap_or_default().to_string()
+}
+
+/// Split a `$id` or `$id.field` reference
+fn parse_reference(text: &str) -> Option<(&str, Option<&str>)> {
+    let reference = text.strip_prefix('$')?;
+    let (id, field) = match reference.split_once('.') {
+        Some((id, field)) => (id, Some(field)),
+        None => (reference, None),
+    };
+    let valid = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    (valid(id) && field.map_or(true, valid)).then_some((id, field))
+}
+
+/// Step ids an argument value refers to
+pub(crate) fn references(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(text) => parse_reference(text).map(|(id, _)| id).into_iter().collect(),
+        Value::Array(items) => items.iter().flat_map(references).collect(),
+        Value::Object(fields) => fields.values().flat_map(references).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Replace references to earlier steps with their results
+pub(crate) fn substitute(value: &Value, outputs: &HashMap<String, Value>) -> Result<Value> {
+    Ok(match value {
+        Value::String(text) => match parse_reference(text) {
+            Some((id, field)) => {
+                let output = outputs.get(id)
+                    .ok_or_else(|| anyhow!("${} refers to a step that hasn't run", id))?;
+                let resolved = match field {
+                    Some(field) => &output[field],
+                    // The actor or channel the step created
+                    None => output.get("channel_id").or_else(|| output.get("actor_id")).unwrap_or(output),
+                };
+                if resolved.is_null() {
+                    return Err(anyhow!("{} is not in the result of step {}", text, id));
+                }
+                resolved.clone()
+            }
+            None => value.clone(),
+        },
+        Value::Array(items) => Value::Array(
+            items.iter().map(|item| substitute(item, outputs)).collect::<Result<_>>()?
+        ),
+        Value::Object(fields) => Value::Object(
+            fields.iter()
+                .map(|(key, item)| Ok((key.clone(), substitute(item, outputs)?)))
+                .collect::<Result<Map<String, Value>>>()?
+        ),
+        other => other.clone(),
+    })
+}
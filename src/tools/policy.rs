@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::theater::types::TheaterError;
+
+/// Argument a tool call sets to show the user approved a `confirm` tool
+pub const CONFIRMED_ARG: &str = "confirmed";
+
+/// What happens when a tool is called
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolAction {
+    /// Run the call
+    #[default]
+    Allow,
+    /// Refuse the call
+    Deny,
+    /// Refuse the call unless it is repeated with `"confirmed": true` after
+    /// the user approved it
+    Confirm,
+}
+
+/// Per-tool permissions, e.g. loaded from a policy file:
+///
+/// ```json
+/// { "default": "allow", "tools": { "start_actor": "deny", "stop_actor": "confirm" } }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolPolicy {
+    /// Action for tools not listed in `tools`
+    pub default: ToolAction,
+    /// Action for each named tool
+    pub tools: HashMap<String, ToolAction>,
+}
+
+impl ToolPolicy {
+    /// Load a policy from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read tool policy {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid tool policy {}", path.display()))
+    }
+
+    /// Action configured for a tool
+    pub fn action(&self, tool: &str) -> ToolAction {
+        self.tools.get(tool).copied().unwrap_or(self.default)
+    }
+
+    /// Decide whether a call may run, given its arguments
+    pub fn check(&self, tool: &str, args: &serde_json::Value) -> Result<(), TheaterError> {
+        match self.action(tool) {
+            ToolAction::Allow => Ok(()),
+            ToolAction::Deny => Err(TheaterError::PermissionDenied(format!(
+                "{} is disabled by the tool policy",
+                tool
+            ))),
+            ToolAction::Confirm => {
+                if args[CONFIRMED_ARG].as_bool() == Some(true) {
+                    Ok(())
+                } else {
+                    Err(TheaterError::ConfirmationRequired(tool.to_string()))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::{anyhow, Result};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::actors::ActorProfiles;
+use crate::tools::actor::ActorTools;
+use crate::tools::exported::ExportedTools;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Starts actors from named launch profiles, so clients pick an actor by
+/// name instead of handling manifest paths.
+///
+/// Starts go through start_actor, so the manifest allowlist and actor quotas
+/// still apply. A profile with an alias also has the actor's exported tools
+/// registered under it, as register_actor_tools would.
+pub struct ProfileTools {
+    actor_tools: Arc<ActorTools>,
+    profiles: ActorProfiles,
+    exported_tools: Option<Arc<ExportedTools>>,
+}
+
+impl ProfileTools {
+    pub fn new(actor_tools: Arc<ActorTools>, profiles: ActorProfiles) -> Self {
+        Self {
+            actor_tools,
+            profiles,
+            exported_tools: None,
+        }
+    }
+
+    /// Register the exported tools of actors started from profiles with an alias
+    pub fn with_exported_tools(mut self, exported_tools: Arc<ExportedTools>) -> Self {
+        self.exported_tools = Some(exported_tools);
+        self
+    }
+
+    pub async fn start_profile(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract the profile name
+        let name = args["profile"].as_str()
+            .ok_or_else(|| anyhow!("Missing profile parameter"))?;
+        let profile = self.profiles.get(name)
+            .ok_or_else(|| anyhow!("Unknown profile '{}'; available profiles: {}", name, self.profiles.names().join(", ")))?;
+
+        // The call's initial state and ownership take precedence over the profile's
+        let mut start_args = json!({ "manifest": profile.manifest });
+        if let Some(state) = args.get("initial_state").filter(|state| !state.is_null()).or(profile.initial_state.as_ref()) {
+            start_args["initial_state"] = state.clone();
+        }
+        if let Some(owned) = args["owned"].as_bool().or(profile.owned) {
+            start_args["owned"] = json!(owned);
+        }
+
+        let started = self.actor_tools.start_actor(start_args).await?;
+        let Some(mut result) = ToolResult::from_call_result(&started) else {
+            return Ok(started);
+        };
+        result.data["profile"] = json!(name);
+
+        if let (Some(alias), Some(exported_tools)) = (&profile.alias, &self.exported_tools) {
+            let actor_id = result.data["actor_id"].clone();
+            result.data["alias"] = json!(alias);
+            match exported_tools.register_actor_tools(json!({ "actor_id": actor_id, "alias": alias })).await {
+                Ok(registered) => {
+                    if let Some(registered) = ToolResult::from_call_result(&registered) {
+                        result.data["tools"] = registered.data["tools"].clone();
+                    }
+                }
+                Err(e) => {
+                    // The actor is running; it just can't be reached by alias
+                    warn!("Could not register tools of profile {} as {}: {}", name, alias, e);
+                    result = result.with_warning(format!("could not register exported tools as {}: {}", alias, e));
+                }
+            }
+        }
+
+        result.into_call_result()
+    }
+
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(
+        self: Arc<Self>,
+        dispatcher: &ToolDispatcher,
+    ) {
+        let profile_list: Vec<String> = self.profiles.iter()
+            .map(|(name, profile)| match &profile.description {
+                Some(description) => format!("{} ({})", name, description),
+                None => name.to_string(),
+            })
+            .collect();
+
+        // Register the start_profile tool
+        let start_profile_tool = Tool {
+            name: "start_profile".to_string(),
+            description: Some(format!(
+                "Start an actor from a named launch profile. Profiles: {}",
+                profile_list.join("; ")
+            )),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "profile": {
+                        "type": "string",
+                        "enum": self.profiles.names(),
+                        "description": "Name of the profile to start"
+                    },
+                    "initial_state": {
+                        "type": "object",
+                        "description": "Initial state to use instead of the profile's"
+                    },
+                    "owned": {
+                        "type": "boolean",
+                        "description": "Stop the actor when this MCP session ends, overriding the profile"
+                    }
+                },
+                "required": ["profile"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            start_profile_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.start_profile(args).await
+                }
+            },
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use mcp_protocol::types::tool::{ToolCallResult, ToolContent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Whether a tool call did what it was asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolStatus {
+    Ok,
+    Error,
+}
+
+/// Envelope every built-in tool returns its result in, as the JSON text of
+/// the call result:
+///
+/// ```json
+/// { "status": "ok", "data": { "actor_id": "..." }, "warnings": ["..."],
+///   "resources": ["theater://actor/.../events?caused_by=..."], "elapsed_ms": 12 }
+/// ```
+///
+/// `data` is the tool's own result; the other fields are the same for every
+/// tool, so clients can check a call's outcome without knowing the tool.
+/// Empty `warnings` and `resources` are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolResult<T = Value> {
+    pub status: ToolStatus,
+    pub data: T,
+    /// Problems that didn't stop the call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// URIs of resources related to the result, e.g. the events the call caused
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<String>,
+    /// How long the call took, filled in when it passes through the dispatcher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub elapsed_ms: Option<u64>,
+}
+
+impl<T: Serialize> ToolResult<T> {
+    /// A call that succeeded
+    pub fn ok(data: T) -> Self {
+        Self::new(ToolStatus::Ok, data)
+    }
+
+    /// A call that failed, with details of the failure in `data`
+    pub fn error(data: T) -> Self {
+        Self::new(ToolStatus::Error, data)
+    }
+
+    fn new(status: ToolStatus, data: T) -> Self {
+        Self {
+            status,
+            data,
+            warnings: Vec::new(),
+            resources: Vec::new(),
+            elapsed_ms: None,
+        }
+    }
+
+    pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
+        self.warnings.push(warning.into());
+        self
+    }
+
+    pub fn with_warnings(mut self, warnings: impl IntoIterator<Item = String>) -> Self {
+        self.warnings.extend(warnings);
+        self
+    }
+
+    pub fn with_resource(mut self, uri: impl Into<String>) -> Self {
+        self.resources.push(uri.into());
+        self
+    }
+
+    /// Serialize into an MCP call result, flagged as an error unless the status is ok
+    pub fn into_call_result(self) -> Result<ToolCallResult> {
+        let is_error = self.status == ToolStatus::Error;
+        Ok(ToolCallResult {
+            content: vec![
+                ToolContent::Text {
+                    text: serde_json::to_string(&self)?
+                }
+            ],
+            is_error: Some(is_error),
+        })
+    }
+}
+
+impl ToolResult<Value> {
+    /// Read the envelope back out of a call result, if it carries one
+    pub fn from_call_result(result: &ToolCallResult) -> Option<Self> {
+        match result.content.as_slice() {
+            [ToolContent::Text { text }] => serde_json::from_str(text).ok(),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use mcp_protocol::types::tool::{Tool, ToolCallResult};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+use theater::id::TheaterId;
+use crate::encoding::PayloadEncoding;
+use crate::schedule::Scheduler;
+use crate::theater::client::TheaterClient;
+use crate::theater::limits::check_size;
+use crate::theater::TheaterIdExt;
+use crate::tools::result::ToolResult;
+use crate::tools::utils::{register_async_tool, ToolDispatcher};
+
+/// Tools to send messages to actors later, through the [`Scheduler`]
+pub struct ScheduleTools {
+    theater_client: Arc<TheaterClient>,
+    scheduler: Arc<Scheduler>,
+}
+
+impl ScheduleTools {
+    pub fn new(theater_client: Arc<TheaterClient>, scheduler: Arc<Scheduler>) -> Self {
+        Self {
+            theater_client,
+            scheduler,
+        }
+    }
+
+    pub async fn schedule_message(&self, args: Value) -> Result<ToolCallResult> {
+        // Extract actor ID, checking it parses
+        let actor_id_str = args["actor_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing actor_id parameter"))?;
+        TheaterId::from_str(actor_id_str)?;
+
+        // Extract message data
+        let data_arg = args.get("data")
+            .filter(|data| !data.is_null())
+            .ok_or_else(|| anyhow!("Missing data parameter"))?;
+        let encoding = PayloadEncoding::from_args(&args, PayloadEncoding::Base64)?;
+        let data = encoding.encode(data_arg)?;
+        check_size("data", data.len(), self.theater_client.limits().max_message_bytes)?;
+
+        // First delivery: at a given time, after a delay, or right away for recurring messages
+        let every_secs = args["every_secs"].as_u64();
+        let deliver_at = match (args["at"].as_str(), args["delay_secs"].as_u64()) {
+            (Some(_), Some(_)) => return Err(anyhow!("Give either at or delay_secs, not both")),
+            (Some(at), None) => DateTime::parse_from_rfc3339(at)
+                .map_err(|e| anyhow!("Invalid at time '{}': {}", at, e))?
+                .with_timezone(&Utc),
+            (None, Some(delay_secs)) => Scheduler::after(delay_secs)?,
+            (None, None) if every_secs.is_some() => Utc::now(),
+            (None, None) => return Err(anyhow!("Missing at, delay_secs or every_secs parameter")),
+        };
+
+        let scheduled = self.scheduler.schedule(actor_id_str, &data, deliver_at, every_secs)?;
+
+        let mut result = ToolResult::ok(json!({
+            "schedule_id": scheduled.schedule_id,
+            "actor_id": scheduled.actor_id,
+            "deliver_at": scheduled.deliver_at,
+            "every_secs": scheduled.every_secs
+        }));
+        if deliver_at < Utc::now() {
+            result = result.with_warning(format!("{} is in the past; the message will be sent right away", deliver_at));
+        }
+
+        result.into_call_result()
+    }
+
+    pub async fn list_scheduled(&self, args: Value) -> Result<ToolCallResult> {
+        let actor_id = args["actor_id"].as_str();
+        let scheduled: Vec<Value> = self.scheduler.list()
+            .into_iter()
+            .filter(|message| actor_id.map_or(true, |id| message.actor_id == id))
+            .map(|message| json!({
+                "schedule_id": message.schedule_id,
+                "actor_id": message.actor_id,
+                "deliver_at": message.deliver_at,
+                "every_secs": message.every_secs,
+                "deliveries": message.deliveries,
+                "last_error": message.last_error
+            }))
+            .collect();
+
+        // Create result
+        let result_json = json!({
+            "scheduled": scheduled,
+            "total": scheduled.len()
+        });
+
+        ToolResult::ok(result_json).into_call_result()
+    }
+
+    pub async fn cancel_scheduled(&self, args: Value) -> Result<ToolCallResult> {
+        let schedule_id = args["schedule_id"].as_str()
+            .ok_or_else(|| anyhow!("Missing schedule_id parameter"))?;
+        let cancelled = self.scheduler.cancel(schedule_id)
+            .ok_or_else(|| anyhow!("No scheduled message {}", schedule_id))?;
+
+        // Create result
+        let result_json = json!({
+            "schedule_id": cancelled.schedule_id,
+            "actor_id": cancelled.actor_id,
+            "deliveries": cancelled.deliveries
+        });
+
+        ToolResult::ok(result_json).into_call_result()
+    }
+
+    /// Register the tools with the MCP tool manager
+    pub fn register_tools(
+        self: Arc<Self>,
+        dispatcher: &ToolDispatcher,
+    ) {
+        // Register the schedule_message tool
+        let schedule_message_tool = Tool {
+            name: "schedule_message".to_string(),
+            description: Some("Send a message to an actor at a later time or after a delay, optionally repeating it on an interval".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "ID of the actor to send the message to"
+                    },
+                    "data": {
+                        "description": "Message data, in the given encoding (base64 by default)"
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "enum": ["utf8", "json", "base64", "msgpack", "cbor"],
+                        "description": "How data is given: base64 bytes (default), utf8 text, or JSON sent as JSON, MessagePack or CBOR"
+                    },
+                    "at": {
+                        "type": "string",
+                        "format": "date-time",
+                        "description": "When to send the message, as an RFC 3339 time"
+                    },
+                    "delay_secs": {
+                        "type": "integer",
+                        "description": "Send the message after this many seconds instead"
+                    },
+                    "every_secs": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Send the message again at this interval until cancelled; starts right away unless at or delay_secs is given"
+                    }
+                },
+                "required": ["actor_id", "data"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            schedule_message_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.schedule_message(args).await
+                }
+            },
+        );
+
+        // Register the list_scheduled tool
+        let list_scheduled_tool = Tool {
+            name: "list_scheduled".to_string(),
+            description: Some("List messages waiting to be sent, soonest first".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "actor_id": {
+                        "type": "string",
+                        "description": "Only list messages to this actor"
+                    }
+                }
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            list_scheduled_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.list_scheduled(args).await
+                }
+            },
+        );
+
+        // Register the cancel_scheduled tool
+        let cancel_scheduled_tool = Tool {
+            name: "cancel_scheduled".to_string(),
+            description: Some("Cancel a scheduled or recurring message".to_string()),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "schedule_id": {
+                        "type": "string",
+                        "description": "ID returned by schedule_message"
+                    }
+                },
+                "required": ["schedule_id"]
+            }),
+            annotations: None,
+        };
+
+        let tools_self = self.clone();
+        register_async_tool(
+            dispatcher,
+            cancel_scheduled_tool,
+            move |args| {
+                let tools_self = tools_self.clone();
+                async move {
+                    tools_self.cancel_scheduled(args).await
+                }
+            },
+        );
+    }
+}
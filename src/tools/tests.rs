@@ -0,0 +1,181 @@
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+use crate::session::SessionId;
+use crate::theater::types::TheaterError;
+use crate::tools::admission::{Admission, AdmissionLimits, TokenBucket};
+use crate::tools::confirm::ConfirmationTokens;
+use crate::tools::manifest::{manifest_problems, ManifestAllowlist, ManifestProblem};
+use crate::tools::policy::{ToolAction, ToolPolicy};
+use crate::tools::result::{ToolResult, ToolStatus};
+
+#[test]
+fn test_token_bucket_refills_over_time() {
+    let start = Instant::now();
+    let mut bucket = TokenBucket::new(2, 4.0, start);
+
+    assert!(bucket.try_take(start).is_ok());
+    assert!(bucket.try_take(start).is_ok());
+
+    // Empty: the next token arrives after a quarter second
+    let retry_after = bucket.try_take(start).unwrap_err();
+    assert_eq!(retry_after, Duration::from_millis(250));
+
+    assert!(bucket.try_take(start + Duration::from_millis(250)).is_ok());
+
+    // Refills never exceed capacity
+    let later = start + Duration::from_secs(60);
+    assert!(bucket.try_take(later).is_ok());
+    assert!(bucket.try_take(later).is_ok());
+    assert!(bucket.try_take(later).is_err());
+}
+
+#[test]
+fn test_admission_caps_calls_in_flight() {
+    let admission = Admission::new(AdmissionLimits {
+        max_concurrent_calls: 1,
+        calls_per_second: 100.0,
+        burst: 100,
+    });
+    let session = SessionId::new();
+
+    let permit = admission.admit(&session).unwrap();
+    assert!(matches!(admission.admit(&session), Err(TheaterError::RateLimited(..))));
+
+    drop(permit);
+    assert!(admission.admit(&session).is_ok());
+}
+
+#[test]
+fn test_manifest_allowlist() {
+    let root = std::env::temp_dir().join(format!("theater-mcp-manifests-{}", std::process::id()));
+    let allowed = root.join("allowed");
+    let other = root.join("other");
+    std::fs::create_dir_all(&allowed).unwrap();
+    std::fs::create_dir_all(&other).unwrap();
+    std::fs::write(allowed.join("actor.toml"), "name = \"a\"").unwrap();
+    std::fs::write(other.join("actor.toml"), "name = \"b\"").unwrap();
+    std::fs::write(other.join("trusted.toml"), "name = \"c\"").unwrap();
+
+    let allowlist = ManifestAllowlist::new(&[
+        allowed.to_string_lossy().into_owned(),
+        "*/trusted.toml".to_string(),
+    ])
+    .unwrap();
+
+    assert!(allowlist.check(&allowed.join("actor.toml").to_string_lossy()).is_ok());
+    assert!(allowlist.check(&other.join("trusted.toml").to_string_lossy()).is_ok());
+    assert!(matches!(
+        allowlist.check(&other.join("actor.toml").to_string_lossy()),
+        Err(TheaterError::PermissionDenied(_))
+    ));
+    // The canonical path is what gets started, not the argument
+    let roundabout = allowed.join("..").join("allowed").join("actor.toml");
+    assert_eq!(
+        allowlist.check(&roundabout.to_string_lossy()).unwrap(),
+        allowed.join("actor.toml").canonicalize().unwrap().to_string_lossy()
+    );
+    // `..` cannot escape an allowed directory
+    let escaped = allowed.join("..").join("other").join("actor.toml");
+    assert!(allowlist.check(&escaped.to_string_lossy()).is_err());
+    assert!(allowlist.check("name = \"inline\"\ncomponent_path = \"/tmp/x.wasm\"").is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_manifest_problems() {
+    let valid = "name = \"echo\"\nversion = \"0.1.0\"\ncomponent_path = \"echo.wasm\"\n\n[[handlers]]\ntype = \"runtime\"\nconfig = {}\n";
+    assert!(manifest_problems(valid).is_empty());
+
+    let problems = manifest_problems("name = 3\nversion = \"0.1.0\"\n\n[[handlers]]\nconfig = {}\n");
+    assert_eq!(
+        problems,
+        vec![
+            ManifestProblem { field: Some("name".to_string()), message: "must be a string".to_string() },
+            ManifestProblem { field: Some("component_path".to_string()), message: "is required".to_string() },
+            ManifestProblem {
+                field: Some("handlers[0].type".to_string()),
+                message: "is required and must be a string".to_string(),
+            },
+        ]
+    );
+
+    // Syntax errors aren't tied to a field, and give a position rather than
+    // quoting the source
+    let problems = manifest_problems("version = \"0.1.0\"\nname = secret-value");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].field.is_none());
+    assert!(problems[0].message.contains("line 2"), "{}", problems[0].message);
+    assert!(!problems[0].message.contains("secret-value"), "{}", problems[0].message);
+}
+
+#[test]
+fn test_tool_policy() {
+    let policy: ToolPolicy = serde_json::from_str(
+        r#"{"default": "deny", "tools": {"send_message": "allow", "stop_actor": "confirm"}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(policy.action("start_actor"), ToolAction::Deny);
+    assert!(policy.check("send_message", &serde_json::json!({})).is_ok());
+    assert!(matches!(
+        policy.check("start_actor", &serde_json::json!({"confirmed": true})),
+        Err(TheaterError::PermissionDenied(_))
+    ));
+    assert!(matches!(
+        policy.check("stop_actor", &serde_json::json!({"actor_id": "a"})),
+        Err(TheaterError::ConfirmationRequired(_))
+    ));
+    assert!(policy.check("stop_actor", &serde_json::json!({"actor_id": "a", "confirmed": true})).is_ok());
+}
+
+#[test]
+fn test_confirmation_tokens_are_single_use() {
+    let confirmations = ConfirmationTokens::new();
+    let token = confirmations.issue("stop_all_actors", vec!["a".to_string(), "b".to_string()]);
+
+    // A token only confirms the operation it was issued for
+    assert!(confirmations.redeem(&token, "close_all_channels").is_err());
+
+    assert_eq!(confirmations.redeem(&token, "stop_all_actors").unwrap(), vec!["a", "b"]);
+    assert!(confirmations.redeem(&token, "stop_all_actors").is_err());
+
+    let expired = ConfirmationTokens::with_ttl(Duration::ZERO);
+    let token = expired.issue("stop_all_actors", vec![]);
+    assert!(expired.redeem(&token, "stop_all_actors").is_err());
+}
+
+#[test]
+fn test_exported_tool_names() {
+    use crate::tools::exported::check_name;
+
+    assert!(check_name("alias", "counter-1").is_ok());
+    assert!(check_name("tool name", "get_count").is_ok());
+    // Names become part of actor.{alias}.{tool}, so no separators or spaces
+    assert!(check_name("alias", "a.b").is_err());
+    assert!(check_name("tool name", "drop table").is_err());
+    assert!(check_name("alias", "").is_err());
+}
+
+#[test]
+fn test_tool_result_envelope() {
+    let result = ToolResult::ok(json!({ "actor_id": "a" }))
+        .with_warning("event stream unavailable")
+        .with_resource("theater://events/a")
+        .into_call_result()
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+
+    let envelope = ToolResult::from_call_result(&result).unwrap();
+    assert_eq!(envelope.status, ToolStatus::Ok);
+    assert_eq!(envelope.data["actor_id"], "a");
+    assert_eq!(envelope.warnings, vec!["event stream unavailable"]);
+    assert_eq!(envelope.resources, vec!["theater://events/a"]);
+
+    // Empty fields are left out
+    let result = ToolResult::error(json!({ "error": "x" })).into_call_result().unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let envelope = serde_json::to_value(ToolResult::from_call_result(&result).unwrap()).unwrap();
+    assert_eq!(envelope, json!({ "status": "error", "data": { "error": "x" } }));
+}
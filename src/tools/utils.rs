@@ -1,50 +1,174 @@
 use anyhow::Result;
+use chrono::Utc;
 use mcp_protocol::types::tool::{Tool, ToolCallResult};
 use mcp_server::tools::ToolManager;
+use std::collections::HashMap;
 use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
-use tokio::runtime::Handle;
+use std::time::Instant;
+use tokio::sync::Semaphore;
+use tracing::debug;
 
-/// Register an async tool with the tool manager
+use crate::session::SessionId;
+use crate::theater::types::TheaterError;
+use crate::tools::admission::Admission;
+use crate::tools::calls::{ToolCall, ToolCallLog};
+use crate::tools::policy::{ToolAction, ToolPolicy, CONFIRMED_ARG};
+use crate::tools::result::{ToolResult, ToolStatus};
+
+/// Boxed future returned by async tool handlers
+pub type ToolFuture = Pin<Box<dyn Future<Output = Result<ToolCallResult>> + Send>>;
+
+/// Where tools are registered: the MCP tool manager, plus the policy,
+/// admission checks and concurrency limits every call passes before its
+/// handler runs
+#[derive(Clone)]
+pub struct ToolDispatcher {
+    tool_manager: Arc<ToolManager>,
+    admission: Arc<Admission>,
+    session_id: SessionId,
+    tool_limits: HashMap<String, usize>,
+    policy: Arc<ToolPolicy>,
+    calls: Option<Arc<ToolCallLog>>,
+}
+
+impl ToolDispatcher {
+    pub fn new(tool_manager: Arc<ToolManager>, admission: Arc<Admission>, session_id: SessionId) -> Self {
+        Self {
+            tool_manager,
+            admission,
+            session_id,
+            tool_limits: HashMap::new(),
+            policy: Arc::new(ToolPolicy::default()),
+            calls: None,
+        }
+    }
+    
+    /// Cap how many calls of each named tool run at once. Calls over the cap
+    /// wait for a running one to finish.
+    pub fn with_tool_limits(mut self, tool_limits: HashMap<String, usize>) -> Self {
+        self.tool_limits = tool_limits;
+        self
+    }
+    
+    /// Allow, deny or require confirmation for tools by name
+    pub fn with_policy(mut self, policy: ToolPolicy) -> Self {
+        self.policy = Arc::new(policy);
+        self
+    }
+    
+    /// Record every finished call in this log
+    pub fn with_call_log(mut self, calls: Arc<ToolCallLog>) -> Self {
+        self.calls = Some(calls);
+        self
+    }
+}
+
+/// Register an async tool with the tool manager.
+///
+/// The handler is awaited on the server's runtime like any other task, so
+/// in-flight tool calls don't each tie up a worker or blocking-pool thread.
+/// Calls the tool policy or admission layer turns away fail without reaching
+/// the handler, and tools with a concurrency limit queue for a free slot.
+/// Payloads over a size limit are reported as a structured error result.
+/// Results in the [`ToolResult`] envelope get the call's `elapsed_ms`, and
+/// finished calls are added to the dispatcher's call log, if it has one.
+/// Tools the policy wants confirmed get a `confirmed` argument in their schema.
 pub fn register_async_tool<F, Fut>(
-    tool_manager: &Arc<ToolManager>,
-    tool: Tool,
+    dispatcher: &ToolDispatcher,
+    mut tool: Tool,
     handler: F,
 )
 where
     F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
     Fut: Future<Output = Result<ToolCallResult>> + Send + 'static,
 {
-    // Clone the handler to an Arc
-    let handler = Arc::new(handler);
-    
-    // Create a sync wrapper that will execute the async handler
-    let sync_handler = move |args: serde_json::Value| -> Result<ToolCallResult> {
-        let handler = handler.clone();
-        let args = args.clone(); // Clone args to avoid borrowing issues
-        
-        // Try to get the current runtime handle
-        if let Ok(handle) = Handle::try_current() {
-            // We're in a tokio runtime, use block_in_place
-            tokio::task::block_in_place(move || {
-                // Run the async handler and wait for the result
-                handle.block_on(async move {
-                    handler(args).await
-                })
-            })
-        } else {
-            // No runtime available, create a new one
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()?;
-                
-            // Run the async handler and wait for the result
-            rt.block_on(async move {
-                handler(args).await
-            })
+    let policy = dispatcher.policy.clone();
+    if policy.action(&tool.name) == ToolAction::Confirm {
+        if let Some(properties) = tool.input_schema["properties"].as_object_mut() {
+            properties.insert(CONFIRMED_ARG.to_string(), serde_json::json!({
+                "type": "boolean",
+                "description": "Set to true once the user has approved this call"
+            }));
+        }
+    }
+    let admission = dispatcher.admission.clone();
+    let calls = dispatcher.calls.clone();
+    let session_id = dispatcher.session_id.clone();
+    let name = tool.name.clone();
+    let slots = dispatcher.tool_limits.get(&name)
+        .map(|limit| Arc::new(Semaphore::new((*limit).max(1))));
+    dispatcher.tool_manager.register_async_tool(tool, move |mut args: serde_json::Value| -> ToolFuture {
+        if let Err(e) = policy.check(&name, &args) {
+            debug!("Refused {} call: {}", name, e);
+            return Box::pin(async move { Err::<ToolCallResult, _>(e.into()) });
         }
+        if let Some(args) = args.as_object_mut() {
+            args.remove(CONFIRMED_ARG);
+        }
+        let permit = match admission.admit(&session_id) {
+            Ok(permit) => permit,
+            Err(e) => {
+                debug!("Rejected {} call: {}", name, e);
+                return Box::pin(async move { Err::<ToolCallResult, _>(e.into()) });
+            }
+        };
+        let call = handler(args);
+        let slots = slots.clone();
+        let calls = calls.clone();
+        let name = name.clone();
+        Box::pin(async move {
+            // Still holding the admission permit, so queued calls count toward the global cap
+            let _slot = match slots {
+                Some(slots) => Some(slots.acquire_owned().await?),
+                None => None,
+            };
+            let started = Instant::now();
+            let result = call.await;
+            drop(permit);
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+            let result = match result {
+                Err(e) => payload_too_large(&e).ok_or(e),
+                Ok(result) => Ok(result),
+            };
+            if let Some(calls) = calls {
+                calls.record(ToolCall {
+                    tool: name,
+                    at: Utc::now(),
+                    status: match &result {
+                        Ok(result) if result.is_error != Some(true) => ToolStatus::Ok,
+                        _ => ToolStatus::Error,
+                    },
+                    elapsed_ms,
+                    error: result.as_ref().err().map(|e| e.to_string()),
+                });
+            }
+            let result = result?;
+            match ToolResult::from_call_result(&result) {
+                Some(mut envelope) => {
+                    envelope.elapsed_ms = Some(elapsed_ms);
+                    envelope.into_call_result()
+                }
+                // Results the tool passes through as they are, e.g. from actors
+                None => Ok(result),
+            }
+        })
+    });
+}
+
+/// Error result for a payload over a size limit, with the field, its size and
+/// the limit, so clients can shrink the payload rather than retry it
+fn payload_too_large(error: &anyhow::Error) -> Option<ToolCallResult> {
+    let TheaterError::TooLarge(what, size, limit) = error.downcast_ref::<TheaterError>()? else {
+        return None;
     };
-    
-    // Register the sync wrapper with the tool manager
-    tool_manager.register_tool(tool, sync_handler);
+    let data = serde_json::json!({
+        "error": "payload_too_large",
+        "message": error.to_string(),
+        "field": what,
+        "size": size,
+        "limit": limit
+    });
+    ToolResult::error(data).into_call_result().ok()
 }
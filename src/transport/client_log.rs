@@ -0,0 +1,125 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mcp_protocol::JsonRpcMessage;
+use mcp_server::transport::Transport;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::logging::{ClientLog, ClientLogLevel};
+
+/// Client messages buffered on their way to the server
+const INCOMING_CAPACITY: usize = 100;
+
+/// Invalid params, from JSON-RPC
+const INVALID_PARAMS: i64 = -32602;
+
+/// Wraps a transport to give the session MCP's logging capability.
+///
+/// The `logging` capability is added to the initialize response, and
+/// `logging/setLevel` requests are answered here rather than reaching the
+/// server. Once the session is initialized, the [`ClientLog`] sends its
+/// messages over the wrapped transport.
+pub struct ClientLogTransport {
+    inner: Box<dyn Transport>,
+    log: Arc<ClientLog>,
+    // ID of the client's initialize request, until it is answered
+    initialize_id: Arc<Mutex<Option<Value>>>,
+}
+
+impl ClientLogTransport {
+    pub fn new<T: Transport + 'static>(inner: T, log: Arc<ClientLog>) -> Self {
+        Self {
+            inner: Box::new(inner),
+            log,
+            initialize_id: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for ClientLogTransport {
+    async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
+        let (incoming_tx, mut incoming) = mpsc::channel::<JsonRpcMessage>(INCOMING_CAPACITY);
+        let transport = self.inner.box_clone();
+        let log = self.log.clone();
+        let initialize_id = self.initialize_id.clone();
+        tokio::spawn(async move {
+            while let Some(message) = incoming.recv().await {
+                let value = serde_json::to_value(&message).unwrap_or_default();
+                match value["method"].as_str() {
+                    Some("logging/setLevel") => {
+                        let response = set_level(&log, &value);
+                        match serde_json::from_value(response) {
+                            Ok(response) => {
+                                if let Err(e) = transport.send(response).await {
+                                    warn!("Could not answer logging/setLevel: {}", e);
+                                }
+                            }
+                            Err(e) => warn!("Could not answer logging/setLevel: {}", e),
+                        }
+                        continue;
+                    }
+                    Some("initialize") => {
+                        *initialize_id.lock().unwrap() = value.get("id").cloned();
+                    }
+                    _ => {}
+                }
+                if message_tx.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        self.inner.start(incoming_tx).await
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        let initialize_id = self.initialize_id.lock().unwrap().clone();
+        let Some(initialize_id) = initialize_id else {
+            return self.inner.send(message).await;
+        };
+        let mut value = serde_json::to_value(&message)?;
+        if value.get("id") != Some(&initialize_id) || value.get("result").is_none() {
+            return self.inner.send(message).await;
+        }
+
+        // Advertise logging alongside the server's own capabilities
+        value["result"]["capabilities"]["logging"] = json!({});
+        self.initialize_id.lock().unwrap().take();
+        self.inner.send(serde_json::from_value(value)?).await?;
+        self.log.attach(self.inner.box_clone());
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.inner.close().await
+    }
+
+    fn box_clone(&self) -> Box<dyn Transport> {
+        Box::new(Self {
+            inner: self.inner.box_clone(),
+            log: self.log.clone(),
+            initialize_id: self.initialize_id.clone(),
+        })
+    }
+}
+
+/// Apply a `logging/setLevel` request, returning its response
+pub(crate) fn set_level(log: &ClientLog, request: &Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    match serde_json::from_value::<ClientLogLevel>(request["params"]["level"].clone()) {
+        Ok(level) => {
+            log.set_level(level);
+            json!({ "jsonrpc": "2.0", "id": id, "result": {} })
+        }
+        Err(_) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": INVALID_PARAMS,
+                "message": format!("Unknown log level {}", request["params"]["level"])
+            }
+        }),
+    }
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::theater::client::TheaterClient;
+
+/// Liveness and readiness of the server, for container orchestrators.
+///
+/// `/healthz` answers as long as the process serves requests. `/readyz`
+/// answers 503 while the connection to the Theater server is down, so
+/// traffic is routed elsewhere until it reconnects.
+pub struct Health {
+    theater_client: Arc<TheaterClient>,
+    started_at: Instant,
+}
+
+impl Health {
+    pub fn new(theater_client: Arc<TheaterClient>) -> Self {
+        Self {
+            theater_client,
+            started_at: Instant::now(),
+        }
+    }
+
+    /// Routes serving `/healthz` and `/readyz`, for mounting on any HTTP listener
+    pub fn router(self: Arc<Self>) -> Router {
+        Router::new()
+            .route("/healthz", get(healthz))
+            .route("/readyz", get(readyz))
+            .with_state(self)
+    }
+
+    /// Serve the health routes on their own listener until the task is aborted
+    pub async fn spawn_listener(self: Arc<Self>, address: SocketAddr) -> Result<JoinHandle<()>> {
+        let listener = TcpListener::bind(address).await?;
+        info!("Serving health checks on {}", listener.local_addr()?);
+        let router = self.router();
+        Ok(tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                warn!("Health check listener failed: {}", e);
+            }
+        }))
+    }
+}
+
+async fn healthz(State(health): State<Arc<Health>>) -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "uptime_secs": health.started_at.elapsed().as_secs()
+    }))
+}
+
+async fn readyz(State(health): State<Arc<Health>>) -> (StatusCode, Json<Value>) {
+    let connected = health.theater_client.is_connected();
+    let status = match connected {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    let body = json!({
+        "status": if connected { "ready" } else { "unavailable" },
+        "theater": {
+            "connected": connected,
+            "connection_generation": health.theater_client.connection_generation()
+        }
+    });
+    (status, Json(body))
+}
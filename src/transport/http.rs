@@ -0,0 +1,335 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use futures::stream::{self, Stream};
+use mcp_protocol::JsonRpcMessage;
+use mcp_server::transport::Transport;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::auth::BearerAuth;
+use crate::transport::listen::bind_listener;
+
+/// Header carrying the session ID after initialization
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Path the MCP endpoint is served at
+pub const MCP_PATH: &str = "/mcp";
+
+/// Server messages buffered for each listening stream before it lags
+const OUTBOUND_CAPACITY: usize = 256;
+
+/// How a JSON-RPC message is routed
+pub(crate) enum MessageKind {
+    /// A request, keyed by its ID
+    Request(String),
+    /// A response to a request, keyed by its ID
+    Response(String),
+    Notification,
+}
+
+impl MessageKind {
+    pub(crate) fn of(message: &Value) -> Self {
+        match (message.get("id"), message.get("method")) {
+            (Some(id), Some(_)) => MessageKind::Request(id.to_string()),
+            (Some(id), None) => MessageKind::Response(id.to_string()),
+            _ => MessageKind::Notification,
+        }
+    }
+}
+
+/// State shared between the transport and its HTTP handlers
+struct HttpShared {
+    auth: Option<BearerAuth>,
+    // Where client messages go; taken when the session ends
+    incoming: Mutex<Option<mpsc::Sender<JsonRpcMessage>>>,
+    // Requests waiting for the server's response, by request ID
+    pending: Mutex<HashMap<String, oneshot::Sender<JsonRpcMessage>>>,
+    // Notifications and server requests, for clients listening with GET
+    outbound: broadcast::Sender<JsonRpcMessage>,
+    session: Mutex<SessionState>,
+}
+
+/// Progress of the transport's one session
+enum SessionState {
+    Idle,
+    /// An initialize request is waiting for the server's answer
+    Initializing,
+    Active(String),
+}
+
+/// MCP's Streamable HTTP transport, serving one MCP session.
+///
+/// Clients POST each JSON-RPC message to `/mcp`. A request is answered in
+/// the POST's response body; notifications and responses get `202
+/// Accepted`. A GET on `/mcp` opens an event stream carrying the server's
+/// notifications and requests. `initialize` starts the session and returns
+/// its ID in `Mcp-Session-Id`, which every later call must send back; a
+/// DELETE with it ends the session, like closing stdio does. When a bearer
+/// token is configured, every call must present it.
+#[derive(Clone)]
+pub struct HttpTransport {
+    shared: Arc<HttpShared>,
+    listener: Arc<Mutex<Option<TcpListener>>>,
+    server: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl HttpTransport {
+    /// Listen on the socket-activated socket or `address`, so bind errors
+    /// surface before the server starts
+    pub async fn bind(address: SocketAddr, auth: Option<BearerAuth>) -> Result<Self> {
+        let listener = bind_listener(address).await?;
+        Ok(Self {
+            shared: Arc::new(HttpShared {
+                auth,
+                incoming: Mutex::new(None),
+                pending: Mutex::new(HashMap::new()),
+                outbound: broadcast::channel(OUTBOUND_CAPACITY).0,
+                session: Mutex::new(SessionState::Idle),
+            }),
+            listener: Arc::new(Mutex::new(Some(listener))),
+            server: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Address the transport listens on
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        match self.listener.lock().unwrap().as_ref() {
+            Some(listener) => Ok(listener.local_addr()?),
+            None => Err(anyhow!("HTTP transport is already serving")),
+        }
+    }
+
+    fn router(&self) -> Router {
+        Router::new()
+            .route(MCP_PATH, post(post_message).get(open_stream).delete(end_session))
+            .with_state(self.shared.clone())
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
+        let listener = self
+            .listener
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow!("HTTP transport is already serving"))?;
+        *self.shared.incoming.lock().unwrap() = Some(message_tx);
+
+        info!("Serving MCP over Streamable HTTP on {}{}", listener.local_addr()?, MCP_PATH);
+        let router = self.router();
+        *self.server.lock().unwrap() = Some(tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                warn!("HTTP transport failed: {}", e);
+            }
+        }));
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        let value = serde_json::to_value(&message)?;
+        if let MessageKind::Response(id) = MessageKind::of(&value) {
+            if let Some(waiting) = self.shared.pending.lock().unwrap().remove(&id) {
+                // The client may have gone away; nothing else wants the response
+                let _ = waiting.send(message);
+                return Ok(());
+            }
+        }
+        // Nobody listening is fine; the message is dropped like an unread stdout line
+        let _ = self.shared.outbound.send(message);
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.shared.incoming.lock().unwrap().take();
+        if let Some(server) = self.server.lock().unwrap().take() {
+            server.abort();
+        }
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
+
+/// Refuse calls without the configured bearer token
+pub(crate) fn check_auth(auth: &Option<BearerAuth>, headers: &HeaderMap) -> Result<(), Response> {
+    let Some(auth) = auth else {
+        return Ok(());
+    };
+    let authorization = headers.get("authorization").and_then(|value| value.to_str().ok());
+    auth.check(authorization).map_err(|e| {
+        debug!("Refused HTTP transport call: {}", e);
+        error_response(StatusCode::UNAUTHORIZED, &e.to_string())
+    })
+}
+
+pub(crate) fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Pass a client message on to the server
+pub(crate) async fn forward(
+    incoming: &Mutex<Option<mpsc::Sender<JsonRpcMessage>>>,
+    message: JsonRpcMessage,
+) -> Result<(), Response> {
+    let incoming = incoming.lock().unwrap().clone();
+    let ended = || error_response(StatusCode::SERVICE_UNAVAILABLE, "the MCP session has ended");
+    let Some(incoming) = incoming else {
+        return Err(ended());
+    };
+    incoming.send(message).await.map_err(|_| ended())
+}
+
+/// Check the call's `Mcp-Session-Id`
+fn check_session(shared: &HttpShared, headers: &HeaderMap) -> Result<(), Response> {
+    let presented = headers.get(SESSION_HEADER).and_then(|value| value.to_str().ok());
+    match (&*shared.session.lock().unwrap(), presented) {
+        (SessionState::Active(current), Some(presented)) if current == presented => Ok(()),
+        (_, None) => Err(error_response(StatusCode::BAD_REQUEST, "missing Mcp-Session-Id header")),
+        _ => Err(error_response(StatusCode::NOT_FOUND, "unknown MCP session")),
+    }
+}
+
+async fn post_message(State(shared): State<Arc<HttpShared>>, headers: HeaderMap, body: Bytes) -> Response {
+    if let Err(response) = check_auth(&shared.auth, &headers) {
+        return response;
+    }
+    let value: Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("invalid JSON: {}", e)),
+    };
+    let message: JsonRpcMessage = match serde_json::from_value(value.clone()) {
+        Ok(message) => message,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("invalid JSON-RPC message: {}", e)),
+    };
+
+    // initialize starts the session; everything else must belong to it
+    if value["method"] != "initialize" {
+        if let Err(response) = check_session(&shared, &headers) {
+            return response;
+        }
+        return reply(exchange(&shared, &value, message).await);
+    }
+
+    {
+        let mut session = shared.session.lock().unwrap();
+        if !matches!(*session, SessionState::Idle) {
+            return error_response(StatusCode::CONFLICT, "an MCP session is already active");
+        }
+        *session = SessionState::Initializing;
+    }
+
+    // The session only starts once the server accepts the initialize request
+    let response = match exchange(&shared, &value, message).await {
+        Ok(Some(response))
+            if serde_json::to_value(&response).is_ok_and(|response| response.get("result").is_some()) =>
+        {
+            response
+        }
+        failed => {
+            *shared.session.lock().unwrap() = SessionState::Idle;
+            return reply(failed);
+        }
+    };
+    let id = Uuid::new_v4().to_string();
+    info!("HTTP transport session {} started", id);
+    *shared.session.lock().unwrap() = SessionState::Active(id.clone());
+
+    let mut response = Json(response).into_response();
+    if let Ok(session_id) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(SESSION_HEADER, session_id);
+    }
+    response
+}
+
+/// The HTTP response for the outcome of an exchange
+fn reply(result: Result<Option<JsonRpcMessage>, Response>) -> Response {
+    match result {
+        Ok(Some(response)) => Json(response).into_response(),
+        Ok(None) => StatusCode::ACCEPTED.into_response(),
+        Err(response) => response,
+    }
+}
+
+/// Forward a client message, returning the server's response if it is a request
+async fn exchange(shared: &HttpShared, value: &Value, message: JsonRpcMessage) -> Result<Option<JsonRpcMessage>, Response> {
+    let MessageKind::Request(id) = MessageKind::of(value) else {
+        forward(&shared.incoming, message).await?;
+        return Ok(None);
+    };
+
+    // Wait for the server's response to the request
+    let (respond, response) = oneshot::channel();
+    shared.pending.lock().unwrap().insert(id.clone(), respond);
+    if let Err(response) = forward(&shared.incoming, message).await {
+        shared.pending.lock().unwrap().remove(&id);
+        return Err(response);
+    }
+    match response.await {
+        Ok(response) => Ok(Some(response)),
+        Err(_) => Err(error_response(StatusCode::SERVICE_UNAVAILABLE, "the MCP session ended before responding")),
+    }
+}
+
+async fn open_stream(State(shared): State<Arc<HttpShared>>, headers: HeaderMap) -> Response {
+    if let Err(response) = check_auth(&shared.auth, &headers) {
+        return response;
+    }
+    if let Err(response) = check_session(&shared, &headers) {
+        return response;
+    }
+    Sse::new(message_events(shared.outbound.subscribe()))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+async fn end_session(State(shared): State<Arc<HttpShared>>, headers: HeaderMap) -> Response {
+    if let Err(response) = check_auth(&shared.auth, &headers) {
+        return response;
+    }
+    if let Err(response) = check_session(&shared, &headers) {
+        return response;
+    }
+    info!("HTTP transport session ended by the client");
+    // Dropping the sender ends the server's run loop, as closing stdio does
+    shared.incoming.lock().unwrap().take();
+    StatusCode::OK.into_response()
+}
+
+/// Server messages as `message` events, until the transport closes
+pub(crate) fn message_events(
+    receiver: broadcast::Receiver<JsonRpcMessage>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(message) => match Event::default().event("message").json_data(&message) {
+                    Ok(event) => return Some((Ok(event), receiver)),
+                    Err(e) => warn!("Could not encode message for the event stream: {}", e),
+                },
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Event stream client lagged; skipped {} messages", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Context, Result};
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// First file descriptor passed by systemd socket activation
+#[cfg(unix)]
+const LISTEN_FDS_START: i32 = 3;
+
+/// Number of sockets passed to this process by systemd socket activation.
+///
+/// `LISTEN_PID` must name this process, so sockets meant for a parent (whose
+/// environment we inherited) are not taken.
+pub(crate) fn activation_fds(listen_pid: Option<&str>, listen_fds: Option<&str>, own_pid: u32) -> usize {
+    let for_us = listen_pid
+        .and_then(|pid| pid.trim().parse::<u32>().ok())
+        .is_some_and(|pid| pid == own_pid);
+    if !for_us {
+        return 0;
+    }
+    listen_fds.and_then(|fds| fds.trim().parse().ok()).unwrap_or(0)
+}
+
+/// Take the listening socket systemd passed to this process, if any.
+///
+/// With socket activation (`LISTEN_PID`/`LISTEN_FDS`), the socket stays open
+/// in systemd while the server restarts, so clients connecting meanwhile
+/// wait in the backlog rather than being refused. Only the first socket is
+/// used. The variables are cleared so child processes don't pick them up.
+#[cfg(unix)]
+pub fn inherited_listener() -> Result<Option<std::net::TcpListener>> {
+    use std::os::unix::io::FromRawFd;
+
+    let fds = activation_fds(
+        std::env::var("LISTEN_PID").ok().as_deref(),
+        std::env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    );
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+    if fds == 0 {
+        return Ok(None);
+    }
+    if fds > 1 {
+        warn!("{} sockets were passed by socket activation; only the first is used", fds);
+    }
+
+    // Safety: systemd passes ownership of descriptors 3.. to this process,
+    // and the variables were cleared so nothing else claims them
+    let listener = unsafe { std::net::TcpListener::from_raw_fd(LISTEN_FDS_START) };
+    let address = listener
+        .local_addr()
+        .context("The socket passed by socket activation is not a TCP listening socket")?;
+    listener.set_nonblocking(true)?;
+    info!("Using socket-activated listener on {}", address);
+    Ok(Some(listener))
+}
+
+/// Socket activation is only available on Unix
+#[cfg(not(unix))]
+pub fn inherited_listener() -> Result<Option<std::net::TcpListener>> {
+    Ok(None)
+}
+
+/// Listen for network transport connections on the socket passed by
+/// socket activation, or else on `address`
+pub async fn bind_listener(address: SocketAddr) -> Result<TcpListener> {
+    match inherited_listener()? {
+        Some(listener) => Ok(TcpListener::from_std(listener)?),
+        None => TcpListener::bind(address)
+            .await
+            .map_err(|e| anyhow!("Failed to listen on {}: {}", address, e)),
+    }
+}
@@ -0,0 +1,45 @@
+pub mod client_log;
+pub mod health;
+pub mod http;
+pub mod listen;
+pub mod sse;
+
+pub use client_log::ClientLogTransport;
+pub use health::Health;
+pub use http::HttpTransport;
+pub use listen::{bind_listener, inherited_listener};
+pub use sse::SseTransport;
+
+use serde::{Deserialize, Serialize};
+
+/// How MCP clients connect to the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    /// JSON-RPC over the process's stdin and stdout
+    #[default]
+    Stdio,
+    /// MCP's Streamable HTTP transport
+    Http,
+    /// MCP's legacy HTTP+SSE transport, for older web clients
+    Sse,
+}
+
+impl std::str::FromStr for TransportKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "stdio" => Ok(TransportKind::Stdio),
+            "http" => Ok(TransportKind::Http),
+            "sse" => Ok(TransportKind::Sse),
+            other => Err(anyhow::anyhow!(
+                "Unknown transport '{}' (expected 'stdio', 'http' or 'sse')",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
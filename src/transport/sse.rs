@@ -0,0 +1,265 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use axum::body::Bytes;
+use axum::extract::{RawQuery, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use futures::stream::{self, StreamExt};
+use mcp_protocol::JsonRpcMessage;
+use mcp_server::transport::Transport;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::auth::BearerAuth;
+use crate::session::Presence;
+use crate::transport::http::{check_auth, error_response, forward, message_events};
+use crate::transport::listen::bind_listener;
+
+/// Path clients open the event stream on
+pub const SSE_PATH: &str = "/sse";
+
+/// Path clients POST their messages to
+pub const MESSAGES_PATH: &str = "/messages";
+
+/// Server messages buffered for the event stream before it lags
+const OUTBOUND_CAPACITY: usize = 256;
+
+/// State shared between the transport and its HTTP handlers
+struct SseShared {
+    auth: Option<BearerAuth>,
+    // Where client messages go; taken when the session ends
+    incoming: Mutex<Option<mpsc::Sender<JsonRpcMessage>>>,
+    // Every server message, for the open event stream
+    outbound: broadcast::Sender<JsonRpcMessage>,
+    session_id: Mutex<Option<String>>,
+    // Whether the client's event stream is open
+    presence: Presence,
+    // How long a client whose stream closed may reopen it and carry on
+    resume_window: Duration,
+    // Ends the session if the client doesn't come back in time
+    expiry: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Detaches the client when its event stream goes away, ending the session
+/// unless it comes back within the resume window
+struct SessionGuard(Arc<SseShared>);
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        let shared = &self.0;
+        if shared.resume_window.is_zero() {
+            info!("SSE transport client disconnected; ending the session");
+            shared.incoming.lock().unwrap().take();
+            return;
+        }
+
+        info!(
+            "SSE transport client disconnected; ending the session in {}s unless it reconnects",
+            shared.resume_window.as_secs()
+        );
+        shared.presence.set_attached(false);
+        let expiring = shared.clone();
+        let expiry = tokio::spawn(async move {
+            tokio::time::sleep(expiring.resume_window).await;
+            info!("SSE transport client did not reconnect; ending the session");
+            expiring.incoming.lock().unwrap().take();
+        });
+        if let Some(previous) = shared.expiry.lock().unwrap().replace(expiry) {
+            previous.abort();
+        }
+    }
+}
+
+/// MCP's legacy HTTP+SSE transport, serving one MCP session.
+///
+/// A client opens an event stream with a GET on `/sse`. Its first event,
+/// `endpoint`, gives the URL to POST JSON-RPC messages to, carrying the
+/// session ID; every POST is answered `202 Accepted`, and all of the
+/// server's responses, notifications and requests arrive on the stream as
+/// `message` events. Closing the stream ends the session, like closing stdio
+/// does, unless a resume window is set: the client may then open `/sse`
+/// again with `?sessionId=` within the window and carry on with the same
+/// session. Kept for web clients that predate the Streamable HTTP transport.
+#[derive(Clone)]
+pub struct SseTransport {
+    shared: Arc<SseShared>,
+    listener: Arc<Mutex<Option<TcpListener>>>,
+    server: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl SseTransport {
+    /// Listen on the socket-activated socket or `address`, so bind errors
+    /// surface before the server starts
+    pub async fn bind(address: SocketAddr, auth: Option<BearerAuth>) -> Result<Self> {
+        let listener = bind_listener(address).await?;
+        Ok(Self {
+            shared: Arc::new(SseShared {
+                auth,
+                incoming: Mutex::new(None),
+                outbound: broadcast::channel(OUTBOUND_CAPACITY).0,
+                session_id: Mutex::new(None),
+                presence: Presence::new(),
+                resume_window: Duration::ZERO,
+                expiry: Mutex::new(None),
+            }),
+            listener: Arc::new(Mutex::new(Some(listener))),
+            server: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Keep the session for `resume_window` after the client's event stream
+    /// closes, reporting whether the client is attached through `presence`
+    pub fn with_resume_window(mut self, presence: Presence, resume_window: Duration) -> Self {
+        let shared = Arc::get_mut(&mut self.shared).expect("SSE transport configured after it was shared");
+        shared.presence = presence;
+        shared.resume_window = resume_window;
+        self
+    }
+
+    /// Address the transport listens on
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        match self.listener.lock().unwrap().as_ref() {
+            Some(listener) => Ok(listener.local_addr()?),
+            None => Err(anyhow!("SSE transport is already serving")),
+        }
+    }
+
+    fn router(&self) -> Router {
+        Router::new()
+            .route(SSE_PATH, get(open_stream))
+            .route(MESSAGES_PATH, post(post_message))
+            .with_state(self.shared.clone())
+    }
+}
+
+#[async_trait]
+impl Transport for SseTransport {
+    async fn start(&self, message_tx: mpsc::Sender<JsonRpcMessage>) -> Result<()> {
+        let listener = self
+            .listener
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow!("SSE transport is already serving"))?;
+        *self.shared.incoming.lock().unwrap() = Some(message_tx);
+
+        info!("Serving MCP over SSE on {}{}", listener.local_addr()?, SSE_PATH);
+        let router = self.router();
+        *self.server.lock().unwrap() = Some(tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router).await {
+                warn!("SSE transport failed: {}", e);
+            }
+        }));
+        Ok(())
+    }
+
+    async fn send(&self, message: JsonRpcMessage) -> Result<()> {
+        // Nobody listening is fine; the message is dropped like an unread stdout line
+        let _ = self.shared.outbound.send(message);
+        Ok(())
+    }
+
+    async fn close(&self) -> Result<()> {
+        self.shared.incoming.lock().unwrap().take();
+        if let Some(server) = self.server.lock().unwrap().take() {
+            server.abort();
+        }
+        Ok(())
+    }
+
+    fn box_clone(&self) -> Box<dyn Transport> {
+        Box::new(self.clone())
+    }
+}
+
+/// The `sessionId` parameter of a query string
+pub(crate) fn session_param(query: Option<&str>) -> Option<&str> {
+    query?
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(name, _)| *name == "sessionId")
+        .map(|(_, value)| value)
+}
+
+async fn open_stream(State(shared): State<Arc<SseShared>>, RawQuery(query): RawQuery, headers: HeaderMap) -> Response {
+    if let Err(response) = check_auth(&shared.auth, &headers) {
+        return response;
+    }
+
+    let session_id = {
+        let mut session_id = shared.session_id.lock().unwrap();
+        match (session_id.clone(), session_param(query.as_deref())) {
+            (None, _) => {
+                let id = Uuid::new_v4().to_string();
+                info!("SSE transport session {} started", id);
+                *session_id = Some(id.clone());
+                id
+            }
+            // The client of a detached session comes back to it
+            (Some(current), Some(presented)) if current == presented && !shared.presence.is_attached() => {
+                if let Some(expiry) = shared.expiry.lock().unwrap().take() {
+                    expiry.abort();
+                }
+                if shared.incoming.lock().unwrap().is_none() {
+                    return error_response(StatusCode::NOT_FOUND, "the MCP session has ended");
+                }
+                info!("SSE transport session {} resumed", current);
+                shared.presence.set_attached(true);
+                current
+            }
+            _ => return error_response(StatusCode::CONFLICT, "an MCP session is already active"),
+        }
+    };
+
+    // Subscribe before announcing the endpoint, so no response is missed
+    let messages = message_events(shared.outbound.subscribe());
+    let endpoint = Event::default()
+        .event("endpoint")
+        .data(format!("{}?sessionId={}", MESSAGES_PATH, session_id));
+    let guard = SessionGuard(shared.clone());
+    let events = stream::once(async move { Ok(endpoint) })
+        .chain(messages)
+        .map(move |event| {
+            let _ = &guard;
+            event
+        });
+
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+async fn post_message(
+    State(shared): State<Arc<SseShared>>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Err(response) = check_auth(&shared.auth, &headers) {
+        return response;
+    }
+    let Some(presented) = session_param(query.as_deref()) else {
+        return error_response(StatusCode::BAD_REQUEST, "missing sessionId parameter");
+    };
+    if shared.session_id.lock().unwrap().as_deref() != Some(presented) {
+        return error_response(StatusCode::NOT_FOUND, "unknown MCP session");
+    }
+
+    let message: JsonRpcMessage = match serde_json::from_slice(&body) {
+        Ok(message) => message,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &format!("invalid JSON-RPC message: {}", e)),
+    };
+
+    // The reply, if any, goes out on the event stream
+    match forward(&shared.incoming, message).await {
+        Ok(()) => StatusCode::ACCEPTED.into_response(),
+        Err(response) => response,
+    }
+}
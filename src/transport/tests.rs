@@ -0,0 +1,64 @@
+use serde_json::json;
+
+use crate::logging::{ClientLog, ClientLogLevel};
+use crate::transport::client_log::set_level;
+use crate::transport::http::MessageKind;
+use crate::transport::listen::activation_fds;
+use crate::transport::sse::session_param;
+use crate::transport::TransportKind;
+
+#[test]
+fn test_activation_fds_only_for_this_process() {
+    assert_eq!(activation_fds(Some("42"), Some("1"), 42), 1);
+    assert_eq!(activation_fds(Some("42"), Some("2"), 42), 2);
+
+    // Sockets meant for another process, or no activation at all
+    assert_eq!(activation_fds(Some("41"), Some("1"), 42), 0);
+    assert_eq!(activation_fds(None, Some("1"), 42), 0);
+    assert_eq!(activation_fds(Some("42"), None, 42), 0);
+    assert_eq!(activation_fds(Some("not a pid"), Some("1"), 42), 0);
+}
+
+#[test]
+fn test_message_kind_routing() {
+    let request = json!({"jsonrpc": "2.0", "id": 7, "method": "tools/list"});
+    assert!(matches!(MessageKind::of(&request), MessageKind::Request(id) if id == "7"));
+
+    let response = json!({"jsonrpc": "2.0", "id": "a", "result": {}});
+    assert!(matches!(MessageKind::of(&response), MessageKind::Response(id) if id == "\"a\""));
+
+    let notification = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+    assert!(matches!(MessageKind::of(&notification), MessageKind::Notification));
+}
+
+#[test]
+fn test_transport_kind_parsing() {
+    assert_eq!("stdio".parse::<TransportKind>().unwrap(), TransportKind::Stdio);
+    assert_eq!("http".parse::<TransportKind>().unwrap(), TransportKind::Http);
+    assert_eq!("sse".parse::<TransportKind>().unwrap(), TransportKind::Sse);
+    assert!("carrier-pigeon".parse::<TransportKind>().is_err());
+}
+
+#[test]
+fn test_sse_session_param() {
+    assert_eq!(session_param(Some("sessionId=abc")), Some("abc"));
+    assert_eq!(session_param(Some("x=1&sessionId=abc")), Some("abc"));
+    assert_eq!(session_param(Some("x=1")), None);
+    assert_eq!(session_param(None), None);
+}
+
+#[test]
+fn test_logging_set_level() {
+    let log = ClientLog::new();
+    assert_eq!(log.level(), ClientLogLevel::Info);
+
+    let request = json!({"jsonrpc": "2.0", "id": 3, "method": "logging/setLevel", "params": {"level": "warning"}});
+    assert_eq!(set_level(&log, &request), json!({"jsonrpc": "2.0", "id": 3, "result": {}}));
+    assert_eq!(log.level(), ClientLogLevel::Warning);
+    assert!(ClientLogLevel::Error > log.level());
+
+    // Unknown levels are refused and leave the level alone
+    let request = json!({"jsonrpc": "2.0", "id": 4, "method": "logging/setLevel", "params": {"level": "loud"}});
+    assert_eq!(set_level(&log, &request)["error"]["code"], -32602);
+    assert_eq!(log.level(), ClientLogLevel::Warning);
+}
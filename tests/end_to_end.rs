@@ -0,0 +1,814 @@
+//! End-to-end tests of every tool and resource against a mock Theater server.
+//!
+//! Each test starts its own [`MockTheater`], connects a real `TheaterClient`
+//! to it and drives the tool and resource handlers the MCP server registers,
+//! checking both their JSON results and the commands that reached Theater.
+
+mod mock_theater;
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use mcp_protocol::types::resource::ResourceContent;
+use mcp_protocol::types::tool::ToolCallResult;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use theater::theater_server::{ManagementCommand, ManagementResponse};
+use theater_mcp_server::actors::{ActorQuota, ActorRegistry, FailureConfig, FailurePolicy, FailureWatcher};
+use theater_mcp_server::channels::{ChannelRegistry, InboxLimits};
+use theater_mcp_server::events::{CausalityTracker, EventFilter, EventHistory, EventStreamHub};
+use theater_mcp_server::conversations::ConversationStore;
+use theater_mcp_server::prompts::PlaybookPrompts;
+use theater_mcp_server::resources::{
+    ActorResources, ChannelResources, ConversationResources, EventResources, WorkspaceResources,
+};
+use theater_mcp_server::schedule::Scheduler;
+use theater_mcp_server::session::{Session, SessionId};
+use theater_mcp_server::state::{ServerState, StateStore};
+use theater_mcp_server::theater::{TheaterClient, TheaterFeatures};
+use theater_mcp_server::tools::{
+    ActorTools, ChannelTools, ConverseTools, EventTools, MessageTools, PlanTools, ScheduleTools, ToolCall,
+    ToolCallLog, ToolResult, ToolStatus,
+};
+
+use mock_theater::MockTheater;
+
+/// The tools and resources of one MCP session, wired to a mock Theater server
+struct Harness {
+    theater: MockTheater,
+    client: Arc<TheaterClient>,
+    event_stream: Arc<EventStreamHub>,
+    actors: ActorTools,
+    messages: MessageTools,
+    channels: ChannelTools,
+    events: EventTools,
+    actor_resources: ActorResources,
+    event_resources: EventResources,
+    channel_resources: ChannelResources,
+}
+
+impl Harness {
+    async fn start() -> Result<Self> {
+        let theater = MockTheater::start().await?;
+        let client = Arc::new(TheaterClient::connect(theater.address()).await?);
+        let session_id = SessionId::new();
+
+        let actor_registry = Arc::new(ActorRegistry::new(client.clone(), ActorQuota::default()));
+        let channel_registry = Arc::new(ChannelRegistry::new(client.clone(), InboxLimits::default()));
+        let event_stream = Arc::new(EventStreamHub::new(client.clone()));
+        let history = Arc::new(EventHistory::new(client.clone()));
+        let causality = Arc::new(CausalityTracker::new());
+
+        Ok(Self {
+            actors: ActorTools::new(client.clone(), actor_registry.clone(), session_id.clone())
+                .with_causality(causality.clone()),
+            messages: MessageTools::new(client.clone()).with_causality(causality.clone()),
+            channels: ChannelTools::new(client.clone(), channel_registry.clone(), session_id),
+            events: EventTools::new(history.clone(), event_stream.clone()),
+            actor_resources: ActorResources::new(client.clone()).with_actor_registry(actor_registry),
+            event_resources: EventResources::new(history, event_stream.clone(), causality),
+            channel_resources: ChannelResources::new(channel_registry),
+            theater,
+            client,
+            event_stream,
+        })
+    }
+
+    async fn start_actor(&self) -> Result<String> {
+        let result = json_result(self.actors.start_actor(json!({ "manifest": "/actors/counter.toml" })).await?);
+        Ok(result["actor_id"].as_str().unwrap().to_string())
+    }
+}
+
+/// The data of a successful tool result
+fn json_result(result: ToolCallResult) -> Value {
+    assert_eq!(result.is_error, Some(false));
+    let envelope = ToolResult::from_call_result(&result).expect("expected a result envelope");
+    assert_eq!(envelope.status, ToolStatus::Ok);
+    envelope.data
+}
+
+fn json_content(content: ResourceContent) -> Value {
+    serde_json::from_str(content.text.as_deref().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn test_actor_lifecycle_tools() -> Result<()> {
+    let h = Harness::start().await?;
+
+    let result = h
+        .actors
+        .start_actor(json!({ "manifest": "/actors/counter.toml", "initial_state": { "count": 1 } }))
+        .await?;
+    let related = ToolResult::from_call_result(&result).unwrap().resources;
+    let started = json_result(result);
+    let actor_id = started["actor_id"].as_str().unwrap();
+    assert_eq!(started["status"], "RUNNING");
+    assert!(related[0].contains(actor_id));
+    assert_eq!(h.theater.actor_manifest(actor_id).as_deref(), Some("/actors/counter.toml"));
+
+    let state = json_result(h.actors.get_actor_state(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(state["state"], json!({ "count": 1 }));
+    let binary = h.theater.add_actor("/actors/binary.toml", Some(vec![0xff, 0x00]));
+    let state = json_result(h.actors.get_actor_state(json!({ "actor_id": binary })).await?);
+    assert_eq!(state["state"]["_raw_state_base64"], "/wA=");
+
+    let restarted = json_result(h.actors.restart_actor(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(restarted["status"], "RUNNING");
+    assert_eq!(h.theater.count("RestartActor"), 1);
+
+    let updated = json_result(h.actors.update_actor(json!({
+        "actor_id": actor_id,
+        "component": "/actors/counter-v2.wasm"
+    })).await?);
+    assert_eq!(updated["component"], "/actors/counter-v2.wasm");
+    assert_eq!(h.theater.count("UpdateActorComponent"), 1);
+
+    let stopped = json_result(h.actors.stop_actor(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(stopped["status"], "STOPPED");
+    assert!(h.theater.actor_ids().is_empty());
+
+    // Stopping it again surfaces Theater's error
+    assert!(h.actors.stop_actor(json!({ "actor_id": actor_id })).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_start_actor_from_manifest_content() -> Result<()> {
+    let h = Harness::start().await?;
+    let manifest = "name = \"counter\"\nversion = \"0.1.0\"\ncomponent_path = \"/actors/counter.wasm\"\n";
+    let started = json_result(h.actors.start_actor(json!({ "manifest_content": manifest })).await?);
+    let actor_id = started["actor_id"].as_str().unwrap();
+    assert_eq!(h.theater.actor_manifest(actor_id).as_deref(), Some(manifest));
+    let recorded = json_content(h.actor_resources.get_actor_manifest_content(actor_id).await?);
+    assert_eq!(recorded["source"], "inline");
+    assert_eq!(recorded["manifest"]["component_path"], "/actors/counter.wasm");
+
+    // Problems are reported without reaching Theater
+    let err = h.actors
+        .start_actor(json!({ "manifest_content": "name = \"counter\"\n" }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("component_path: is required"));
+    assert_eq!(h.theater.count("StartActor"), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_validate_manifest() -> Result<()> {
+    let h = Harness::start().await?;
+    let dir = std::env::temp_dir().join(format!("theater-mcp-validate-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let manifest = dir.join("counter.toml");
+    std::fs::write(&manifest, "name = \"counter\"\nversion = \"0.1.0\"\ncomponent_path = \"counter.wasm\"\n")?;
+    let manifest = manifest.to_string_lossy().into_owned();
+
+    // The component is missing until it is written next to the manifest
+    let checked = json_result(h.actors.validate_manifest(json!({ "manifest": manifest })).await?);
+    assert_eq!(checked["valid"], false);
+    assert_eq!(checked["diagnostics"][0]["field"], "component_path");
+    std::fs::write(dir.join("counter.wasm"), b"\0asm")?;
+    let checked = json_result(h.actors.validate_manifest(json!({ "manifest": manifest })).await?);
+    assert_eq!(checked["valid"], true);
+
+    let checked = json_result(h.actors.validate_manifest(json!({ "manifest_content": "version = 1" })).await?);
+    assert_eq!(checked["diagnostics"].as_array().unwrap().len(), 3);
+    assert_eq!(h.theater.count("StartActor"), 0);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_actor_tools() -> Result<()> {
+    let h = Harness::start().await?;
+    let counter = h.start_actor().await?;
+    let started = json_result(h.actors.start_actor(json!({ "manifest": "/actors/worker.toml" })).await?);
+    let worker = started["actor_id"].as_str().unwrap().to_string();
+
+    let restarted = json_result(h.actors.restart_actors(json!({ "manifest": "*/counter.toml" })).await?);
+    assert_eq!(restarted["count"], 1);
+    assert_eq!(restarted["results"][0]["actor_id"], counter.as_str());
+    assert_eq!(h.theater.count("RestartActor"), 1);
+
+    // One unknown actor doesn't stop the others
+    let missing = "00000000-0000-0000-0000-000000000000";
+    let result = h.actors.stop_actors(json!({ "actor_ids": [counter, worker, missing] })).await?;
+    let envelope = ToolResult::from_call_result(&result).unwrap();
+    assert_eq!(envelope.data["failed"], 1);
+    assert_eq!(envelope.warnings.len(), 1);
+    assert!(h.theater.actor_ids().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_all_actors_requires_confirmation() -> Result<()> {
+    let h = Harness::start().await?;
+    h.start_actor().await?;
+    h.theater.add_actor("/actors/external.toml", None);
+
+    let preview = json_result(h.actors.stop_all_actors(json!({})).await?);
+    assert_eq!(preview["count"], 2);
+    assert_eq!(h.theater.count("StopActor"), 0);
+
+    let token = preview["confirmation_token"].as_str().unwrap();
+    let stopped = json_result(h.actors.stop_all_actors(json!({ "confirmation_token": token })).await?);
+    assert_eq!(stopped["count"], 2);
+    assert!(h.theater.actor_ids().is_empty());
+
+    // Tokens are single-use
+    assert!(h.actors.stop_all_actors(json!({ "confirmation_token": token })).await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_message_tools() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.start_actor().await?;
+
+    let sent = json_result(
+        h.messages
+            .send_message(json!({ "actor_id": actor_id, "data": BASE64.encode(b"{\"increment\":1}") }))
+            .await?,
+    );
+    assert_eq!(sent["success"], true);
+
+    // The mock echoes requests back as the response
+    let reply = json_result(
+        h.messages
+            .request_message(json!({ "actor_id": actor_id, "data": BASE64.encode(b"ping") }))
+            .await?,
+    );
+    assert_eq!(BASE64.decode(reply["response"].as_str().unwrap())?, b"ping");
+    assert_eq!(reply["text"], "ping");
+
+    let request = json!({ "op": "get", "key": "greeting" });
+    let reply = json_result(
+        h.messages
+            .request_json(json!({ "actor_id": actor_id, "message": request }))
+            .await?,
+    );
+    assert_eq!(reply["response"], request);
+
+    // JSON payloads are transcoded to MessagePack or CBOR and replies decoded back
+    for encoding in ["msgpack", "cbor"] {
+        let payload = json!({ "op": "get", "keys": ["a", "b"], "limit": 2 });
+        let reply = json_result(
+            h.messages
+                .request_message(json!({ "actor_id": actor_id, "data": payload, "encoding": encoding }))
+                .await?,
+        );
+        assert_eq!(reply["response"], payload);
+    }
+
+    // Both messages landed in the actor's chain and are attributed to the request
+    let operation_id = reply["operation_id"].as_str().unwrap();
+    let caused = json_content(h.event_resources.get_caused_events_content(operation_id).await?);
+    let caused_types: Vec<_> = caused["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["event_type"].clone())
+        .collect();
+    assert!(caused_types.contains(&json!("request-received")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_channel_tools() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.start_actor().await?;
+
+    let opened = json_result(
+        h.channels
+            .open_channel(json!({ "actor_id": actor_id, "encoding": "json" }))
+            .await?,
+    );
+    let channel_id = opened["channel_id"].as_str().unwrap().to_string();
+    assert_eq!(opened["encoding"], "json");
+
+    let sent = json_result(
+        h.channels
+            .send_on_channel(json!({ "channel_id": channel_id, "message": { "n": 1 } }))
+            .await?,
+    );
+    assert_eq!(sent["success"], true);
+
+    // The mock participant echoes every message back
+    let reply = json_result(
+        h.channels
+            .channel_request(json!({ "channel_id": channel_id, "message": { "n": 2 }, "timeout_ms": 2000 }))
+            .await?,
+    );
+    assert_eq!(reply["reply"], json!({ "n": 2 }));
+
+    // The echo of the first message is still in the inbox
+    let received = json_result(
+        h.channels
+            .receive_channel_message(json!({ "channel_id": channel_id, "wait_ms": 2000 }))
+            .await?,
+    );
+    assert_eq!(received["messages"][0]["data"], json!({ "n": 1 }));
+    assert_eq!(received["remaining"], 0);
+    let empty = json_result(h.channels.receive_channel_message(json!({ "channel_id": channel_id })).await?);
+    assert!(empty["messages"].as_array().unwrap().is_empty());
+
+    let shared = json_result(h.channels.share_channel(json!({ "channel_id": channel_id })).await?);
+    assert_eq!(shared["shared"], true);
+
+    let status = json_result(h.channels.channel_status(json!({ "channel_id": channel_id })).await?);
+    assert_eq!(status["channel_id"], channel_id.as_str());
+
+    let listed = json_result(h.channels.list_channels(json!({})).await?);
+    assert_eq!(listed["total"], 1);
+
+    let channels = json_content(h.channel_resources.get_channels_content()?);
+    assert_eq!(channels["channels"][0]["participant"], listed["channels"][0]["participant"]);
+    assert_eq!(channels["channels"][0]["messages_sent"], 2);
+    let details = json_content(h.channel_resources.get_channel_content(&channel_id)?);
+    assert_eq!(details["channel"]["channel_id"], channel_id.as_str());
+
+    json_result(h.channels.close_channel(json!({ "channel_id": channel_id })).await?);
+    assert!(h.theater.channel_ids().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_all_channels_requires_confirmation() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.start_actor().await?;
+    for _ in 0..2 {
+        h.channels.open_channel(json!({ "actor_id": actor_id })).await?;
+    }
+
+    let preview = json_result(h.channels.close_all_channels(json!({})).await?);
+    assert_eq!(preview["count"], 2);
+    assert_eq!(h.theater.channel_ids().len(), 2);
+
+    let token = preview["confirmation_token"].as_str().unwrap();
+    let closed = json_result(h.channels.close_all_channels(json!({ "confirmation_token": token })).await?);
+    assert_eq!(closed["count"], 2);
+    assert!(h.theater.channel_ids().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chain_tools() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.start_actor().await?;
+    h.theater.emit_event(&actor_id, "wasm-call", br#"{"function":"handle","user":"bob"}"#);
+    h.theater.emit_event(&actor_id, "wasm-result", br#"{"ok":true}"#);
+
+    let head = json_result(h.events.get_chain_head(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(head["event_count"], 3);
+    assert_eq!(head["head_event_type"], "wasm-result");
+
+    let verified = json_result(h.events.verify_actor_chain(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(verified["valid"], true);
+
+    h.theater.tamper_event(&actor_id, 1, br#"{"function":"handle","user":"mallory"}"#);
+    let verified = json_result(h.events.verify_actor_chain(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(verified["valid"], false);
+
+    let summary = json_result(h.events.summarize_actor_events(json!({ "actor_id": actor_id })).await?);
+    assert_eq!(summary["event_count"], 3);
+
+    let found = json_result(h.events.search_events(json!({ "query": "mallory" })).await?);
+    assert_eq!(found["total"], 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aggregate_and_tail_events() -> Result<()> {
+    let h = Harness::start().await?;
+    let first = h.start_actor().await?;
+    let second = h.start_actor().await?;
+    h.theater.emit_event(&second, "wasm-call", b"{}");
+
+    let merged = json_result(
+        h.events
+            .aggregate_events(json!({ "actor_ids": [first, second], "filter": "type:wasm*" }))
+            .await?,
+    );
+    assert_eq!(merged["total"], 1);
+
+    h.theater.emit_event(&second, "wasm-call", b"{}");
+    let slice = h.events.get_actor_events(json!({ "actor_id": second, "event_type": "wasm*", "limit": 1 })).await?;
+    assert_eq!(ToolResult::from_call_result(&slice).unwrap().warnings.len(), 1);
+    let slice = json_result(slice);
+    assert_eq!(slice["total"], 2);
+    assert_eq!(slice["events"].as_array().unwrap().len(), 1);
+    let future = json_result(h.events.get_actor_events(json!({ "actor_id": second, "since": "2999-01-01T00:00:00Z" })).await?);
+    assert_eq!(future["total"], 0);
+
+    let tail = json_result(h.events.tail_actor_events(json!({ "actor_id": first })).await?);
+    assert_eq!(tail["events"].as_array().unwrap().len(), 1);
+    let cursor = tail["cursor"].clone();
+
+    // A long poll returns as soon as the next event streams in
+    let theater = h.theater.clone();
+    let emitter = first.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        theater.emit_event(&emitter, "wasm-call", b"{}");
+    });
+    let tail = json_result(
+        h.events
+            .tail_actor_events(json!({ "actor_id": first, "cursor": cursor, "wait_ms": 5000 }))
+            .await?,
+    );
+    assert_eq!(tail["events"][0]["event_type"], "wasm-call");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_event_pages() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.theater.add_actor("/actors/counter.toml", None);
+    h.theater.emit_event(&actor_id, "wasm-call", b"{}");
+    h.theater.emit_event(&actor_id, "wasm-result", b"{}");
+
+    let first = json_content(h.event_resources.get_actor_events_page_content(&actor_id, &EventFilter::all(), None, 2).await?);
+    assert_eq!(first["returned"], 2);
+    assert_eq!(first["total"], 3);
+    assert_eq!(first["has_more"], true);
+    let cursor = first["cursor"].as_str().unwrap();
+    assert_eq!(first["next"], format!("theater://events/{}?cursor={}&limit=2", actor_id, cursor));
+
+    let rest = json_content(h.event_resources.get_actor_events_page_content(&actor_id, &EventFilter::all(), Some(cursor), 2).await?);
+    assert_eq!(rest["events"][0]["event_type"], "wasm-result");
+    assert_eq!(rest["has_more"], false);
+    assert!(rest["next"].is_null());
+
+    // Filtered pages only count and return matching events
+    let filter = EventFilter::parse("type:wasm*")?;
+    let filtered = json_content(h.event_resources.get_actor_events_page_content(&actor_id, &filter, None, 1).await?);
+    assert_eq!(filtered["events"][0]["event_type"], "wasm-call");
+    assert_eq!(filtered["total"], 2);
+    let cursor = filtered["cursor"].as_str().unwrap();
+    assert_eq!(
+        filtered["next"],
+        format!("theater://events/{}?cursor={}&limit=1&filter=type%3Awasm*", actor_id, cursor)
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_event_rates_and_stream() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.start_actor().await?;
+    h.event_stream.watch(&actor_id).await?;
+
+    let mut live = h.event_stream.subscribe(&actor_id).await?;
+    h.theater.emit_event(&actor_id, "wasm-call", b"{}");
+    tokio::time::timeout(Duration::from_secs(2), live.recv()).await??;
+
+    let rates = json_result(h.events.get_event_rates(json!({})).await?);
+    assert_eq!(rates["actors"][0]["actor_id"], actor_id.as_str());
+
+    let rates = json_content(h.event_resources.get_event_rates_content()?);
+    assert_eq!(rates["streaming"], json!([actor_id]));
+
+    let stream = json_content(h.event_resources.get_event_stream_content().await?);
+    assert_eq!(stream["total"], 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_actor_failure_restart_policy() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_registry = Arc::new(ActorRegistry::new(h.client.clone(), ActorQuota::default()));
+    let actors = ActorTools::new(h.client.clone(), actor_registry.clone(), SessionId::new());
+    let started = json_result(actors.start_actor(json!({ "manifest": "/actors/counter.toml" })).await?);
+    let actor_id = started["actor_id"].as_str().unwrap();
+    let config = FailureConfig {
+        policy: FailurePolicy::Restart,
+        max_restarts: 1,
+        ..FailureConfig::default()
+    };
+    let watcher = FailureWatcher::new(config, h.client.clone(), actor_registry.clone(), h.event_stream.clone());
+    let resources = ActorResources::new(h.client.clone()).with_actor_registry(actor_registry.clone());
+
+    let mut events = h.event_stream.subscribe_all();
+    h.event_stream.watch(actor_id).await?;
+    h.theater.emit_event(actor_id, "wasm-call", b"{}");
+    h.theater.emit_event(actor_id, "wasm-error", br#"{"error":"trap"}"#);
+    h.theater.emit_event(actor_id, "wasm-error", br#"{"error":"trap"}"#);
+    let mut received = Vec::new();
+    for _ in 0..3 {
+        received.push(tokio::time::timeout(Duration::from_secs(2), events.recv()).await??);
+    }
+    assert!(!watcher.is_failure(&received[0]));
+
+    // The first failure restarts the actor, the second exhausts the restarts
+    watcher.handle_failure(&received[1]).await;
+    assert_eq!(h.theater.count("RestartActor"), 1);
+    assert!(!actor_registry.failure(actor_id).unwrap().marked);
+    watcher.handle_failure(&received[2]).await;
+    assert_eq!(h.theater.count("RestartActor"), 1);
+
+    let details = json_content(resources.get_actor_details_content(actor_id).await?);
+    assert_eq!(details["status"], "FAILED");
+    assert_eq!(details["failure"]["restarts"], 1);
+    assert_eq!(details["failure"]["detail"]["error"], "trap");
+
+    // Restarting by hand clears the failure
+    json_result(actors.restart_actor(json!({ "actor_id": actor_id })).await?);
+    assert!(actor_registry.failure(actor_id).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_actor_resources() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.theater.add_actor("/actors/counter.toml", Some(br#"{"count":7}"#.to_vec()));
+
+    let list = json_content(h.actor_resources.get_actors_list_content().await?);
+    assert_eq!(list["total"], 1);
+    assert_eq!(list["actors"][0]["id"], actor_id.as_str());
+
+    // Nothing changed since that version
+    let version = list["version"].as_u64().unwrap();
+    let unchanged = json_content(h.actor_resources.get_actors_list_changes_content(version).await?);
+    assert_eq!(unchanged["unchanged"], true);
+
+    let details = json_content(h.actor_resources.get_actor_details_content(&actor_id).await?);
+    assert_eq!(details["id"], actor_id.as_str());
+    assert_eq!(details["status"], "RUNNING");
+    assert_eq!(details["event_count"], 1);
+    assert_eq!(details["created_at"], details["last_event_at"]);
+
+    let state = json_content(h.actor_resources.get_actor_state_content(&actor_id).await?);
+    assert_eq!(state, json!({ "count": 7 }));
+
+    let events = json_content(
+        h.event_resources
+            .get_actor_events_content(&actor_id, &EventFilter::all())
+            .await?,
+    );
+    assert_eq!(events.as_array().unwrap().len(), 1);
+    assert_eq!(events[0]["payload"], json!({ "phase": "started" }));
+
+    // A child spawned by the actor is nested under it
+    let child_id = h.theater.add_actor("/actors/worker.toml", None);
+    let spawn = format!(r#"{{"child_id":"{}"}}"#, child_id);
+    h.theater.emit_event(&actor_id, "theater:simple/supervisor/spawn", spawn.as_bytes());
+    let tree = json_content(h.actor_resources.get_actor_tree_content().await?);
+    assert_eq!(tree["total"], 2);
+    assert_eq!(tree["actors"].as_array().unwrap().len(), 1);
+    assert_eq!(tree["actors"][0]["id"], actor_id.as_str());
+    assert_eq!(tree["actors"][0]["children"][0]["id"], child_id.as_str());
+
+    // Pages of the list, in ID order
+    let mut ids = vec![actor_id.clone(), child_id.clone()];
+    ids.sort();
+    let page = json_content(h.actor_resources.get_actors_page_content(0, 1).await?);
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["actors"][0]["id"], ids[0].as_str());
+    assert_eq!(page["next"], "theater://actors?offset=1&limit=1");
+    let listed = json_result(h.actors.list_actors(json!({ "offset": 1, "limit": 1 })).await?);
+    assert_eq!(listed["actors"][0]["id"], ids[1].as_str());
+    assert!(listed.get("next_offset").is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_playbook_prompts() -> Result<()> {
+    let h = Harness::start().await?;
+    let prompts = PlaybookPrompts::new(Arc::new(EventHistory::new(h.client.clone())));
+    let actor_id = h.theater.add_actor("/actors/counter.toml", Some(br#"{"count":3}"#.to_vec()));
+
+    let arguments = HashMap::from([("actor_id".to_string(), actor_id.clone())]);
+    let debug = serde_json::to_value(prompts.debug_actor(arguments).await?)?;
+    let text = debug["messages"][0]["content"]["text"].as_str().unwrap();
+    assert!(text.contains(&actor_id));
+    assert!(text.contains("\"count\": 3"));
+    assert!(text.contains("The latest 1 of 1 events"));
+
+    let arguments = HashMap::from([
+        ("manifest".to_string(), "/actors/counter.toml".to_string()),
+        ("initial_state".to_string(), "not json".to_string()),
+    ]);
+    assert!(prompts.deploy_actor(arguments).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scripted_theater_error() -> Result<()> {
+    let h = Harness::start().await?;
+    h.theater.respond_with(|command| match command {
+        ManagementCommand::StartActor { .. } => Some(ManagementResponse::Error {
+            message: "component not found".to_string(),
+        }),
+        _ => None,
+    });
+
+    let err = h
+        .actors
+        .start_actor(json!({ "manifest": "/actors/missing.toml" }))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("component not found"));
+    assert!(h.theater.actor_ids().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_feature_probe() -> Result<()> {
+    let h = Harness::start().await?;
+    assert_eq!(TheaterFeatures::probe(&h.client).await, TheaterFeatures::default());
+
+    h.theater.reject_command("OpenChannel");
+    let features = TheaterFeatures::probe(&h.client).await;
+    assert!(!features.channels);
+    assert!(features.messages && features.actor_events && features.subscriptions);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_run_plan_rolls_back() -> Result<()> {
+    let h = Harness::start().await?;
+    let session_id = SessionId::new();
+    let plan = PlanTools::new(
+        Arc::new(ActorTools::new(
+            h.client.clone(),
+            Arc::new(ActorRegistry::new(h.client.clone(), ActorQuota::default())),
+            session_id.clone(),
+        )),
+        Arc::new(MessageTools::new(h.client.clone())),
+        Arc::new(ChannelTools::new(
+            h.client.clone(),
+            Arc::new(ChannelRegistry::new(h.client.clone(), InboxLimits::default())),
+            session_id,
+        )),
+    );
+    let steps = json!([
+        { "op": "start_actor", "id": "counter", "manifest": "/actors/counter.toml" },
+        { "op": "open_channel", "id": "chan", "actor_id": "$counter", "encoding": "json" },
+        { "op": "send_message", "actor_id": "$counter", "encoding": "json", "data": { "add": 1 } }
+    ]);
+
+    // References must point at earlier steps
+    let invalid = json!([{ "op": "send_message", "actor_id": "$missing", "data": "x" }]);
+    assert!(plan.run_plan(json!({ "steps": invalid })).await.is_err());
+
+    h.theater.reject_command("SendActorMessage");
+    let result = plan.run_plan(json!({ "steps": steps })).await?;
+    assert_eq!(result.is_error, Some(true));
+    let report = ToolResult::from_call_result(&result).unwrap().data;
+    assert_eq!(report["failed_step"], 2);
+    assert_eq!(report["rolled_back"], json!([1, 0]));
+    assert!(h.theater.actor_ids().is_empty());
+    assert!(h.theater.channel_ids().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_converse_with_actor() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.theater.add_actor("/actors/echo.toml", None);
+    let conversations = Arc::new(ConversationStore::new());
+    let converse = ConverseTools::new(h.client.clone(), conversations.clone());
+    let transcripts = ConversationResources::new(conversations);
+
+    // The first exchange starts the conversation; the mock echoes requests back
+    let first = json_result(converse.converse_with_actor(json!({ "actor_id": actor_id, "message": "hello" })).await?);
+    assert_eq!(first["seq"], 1);
+    assert_eq!(first["text"], "hello");
+    let conversation_id = first["conversation_id"].as_str().unwrap();
+
+    // With context the actor also receives the earlier exchanges
+    let second = json_result(converse.converse_with_actor(json!({
+        "conversation_id": conversation_id,
+        "message": "again",
+        "with_context": true
+    })).await?);
+    assert_eq!(second["seq"], 2);
+    assert_eq!(second["reply"]["history"][0]["reply"], "hello");
+
+    let transcript = transcripts.get_transcript_content(conversation_id)?;
+    let transcript: Value = serde_json::from_str(transcript.text.as_deref().unwrap())?;
+    assert_eq!(transcript["exchanges"].as_array().unwrap().len(), 2);
+    assert_eq!(transcript["exchanges"][1]["message"], "again");
+
+    json_result(converse.end_conversation(json!({ "conversation_id": conversation_id })).await?);
+    assert!(transcripts.get_transcript_content(conversation_id).is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scheduled_messages() -> Result<()> {
+    let h = Harness::start().await?;
+    let actor_id = h.theater.add_actor("/actors/counter.toml", None);
+    let scheduler = Arc::new(Scheduler::new(h.client.clone()));
+    let schedule = ScheduleTools::new(h.client.clone(), scheduler.clone());
+
+    // One message due now, one recurring, one far off
+    let due = json!({ "actor_id": actor_id, "data": "now", "encoding": "utf8", "delay_secs": 0 });
+    json_result(schedule.schedule_message(due).await?);
+    let recurring = json!({ "actor_id": actor_id, "data": "tick", "encoding": "utf8", "every_secs": 60 });
+    let recurring = json_result(schedule.schedule_message(recurring).await?);
+    let later = json!({ "actor_id": actor_id, "data": "later", "encoding": "utf8", "delay_secs": 3600 });
+    let later = json_result(schedule.schedule_message(later).await?);
+    assert!(schedule.schedule_message(json!({ "actor_id": actor_id, "data": "x", "encoding": "utf8" })).await.is_err());
+    // Out-of-range delays and intervals are refused rather than panicking
+    let huge_delay = json!({ "actor_id": actor_id, "data": "x", "encoding": "utf8", "delay_secs": 10_000_000_000_000u64 });
+    assert!(schedule.schedule_message(huge_delay).await.is_err());
+    let huge_interval = json!({ "actor_id": actor_id, "data": "x", "encoding": "utf8", "every_secs": u64::MAX });
+    assert!(schedule.schedule_message(huge_interval).await.is_err());
+
+    assert_eq!(scheduler.deliver_due().await, 2);
+    assert_eq!(h.theater.count("SendActorMessage"), 2);
+
+    // The one-off message is gone and the recurring one waits for its next time
+    let listed = json_result(schedule.list_scheduled(json!({})).await?);
+    assert_eq!(listed["total"], 2);
+    assert_eq!(listed["scheduled"][0]["schedule_id"], recurring["schedule_id"]);
+    assert_eq!(listed["scheduled"][0]["deliveries"], 1);
+
+    json_result(schedule.cancel_scheduled(json!({ "schedule_id": later["schedule_id"] })).await?);
+    assert_eq!(scheduler.deliver_due().await, 0);
+    assert_eq!(scheduler.list().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_session_workspace() -> Result<()> {
+    let h = Harness::start().await?;
+    let session = Session::new();
+    let actor_registry = Arc::new(ActorRegistry::new(h.client.clone(), ActorQuota::default()));
+    let channel_registry = Arc::new(ChannelRegistry::new(h.client.clone(), InboxLimits::default()));
+    let calls = Arc::new(ToolCallLog::new().with_capacity(1));
+    let workspace = WorkspaceResources::new(
+        session.clone(),
+        actor_registry.clone(),
+        channel_registry.clone(),
+        h.event_stream.clone(),
+        calls.clone(),
+    );
+
+    // Only what this session started shows up
+    let actors = ActorTools::new(h.client.clone(), actor_registry.clone(), session.id.clone());
+    let channels = ChannelTools::new(h.client.clone(), channel_registry, session.id.clone());
+    let started = json_result(actors.start_actor(json!({ "manifest": "/actors/counter.toml" })).await?);
+    let actor_id = started["actor_id"].as_str().unwrap();
+    json_result(channels.open_channel(json!({ "actor_id": actor_id })).await?);
+    ActorTools::new(h.client.clone(), actor_registry, SessionId::new())
+        .start_actor(json!({ "manifest": "/actors/other.toml" }))
+        .await?;
+    for tool in ["start_actor", "open_channel"] {
+        calls.record(ToolCall {
+            tool: tool.to_string(),
+            at: chrono::Utc::now(),
+            status: ToolStatus::Ok,
+            elapsed_ms: 1,
+            error: None,
+        });
+    }
+
+    let content = json_content(workspace.get_workspace_content()?);
+    assert_eq!(content["session_id"], session.id.as_str());
+    assert_eq!(content["actors"].as_array().unwrap().len(), 1);
+    assert_eq!(content["actors"][0]["actor_id"], actor_id);
+    assert_eq!(content["channels"][0]["participant"]["id"], actor_id);
+    assert_eq!(content["recent_calls"].as_array().unwrap().len(), 1);
+    assert_eq!(content["recent_calls"][0]["tool"], "open_channel");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_server_state_restored() -> Result<()> {
+    let h = Harness::start().await?;
+    let path = std::env::temp_dir().join(format!("theater-mcp-state-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    // A first run starts an actor and opens a channel to it
+    let actor_registry = Arc::new(ActorRegistry::new(h.client.clone(), ActorQuota::default()));
+    let channel_registry = Arc::new(ChannelRegistry::new(h.client.clone(), InboxLimits::default()));
+    let session_id = SessionId::new();
+    let actors = ActorTools::new(h.client.clone(), actor_registry.clone(), session_id.clone());
+    let channels = ChannelTools::new(h.client.clone(), channel_registry.clone(), session_id.clone());
+    let started = json_result(actors.start_actor(json!({ "manifest": "/actors/counter.toml" })).await?);
+    let actor_id = started["actor_id"].as_str().unwrap();
+    let opened = json_result(channels.open_channel(json!({ "actor_id": actor_id, "encoding": "json" })).await?);
+    let channel_id = opened["channel_id"].as_str().unwrap();
+    ServerState::new(StateStore::new(&path), actor_registry, channel_registry, session_id).save()?;
+
+    // The next run picks them up for its own session
+    let actor_registry = Arc::new(ActorRegistry::new(h.client.clone(), ActorQuota::default()));
+    let channel_registry = Arc::new(ChannelRegistry::new(h.client.clone(), InboxLimits::default()));
+    let session_id = SessionId::new();
+    ServerState::new(StateStore::new(&path), actor_registry.clone(), channel_registry.clone(), session_id.clone())
+        .restore()
+        .await?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(actor_registry.get(actor_id).unwrap().session_id, session_id.as_str());
+    let channel = channel_registry.get(channel_id).unwrap();
+    assert_eq!(channel.reopen_count, 1);
+    assert_eq!(channel.session_id, session_id.as_str());
+    assert_eq!(h.theater.count("OpenChannel"), 2);
+    Ok(())
+}
@@ -0,0 +1,373 @@
+//! An in-process stand-in for the Theater server.
+//!
+//! The mock speaks the same length-prefixed `ManagementCommand` /
+//! `ManagementResponse` protocol as Theater, keeps a small in-memory model of
+//! actors, their state and event chains, channels and subscriptions, and
+//! records every command it receives. Tests can script a response for any
+//! command with [`MockTheater::respond_with`].
+
+#![allow(dead_code)]
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use theater::chain::ChainEvent;
+use theater::id::TheaterId;
+use theater::messages::{ActorStatus, ChannelParticipant};
+use theater::theater_server::{ManagementCommand, ManagementResponse};
+use theater_mcp_server::events::verify::compute_hash;
+use theater_mcp_server::theater::framing::{read_frame, write_frame};
+use theater_mcp_server::theater::TheaterIdExt;
+
+/// A scripted response; returning `None` falls through to the default behaviour
+type Responder = Box<dyn Fn(&ManagementCommand) -> Option<ManagementResponse> + Send>;
+
+/// Frames queued for one client connection
+type Outbox = mpsc::UnboundedSender<ManagementResponse>;
+
+struct MockActor {
+    id: TheaterId,
+    manifest: String,
+    state: Option<Vec<u8>>,
+    events: Vec<ChainEvent>,
+}
+
+impl MockActor {
+    /// Append an event to the actor's chain, linked to the previous head
+    fn record(&mut self, event_type: &str, data: Vec<u8>) -> ChainEvent {
+        let parent_hash = self.events.last().map(|e| e.hash.clone());
+        let event = ChainEvent {
+            hash: compute_hash(parent_hash.as_deref(), &data),
+            parent_hash,
+            event_type: event_type.to_string(),
+            data,
+            timestamp: chrono::Utc::now().timestamp() as u64,
+            description: Some(format!("mock {} event", event_type)),
+        };
+        self.events.push(event.clone());
+        event
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    actors: HashMap<String, MockActor>,
+    channels: HashMap<String, Outbox>,
+    subscribers: HashMap<String, Vec<Outbox>>,
+    commands: Vec<serde_json::Value>,
+    responders: Vec<Responder>,
+}
+
+/// A mock Theater server listening on a local port
+#[derive(Clone)]
+pub struct MockTheater {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockTheater {
+    /// Start the server on an ephemeral port
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mock = Self {
+            addr: listener.local_addr()?,
+            state: Arc::new(Mutex::new(MockState::default())),
+        };
+
+        let server = mock.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(server.clone().serve(stream));
+            }
+        });
+
+        Ok(mock)
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Answer commands matching `responder` with its response instead of the
+    /// default behaviour. Later responders take precedence.
+    pub fn respond_with<F>(&self, responder: F)
+    where
+        F: Fn(&ManagementCommand) -> Option<ManagementResponse> + Send + 'static,
+    {
+        self.state.lock().unwrap().responders.insert(0, Box::new(responder));
+    }
+
+    /// Reject every command of the given variant as unknown, like an older Theater release
+    pub fn reject_command(&self, variant: &'static str) {
+        self.respond_with(move |command| {
+            (command_name(command) == variant).then(|| ManagementResponse::Error {
+                message: format!("unknown variant `{}`", variant),
+            })
+        });
+    }
+
+    /// Add an actor directly, as if started outside the MCP server
+    pub fn add_actor(&self, manifest: &str, state: Option<Vec<u8>>) -> String {
+        let id = TheaterId::from_str(&Uuid::new_v4().to_string()).unwrap();
+        let mut actor = MockActor {
+            id: id.clone(),
+            manifest: manifest.to_string(),
+            state,
+            events: Vec::new(),
+        };
+        actor.record("runtime-init", br#"{"phase":"started"}"#.to_vec());
+        let key = id.as_string();
+        self.state.lock().unwrap().actors.insert(key.clone(), actor);
+        key
+    }
+
+    /// Append an event to an actor's chain and push it to its subscribers
+    pub fn emit_event(&self, actor_id: &str, event_type: &str, data: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        let event = state
+            .actors
+            .get_mut(actor_id)
+            .expect("unknown mock actor")
+            .record(event_type, data.to_vec());
+        publish(&mut state, actor_id, event);
+    }
+
+    /// Replace the payload of one event without fixing its hash
+    pub fn tamper_event(&self, actor_id: &str, index: usize, data: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        let actor = state.actors.get_mut(actor_id).expect("unknown mock actor");
+        actor.events[index].data = data.to_vec();
+    }
+
+    /// Push a message to the MCP server on an open channel
+    pub fn push_channel_message(&self, channel_id: &str, message: &[u8]) {
+        if let Some(outbox) = self.state.lock().unwrap().channels.get(channel_id) {
+            let _ = outbox.send(ManagementResponse::ChannelMessage {
+                channel_id: channel_id.to_string(),
+                message: message.to_vec(),
+            });
+        }
+    }
+
+    pub fn actor_ids(&self) -> Vec<String> {
+        self.state.lock().unwrap().actors.keys().cloned().collect()
+    }
+
+    pub fn actor_manifest(&self, actor_id: &str) -> Option<String> {
+        self.state.lock().unwrap().actors.get(actor_id).map(|a| a.manifest.clone())
+    }
+
+    pub fn channel_ids(&self) -> Vec<String> {
+        self.state.lock().unwrap().channels.keys().cloned().collect()
+    }
+
+    /// Names of the commands received so far, in order
+    pub fn command_names(&self) -> Vec<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .commands
+            .iter()
+            .map(|command| match command {
+                serde_json::Value::Object(map) => map.keys().next().cloned().unwrap_or_default(),
+                other => other.as_str().unwrap_or_default().to_string(),
+            })
+            .collect()
+    }
+
+    /// How many times a command was received
+    pub fn count(&self, variant: &str) -> usize {
+        self.command_names().iter().filter(|name| *name == variant).count()
+    }
+
+    /// Serve one client connection until it closes
+    async fn serve(self, stream: TcpStream) {
+        let (mut reader, mut writer) = stream.into_split();
+        let (outbox, mut frames) = mpsc::unbounded_channel::<ManagementResponse>();
+
+        tokio::spawn(async move {
+            while let Some(frame) = frames.recv().await {
+                if write_frame(&mut writer, &frame).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        while let Ok(command) = read_frame::<_, ManagementCommand>(&mut reader, usize::MAX).await {
+            let response = self.handle(&command, &outbox);
+            if outbox.send(response).is_err() {
+                break;
+            }
+        }
+    }
+
+    fn handle(&self, command: &ManagementCommand, outbox: &Outbox) -> ManagementResponse {
+        let mut state = self.state.lock().unwrap();
+        if let Ok(recorded) = serde_json::to_value(command) {
+            state.commands.push(recorded);
+        }
+        if let Some(response) = state.responders.iter().find_map(|responder| responder(command)) {
+            return response;
+        }
+
+        match command {
+            ManagementCommand::ListActors => ManagementResponse::ActorList {
+                actors: state.actors.values().map(|a| a.id.clone()).collect(),
+            },
+            ManagementCommand::StartActor { manifest, initial_state } => {
+                let id = TheaterId::from_str(&Uuid::new_v4().to_string()).unwrap();
+                let mut actor = MockActor {
+                    id: id.clone(),
+                    manifest: manifest.clone(),
+                    state: initial_state.clone(),
+                    events: Vec::new(),
+                };
+                actor.record("runtime-init", br#"{"phase":"started"}"#.to_vec());
+                state.actors.insert(id.as_string(), actor);
+                ManagementResponse::ActorStarted { id }
+            }
+            ManagementCommand::StopActor { id } => match state.actors.remove(&id.as_string()) {
+                Some(_) => {
+                    state.subscribers.remove(&id.as_string());
+                    ManagementResponse::ActorStopped { id: id.clone() }
+                }
+                None => not_found(id),
+            },
+            ManagementCommand::RestartActor { id } => match state.actors.get_mut(&id.as_string()) {
+                Some(actor) => {
+                    let event = actor.record("runtime-restart", br#"{"phase":"restarted"}"#.to_vec());
+                    publish(&mut state, &id.as_string(), event);
+                    ManagementResponse::Restarted { id: id.clone() }
+                }
+                None => not_found(id),
+            },
+            ManagementCommand::UpdateActorComponent { id, component } => match state.actors.get_mut(&id.as_string()) {
+                Some(actor) => {
+                    let payload = format!(r#"{{"phase":"updated","component":{:?}}}"#, component).into_bytes();
+                    let event = actor.record("runtime-update", payload);
+                    publish(&mut state, &id.as_string(), event);
+                    ManagementResponse::ActorComponentUpdated { id: id.clone() }
+                }
+                None => not_found(id),
+            },
+            ManagementCommand::GetActorStatus { id } => match state.actors.get(&id.as_string()) {
+                Some(_) => ManagementResponse::ActorStatus {
+                    id: id.clone(),
+                    status: ActorStatus::Running,
+                },
+                None => not_found(id),
+            },
+            ManagementCommand::GetActorState { id } => match state.actors.get(&id.as_string()) {
+                Some(actor) => ManagementResponse::ActorState {
+                    id: id.clone(),
+                    state: actor.state.clone(),
+                },
+                None => not_found(id),
+            },
+            ManagementCommand::GetActorEvents { id } => match state.actors.get(&id.as_string()) {
+                Some(actor) => ManagementResponse::ActorEvents {
+                    id: id.clone(),
+                    events: actor.events.clone(),
+                },
+                None => not_found(id),
+            },
+            ManagementCommand::SendActorMessage { id, data } => match state.actors.get_mut(&id.as_string()) {
+                Some(actor) => {
+                    let event = actor.record("message-received", data.clone());
+                    publish(&mut state, &id.as_string(), event);
+                    ManagementResponse::SentMessage { id: id.clone() }
+                }
+                None => not_found(id),
+            },
+            ManagementCommand::RequestActorMessage { id, data } => match state.actors.get_mut(&id.as_string()) {
+                Some(actor) => {
+                    let event = actor.record("request-received", data.clone());
+                    publish(&mut state, &id.as_string(), event);
+                    // Echo the request back as the response
+                    ManagementResponse::RequestedMessage {
+                        id: id.clone(),
+                        message: data.clone(),
+                    }
+                }
+                None => not_found(id),
+            },
+            ManagementCommand::OpenChannel { actor_id, .. } => {
+                if let ChannelParticipant::Actor(id) = actor_id {
+                    if !state.actors.contains_key(&id.as_string()) {
+                        return not_found(id);
+                    }
+                }
+                let channel_id = Uuid::new_v4().to_string();
+                state.channels.insert(channel_id.clone(), outbox.clone());
+                ManagementResponse::ChannelOpened {
+                    channel_id,
+                    actor_id: actor_id.clone(),
+                }
+            }
+            ManagementCommand::SendOnChannel { channel_id, message } => match state.channels.get(channel_id) {
+                Some(channel) => {
+                    // The participant echoes every message back on the channel
+                    let _ = channel.send(ManagementResponse::ChannelMessage {
+                        channel_id: channel_id.clone(),
+                        message: message.clone(),
+                    });
+                    ManagementResponse::MessageSent {
+                        channel_id: channel_id.clone(),
+                    }
+                }
+                None => ManagementResponse::Error {
+                    message: format!("Channel not found: {}", channel_id),
+                },
+            },
+            ManagementCommand::CloseChannel { channel_id } => match state.channels.remove(channel_id) {
+                Some(_) => ManagementResponse::ChannelClosed {
+                    channel_id: channel_id.clone(),
+                },
+                None => ManagementResponse::Error {
+                    message: format!("Channel not found: {}", channel_id),
+                },
+            },
+            ManagementCommand::SubscribeToActor { id } => {
+                if !state.actors.contains_key(&id.as_string()) {
+                    return not_found(id);
+                }
+                state.subscribers.entry(id.as_string()).or_default().push(outbox.clone());
+                ManagementResponse::Subscribed {
+                    id: id.clone(),
+                    subscription_id: Uuid::new_v4(),
+                }
+            }
+            other => ManagementResponse::Error {
+                message: format!("unknown command `{}`", command_name(other)),
+            },
+        }
+    }
+}
+
+/// Push an event to every live subscriber of an actor
+fn publish(state: &mut MockState, actor_id: &str, event: ChainEvent) {
+    if let Some(subscribers) = state.subscribers.get_mut(actor_id) {
+        subscribers.retain(|outbox| {
+            outbox
+                .send(ManagementResponse::ActorEvent { event: event.clone() })
+                .is_ok()
+        });
+    }
+}
+
+fn not_found(id: &TheaterId) -> ManagementResponse {
+    ManagementResponse::Error {
+        message: format!("Actor not found: {}", id),
+    }
+}
+
+/// The variant name of a command, e.g. `StartActor`
+pub fn command_name(command: &ManagementCommand) -> String {
+    let debug = format!("{:?}", command);
+    debug.split([' ', '{', '(']).next().unwrap_or_default().to_string()
+}
@@ -0,0 +1,112 @@
+//! Golden tests for what clients see in `tools/list` and `resources/list`.
+//!
+//! LLM clients build their calls from these schemas, so an accidental rename
+//! or a dropped property breaks them without any error on our side. The
+//! catalogs are serialized exactly as sent and compared against the fixtures
+//! in `tests/fixtures`, which are recorded on the first run. After an
+//! intended change, regenerate them with
+//!
+//! ```bash
+//! UPDATE_SNAPSHOTS=1 cargo test --test schema_snapshots
+//! ```
+//!
+//! and review the fixture diff along with the code.
+
+mod mock_theater;
+
+use anyhow::Result;
+use mcp_server::resources::ResourceManager;
+use mcp_server::tools::ToolManager;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use theater_mcp_server::actors::{ActorQuota, ActorRegistry};
+use theater_mcp_server::channels::{ChannelRegistry, InboxLimits};
+use theater_mcp_server::events::{CausalityTracker, EventHistory, EventStreamHub};
+use theater_mcp_server::resources::{ActorResources, EventResources};
+use theater_mcp_server::session::SessionId;
+use theater_mcp_server::theater::TheaterClient;
+use theater_mcp_server::tools::{
+    Admission, AdmissionLimits, ActorTools, ChannelTools, EventTools, MessageTools, ToolDispatcher,
+};
+
+use mock_theater::MockTheater;
+
+/// Register every tool and resource the server offers, as the server does
+async fn catalogs() -> Result<(Arc<ToolManager>, Arc<ResourceManager>)> {
+    let theater = MockTheater::start().await?;
+    let client = Arc::new(TheaterClient::connect(theater.address()).await?);
+    let session_id = SessionId::new();
+
+    let actor_registry = Arc::new(ActorRegistry::new(client.clone(), ActorQuota::default()));
+    let channel_registry = Arc::new(ChannelRegistry::new(client.clone(), InboxLimits::default()));
+    let event_stream = Arc::new(EventStreamHub::new(client.clone()));
+    let history = Arc::new(EventHistory::new(client.clone()));
+    let causality = Arc::new(CausalityTracker::new());
+
+    let tool_manager = Arc::new(ToolManager::new());
+    let admission = Arc::new(Admission::new(AdmissionLimits::default()));
+    let dispatcher = ToolDispatcher::new(tool_manager.clone(), admission, session_id.clone());
+    Arc::new(ActorTools::new(client.clone(), actor_registry, session_id.clone())).register_tools(&dispatcher);
+    Arc::new(MessageTools::new(client.clone())).register_tools(&dispatcher);
+    Arc::new(ChannelTools::new(client.clone(), channel_registry, session_id)).register_tools(&dispatcher);
+    Arc::new(EventTools::new(history.clone(), event_stream.clone())).register_tools(&dispatcher);
+
+    let resource_manager = Arc::new(ResourceManager::new());
+    Arc::new(ActorResources::new(client)).register_resources(&resource_manager);
+    Arc::new(EventResources::new(history, event_stream, causality)).register_resources(&resource_manager);
+
+    Ok((tool_manager, resource_manager))
+}
+
+/// Compare `actual` with a fixture, or rewrite the fixture when
+/// `UPDATE_SNAPSHOTS` is set or it doesn't exist yet
+fn assert_snapshot(name: &str, actual: &Value) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+    let actual_text = serde_json::to_string_pretty(actual).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, &actual_text).unwrap();
+        eprintln!("Wrote snapshot {}", path.display());
+        return;
+    }
+
+    let expected_text = std::fs::read_to_string(&path).unwrap();
+    let expected: Value = serde_json::from_str(&expected_text).unwrap();
+    assert!(
+        expected == *actual,
+        "{} no longer matches the current catalog. If the change is intended, \
+         rerun with UPDATE_SNAPSHOTS=1 and review the fixture diff.\n\nCurrent:\n{}",
+        path.display(),
+        actual_text
+    );
+}
+
+#[tokio::test]
+async fn test_tools_list_snapshot() -> Result<()> {
+    let (tool_manager, _) = catalogs().await?;
+
+    let mut tools = tool_manager.list_tools();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_snapshot("tools_list.json", &json!({ "tools": tools }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resources_list_snapshot() -> Result<()> {
+    let (_, resource_manager) = catalogs().await?;
+
+    let mut resources = resource_manager.list_resources();
+    resources.sort_by(|a, b| a.uri.cmp(&b.uri));
+    let mut templates = resource_manager.list_templates();
+    templates.sort_by(|a, b| a.uri_template.cmp(&b.uri_template));
+    assert_snapshot(
+        "resources_list.json",
+        &json!({ "resources": resources, "resourceTemplates": templates }),
+    );
+    Ok(())
+}